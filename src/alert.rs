@@ -0,0 +1,93 @@
+//! Getting the player's attention with the terminal bell or a screen flash.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    style::{Modifier, Style},
+    Frame,
+};
+
+/// How long the screen stays inverted after an alert.
+const FLASH_DURATION: Duration = Duration::from_millis(150);
+
+/// Alerts given when a question is answered, when the time limit is about to
+/// run out, and when a multiplayer quiz starts or the host announces
+/// something. All off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alerts {
+    /// Ring the terminal bell.
+    pub bell: bool,
+    /// Briefly invert the screen colors.
+    pub flash: bool,
+    /// Send a desktop notification (OSC 9) when a multiplayer quiz starts or
+    /// the host announces something, for players in another window.
+    pub notify: bool,
+}
+
+/// Gives the enabled [`Alerts`] from an event loop.
+pub(crate) struct Alerter {
+    alerts: Alerts,
+    flash_until: Option<Instant>,
+}
+
+impl Alerter {
+    pub(crate) fn new(alerts: Alerts) -> Self {
+        Self {
+            alerts,
+            flash_until: None,
+        }
+    }
+
+    /// Ring the bell now and flash on the next draws.
+    pub(crate) fn alert(&mut self) -> io::Result<()> {
+        if self.alerts.bell {
+            let mut stdout = io::stdout();
+            stdout.write_all(b"\x07")?;
+            stdout.flush()?;
+        }
+        if self.alerts.flash {
+            self.flash_until = Some(Instant::now() + FLASH_DURATION);
+        }
+        Ok(())
+    }
+
+    /// Show `text` as a desktop notification, in terminals that support
+    /// OSC 9 (e.g. iTerm2, WezTerm, kitty); others ignore it.
+    pub(crate) fn notify(&self, text: &str) -> io::Result<()> {
+        if self.alerts.notify {
+            let mut stdout = io::stdout();
+            stdout.write_all(osc9(text).as_bytes())?;
+            stdout.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Invert the frame while a flash is running. Call after drawing the UI.
+    pub(crate) fn render(&self, frame: &mut Frame) {
+        if self.flash_until.is_some_and(|until| Instant::now() < until) {
+            let area = frame.area();
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
+
+/// The OSC 9 sequence showing `text` as a notification. Control characters
+/// are left out, so the text can't end the sequence early.
+fn osc9(text: &str) -> String {
+    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]9;{}\x07", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc9() {
+        assert_eq!(osc9("Quiz started"), "\x1b]9;Quiz started\x07");
+        assert_eq!(osc9("Break\x07\x1b]9;over"), "\x1b]9;Break]9;over\x07");
+    }
+}
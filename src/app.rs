@@ -1,66 +1,498 @@
-use crate::models::{AppState, Question};
+use std::time::Duration;
+
+use crate::catalog::QuizEntry;
+use crate::engine::{QuizEngine, QuizResult};
+use crate::flashcards::Deck;
+use crate::history::HistoryEntry;
+use crate::hooks::{AnswerSubmitted, Hooks, QuestionShown};
+use crate::models::{AppState, Confidence, Question, QuizMeta};
+use crate::playlist::Playlist;
+use crate::session::SavedSession;
 
 const NUM_OPTIONS: usize = 4;
+/// Remaining time at which the player is alerted that time is running out.
+const TIME_WARNING: Duration = Duration::from_secs(10);
 
+/// Terminal UI state around a [`QuizEngine`].
 pub struct App {
     pub state: AppState,
-    questions: Vec<Question>,
-    current_question_index: usize,
+    engine: QuizEngine,
+    /// Every question of the quiz while retaking only the missed ones,
+    /// brought back by [`restart`](Self::restart).
+    full_bank: Option<Vec<Question>>,
+    meta: QuizMeta,
     selected_option: usize,
-    answers: Vec<Option<usize>>,
-    result_scroll: usize,
+    checked_options: [bool; NUM_OPTIONS],
+    /// Question highlighted in the results list, and shown when reviewing.
+    selected_result: usize,
+    /// The results list shows the given and correct answer of missed
+    /// questions.
+    showing_answers: bool,
+    /// The results list hides correctly answered questions.
+    wrong_only: bool,
+    instant_feedback: bool,
+    /// The questions were put in random order, as shown in the help.
+    shuffled: bool,
+    /// The list of keys and settings is shown over the screen.
+    showing_help: bool,
+    /// The answer is waiting for the player to say how sure they are.
+    choosing_confidence: bool,
+    /// Study session, when studying flashcards instead of taking the quiz.
+    deck: Option<Deck>,
+    /// One-off message shown on the welcome or results screen, e.g. after
+    /// an export.
+    status_message: Option<String>,
+    /// Past runs, oldest first.
+    history: Vec<HistoryEntry>,
+    history_scroll: usize,
+    /// State to go back to when the quit prompt is dismissed.
+    paused_state: AppState,
+    hooks: Hooks,
+    /// Question the question-shown hooks last ran for.
+    shown_question: Option<usize>,
+    /// Something happened that should get the player's attention.
+    alert_pending: bool,
+    /// The running-out-of-time alert was given.
+    time_warned: bool,
+    /// The question file changed on disk and can be reloaded.
+    reload_offered: bool,
+    reload_requested: bool,
+    /// Question files to choose from on the welcome screen, when offered
+    /// several.
+    quizzes: Vec<QuizEntry>,
+    selected_quiz: usize,
+    /// Other question files played before, latest first, offered below the
+    /// quiz on the welcome screen.
+    recent_quizzes: Vec<QuizEntry>,
+    /// Recent quiz highlighted instead of the loaded one.
+    selected_recent: Option<usize>,
+    /// Quizzes played one after the other, when running a playlist.
+    playlist: Option<Playlist>,
+    /// The player started the selected quiz, which has to be read first.
+    quiz_requested: bool,
+    /// Images are drawn over the screen with terminal graphics, so their
+    /// boxes are left blank.
+    graphics: bool,
 }
 
 impl App {
     /// Create a new App with the given questions.
     pub fn with_questions(questions: Vec<Question>) -> Self {
-        let num_questions = questions.len();
+        Self::with_engine(QuizEngine::new(questions))
+    }
 
+    fn with_engine(engine: QuizEngine) -> Self {
         Self {
             state: AppState::Welcome,
-            questions,
-            current_question_index: 0,
+            engine,
+            full_bank: None,
+            meta: QuizMeta::default(),
             selected_option: 0,
-            answers: vec![None; num_questions],
-            result_scroll: 0,
+            checked_options: [false; NUM_OPTIONS],
+            selected_result: 0,
+            showing_answers: false,
+            wrong_only: false,
+            instant_feedback: false,
+            shuffled: false,
+            showing_help: false,
+            choosing_confidence: false,
+            deck: None,
+            status_message: None,
+            history: Vec::new(),
+            history_scroll: 0,
+            paused_state: AppState::Quiz,
+            hooks: Hooks::default(),
+            shown_question: None,
+            alert_pending: false,
+            time_warned: false,
+            reload_offered: false,
+            reload_requested: false,
+            quizzes: Vec::new(),
+            selected_quiz: 0,
+            recent_quizzes: Vec::new(),
+            selected_recent: None,
+            playlist: None,
+            quiz_requested: false,
+            graphics: false,
         }
     }
 
-    pub fn current_question(&self) -> &Question {
-        &self.questions[self.current_question_index]
+    /// Rebuild a quiz in progress from a saved session.
+    ///
+    /// The quiz continues right away, with the time already spent counted
+    /// towards any time limit.
+    pub fn from_saved_session(mut session: SavedSession) -> Self {
+        let instant_feedback = session.instant_feedback;
+        let meta = std::mem::take(&mut session.meta);
+        let mut app = Self::with_engine(QuizEngine::from_saved_session(session));
+        app.instant_feedback = instant_feedback;
+        app.meta = meta;
+        app.state = if app.engine.is_finished() {
+            AppState::Result
+        } else {
+            AppState::Quiz
+        };
+        app.restore_selection();
+        app
+    }
+
+    /// Snapshot the quiz in progress so it can be resumed later.
+    pub fn to_saved_session(&self, source: &str) -> SavedSession {
+        SavedSession {
+            instant_feedback: self.instant_feedback,
+            meta: self.meta.clone(),
+            ..self.engine.to_saved_session(source)
+        }
+    }
+
+    /// Title, pass threshold and other details of the question file.
+    pub fn meta(&self) -> &QuizMeta {
+        &self.meta
+    }
+
+    pub fn set_meta(&mut self, meta: QuizMeta) {
+        self.meta = meta;
+    }
+
+    /// The quiz logic, for reading progress and scores.
+    pub fn engine(&self) -> &QuizEngine {
+        &self.engine
+    }
+
+    /// Replace the questions, keeping settings such as the time limit.
+    ///
+    /// Progress is reset as if the quiz was restarted.
+    pub fn replace_questions(&mut self, questions: Vec<Question>) {
+        self.full_bank = None;
+        self.engine.replace_questions(questions);
+        self.restart();
+    }
+
+    /// Set a time budget for the whole quiz.
+    ///
+    /// When it runs out, unanswered questions count as wrong and the quiz
+    /// moves to the results.
+    pub fn set_time_limit(&mut self, time_limit: Option<Duration>) {
+        self.engine.set_time_limit(time_limit);
+    }
+
+    /// Advance time-based state; ends the quiz if the time limit expired.
+    pub fn tick(&mut self) {
+        let in_progress = matches!(
+            self.state,
+            AppState::Quiz | AppState::Feedback | AppState::QuitPrompt
+        );
+        if in_progress && self.engine.tick() {
+            self.finish();
+        }
+        if let Some(remaining) = self.engine.remaining_time() {
+            let running_out = in_progress && remaining <= TIME_WARNING;
+            if running_out && !self.time_warned {
+                self.alert_pending = true;
+            }
+            self.time_warned = running_out;
+        }
+        self.notify_question_shown();
+    }
+
+    /// Whether an answer was submitted or time started running out since
+    /// the last call.
+    pub(crate) fn take_alert(&mut self) -> bool {
+        std::mem::take(&mut self.alert_pending)
+    }
+
+    /// Whether the question file changed and the welcome screen offers to
+    /// reload it.
+    pub fn is_reload_offered(&self) -> bool {
+        self.reload_offered
+    }
+
+    pub(crate) fn offer_reload(&mut self) {
+        self.reload_offered = true;
+    }
+
+    pub(crate) fn request_reload(&mut self) {
+        self.reload_requested = self.reload_offered;
+        self.reload_offered = false;
+    }
+
+    /// Whether the player asked to reload the question file since the last
+    /// call.
+    pub(crate) fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
+    }
+
+    /// Question files to choose from on the welcome screen, empty unless
+    /// several were offered.
+    pub fn quizzes(&self) -> &[QuizEntry] {
+        &self.quizzes
+    }
+
+    pub fn set_quizzes(&mut self, quizzes: Vec<QuizEntry>) {
+        self.quizzes = quizzes;
+        self.selected_quiz = 0;
+    }
+
+    /// Index of the quiz highlighted on the welcome screen.
+    pub fn selected_quiz(&self) -> usize {
+        self.selected_quiz
+    }
+
+    /// Other question files played before, latest first.
+    pub fn recent_quizzes(&self) -> &[QuizEntry] {
+        &self.recent_quizzes
+    }
+
+    pub fn set_recent_quizzes(&mut self, quizzes: Vec<QuizEntry>) {
+        self.recent_quizzes = quizzes;
+        self.selected_recent = None;
+    }
+
+    /// Index of the recent quiz highlighted on the welcome screen, or
+    /// `None` while the loaded quiz is.
+    pub fn selected_recent(&self) -> Option<usize> {
+        self.selected_recent
+    }
+
+    /// Highlight the next quiz offered, or the next recent quiz.
+    pub fn select_next_quiz(&mut self) {
+        if !self.quizzes.is_empty() {
+            if self.selected_quiz + 1 < self.quizzes.len() {
+                self.selected_quiz += 1;
+            }
+            return;
+        }
+        let next = self.selected_recent.map_or(0, |index| index + 1);
+        if next < self.recent_quizzes.len() {
+            self.selected_recent = Some(next);
+        }
+    }
+
+    /// Highlight the previous quiz offered, or go back up the recent
+    /// quizzes to the loaded one.
+    pub fn select_previous_quiz(&mut self) {
+        if !self.quizzes.is_empty() {
+            self.selected_quiz = self.selected_quiz.saturating_sub(1);
+            return;
+        }
+        self.selected_recent = self.selected_recent.and_then(|index| index.checked_sub(1));
+    }
+
+    /// The highlighted quiz that has to be read before starting, rather
+    /// than the loaded one.
+    pub fn chosen_quiz(&self) -> Option<&QuizEntry> {
+        if let Some(playlist) = &self.playlist {
+            playlist.current_quiz()
+        } else if self.quizzes.is_empty() {
+            self.recent_quizzes.get(self.selected_recent?)
+        } else {
+            self.quizzes.get(self.selected_quiz)
+        }
+    }
+
+    pub(crate) fn request_quiz(&mut self) {
+        self.quiz_requested = self.chosen_quiz().is_some();
+    }
+
+    /// The quiz the player chose to start since the last call, to be read
+    /// before starting it.
+    pub(crate) fn take_quiz_request(&mut self) -> Option<QuizEntry> {
+        if !std::mem::take(&mut self.quiz_requested) {
+            return None;
+        }
+        self.chosen_quiz().cloned()
+    }
+
+    /// The playlist being run, if any.
+    pub fn playlist(&self) -> Option<&Playlist> {
+        self.playlist.as_ref()
+    }
+
+    pub fn set_playlist(&mut self, playlist: Playlist) {
+        self.playlist = Some(playlist);
+    }
+
+    /// Count a finished run towards the playlist's report.
+    pub(crate) fn record_playlist_score(&mut self, score: usize, max_score: usize) {
+        if let Some(playlist) = &mut self.playlist {
+            playlist.record(score, max_score);
+        }
+    }
+
+    /// Go on to the next quiz of the playlist, or after the last one back
+    /// to the welcome screen, which then shows every quiz's score.
+    pub fn next_in_playlist(&mut self) {
+        let Some(playlist) = &mut self.playlist else {
+            return;
+        };
+        playlist.advance();
+        self.restart();
+        self.request_quiz();
+    }
+
+    /// Play the playlist again from its first quiz.
+    pub fn restart_playlist(&mut self) {
+        if let Some(playlist) = &mut self.playlist {
+            playlist.restart();
+            self.request_quiz();
+        }
+    }
+
+    /// The latest run of the quiz read from `quiz`, as named in the history.
+    pub fn last_run(&self, quiz: &str) -> Option<&HistoryEntry> {
+        self.history.iter().rev().find(|entry| entry.quiz == quiz)
     }
 
-    pub fn current_question_number(&self) -> usize {
-        self.current_question_index + 1
+    pub(crate) fn has_graphics(&self) -> bool {
+        self.graphics
     }
 
-    pub fn total_questions(&self) -> usize {
-        self.questions.len()
+    pub(crate) fn set_graphics(&mut self, graphics: bool) {
+        self.graphics = graphics;
+    }
+
+    /// Call `hook` each time a new question is put on screen.
+    pub fn on_question_shown(&mut self, hook: impl FnMut(&QuestionShown) + 'static) {
+        self.hooks.on_question_shown.push(Box::new(hook));
+    }
+
+    /// Call `hook` each time an answer is submitted, before moving on.
+    pub fn on_answer_submitted(&mut self, hook: impl FnMut(&AnswerSubmitted) + 'static) {
+        self.hooks.on_answer_submitted.push(Box::new(hook));
+    }
+
+    /// Call `hook` with the result when the quiz ends.
+    pub fn on_finished(&mut self, hook: impl FnMut(&QuizResult) + 'static) {
+        self.hooks.on_finished.push(Box::new(hook));
+    }
+
+    pub fn current_question(&self) -> &Question {
+        self.engine.current_question()
     }
 
     pub fn selected_option(&self) -> usize {
         self.selected_option
     }
 
-    pub fn questions(&self) -> &[Question] {
-        &self.questions
+    /// Check if an option is ticked on the current checkbox question.
+    pub fn is_option_checked(&self, index: usize) -> bool {
+        self.checked_options.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn selected_result(&self) -> usize {
+        self.selected_result
+    }
+
+    pub fn select_next_result(&mut self) {
+        if let Some(next) = (self.selected_result + 1..self.engine.total_questions())
+            .find(|&index| self.is_result_listed(index))
+        {
+            self.selected_result = next;
+        }
+    }
+
+    pub fn select_previous_result(&mut self) {
+        if let Some(previous) =
+            (0..self.selected_result).rev().find(|&index| self.is_result_listed(index))
+        {
+            self.selected_result = previous;
+        }
+    }
+
+    /// Move the results selection `count` listed questions down, stopping
+    /// at the last.
+    pub fn select_next_results(&mut self, count: usize) {
+        for _ in 0..count {
+            self.select_next_result();
+        }
+    }
+
+    /// Move the results selection `count` listed questions up, stopping at
+    /// the first.
+    pub fn select_previous_results(&mut self, count: usize) {
+        for _ in 0..count {
+            self.select_previous_result();
+        }
+    }
+
+    pub fn select_first_result(&mut self) {
+        if let Some(first) =
+            (0..self.engine.total_questions()).find(|&index| self.is_result_listed(index))
+        {
+            self.selected_result = first;
+        }
+    }
+
+    pub fn select_last_result(&mut self) {
+        if let Some(last) =
+            (0..self.engine.total_questions()).rev().find(|&index| self.is_result_listed(index))
+        {
+            self.selected_result = last;
+        }
     }
 
-    pub fn answers(&self) -> &[Option<usize>] {
-        &self.answers
+    /// Whether the results list shows only wrongly answered questions.
+    pub fn is_wrong_only(&self) -> bool {
+        self.wrong_only
     }
 
-    pub fn result_scroll(&self) -> usize {
-        self.result_scroll
+    /// Hide correctly answered questions from the results list, or show
+    /// them again, keeping a listed question selected.
+    pub fn toggle_wrong_only(&mut self) {
+        self.wrong_only = !self.wrong_only;
+        if !self.is_result_listed(self.selected_result) {
+            self.select_next_result();
+        }
+        if !self.is_result_listed(self.selected_result) {
+            self.select_previous_result();
+        }
     }
 
-    pub fn scroll_results_down(&mut self) {
-        let max_scroll = self.questions.len().saturating_sub(1);
-        self.result_scroll = (self.result_scroll + 1).min(max_scroll);
+    /// Whether a question is in the results list.
+    pub fn is_result_listed(&self, index: usize) -> bool {
+        let is_correct = self.engine.answers()[index]
+            .as_deref()
+            .is_some_and(|answer| self.engine.questions()[index].is_correct(answer));
+        !self.wrong_only || !is_correct
     }
 
-    pub fn scroll_results_up(&mut self) {
-        self.result_scroll = self.result_scroll.saturating_sub(1);
+    /// Whether missed questions in the results list show the given and
+    /// correct answer.
+    pub fn is_showing_answers(&self) -> bool {
+        self.showing_answers
+    }
+
+    pub fn toggle_answers(&mut self) {
+        self.showing_answers = !self.showing_answers;
+    }
+
+    pub fn status_message(&self) -> Option<&str> {
+        self.status_message.as_deref()
+    }
+
+    pub fn set_status_message(&mut self, message: impl Into<String>) {
+        self.status_message = Some(message.into());
+    }
+
+    /// Go on from congratulating the player to the results.
+    pub fn show_results(&mut self) {
+        if self.state == AppState::Passed {
+            self.state = AppState::Result;
+        }
+    }
+
+    /// Open the detailed review of the selected question from the results.
+    pub fn open_review(&mut self) {
+        if self.state == AppState::Result {
+            self.state = AppState::Review;
+        }
+    }
+
+    /// Go back from the review to the results list.
+    pub fn close_review(&mut self) {
+        if self.state == AppState::Review {
+            self.state = AppState::Result;
+        }
     }
 
     pub fn select_next_option(&mut self) {
@@ -71,33 +503,644 @@ impl App {
         self.selected_option = (self.selected_option + NUM_OPTIONS - 1) % NUM_OPTIONS;
     }
 
+    /// Tick or untick the selected option on a checkbox question.
+    pub fn toggle_selected_option(&mut self) {
+        if self.current_question().is_multiple() {
+            let checked = &mut self.checked_options[self.selected_option];
+            *checked = !*checked;
+        }
+    }
+
+    /// Past runs, oldest first.
+    pub fn history(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    pub fn set_history(&mut self, history: Vec<HistoryEntry>) {
+        self.history = history;
+    }
+
+    /// Add a finished run to the in-memory history.
+    pub fn push_history(&mut self, entry: HistoryEntry) {
+        self.history.push(entry);
+    }
+
+    pub fn history_scroll(&self) -> usize {
+        self.history_scroll
+    }
+
+    pub fn scroll_history_down(&mut self) {
+        let max_scroll = self.history.len().saturating_sub(1);
+        self.history_scroll = (self.history_scroll + 1).min(max_scroll);
+    }
+
+    pub fn scroll_history_up(&mut self) {
+        self.history_scroll = self.history_scroll.saturating_sub(1);
+    }
+
+    /// Show the list of past runs from the welcome screen.
+    pub fn show_history(&mut self) {
+        if self.state == AppState::Welcome {
+            self.history_scroll = 0;
+            self.state = AppState::History;
+        }
+    }
+
+    /// Go back from the history list to the welcome screen.
+    pub fn close_history(&mut self) {
+        if self.state == AppState::History {
+            self.state = AppState::Welcome;
+        }
+    }
+
+    /// Ask whether to save progress before quitting mid-quiz.
+    pub fn prompt_quit(&mut self) {
+        if matches!(self.state, AppState::Quiz | AppState::Feedback) {
+            self.paused_state = self.state;
+            self.state = AppState::QuitPrompt;
+        }
+    }
+
+    /// Dismiss the quit prompt and carry on with the quiz.
+    pub fn cancel_quit(&mut self) {
+        if self.state == AppState::QuitPrompt {
+            self.status_message = None;
+            self.state = self.paused_state;
+        }
+    }
+
+    /// Show statistics over past runs from the welcome screen.
+    pub fn show_stats(&mut self) {
+        if self.state == AppState::Welcome {
+            self.state = AppState::Stats;
+        }
+    }
+
+    /// Go back from the statistics to the welcome screen.
+    pub fn close_stats(&mut self) {
+        if self.state == AppState::Stats {
+            self.state = AppState::Welcome;
+        }
+    }
+
     pub fn start_quiz(&mut self) {
+        if let Some(deck) = &mut self.deck {
+            *deck = Deck::new(self.engine.total_questions());
+            deck.start();
+            self.state = AppState::Flashcards;
+            self.status_message = None;
+            return;
+        }
         self.state = AppState::Quiz;
+        self.status_message = None;
+        self.engine.start();
+        self.notify_question_shown();
+    }
+
+    fn finish(&mut self) {
+        let engine = &self.engine;
+        let passed = self.meta.passed(engine.calculate_score(), engine.max_score());
+        self.state = if passed == Some(true) {
+            AppState::Passed
+        } else {
+            AppState::Result
+        };
+        if let Some(result) = self.engine.result() {
+            self.hooks.finished(&result);
+        }
+    }
+
+    /// Run the question-shown hooks if a new question is on screen.
+    fn notify_question_shown(&mut self) {
+        let index = self.engine.current_question_index();
+        if self.state != AppState::Quiz || self.shown_question == Some(index) {
+            return;
+        }
+
+        self.shown_question = Some(index);
+        self.hooks.question_shown(&QuestionShown {
+            question: self.engine.current_question(),
+            number: index + 1,
+            total: self.engine.total_questions(),
+        });
     }
 
+    /// Submit the current answer and move on.
+    ///
+    /// Checkbox questions submit the ticked options and are ignored while
+    /// nothing is ticked; other questions submit the selected option.
+    ///
+    /// In confidence mode the answer waits for
+    /// [`submit_with_confidence`](Self::submit_with_confidence) instead.
+    ///
+    /// In exam mode the answer is only saved: it can be changed later and
+    /// the quiz ends with [`finish_quiz`](Self::finish_quiz) instead. In
+    /// instant feedback mode the quiz stays on the question in the
+    /// [`AppState::Feedback`] state until
+    /// [`continue_after_feedback`](Self::continue_after_feedback) is called.
     pub fn submit_answer(&mut self) {
-        self.answers[self.current_question_index] = Some(self.selected_option);
-        self.current_question_index += 1;
+        let answer = self.answer_to_submit();
+        if self.engine.is_confidence_mode() {
+            self.choosing_confidence = !answer.is_empty();
+            return;
+        }
+        let is_correct = self.engine.record_answer(answer);
+        self.answer_recorded(is_correct);
+    }
+
+    /// Whether the answer is waiting for the player to say how sure they
+    /// are, in confidence mode.
+    pub fn is_choosing_confidence(&self) -> bool {
+        self.choosing_confidence
+    }
+
+    /// Submit the answer waiting in confidence mode and move on.
+    pub fn submit_with_confidence(&mut self, confidence: Confidence) {
+        if !std::mem::take(&mut self.choosing_confidence) {
+            return;
+        }
+        let answer = self.answer_to_submit();
+        let is_correct = self.engine.record_answer_with_confidence(answer, confidence);
+        self.answer_recorded(is_correct);
+    }
+
+    /// Go back to changing the answer instead of submitting it.
+    pub fn cancel_confidence(&mut self) {
+        self.choosing_confidence = false;
+    }
+
+    fn answer_to_submit(&self) -> Vec<usize> {
+        if self.current_question().is_multiple() {
+            (0..NUM_OPTIONS)
+                .filter(|&index| self.checked_options[index])
+                .collect()
+        } else {
+            vec![self.selected_option]
+        }
+    }
+
+    fn answer_recorded(&mut self, is_correct: Option<bool>) {
+        let Some(is_correct) = is_correct else {
+            return;
+        };
+        self.alert_pending = true;
+        self.hooks.answer_submitted(&AnswerSubmitted {
+            question: self.engine.current_question(),
+            number: self.engine.current_question_number(),
+            answer: self.engine.current_answer().unwrap_or_default(),
+            is_correct,
+            hint_used: self.engine.is_hint_revealed(),
+        });
+        if self.engine.is_exam_mode() {
+            self.next_question();
+            return;
+        }
+        if self.instant_feedback {
+            self.state = AppState::Feedback;
+            return;
+        }
+
+        self.advance();
+    }
+
+    /// Leave the feedback screen and move on to the next question.
+    pub fn continue_after_feedback(&mut self) {
+        if self.state == AppState::Feedback {
+            self.state = AppState::Quiz;
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        self.clear_selection();
+        self.engine.advance();
+        if self.engine.is_finished() {
+            self.finish();
+        } else {
+            self.notify_question_shown();
+        }
+    }
+
+    /// Defer the current question to the end of the queue.
+    ///
+    /// Does nothing in exam mode or when it is the last question left.
+    pub fn skip_question(&mut self) {
+        if self.engine.skip() {
+            self.clear_selection();
+            self.notify_question_shown();
+        }
+    }
+
+    /// Move to the next question in exam mode, restoring any saved answer.
+    pub fn next_question(&mut self) {
+        if self.engine.next_question() {
+            self.restore_selection();
+            self.notify_question_shown();
+        }
+    }
+
+    /// Move to the previous question in exam mode, restoring any saved answer.
+    pub fn previous_question(&mut self) {
+        if self.engine.previous_question() {
+            self.restore_selection();
+            self.notify_question_shown();
+        }
+    }
+
+    /// End an exam-mode quiz and score the saved answers.
+    pub fn finish_quiz(&mut self) {
+        if self.engine.is_exam_mode() && self.state == AppState::Quiz {
+            self.engine.finish();
+            self.finish();
+        }
+    }
+
+    /// Study the questions as flashcards instead: each answer is hidden
+    /// until revealed, and the player says whether they knew it.
+    pub fn set_flashcards(&mut self, flashcards: bool) {
+        self.deck = flashcards.then(|| Deck::new(self.engine.total_questions()));
+    }
+
+    /// The flashcard study session, when studying flashcards.
+    pub fn deck(&self) -> Option<&Deck> {
+        self.deck.as_ref()
+    }
+
+    /// The question on the flashcard on screen, until the deck is done.
+    pub fn current_card(&self) -> Option<&Question> {
+        let deck = self.deck.as_ref()?;
+        self.engine.questions().get(deck.card())
+    }
+
+    /// Show the answer on the flashcard on screen.
+    pub fn reveal_card(&mut self) {
+        if self.state == AppState::Flashcards
+            && let Some(deck) = &mut self.deck
+        {
+            deck.reveal();
+        }
+    }
+
+    /// Say whether the player knew the revealed flashcard, and go on to the
+    /// next one.
+    pub fn rate_card(&mut self, knew: bool) {
+        if self.state == AppState::Flashcards
+            && let Some(deck) = &mut self.deck
+        {
+            deck.rate(knew);
+        }
+    }
+
+    /// Enable exam mode, where answers can be revised until the quiz is
+    /// finished explicitly.
+    pub fn set_exam_mode(&mut self, exam_mode: bool) {
+        self.engine.set_exam_mode(exam_mode);
+    }
+
+    /// Ask how sure the player is of each answer, and weight its score by
+    /// that.
+    pub fn set_confidence_mode(&mut self, confidence_mode: bool) {
+        self.engine.set_confidence_mode(confidence_mode);
+    }
+
+    pub fn is_instant_feedback(&self) -> bool {
+        self.instant_feedback
+    }
+
+    /// Enable instant feedback, where each answer is marked right or wrong
+    /// as soon as it is submitted. Has no effect in exam mode.
+    pub fn set_instant_feedback(&mut self, instant_feedback: bool) {
+        self.instant_feedback = instant_feedback;
+    }
+
+    pub fn is_shuffled(&self) -> bool {
+        self.shuffled
+    }
+
+    /// Note that the questions were put in random order.
+    pub fn set_shuffled(&mut self, shuffled: bool) {
+        self.shuffled = shuffled;
+    }
+
+    /// Whether the list of keys and settings is shown over the screen.
+    pub fn is_showing_help(&self) -> bool {
+        self.showing_help
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.showing_help = !self.showing_help;
+    }
+
+    /// Reveal the hint of the current question, if it has one.
+    pub fn reveal_hint(&mut self) {
+        if self.state == AppState::Quiz {
+            self.engine.reveal_hint();
+        }
+    }
+
+    /// Deduct `hint_penalty` points from questions answered with a hint.
+    ///
+    /// A question never scores below zero.
+    pub fn set_hint_penalty(&mut self, hint_penalty: usize) {
+        self.engine.set_hint_penalty(hint_penalty);
+    }
+
+    fn clear_selection(&mut self) {
         self.selected_option = 0;
+        self.checked_options = [false; NUM_OPTIONS];
+    }
 
-        if self.current_question_index >= self.questions.len() {
-            self.state = AppState::Result;
+    fn restore_selection(&mut self) {
+        let saved = self.engine.current_answer().unwrap_or(&[]);
+        self.selected_option = saved.first().copied().unwrap_or(0);
+        self.checked_options = [false; NUM_OPTIONS];
+        for &index in saved {
+            if let Some(checked) = self.checked_options.get_mut(index) {
+                *checked = true;
+            }
         }
     }
 
-    pub fn calculate_score(&self) -> usize {
-        self.answers
+    /// Go back to the welcome screen with every question of the quiz, also
+    /// after retaking only the missed ones.
+    pub fn restart(&mut self) {
+        match self.full_bank.take() {
+            Some(questions) => self.engine.replace_questions(questions),
+            None => self.engine.reset(),
+        }
+        self.reset_run();
+    }
+
+    /// Start again right away with only the questions answered wrongly or
+    /// not at all, keeping the full quiz for [`restart`](Self::restart).
+    ///
+    /// Can be repeated until every question is answered correctly. Returns
+    /// `false`, changing nothing, when there is nothing to retake.
+    pub fn retake_missed(&mut self) -> bool {
+        let engine = &self.engine;
+        let missed: Vec<Question> = engine
+            .questions()
             .iter()
-            .zip(self.questions.iter())
-            .filter(|(answer, question)| *answer == &Some(question.correct_answer))
-            .count()
+            .zip(engine.answers())
+            .filter(|(question, answer)| {
+                !answer.as_deref().is_some_and(|answer| question.is_correct(answer))
+            })
+            .map(|(question, _)| question.clone())
+            .collect();
+        if missed.is_empty() {
+            return false;
+        }
+
+        if self.full_bank.is_none() {
+            self.full_bank = Some(self.engine.questions().to_vec());
+        }
+        self.engine.replace_questions(missed);
+        self.reset_run();
+        self.start_quiz();
+        true
     }
 
-    pub fn restart(&mut self) {
+    /// Whether only the missed questions of the quiz are being retaken.
+    pub fn is_retaking(&self) -> bool {
+        self.full_bank.is_some()
+    }
+
+    /// Clear everything about the run besides the engine's progress.
+    fn reset_run(&mut self) {
         self.state = AppState::Welcome;
-        self.current_question_index = 0;
-        self.selected_option = 0;
-        self.answers = vec![None; self.questions.len()];
-        self.result_scroll = 0;
+        self.clear_selection();
+        self.choosing_confidence = false;
+        if let Some(deck) = &mut self.deck {
+            *deck = Deck::new(self.engine.total_questions());
+        }
+        self.selected_result = 0;
+        self.status_message = None;
+        self.shown_question = None;
+        self.alert_pending = false;
+        self.time_warned = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::questions;
+
+    #[test]
+    fn test_exam_mode_revise_and_finish() {
+        let mut app = App::with_questions(questions(3));
+        app.set_exam_mode(true);
+        app.start_quiz();
+
+        app.select_next_option();
+        app.submit_answer();
+        assert_eq!(app.engine().current_question_number(), 2);
+
+        app.previous_question();
+        assert_eq!(app.selected_option(), 1);
+        app.select_next_option();
+        app.submit_answer();
+        assert_eq!(app.engine().answers()[0], Some(vec![2]));
+
+        app.next_question();
+        app.next_question();
+        assert_eq!(app.engine().current_question_number(), 3);
+        assert_eq!(app.state, AppState::Quiz);
+
+        app.finish_quiz();
+        assert_eq!(app.state, AppState::Result);
+        assert_eq!(app.engine().calculate_score(), 0);
+    }
+
+    #[test]
+    fn test_saved_session_round_trip() {
+        let mut app = App::with_questions(questions(3));
+        app.set_instant_feedback(true);
+        app.set_time_limit(Some(Duration::from_secs(600)));
+        app.start_quiz();
+        app.skip_question();
+        app.select_next_option();
+        app.submit_answer();
+        assert_eq!(app.state, AppState::Feedback);
+
+        let json = serde_json::to_string(&app.to_saved_session("questions.json")).unwrap();
+        let session: SavedSession = serde_json::from_str(&json).unwrap();
+        assert_eq!(session.source(), "questions.json");
+
+        let mut resumed = App::from_saved_session(session);
+        assert_eq!(resumed.state, AppState::Quiz);
+        assert_eq!(resumed.engine().current_question_number(), 3);
+        assert_eq!(resumed.engine().skipped_count(), 1);
+        assert_eq!(
+            resumed.engine().time_limit(),
+            Some(Duration::from_secs(600))
+        );
+        assert!(resumed.is_instant_feedback());
+
+        resumed.select_next_option();
+        resumed.submit_answer();
+        resumed.continue_after_feedback();
+        assert_eq!(resumed.engine().current_question_number(), 1);
+        resumed.submit_answer();
+        resumed.continue_after_feedback();
+        assert_eq!(resumed.state, AppState::Result);
+        assert_eq!(resumed.engine().calculate_score(), 2);
+    }
+
+    #[test]
+    fn test_instant_feedback_waits_before_advancing() {
+        let mut app = App::with_questions(questions(2));
+        app.set_instant_feedback(true);
+        app.start_quiz();
+
+        app.submit_answer();
+        assert_eq!(app.state, AppState::Feedback);
+        assert_eq!(app.engine().current_question_number(), 1);
+        assert_eq!(app.engine().current_answer(), Some(&[0][..]));
+
+        app.continue_after_feedback();
+        assert_eq!(app.state, AppState::Quiz);
+        assert_eq!(app.engine().current_question_number(), 2);
+
+        app.select_next_option();
+        app.submit_answer();
+        app.continue_after_feedback();
+        assert_eq!(app.state, AppState::Result);
+        assert_eq!(app.engine().calculate_score(), 1);
+    }
+
+    #[test]
+    fn test_wrong_only_results() {
+        let mut app = App::with_questions(questions(4));
+        app.start_quiz();
+        // Right, wrong, right, wrong
+        for correct in [true, false, true, false] {
+            if correct {
+                app.select_next_option();
+            }
+            app.submit_answer();
+        }
+        assert_eq!(app.state, AppState::Result);
+
+        app.toggle_wrong_only();
+        assert_eq!(app.selected_result(), 1);
+        app.select_next_result();
+        assert_eq!(app.selected_result(), 3);
+        app.select_next_result();
+        assert_eq!(app.selected_result(), 3);
+        app.select_previous_result();
+        assert_eq!(app.selected_result(), 1);
+
+        app.toggle_wrong_only();
+        app.select_next_result();
+        assert_eq!(app.selected_result(), 2);
+        app.select_previous_results(10);
+        assert_eq!(app.selected_result(), 0);
+        app.select_last_result();
+        assert_eq!(app.selected_result(), 3);
+        app.toggle_wrong_only();
+        app.select_first_result();
+        assert_eq!(app.selected_result(), 1);
+    }
+
+    #[test]
+    fn test_retake_missed_until_all_correct() {
+        let mut app = App::with_questions(questions(3));
+        app.start_quiz();
+        app.select_next_option();
+        app.submit_answer();
+        app.submit_answer();
+        app.submit_answer();
+
+        assert!(app.retake_missed());
+        assert_eq!(app.state, AppState::Quiz);
+        assert_eq!(app.engine().total_questions(), 2);
+        app.select_next_option();
+        app.submit_answer();
+        app.submit_answer();
+
+        assert!(app.retake_missed());
+        assert_eq!(app.engine().questions()[0].text, "Question 2?");
+        app.select_next_option();
+        app.submit_answer();
+        assert!(!app.retake_missed());
+
+        app.restart();
+        assert!(!app.is_retaking());
+        assert_eq!(app.engine().total_questions(), 3);
+    }
+
+    #[test]
+    fn test_passing_congratulates_first() {
+        let mut app = App::with_questions(questions(2));
+        app.set_meta(QuizMeta {
+            pass_score: Some(1),
+            ..QuizMeta::default()
+        });
+        app.start_quiz();
+        app.select_next_option();
+        app.submit_answer();
+        app.submit_answer();
+
+        assert_eq!(app.state, AppState::Passed);
+        app.show_results();
+        assert_eq!(app.state, AppState::Result);
+    }
+
+    #[test]
+    fn test_hooks_follow_quiz_progress() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut app = App::with_questions(questions(2));
+        let log = Rc::clone(&events);
+        app.on_question_shown(move |event| {
+            log.borrow_mut().push(format!("shown {}", event.number))
+        });
+        let log = Rc::clone(&events);
+        app.on_answer_submitted(move |event| {
+            log.borrow_mut()
+                .push(format!("answered {} {}", event.number, event.is_correct))
+        });
+        let log = Rc::clone(&events);
+        app.on_finished(move |result| log.borrow_mut().push(format!("finished {}", result.score)));
+
+        app.start_quiz();
+        app.skip_question();
+        app.tick();
+        app.select_next_option();
+        app.submit_answer();
+        app.submit_answer();
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                "shown 1",
+                "shown 2",
+                "answered 2 true",
+                "shown 1",
+                "answered 1 false",
+                "finished 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alerts_on_answer_and_low_time() {
+        let mut app = App::with_questions(questions(2));
+        app.set_time_limit(Some(Duration::from_secs(5)));
+        app.start_quiz();
+        assert!(!app.take_alert());
+
+        // Under the warning threshold from the start: alert once
+        app.tick();
+        assert!(app.take_alert());
+        app.tick();
+        assert!(!app.take_alert());
+
+        app.submit_answer();
+        assert!(app.take_alert());
+        assert!(!app.take_alert());
     }
 }
@@ -0,0 +1,125 @@
+//! Question files to choose from on the welcome screen.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::data::{load_bank_from_json, load_bank_from_toml, LoadError, QuestionBank};
+use crate::history::HistoryEntry;
+
+/// A question file offered on the welcome screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizEntry {
+    /// Where the questions are read from once the quiz is chosen.
+    pub path: PathBuf,
+    /// The file's title, or its name without the extension.
+    pub title: String,
+    /// Number of questions in the file.
+    pub questions: usize,
+}
+
+impl QuizEntry {
+    /// Read the title and question count of a JSON or TOML question file.
+    pub fn read(path: &Path) -> Result<Self, LoadError> {
+        let bank = load_bank(path)?;
+        let title = bank.meta.title.unwrap_or_else(|| {
+            let stem = path.file_stem().unwrap_or(path.as_os_str());
+            stem.to_string_lossy().into_owned()
+        });
+        Ok(Self {
+            path: path.into(),
+            title,
+            questions: bank.questions.len(),
+        })
+    }
+}
+
+/// The JSON and TOML files directly in `dir`, by name.
+pub fn question_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_question_file = path
+            .extension()
+            .is_some_and(|ext| ext == "json" || ext == "toml");
+        if is_question_file && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Entries for the files that can be read as question files, in order.
+/// Others, such as encrypted files or unrelated JSON, are left out.
+pub(crate) fn read_entries<P: AsRef<Path>>(paths: &[P]) -> Vec<QuizEntry> {
+    paths
+        .iter()
+        .filter_map(|path| QuizEntry::read(path.as_ref()).ok())
+        .collect()
+}
+
+/// Up to `limit` question files played before other than `current`, latest
+/// first. Files that can no longer be read, and URLs, are left out.
+pub(crate) fn recent_quizzes(
+    history: &[HistoryEntry],
+    current: &str,
+    limit: usize,
+) -> Vec<QuizEntry> {
+    let mut seen = vec![current];
+    let mut quizzes = Vec::new();
+    for entry in history.iter().rev() {
+        if quizzes.len() == limit {
+            break;
+        }
+        if seen.contains(&entry.quiz.as_str()) {
+            continue;
+        }
+        seen.push(&entry.quiz);
+        if let Ok(quiz) = QuizEntry::read(Path::new(&entry.quiz)) {
+            quizzes.push(quiz);
+        }
+    }
+    quizzes
+}
+
+/// Load a question file as TOML or JSON, by its extension.
+fn load_bank(path: &Path) -> Result<QuestionBank, LoadError> {
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        load_bank_from_toml(path)
+    } else {
+        load_bank_from_json(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_of_question_files() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-catalog-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let question = |text: &str| {
+            format!(r#"{{"text": "{}", "options": ["a", "b", "c", "d"], "correct_answer": 0}}"#, text)
+        };
+        let traits = format!(
+            r#"{{"meta": {{"title": "Traits"}}, "questions": [{}, {}]}}"#,
+            question("Q1"),
+            question("Q2")
+        );
+        fs::write(dir.join("b-traits.json"), traits).unwrap();
+        fs::write(dir.join("a-ownership.json"), format!("[{}]", question("Q1"))).unwrap();
+        fs::write(dir.join("package.json"), r#"{"name": "not a quiz"}"#).unwrap();
+        fs::write(dir.join("notes.txt"), "not a quiz either").unwrap();
+
+        let files = question_files(&dir).unwrap();
+        let entries = read_entries(&files);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 3);
+        let entries: Vec<(&str, usize)> =
+            entries.iter().map(|entry| (entry.title.as_str(), entry.questions)).collect();
+        assert_eq!(entries, [("a-ownership", 1), ("Traits", 2)]);
+    }
+}
@@ -0,0 +1,108 @@
+//! Signed records of passing a quiz.
+//!
+//! A certificate is a small JSON file naming who passed which quiz, with
+//! what score and when, signed with HMAC-SHA256 under a key kept by whoever
+//! hands them out. Changing any field afterwards breaks the signature, so
+//! anyone with the key can check a certificate with `rust-quiz verify`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Environment variable holding the key certificates are signed with.
+pub const KEY_VAR: &str = "RUST_QUIZ_CERTIFICATE_KEY";
+
+/// Proof that `name` passed `quiz`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Certificate {
+    pub name: String,
+    /// The quiz's title, or its file if it has none.
+    pub quiz: String,
+    pub score: usize,
+    pub max_score: usize,
+    /// When the quiz was passed, in RFC 3339.
+    pub date: String,
+    /// HMAC-SHA256 of the other fields, in hex.
+    pub signature: String,
+}
+
+impl Certificate {
+    /// A certificate for passing now, signed with `key`.
+    pub fn issue(name: &str, quiz: &str, score: usize, max_score: usize, key: &[u8]) -> Self {
+        let mut certificate = Self {
+            name: name.to_string(),
+            quiz: quiz.to_string(),
+            score,
+            max_score,
+            date: chrono::Local::now().to_rfc3339(),
+            signature: String::new(),
+        };
+        let signature = certificate.mac(key).finalize().into_bytes();
+        certificate.signature = signature.iter().map(|byte| format!("{:02x}", byte)).collect();
+        certificate
+    }
+
+    /// Whether the certificate was signed with `key` and is unchanged since.
+    pub fn verify(&self, key: &[u8]) -> bool {
+        decode_hex(&self.signature)
+            .is_some_and(|signature| self.mac(key).verify_slice(&signature).is_ok())
+    }
+
+    /// Read a certificate written by [`Certificate::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// The MAC of every field but the signature.
+    fn mac(&self, key: &[u8]) -> Hmac<Sha256> {
+        // HMAC takes keys of any length
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+        // A JSON array, so no field can run into the next
+        let fields = (&self.name, &self.quiz, self.score, self.max_score, &self.date);
+        mac.update(serde_json::to_string(&fields).unwrap_or_default().as_bytes());
+        mac
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let certificate = Certificate::issue("Ada", "Ownership", 8, 10, b"secret");
+        assert_eq!(certificate.signature.len(), 64);
+        assert!(certificate.verify(b"secret"));
+        assert!(!certificate.verify(b"guess"));
+
+        let forged = Certificate {
+            score: 10,
+            ..certificate.clone()
+        };
+        assert!(!forged.verify(b"secret"));
+        let garbled = Certificate {
+            signature: "zz".to_string(),
+            ..certificate
+        };
+        assert!(!garbled.verify(b"secret"));
+    }
+}
@@ -1,98 +1,489 @@
 //! WebSocket client implementation.
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use futures_util::{SinkExt, StreamExt};
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Mutex};
-use tokio_tungstenite::tungstenite::Message;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{HeaderValue, SEC_WEBSOCKET_PROTOCOL};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
-use crate::protocol::{ClientMessage, ServerMessage};
+use crate::alert::{Alerter, Alerts};
+use crate::protocol::{
+    decode, ClientMessage, Encoding, ServerMessage, StandingEntry, SubmittedAnswer,
+    MSGPACK_PROTOCOL,
+};
+use crate::layout::LayoutOptions;
+use crate::models::Confidence;
 use crate::terminal;
+use crate::theme::Theme;
+use crate::{NetworkError, QuizError};
 
-use super::state::{ClientApp, ClientState};
+use super::state::{ClientApp, ClientState, QuestionData};
 use super::ui;
 
 /// Shared client app state.
 type SharedApp = Arc<Mutex<ClientApp>>;
 
-/// Run the quiz client.
-pub async fn run(host: String, port: u16) -> Result<(), Box<dyn std::error::Error>> {
-    let app = Arc::new(Mutex::new(ClientApp::new(host.clone(), port)));
+/// WebSocket connection to the server.
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
-    // Connect to server
-    let url = format!("ws://{}:{}", host, port);
-    println!("Connecting to {}...", url);
+/// Reconnection attempts before giving up on the server.
+const MAX_RECONNECT_ATTEMPTS: u32 = 8;
+/// Wait before the first reconnection attempt, doubled after each failure.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Longest wait between reconnection attempts.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// Lines Page Up and Page Down scroll the results by.
+const RESULTS_PAGE: usize = 10;
 
-    let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
-        Ok(result) => result,
-        Err(e) => {
-            return Err(format!("Failed to connect to server: {}", e).into());
-        }
-    };
+/// Settings for [`run`].
+#[derive(Debug, Clone, Default)]
+pub struct JoinOptions {
+    /// Code of the room to join; `None` joins the server's first room.
+    pub room: Option<String>,
+    /// Filled in on the name screen.
+    pub username: Option<String>,
+    /// Ask the server for MessagePack instead of JSON messages.
+    pub binary: bool,
+}
 
-    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+/// Run the quiz client, drawn with `theme` and sized by `layout`.
+///
+/// `alerts` get the player's attention when the quiz starts, when an
+/// answer is sent and when the host announces something.
+///
+/// `host` may start with `wss://` to connect over TLS, e.g.
+/// `wss://quiz.example.com`; without a scheme, `ws://` is used.
+pub async fn run(
+    host: String,
+    port: u16,
+    theme: Theme,
+    layout: LayoutOptions,
+    alerts: Alerts,
+    options: JoinOptions,
+) -> Result<(), QuizError> {
+    println!("Connecting to {}...", server_url(&host, port, options.room.as_deref()));
+    let client = QuizClient::connect(&host, port, options).await?;
+    client.run_tui(theme, layout, alerts).await
+}
+
+/// A connection to a quiz server, for playing from another program.
+///
+/// The client follows the quiz in the background without drawing
+/// anything, reconnecting if the connection drops, and plays through
+/// [`QuizClient::join`] and [`QuizClient::answer`]; or the player's
+/// interface can be shown with [`QuizClient::run_tui`]. Dropping the client
+/// closes the connection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::client::{ClientState, JoinOptions, QuizClient};
+///
+/// # async fn play() -> Result<(), rust_quiz::QuizError> {
+/// let client = QuizClient::connect("localhost", 8712, JoinOptions::default()).await?;
+/// client.join("robot");
+/// // ...once the host starts the quiz
+/// if let ClientState::Quiz { current_index, .. } = client.state().await {
+///     client.answer(current_index, vec![0]);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct QuizClient {
+    app: SharedApp,
+    tx: mpsc::UnboundedSender<ClientMessage>,
+    connection_task: JoinHandle<()>,
+}
 
-    // Create channel for outgoing messages
-    let (tx, mut rx) = mpsc::unbounded_channel::<ClientMessage>();
+impl QuizClient {
+    /// Connect to the server at `host`, which may start with `wss://` as
+    /// for [`run`].
+    pub async fn connect(
+        host: &str,
+        port: u16,
+        options: JoinOptions,
+    ) -> Result<Self, QuizError> {
+        let JoinOptions {
+            room,
+            username,
+            binary,
+        } = options;
+        let mut client_app = ClientApp::new(host.to_string(), port);
+        client_app.default_username = username;
+        client_app.room = room.clone();
+        let app = Arc::new(Mutex::new(client_app));
 
-    // Spawn task to send messages
-    tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            let json = serde_json::to_string(&msg).unwrap();
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
-                break;
+        // Connect to server
+        let url = server_url(host, port, room.as_deref());
+        let (ws_stream, encoding) = match connect(&url, binary).await {
+            Ok(connected) => connected,
+            Err(error) => {
+                let error = Box::new(error);
+                return Err(NetworkError::Connect { url, error }.into());
             }
+        };
+
+        // Create channel for outgoing messages
+        let (tx, rx) = mpsc::unbounded_channel::<ClientMessage>();
+
+        // Spawn task to relay messages, reconnecting if the connection drops
+        let connection = Connection {
+            url,
+            binary,
+            ws_stream,
+            encoding,
+        };
+        let connection_task = tokio::spawn(stay_connected(Arc::clone(&app), connection, rx));
+
+        Ok(Self {
+            app,
+            tx,
+            connection_task,
+        })
+    }
+
+    /// Ask to join as `username`; the state moves to the lobby if the
+    /// server accepts, and has the reason in its name entry otherwise.
+    pub fn join(&self, username: &str) {
+        let _ = self.tx.send(ClientMessage::Join {
+            username: username.to_string(),
+        });
+    }
+
+    /// Answer question `question_index` with the indices of the chosen
+    /// options.
+    pub fn answer(&self, question_index: usize, answers: Vec<usize>) {
+        let _ = self.tx.send(ClientMessage::SubmitAnswer {
+            question_index,
+            answers,
+            confidence: None,
+        });
+    }
+
+    /// Answer question `question_index` and say how sure the player is, for
+    /// hosts that ask.
+    pub fn answer_with_confidence(
+        &self,
+        question_index: usize,
+        answers: Vec<usize>,
+        confidence: Confidence,
+    ) {
+        let _ = self.tx.send(ClientMessage::SubmitAnswer {
+            question_index,
+            answers,
+            confidence: Some(confidence),
+        });
+    }
+
+    /// Send a chat message to the room.
+    pub fn chat(&self, text: &str) {
+        let _ = self.tx.send(ClientMessage::Chat {
+            text: text.to_string(),
+        });
+    }
+
+    /// Where the player is in the quiz.
+    pub async fn state(&self) -> ClientState {
+        self.app.lock().await.state.clone()
+    }
+
+    /// Usernames of the players in the room.
+    pub async fn players(&self) -> Vec<String> {
+        self.app.lock().await.players.clone()
+    }
+
+    /// Latest standings, while the quiz is running.
+    pub async fn standings(&self) -> Vec<StandingEntry> {
+        self.app.lock().await.standings.clone()
+    }
+
+    /// Draw the player's interface at `width` by `height` cells with the
+    /// default theme and layout, off screen, and return what was drawn.
+    pub async fn render_to_buffer(&self, width: u16, height: u16) -> Buffer {
+        let app = self.app.lock().await;
+        terminal::render_to_buffer(width, height, |frame| {
+            ui::render(frame, &app, &Theme::default(), &LayoutOptions::default())
+        })
+    }
+
+    /// Draw the player's interface in the terminal until the player quits,
+    /// then close the connection.
+    pub async fn run_tui(
+        self,
+        theme: Theme,
+        layout: LayoutOptions,
+        alerts: Alerts,
+    ) -> Result<(), QuizError> {
+        let mut terminal = terminal::init().map_err(QuizError::Terminal)?;
+        let result = self.run_tui_on(&mut terminal, theme, layout, alerts).await;
+        terminal::restore().map_err(QuizError::Terminal)?;
+        result
+    }
+
+    /// Draw the player's interface on a terminal the caller has set up,
+    /// e.g. one drawing to stderr, until the player quits, then close the
+    /// connection.
+    ///
+    /// Keys are still read with crossterm. Unlike [`QuizClient::run_tui`],
+    /// raw mode and the alternate screen are left to the caller.
+    pub async fn run_tui_on<B: Backend>(
+        self,
+        terminal: &mut Terminal<B>,
+        theme: Theme,
+        layout: LayoutOptions,
+        alerts: Alerts,
+    ) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        let app = Arc::clone(&self.app);
+        run_tui(terminal, app, self.tx.clone(), theme, layout, alerts).await
+    }
+}
+
+impl Drop for QuizClient {
+    fn drop(&mut self) {
+        self.connection_task.abort();
+    }
+}
+
+/// An open connection to the server, and how to open another.
+struct Connection {
+    url: String,
+    /// Whether to ask for MessagePack messages.
+    binary: bool,
+    ws_stream: WsStream,
+    encoding: Encoding,
+}
+
+/// Connect to `url`, asking for MessagePack messages if `binary`, and
+/// return the stream with the encoding the server agreed to.
+async fn connect(url: &str, binary: bool) -> Result<(WsStream, Encoding), WsError> {
+    let mut request = url.into_client_request()?;
+    if binary {
+        request
+            .headers_mut()
+            .insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(MSGPACK_PROTOCOL));
+    }
+    let (ws_stream, response) = tokio_tungstenite::connect_async(request).await?;
+    let protocol = response.headers().get(SEC_WEBSOCKET_PROTOCOL);
+    let encoding = Encoding::negotiate(protocol.and_then(|value| value.to_str().ok()));
+    Ok((ws_stream, encoding))
+}
+
+/// Relay messages over the connection and, whenever it drops without the
+/// server giving a reason, reconnect with exponential backoff.
+///
+/// The server recognises the player by their address and resumes their
+/// session, sending the current question again. Players answering offline
+/// keep answering meanwhile, and keep trying to reconnect for as long as
+/// they do; their answers are sent again once the connection is back.
+async fn stay_connected(
+    app: SharedApp,
+    mut connection: Connection,
+    mut rx: mpsc::UnboundedReceiver<ClientMessage>,
+) {
+    loop {
+        let Err(lost) = relay(&app, connection.ws_stream, connection.encoding, &mut rx).await
+        else {
+            return;
+        };
+
+        // Finished players have nothing to resume
+        if matches!(app.lock().await.state, ClientState::Results { .. }) {
+            app.lock().await.disconnect(lost);
+            return;
         }
-    });
 
-    // Spawn task to receive messages
-    let app_clone = Arc::clone(&app);
-    let recv_task = tokio::spawn(async move {
-        while let Some(msg) = ws_receiver.next().await {
-            let text = match msg {
-                Ok(Message::Text(text)) => text.to_string(),
-                Ok(Message::Close(_)) => {
-                    let mut app = app_clone.lock().await;
-                    app.disconnect("Connection closed by server".to_string());
-                    break;
-                }
-                Err(e) => {
-                    let mut app = app_clone.lock().await;
-                    app.disconnect(format!("Connection error: {}", e));
-                    break;
-                }
-                _ => continue,
+        // Messages sent while the connection is down, to send once it's back
+        let mut unsent = Vec::new();
+        let mut attempt = 0;
+        (connection.ws_stream, connection.encoding) = loop {
+            attempt += 1;
+            if attempt > MAX_RECONNECT_ATTEMPTS && !app.lock().await.answers_offline() {
+                let mut app = app.lock().await;
+                app.reconnecting = None;
+                app.disconnect(lost);
+                return;
+            }
+            app.lock().await.reconnecting = Some(attempt);
+            let reconnect = async {
+                tokio::time::sleep(reconnect_delay(attempt)).await;
+                connect(&connection.url, connection.binary).await
             };
-
-            let server_msg: ServerMessage = match serde_json::from_str(&text) {
-                Ok(m) => m,
-                Err(_) => continue,
+            tokio::pin!(reconnect);
+            let reconnected = loop {
+                tokio::select! {
+                    reconnected = &mut reconnect => break reconnected,
+                    msg = rx.recv() => {
+                        // The TUI has gone
+                        let Some(msg) = msg else {
+                            return;
+                        };
+                        if outgoing(&app, &msg).await {
+                            unsent.push(msg);
+                        }
+                    }
+                }
             };
+            if let Ok(reconnected) = reconnected {
+                break reconnected;
+            }
+        };
 
-            handle_server_message(&app_clone, server_msg).await;
+        // Answers given offline go first, in case some never arrived
+        let given = {
+            let mut app = app.lock().await;
+            app.reconnecting = None;
+            app.answers_offline().then(|| app.given.clone())
+        };
+        let sync = given
+            .filter(|answers| !answers.is_empty())
+            .map(|answers| ClientMessage::SyncAnswers { answers });
+        for msg in sync.into_iter().chain(unsent) {
+            if connection.ws_stream.send(connection.encoding.encode(&msg)).await.is_err() {
+                break;
+            }
         }
+    }
+}
+
+/// Let the app know about a message on its way to the server, returning
+/// `false` if it shouldn't be sent now.
+///
+/// Players answering offline move on to the next question as soon as they
+/// answer. Their answers only go out while connected, as all of them are
+/// sent again on reconnecting, and repeats go nowhere.
+async fn outgoing(app: &SharedApp, msg: &ClientMessage) -> bool {
+    let ClientMessage::SubmitAnswer {
+        question_index,
+        answers,
+        confidence,
+    } = msg
+    else {
+        return true;
+    };
+    let mut app = app.lock().await;
+    if !app.answers_offline() {
+        return true;
+    }
+    let seconds = app.question_shown_at.map(|shown_at| shown_at.elapsed().as_secs_f64());
+    let answered = app.answer_offline(SubmittedAnswer {
+        question_index: *question_index,
+        answers: answers.clone(),
+        confidence: *confidence,
+        seconds,
     });
+    answered && app.reconnecting.is_none()
+}
 
-    // Run TUI
-    run_tui(app, tx).await?;
+/// Pass messages between the TUI and the server until the connection ends.
+///
+/// Returns `Ok` if it ended on purpose: the player quit, or the server gave
+/// a reason such as being kicked. Otherwise returns why it was lost.
+async fn relay(
+    app: &SharedApp,
+    ws_stream: WsStream,
+    encoding: Encoding,
+    rx: &mut mpsc::UnboundedReceiver<ClientMessage>,
+) -> Result<(), String> {
+    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+
+    let lost = loop {
+        tokio::select! {
+            msg = rx.recv() => {
+                // The TUI has gone
+                let Some(msg) = msg else {
+                    return Ok(());
+                };
+                if !outgoing(app, &msg).await {
+                    continue;
+                }
+                if let Err(e) = ws_sender.send(encoding.encode(&msg)).await {
+                    break format!("Connection error: {}", e);
+                }
+            }
+            frame = ws_receiver.next() => {
+                let frame = match frame {
+                    Some(Ok(frame @ (Message::Text(_) | Message::Binary(_)))) => frame,
+                    // Read on, which replies to a close and ends the stream
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => break format!("Connection error: {}", e),
+                    None => break "Connection closed by server".to_string(),
+                };
+                if let Some(server_msg) = decode::<ServerMessage>(&frame) {
+                    handle_server_message(app, server_msg).await;
+                }
+            }
+        }
+    };
 
-    // Clean up
-    recv_task.abort();
+    // Keep the reason if the server gave one, e.g. when closing
+    if matches!(app.lock().await.state, ClientState::Disconnected { .. }) {
+        Ok(())
+    } else {
+        Err(lost)
+    }
+}
 
-    Ok(())
+/// How long to wait before reconnection attempt `attempt`, from 1.
+fn reconnect_delay(attempt: u32) -> Duration {
+    RECONNECT_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RECONNECT_DELAY)
+}
+
+/// WebSocket URL of `room` on the server at `host`, which may start with a
+/// scheme.
+fn server_url(host: &str, port: u16, room: Option<&str>) -> String {
+    let (scheme, host) = host.split_once("://").unwrap_or(("ws", host));
+    format!("{}://{}:{}/{}", scheme, host, port, room.unwrap_or_default())
 }
 
 /// Handle a message from the server.
 async fn handle_server_message(app: &SharedApp, msg: ServerMessage) {
-    let mut app = app.lock().await;
+    apply_server_message(&mut *app.lock().await, msg);
+}
+
+/// Update the app for a message from the server.
+fn apply_server_message(app: &mut ClientApp, msg: ServerMessage) {
+    // What comes after an answer waits until the player has seen whether
+    // it was right
+    if app.feedback.is_some()
+        && matches!(
+            msg,
+            ServerMessage::Question { .. }
+                | ServerMessage::ProgressSummary { .. }
+                | ServerMessage::QuizResults { .. }
+        )
+    {
+        app.held.push(msg);
+        return;
+    }
 
     match msg {
         ServerMessage::ConnectionAck => {
             app.enter_name_entry();
         }
-        ServerMessage::JoinAccepted { username } => {
+        ServerMessage::JoinAccepted {
+            username,
+            confirm_answers,
+            confidence_mode,
+            speed_bonus_window,
+        } => {
+            app.confirm_answers = confirm_answers;
+            app.confidence_mode = confidence_mode;
+            app.speed_bonus_window = speed_bonus_window.map(Duration::from_secs);
             app.enter_lobby(username);
         }
         ServerMessage::JoinRejected { reason } => {
@@ -100,54 +491,165 @@ async fn handle_server_message(app: &SharedApp, msg: ServerMessage) {
         }
         ServerMessage::ReconnectAccepted {
             username,
-            current_question: _,
+            current_question,
+            confirm_answers,
+            confidence_mode,
+            speed_bonus_window,
+            questions,
         } => {
-            // We'll receive QuizStart and Question messages separately
-            // For now, just note we reconnected
-            app.state = ClientState::Lobby { username };
+            app.confirm_answers = confirm_answers;
+            app.confidence_mode = confidence_mode;
+            app.speed_bonus_window = speed_bonus_window.map(Duration::from_secs);
+            // The server sends the current question, whether the quiz is
+            // paused and any scheduled start again, so a quiz in progress
+            // carries on where it was
+            app.paused = false;
+            app.starts_at = None;
+            let resuming = matches!(
+                &app.state,
+                ClientState::Quiz { username: current, .. }
+                | ClientState::Waiting { username: current, .. } if *current == username
+            );
+            // Players answering offline carry on from where they were, or
+            // from where the server has them after starting afresh
+            let questions: Vec<_> = questions.into_iter().map(QuestionData::from).collect();
+            if !resuming {
+                match questions.get(current_question).cloned() {
+                    Some(question) => {
+                        app.enter_quiz(username, questions.len());
+                        app.set_question(question);
+                    }
+                    None => app.state = ClientState::Lobby { username },
+                }
+            }
+            app.questions = questions;
+        }
+        ServerMessage::StartsIn { seconds } => {
+            app.starts_at = Some(Instant::now() + Duration::from_secs(seconds.into()));
         }
-        ServerMessage::QuizStart { total_questions } => {
+        ServerMessage::StartCancelled => {
+            app.starts_at = None;
+        }
+        ServerMessage::QuizStart {
+            total_questions,
+            questions,
+        } => {
+            // The player may be in another window while waiting in the lobby
+            if matches!(app.state, ClientState::Lobby { .. }) {
+                app.alert_pending = true;
+                app.notification = Some("The quiz is starting".to_string());
+            }
             let username = app.state.username().unwrap_or("").to_string();
             app.enter_quiz(username, total_questions);
+            app.questions = questions.into_iter().map(QuestionData::from).collect();
+        }
+        ServerMessage::Countdown { seconds } => {
+            let seconds = Duration::from_secs(seconds.into());
+            app.countdown_ends = Some(Instant::now() + seconds);
         }
         ServerMessage::Question {
             index,
             text,
             code,
+            code_language,
             options,
+            multiple,
         } => {
+            // Players answering offline move on by themselves, so only the
+            // first question, ending the countdown, is news
+            if app.answers_offline()
+                && !matches!(app.state, ClientState::Quiz { current_question: None, .. })
+            {
+                return;
+            }
+            let question = QuestionData {
+                index,
+                text,
+                code,
+                code_language,
+                options,
+                multiple,
+            };
             // Update quiz with new question
             if let ClientState::Quiz { .. } = &app.state {
-                app.set_question(index, text, code, options);
+                app.set_question(question);
             } else {
                 // Might be reconnecting or late joining
                 let username = app.state.username().unwrap_or("").to_string();
                 // We don't have total here, but we can estimate
                 app.state = ClientState::Quiz {
                     username,
-                    current_question: Some(super::state::QuestionData {
-                        index,
-                        text,
-                        code,
-                        options,
-                    }),
+                    current_question: Some(question),
                     current_index: index,
                     total: index + 1, // Will be updated as we get more questions
                     selected_option: 0,
+                    checked_options: [false; 4],
                 };
             }
         }
+        ServerMessage::AnswerFeedback {
+            correct,
+            correct_answer,
+        } => {
+            if matches!(app.state, ClientState::Quiz { .. }) {
+                app.show_feedback(correct, correct_answer);
+            }
+        }
+        ServerMessage::ProgressSummary { finished, players } => {
+            app.enter_waiting(finished, players);
+        }
         ServerMessage::QuizResults {
             score,
             total,
+            seconds,
             answers,
             leaderboard,
+            grades,
         } => {
-            app.enter_results(score, total, answers, leaderboard);
+            app.enter_results(score, total, seconds, answers, leaderboard, grades);
+        }
+        ServerMessage::Standings { standings } => {
+            app.standings = standings;
+        }
+        ServerMessage::Progress { players } => {
+            app.progress = players;
+        }
+        ServerMessage::LobbyUpdate { players } => {
+            app.players = players;
+        }
+        ServerMessage::Announcement { text } => {
+            app.alert_pending = true;
+            app.notification = Some(format!("Host: {}", text));
+            app.announcement = Some((text, Instant::now()));
+        }
+        ServerMessage::Paused => {
+            app.paused = true;
+        }
+        ServerMessage::Resumed => {
+            app.paused = false;
+        }
+        ServerMessage::Chat { username, text } => {
+            app.push_chat(Some(username), text);
+        }
+        ServerMessage::ChatRejected { reason } => {
+            app.push_chat(None, reason);
+        }
+        ServerMessage::RoomNotFound { code } => {
+            app.disconnect(format!("No room with code {}", code));
         }
         ServerMessage::Kicked { reason } => {
             app.disconnect(format!("Kicked: {}", reason));
         }
+        ServerMessage::ReturnToLobby { username } => {
+            // The player may still be reading their results
+            if matches!(app.state, ClientState::Results { .. }) {
+                app.alert_pending = true;
+                app.notification = Some("The host started another round".to_string());
+            }
+            app.standings.clear();
+            app.progress.clear();
+            app.enter_lobby(username);
+        }
         ServerMessage::HostEndedQuiz => {
             app.disconnect("HOST ENDED QUIZ".to_string());
         }
@@ -157,12 +659,38 @@ async fn handle_server_message(app: &SharedApp, msg: ServerMessage) {
     }
 }
 
+/// Send the selected answer to the current question, if any, with how
+/// sure the player is of it in confidence mode.
+fn submit_answer(
+    app: &mut ClientApp,
+    tx: &mpsc::UnboundedSender<ClientMessage>,
+    confidence: Option<Confidence>,
+) {
+    let question_index = app.current_question_index();
+    let answers = app.answer_to_submit();
+    if !answers.is_empty() {
+        let _ = tx.send(ClientMessage::SubmitAnswer {
+            question_index,
+            answers,
+            confidence,
+        });
+        app.alert_pending = true;
+    }
+}
+
 /// Run the client TUI.
-async fn run_tui(
+async fn run_tui<B: Backend>(
+    terminal: &mut Terminal<B>,
     app: SharedApp,
     tx: mpsc::UnboundedSender<ClientMessage>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut terminal = terminal::init()?;
+    theme: Theme,
+    layout: LayoutOptions,
+    alerts: Alerts,
+) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
+    let mut alerter = Alerter::new(alerts);
 
     loop {
         // Check if should quit
@@ -175,26 +703,36 @@ async fn run_tui(
 
         // Render UI
         {
-            let app = app.lock().await;
-            terminal.draw(|frame| ui::render(frame, &app))?;
+            let mut app = app.lock().await;
+            if std::mem::take(&mut app.alert_pending) {
+                alerter.alert()?;
+            }
+            if let Some(text) = app.notification.take() {
+                alerter.notify(&text)?;
+            }
+            terminal
+                .draw(|frame| {
+                    ui::render(frame, &app, &theme, &layout);
+                    alerter.render(frame);
+                })
+                .map_err(terminal::backend_error::<B>)?;
         }
 
         // Handle input with timeout
         if event::poll(Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
-
-                let should_quit = handle_input(&app, &tx, key.code).await;
-                if should_quit {
-                    break;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let should_quit = handle_input(&app, &tx, key.code).await;
+                    if should_quit {
+                        break;
+                    }
                 }
+                Event::Resize(..) => terminal::handle_resize(terminal)?,
+                _ => {}
             }
         }
     }
 
-    terminal::restore()?;
     Ok(())
 }
 
@@ -206,6 +744,67 @@ async fn handle_input(
 ) -> bool {
     let mut app = app.lock().await;
 
+    // Only quitting works while the connection is down, unless the player
+    // can carry on answering offline
+    if app.reconnecting.is_some() && !app.answers_offline() {
+        if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q')) {
+            app.should_quit = true;
+            return true;
+        }
+        return false;
+    }
+
+    // An answer awaiting confirmation is sent with Enter or y, and kept
+    // back with Esc or n; other keys carry on as usual
+    if app.confirming && !app.paused {
+        app.confirming = false;
+        match key {
+            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                submit_answer(&mut app, tx, None);
+                return false;
+            }
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => return false,
+            _ => {}
+        }
+    }
+
+    // In confidence mode the answer is sent with s (sure) or u (unsure),
+    // and kept back with Esc
+    if app.choosing_confidence && !app.paused {
+        let confidence = match key {
+            KeyCode::Char('s') | KeyCode::Char('S') => Confidence::Sure,
+            KeyCode::Char('u') | KeyCode::Char('U') => Confidence::Unsure,
+            KeyCode::Esc => {
+                app.choosing_confidence = false;
+                return false;
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => {
+                app.should_quit = true;
+                return true;
+            }
+            _ => return false,
+        };
+        app.choosing_confidence = false;
+        submit_answer(&mut app, tx, Some(confidence));
+        return false;
+    }
+
+    // Keys go to the chat message while one is being typed
+    if app.is_composing() {
+        match key {
+            KeyCode::Char(c) => app.chat_input_push(c),
+            KeyCode::Backspace => app.chat_input_pop(),
+            KeyCode::Enter => {
+                if let Some(text) = app.take_chat_input() {
+                    let _ = tx.send(ClientMessage::Chat { text });
+                }
+            }
+            KeyCode::Esc => app.chat_input = None,
+            _ => {}
+        }
+        return false;
+    }
+
     match &app.state {
         ClientState::Connecting => {
             if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q')) {
@@ -241,11 +840,33 @@ async fn handle_input(
             }
         }
         ClientState::Lobby { .. } => {
-            if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc) {
+            match key {
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    app.open_chat();
+                }
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                    app.should_quit = true;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        ClientState::Quiz { .. } if app.paused => {
+            if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q')) {
                 app.should_quit = true;
                 return true;
             }
         }
+        // Any other key goes on from the verdict to what was held back
+        ClientState::Quiz { .. } if app.feedback.is_some() => {
+            if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q')) {
+                app.should_quit = true;
+                return true;
+            }
+            for msg in app.clear_feedback() {
+                apply_server_message(&mut app, msg);
+            }
+        }
         ClientState::Quiz { current_question, .. } => {
             match key {
                 KeyCode::Up | KeyCode::Char('k') => {
@@ -254,14 +875,17 @@ async fn handle_input(
                 KeyCode::Down | KeyCode::Char('j') => {
                     app.select_next_option();
                 }
-                KeyCode::Enter | KeyCode::Char(' ') => {
-                    if current_question.is_some() {
-                        let question_index = app.current_question_index();
-                        let answer = app.selected_option();
-                        let _ = tx.send(ClientMessage::SubmitAnswer {
-                            question_index,
-                            answer,
-                        });
+                KeyCode::Char(' ') if app.is_multiple_question() => {
+                    app.toggle_selected_option();
+                }
+                KeyCode::Enter | KeyCode::Char(' ') if current_question.is_some() => {
+                    if app.confidence_mode {
+                        // Choosing a confidence confirms the answer as well
+                        app.choosing_confidence = !app.answer_to_submit().is_empty();
+                    } else if !app.confirm_answers {
+                        submit_answer(&mut app, tx, None);
+                    } else if !app.answer_to_submit().is_empty() {
+                        app.confirming = true;
                     }
                 }
                 KeyCode::Char('q') | KeyCode::Char('Q') => {
@@ -271,13 +895,37 @@ async fn handle_input(
                 _ => {}
             }
         }
+        ClientState::Waiting { .. } => {
+            if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc) {
+                app.should_quit = true;
+                return true;
+            }
+        }
         ClientState::Results { .. } => {
             match key {
                 KeyCode::Down | KeyCode::Char('j') => {
-                    app.scroll_results_down();
+                    app.scroll_results_down(1);
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
-                    app.scroll_results_up();
+                    app.scroll_results_up(1);
+                }
+                KeyCode::PageDown => {
+                    app.scroll_results_down(RESULTS_PAGE);
+                }
+                KeyCode::PageUp => {
+                    app.scroll_results_up(RESULTS_PAGE);
+                }
+                KeyCode::Home => {
+                    app.scroll_results_up(usize::MAX);
+                }
+                KeyCode::End => {
+                    app.scroll_results_down(usize::MAX);
+                }
+                KeyCode::Char('w') | KeyCode::Char('W') => {
+                    app.toggle_wrong_only();
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    app.open_chat();
                 }
                 KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
                     app.should_quit = true;
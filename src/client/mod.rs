@@ -2,8 +2,10 @@
 //!
 //! Provides WebSocket-based multiplayer quiz client.
 
+#[allow(clippy::module_inception)]
 mod client;
 mod state;
 mod ui;
 
-pub use client::run;
+pub use client::{run, JoinOptions, QuizClient};
+pub use state::{ClientState, QuestionData};
@@ -1,11 +1,18 @@
 //! Client state management.
 
-use crate::protocol::{AnswerResult, LeaderboardEntry};
+use std::time::{Duration, Instant};
+
+use crate::models::Grades;
+use crate::protocol::{
+    AnswerResult, LeaderboardEntry, PlayerProgress, QuizQuestion, ServerMessage, StandingEntry,
+    SubmittedAnswer, CHAT_MAX_LENGTH,
+};
 
 /// Current state of the client.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum ClientState {
     /// Connecting to server.
+    #[default]
     Connecting,
 
     /// Entering username.
@@ -24,15 +31,32 @@ pub enum ClientState {
         current_index: usize,
         total: usize,
         selected_option: usize,
+        /// Ticked options on a checkbox question.
+        checked_options: [bool; 4],
+    },
+
+    /// Answered every question, waiting for the others to finish.
+    Waiting {
+        username: String,
+        /// Players who have finished, including this one.
+        finished: usize,
+        /// Players taking the quiz.
+        players: usize,
     },
 
     /// Viewing results after quiz completion.
     Results {
         score: usize,
         total: usize,
+        /// Seconds taken over the whole quiz.
+        seconds: Option<f64>,
         answers: Vec<AnswerResult>,
         leaderboard: Vec<LeaderboardEntry>,
+        /// How the quiz grades scores.
+        grades: Grades,
         scroll: usize,
+        /// Only wrong answers are listed.
+        wrong_only: bool,
     },
 
     /// Disconnected from server.
@@ -46,20 +70,30 @@ pub struct QuestionData {
     pub index: usize,
     pub text: String,
     pub code: Option<String>,
+    pub code_language: Option<String>,
     pub options: [String; 4],
+    /// Whether several options may be selected (checkbox question).
+    pub multiple: bool,
 }
 
-impl Default for ClientState {
-    fn default() -> Self {
-        Self::Connecting
+impl From<QuizQuestion> for QuestionData {
+    fn from(question: QuizQuestion) -> Self {
+        Self {
+            index: question.index,
+            text: question.text,
+            code: question.code,
+            code_language: question.code_language,
+            options: question.options,
+            multiple: question.multiple,
+        }
     }
 }
 
 impl ClientState {
-    /// Create a new name entry state.
-    pub fn name_entry() -> Self {
+    /// Create a new name entry state, with `input` already typed.
+    pub fn name_entry(input: String) -> Self {
         Self::NameEntry {
-            input: String::new(),
+            input,
             error: None,
         }
     }
@@ -77,6 +111,7 @@ impl ClientState {
             current_index: 0,
             total,
             selected_option: 0,
+            checked_options: [false; 4],
         }
     }
 
@@ -84,15 +119,20 @@ impl ClientState {
     pub fn results(
         score: usize,
         total: usize,
+        seconds: Option<f64>,
         answers: Vec<AnswerResult>,
         leaderboard: Vec<LeaderboardEntry>,
+        grades: Grades,
     ) -> Self {
         Self::Results {
             score,
             total,
+            seconds,
             answers,
             leaderboard,
+            grades,
             scroll: 0,
+            wrong_only: false,
         }
     }
 
@@ -110,12 +150,22 @@ impl ClientState {
     /// Get the username if available.
     pub fn username(&self) -> Option<&str> {
         match self {
-            Self::Lobby { username } | Self::Quiz { username, .. } => Some(username),
+            Self::Lobby { username }
+            | Self::Quiz { username, .. }
+            | Self::Waiting { username, .. } => Some(username),
             _ => None,
         }
     }
 }
 
+/// A line in the chat panel.
+#[derive(Debug, Clone)]
+pub struct ChatLine {
+    /// Sender, or `None` for notices from the server.
+    pub username: Option<String>,
+    pub text: String,
+}
+
 /// Client application state.
 pub struct ClientApp {
     /// Current state.
@@ -124,8 +174,70 @@ pub struct ClientApp {
     pub host: String,
     /// Server port.
     pub port: u16,
+    /// Code of the room to join, or `None` for the server's first room.
+    pub room: Option<String>,
     /// Whether the client should quit.
     pub should_quit: bool,
+    /// Whether the quiz started, an answer was sent or the host announced
+    /// something since the TUI last alerted the player.
+    pub alert_pending: bool,
+    /// Desktop notification for the TUI to send with the next alert.
+    pub notification: Option<String>,
+    /// Name filled in on the name entry screen.
+    pub default_username: Option<String>,
+    /// Whether the host has paused the quiz.
+    pub paused: bool,
+    /// Latest interim standings, while the quiz is running.
+    pub standings: Vec<StandingEntry>,
+    /// How far each player has got, while the quiz is running.
+    pub progress: Vec<PlayerProgress>,
+    /// Usernames of everyone connected to the room.
+    pub players: Vec<String>,
+    /// Recent chat messages in the room.
+    pub chat: Vec<ChatLine>,
+    /// Chat message being typed, or `None` when not composing.
+    pub chat_input: Option<String>,
+    /// Whether the host wants answers confirmed before they are sent.
+    pub confirm_answers: bool,
+    /// Whether the selected answer is waiting to be confirmed.
+    pub confirming: bool,
+    /// Whether the host wants to know how sure players are of each answer.
+    pub confidence_mode: bool,
+    /// Whether the selected answer is waiting for how sure the player is.
+    pub choosing_confidence: bool,
+    /// When the host's scheduled start comes, while waiting in the lobby.
+    pub starts_at: Option<Instant>,
+    /// When the first question arrives, while counting down to it.
+    pub countdown_ends: Option<Instant>,
+    /// How long a correct answer earns a speed bonus, if the host turned it
+    /// on.
+    pub speed_bonus_window: Option<Duration>,
+    /// When the question on screen arrived.
+    pub question_shown_at: Option<Instant>,
+    /// Latest message from the host, and when it arrived.
+    pub announcement: Option<(String, Instant)>,
+    /// Reconnection attempt under way after losing the connection.
+    pub reconnecting: Option<u32>,
+    /// Verdict on the answer just sent, shown until the player moves on,
+    /// when the host turned on feedback.
+    pub feedback: Option<Feedback>,
+    /// Next question or results, held back while the verdict is shown.
+    pub held: Vec<ServerMessage>,
+    /// Every question, when the host sent them all at the start for
+    /// answering offline.
+    pub questions: Vec<QuestionData>,
+    /// Answers given while answering offline, sent again after
+    /// reconnecting in case some never arrived.
+    pub given: Vec<SubmittedAnswer>,
+}
+
+/// Whether an answer was right, with the options the player picked and the
+/// right ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feedback {
+    pub correct: bool,
+    pub answer: Vec<usize>,
+    pub correct_answer: Vec<usize>,
 }
 
 impl ClientApp {
@@ -135,54 +247,108 @@ impl ClientApp {
             state: ClientState::Connecting,
             host,
             port,
+            room: None,
             should_quit: false,
+            alert_pending: false,
+            notification: None,
+            default_username: None,
+            paused: false,
+            standings: Vec::new(),
+            progress: Vec::new(),
+            players: Vec::new(),
+            chat: Vec::new(),
+            chat_input: None,
+            confirm_answers: false,
+            confirming: false,
+            confidence_mode: false,
+            choosing_confidence: false,
+            starts_at: None,
+            countdown_ends: None,
+            speed_bonus_window: None,
+            question_shown_at: None,
+            announcement: None,
+            reconnecting: None,
+            feedback: None,
+            held: Vec::new(),
+            questions: Vec::new(),
+            given: Vec::new(),
         }
     }
 
-    /// Get the server address string.
+    /// Get the server address string, with the room code if there is one.
     pub fn server_addr(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        match &self.room {
+            Some(room) => format!("{}:{}/{}", self.host, self.port, room),
+            None => format!("{}:{}", self.host, self.port),
+        }
     }
 
     /// Move to name entry state.
     pub fn enter_name_entry(&mut self) {
-        self.state = ClientState::name_entry();
+        let input = self.default_username.clone().unwrap_or_default();
+        self.state = ClientState::name_entry(input);
     }
 
     /// Move to lobby state.
     pub fn enter_lobby(&mut self, username: String) {
+        self.clear_feedback();
+        self.questions.clear();
+        self.given.clear();
         self.state = ClientState::lobby(username);
     }
 
     /// Move to quiz state.
     pub fn enter_quiz(&mut self, username: String, total: usize) {
+        self.clear_feedback();
+        self.given.clear();
+        self.chat_input = None;
+        self.starts_at = None;
+        self.paused = false;
+        self.progress.clear();
         self.state = ClientState::quiz(username, total);
     }
 
     /// Set the current question.
-    pub fn set_question(
-        &mut self,
-        index: usize,
-        text: String,
-        code: Option<String>,
-        options: [String; 4],
-    ) {
+    pub fn set_question(&mut self, question: QuestionData) {
         if let ClientState::Quiz {
             current_question,
             current_index,
             selected_option,
+            checked_options,
             ..
         } = &mut self.state
         {
-            *current_question = Some(QuestionData {
-                index,
-                text,
-                code,
-                options,
-            });
-            *current_index = index;
+            *current_index = question.index;
+            *current_question = Some(question);
             *selected_option = 0;
+            *checked_options = [false; 4];
+        }
+        self.confirming = false;
+        self.choosing_confidence = false;
+        self.countdown_ends = None;
+        self.question_shown_at = Some(Instant::now());
+    }
+
+    /// Time left to earn the speed bonus on the question on screen, with the
+    /// length of the bonus window, when the host turned it on.
+    pub fn speed_bonus_left(&self) -> Option<(Duration, Duration)> {
+        let window = self.speed_bonus_window?;
+        let shown_at = self.question_shown_at?;
+        Some((window.saturating_sub(shown_at.elapsed()), window))
+    }
+
+    /// Wait for results, with how many players have finished so far.
+    pub fn enter_waiting(&mut self, finished: usize, players: usize) {
+        // Results and disconnection come after waiting, never before
+        if matches!(self.state, ClientState::Results { .. } | ClientState::Disconnected { .. }) {
+            return;
         }
+        let username = self.state.username().unwrap_or_default().to_string();
+        self.state = ClientState::Waiting {
+            username,
+            finished,
+            players,
+        };
     }
 
     /// Move to results state.
@@ -190,10 +356,73 @@ impl ClientApp {
         &mut self,
         score: usize,
         total: usize,
+        seconds: Option<f64>,
         answers: Vec<AnswerResult>,
         leaderboard: Vec<LeaderboardEntry>,
+        grades: Grades,
     ) {
-        self.state = ClientState::results(score, total, answers, leaderboard);
+        self.state = ClientState::results(score, total, seconds, answers, leaderboard, grades);
+    }
+
+    /// Check if the player moves through the questions by themselves,
+    /// having been sent them all, and may carry on while disconnected.
+    pub fn answers_offline(&self) -> bool {
+        !self.questions.is_empty()
+            && matches!(self.state, ClientState::Quiz { .. } | ClientState::Waiting { .. })
+    }
+
+    /// Keep an answer given while answering offline and move on to the next
+    /// question, or wait for results after the last.
+    ///
+    /// Returns `false` for an answer to a question already left behind,
+    /// which goes nowhere.
+    pub fn answer_offline(&mut self, answer: SubmittedAnswer) -> bool {
+        let ClientState::Quiz {
+            current_question: Some(_),
+            current_index,
+            ..
+        } = &self.state
+        else {
+            return false;
+        };
+        if answer.question_index != *current_index {
+            return false;
+        }
+        let next = answer.question_index + 1;
+        self.given.push(answer);
+
+        match self.questions.get(next) {
+            Some(question) => self.set_question(question.clone()),
+            None => {
+                // Go by the last progress heard until the server says more
+                let total = self.questions.len();
+                let you = self.state.username().unwrap_or_default();
+                let finished = self
+                    .progress
+                    .iter()
+                    .filter(|player| player.answered >= total || player.username == you)
+                    .count();
+                self.enter_waiting(finished.max(1), self.progress.len().max(1));
+            }
+        }
+        true
+    }
+
+    /// Show whether the answer just sent was right, until the player moves
+    /// on.
+    pub fn show_feedback(&mut self, correct: bool, correct_answer: Vec<usize>) {
+        self.feedback = Some(Feedback {
+            correct,
+            answer: self.answer_to_submit(),
+            correct_answer,
+        });
+    }
+
+    /// Forget the verdict and anything held back behind it, returning what
+    /// was held.
+    pub fn clear_feedback(&mut self) -> Vec<ServerMessage> {
+        self.feedback = None;
+        std::mem::take(&mut self.held)
     }
 
     /// Move to disconnected state.
@@ -233,6 +462,46 @@ impl ClientApp {
         }
     }
 
+    /// Tick or untick the selected option on a checkbox question.
+    pub fn toggle_selected_option(&mut self) {
+        if let ClientState::Quiz {
+            current_question: Some(question),
+            selected_option,
+            checked_options,
+            ..
+        } = &mut self.state
+            && question.multiple
+        {
+            checked_options[*selected_option] = !checked_options[*selected_option];
+        }
+    }
+
+    /// Check if the current question is a checkbox question.
+    pub fn is_multiple_question(&self) -> bool {
+        matches!(
+            &self.state,
+            ClientState::Quiz {
+                current_question: Some(question),
+                ..
+            } if question.multiple
+        )
+    }
+
+    /// Get the answer to submit for the current question.
+    ///
+    /// Returns the ticked options for checkbox questions (empty if nothing
+    /// is ticked) and the selected option otherwise.
+    pub fn answer_to_submit(&self) -> Vec<usize> {
+        match &self.state {
+            ClientState::Quiz {
+                current_question: Some(question),
+                checked_options,
+                ..
+            } if question.multiple => (0..4).filter(|&i| checked_options[i]).collect(),
+            _ => vec![self.selected_option()],
+        }
+    }
+
     /// Get current question index.
     pub fn current_question_index(&self) -> usize {
         if let ClientState::Quiz { current_index, .. } = &self.state {
@@ -242,30 +511,97 @@ impl ClientApp {
         }
     }
 
-    /// Scroll results down.
-    pub fn scroll_results_down(&mut self) {
+    /// Scroll results down by `lines`, stopping at the last answer.
+    pub fn scroll_results_down(&mut self, lines: usize) {
         if let ClientState::Results {
-            scroll, answers, ..
+            scroll,
+            answers,
+            wrong_only,
+            ..
         } = &mut self.state
         {
-            let max_scroll = answers.len().saturating_sub(1);
-            *scroll = (*scroll + 1).min(max_scroll);
+            let shown = answers
+                .iter()
+                .filter(|answer| !*wrong_only || !answer.is_correct)
+                .count();
+            *scroll = scroll.saturating_add(lines).min(shown.saturating_sub(1));
         }
     }
 
-    /// Scroll results up.
-    pub fn scroll_results_up(&mut self) {
+    /// Scroll results up by `lines`, stopping at the first answer.
+    pub fn scroll_results_up(&mut self, lines: usize) {
         if let ClientState::Results { scroll, .. } = &mut self.state {
-            *scroll = scroll.saturating_sub(1);
+            *scroll = scroll.saturating_sub(lines);
+        }
+    }
+
+    /// Show only wrong answers in the results, or all of them again.
+    pub fn toggle_wrong_only(&mut self) {
+        if let ClientState::Results {
+            scroll, wrong_only, ..
+        } = &mut self.state
+        {
+            *wrong_only = !*wrong_only;
+            *scroll = 0;
+        }
+    }
+
+    /// Check if the player can chat on the current screen.
+    pub fn can_chat(&self) -> bool {
+        matches!(self.state, ClientState::Lobby { .. } | ClientState::Results { .. })
+    }
+
+    /// Check if a chat message is being typed.
+    pub fn is_composing(&self) -> bool {
+        self.chat_input.is_some() && self.can_chat()
+    }
+
+    /// Start typing a chat message.
+    pub fn open_chat(&mut self) {
+        if self.can_chat() {
+            self.chat_input = Some(String::new());
+        }
+    }
+
+    /// Add a character to the chat message.
+    pub fn chat_input_push(&mut self, c: char) {
+        if let Some(input) = &mut self.chat_input
+            && input.chars().count() < CHAT_MAX_LENGTH
+        {
+            input.push(c);
+        }
+    }
+
+    /// Remove a character from the chat message.
+    pub fn chat_input_pop(&mut self) {
+        if let Some(input) = &mut self.chat_input {
+            input.pop();
+        }
+    }
+
+    /// Stop composing, returning the message typed if it isn't blank.
+    pub fn take_chat_input(&mut self) -> Option<String> {
+        self.chat_input
+            .take()
+            .map(|input| input.trim().to_string())
+            .filter(|input| !input.is_empty())
+    }
+
+    /// Add a line to the chat panel.
+    pub fn push_chat(&mut self, username: Option<String>, text: String) {
+        self.chat.push(ChatLine { username, text });
+        // Keep only the last 50 lines
+        if self.chat.len() > 50 {
+            self.chat.remove(0);
         }
     }
 
     /// Add a character to name input.
     pub fn name_input_push(&mut self, c: char) {
-        if let ClientState::NameEntry { input, .. } = &mut self.state {
-            if input.len() < 16 {
-                input.push(c);
-            }
+        if let ClientState::NameEntry { input, .. } = &mut self.state
+            && input.len() < 16
+        {
+            input.push(c);
         }
     }
 
@@ -0,0 +1,50 @@
+//! Banner showing the host's latest announcement.
+
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap};
+
+use crate::text;
+use crate::theme::Theme;
+
+/// How long an announcement stays on screen.
+pub const DURATION: Duration = Duration::from_secs(20);
+
+/// Widest the banner gets on large screens.
+const MAX_WIDTH: u16 = 80;
+
+/// Draw `text` in a banner across the top of the screen.
+pub fn render(frame: &mut Frame, area: Rect, text: &str, theme: &Theme) {
+    let width = area.width.saturating_sub(4).min(MAX_WIDTH);
+    // Borders and padding take 4 columns; allow up to three lines of text
+    let lines = (text::width(text) as u16).div_ceil(width.saturating_sub(4).max(1));
+    let height = lines.clamp(1, 3) + 2;
+
+    let [_, row, _] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, banner, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+
+    let widget = Paragraph::new(text.fg(theme.text).bold())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.accent)
+                .title(" Announcement ")
+                .title_style(Style::default().fg(theme.accent))
+                .padding(Padding::horizontal(1)),
+        );
+    frame.render_widget(Clear, banner);
+    frame.render_widget(widget, banner);
+}
@@ -0,0 +1,66 @@
+//! Chat panel for the client's lobby and results screens.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+
+use crate::client::state::ClientApp;
+use crate::theme::Theme;
+
+/// Render recent chat messages above the message being typed.
+pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
+    // Leave room for the borders and the input line
+    let max_display = (area.height as usize).saturating_sub(3);
+    let skip = app.chat.len().saturating_sub(max_display);
+    let you = app.state.username();
+
+    let mut lines: Vec<Line> = app
+        .chat
+        .iter()
+        .skip(skip)
+        .map(|line| match &line.username {
+            Some(username) => {
+                let color = if Some(username.as_str()) == you {
+                    theme.correct
+                } else {
+                    theme.accent
+                };
+                Line::from(vec![
+                    Span::styled(format!("{}: ", username), Style::default().fg(color)),
+                    Span::styled(line.text.as_str(), Style::default().fg(theme.text)),
+                ])
+            }
+            None => Line::from(Span::styled(
+                line.text.as_str(),
+                Style::default().fg(theme.incorrect).italic(),
+            )),
+        })
+        .collect();
+
+    // Keep the input line at the bottom of the panel
+    while lines.len() < max_display {
+        lines.insert(0, Line::from(""));
+    }
+
+    lines.push(match &app.chat_input {
+        Some(input) => Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.highlight)),
+            Span::styled(input.as_str(), Style::default().fg(theme.text)),
+            Span::styled("_", Style::default().fg(theme.highlight)),
+        ]),
+        None => Line::from(Span::styled(
+            "c to chat",
+            Style::default().fg(theme.dimmed),
+        )),
+    });
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(" Chat ")
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
@@ -1,20 +1,28 @@
 //! Lobby waiting screen for the client.
 
+use std::time::Instant;
+
+use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Paragraph, Wrap};
 
 use crate::client::state::{ClientApp, ClientState};
+use crate::theme::Theme;
+
+use super::chat;
 
 /// Render the lobby screen.
-pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
+pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
     let ClientState::Lobby { username } = &app.state else {
         return;
     };
 
     let chunks = Layout::vertical([
-        Constraint::Percentage(35),
-        Constraint::Length(11),
-        Constraint::Percentage(35),
+        Constraint::Fill(1),
+        Constraint::Length(9),
+        Constraint::Length(3), // Players
+        Constraint::Length(8), // Chat
+        Constraint::Fill(1),
     ])
     .split(area);
 
@@ -22,28 +30,82 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
         Line::from(""),
         Line::from(Span::styled(
             "RUST QUIZ",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Welcome, ", Style::default().fg(Color::White)),
-            Span::styled(username, Style::default().fg(Color::Green).bold()),
-            Span::styled("!", Style::default().fg(Color::White)),
+            Span::styled("Welcome, ", Style::default().fg(theme.text)),
+            Span::styled(username, Style::default().fg(theme.correct).bold()),
+            Span::styled("!", Style::default().fg(theme.text)),
         ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "Waiting for host to start...",
-            Style::default().fg(Color::Yellow),
-        )),
-        Line::from(""),
+        waiting_line(app, theme),
         Line::from(""),
         Line::from(Span::styled(
-            "[Q] to quit",
-            Style::default().fg(Color::DarkGray),
+            "[C] to chat  ·  [Q] to quit",
+            Style::default().fg(theme.dimmed),
         )),
-        Line::from(""),
     ];
 
     let widget = Paragraph::new(content).alignment(Alignment::Center);
     frame.render_widget(widget, chunks[1]);
+
+    let [players_area] = Layout::horizontal([Constraint::Max(64)])
+        .flex(Flex::Center)
+        .areas(chunks[2]);
+    render_players(frame, players_area, app, username, theme);
+
+    let [chat_area] = Layout::horizontal([Constraint::Max(64)])
+        .flex(Flex::Center)
+        .areas(chunks[3]);
+    chat::render(frame, chat_area, app, theme);
+}
+
+/// When the quiz starts, counting down live once the host has scheduled it.
+fn waiting_line<'a>(app: &ClientApp, theme: &Theme) -> Line<'a> {
+    let Some(starts_at) = app.starts_at else {
+        return Line::from(Span::styled(
+            "Waiting for host to start...",
+            Style::default().fg(theme.highlight),
+        ));
+    };
+    let left = starts_at.saturating_duration_since(Instant::now());
+    let seconds = left.as_secs_f64().ceil() as u64;
+    Line::from(vec![
+        Span::styled("Quiz starts in ", Style::default().fg(theme.highlight)),
+        Span::styled(
+            format!("{}:{:02}", seconds / 60, seconds % 60),
+            Style::default().fg(theme.highlight).bold(),
+        ),
+    ])
+}
+
+/// Who else is waiting, with the player's own name picked out.
+fn render_players(frame: &mut Frame, area: Rect, app: &ClientApp, username: &str, theme: &Theme) {
+    let count = match app.players.len() {
+        1 => "1 player here".to_string(),
+        n => format!("{} players here", n),
+    };
+
+    let mut names = Vec::new();
+    for (i, name) in app.players.iter().enumerate() {
+        if i > 0 {
+            names.push(Span::styled("  ·  ", Style::default().fg(theme.dimmed)));
+        }
+        let style = if name == username {
+            Style::default().fg(theme.correct).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+        names.push(Span::styled(name.as_str(), style));
+    }
+
+    let content = vec![
+        Line::from(Span::styled(count, Style::default().fg(theme.accent))),
+        Line::from(names),
+    ];
+    let widget = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(widget, area);
 }
@@ -1,9 +1,125 @@
 //! Client UI components.
 
+mod announcement;
+mod chat;
 mod lobby;
 mod name_entry;
+mod paused;
 mod quiz;
+mod reconnecting;
 mod render;
 mod results;
+mod waiting;
 
 pub use render::render;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::state::{ClientApp, ClientState, QuestionData};
+    use crate::layout::LayoutOptions;
+    use crate::protocol::SubmittedAnswer;
+    use crate::terminal::{buffer_lines, render_to_buffer};
+    use crate::theme::Theme;
+
+    const LOBBY: &str = "
+                                    RUST QUIZ
+
+                                 Welcome, alice!
+
+                          Waiting for host to start...
+
+                           [C] to chat  ·  [Q] to quit
+
+                                 2 players here
+                                 alice  ·  bobby
+
+        ┌ Chat ────────────────────────────────────────────────────────┐
+        │                                                              │
+        │                                                              │
+        │                                                              │
+        │                                                              │
+        │                                                              │
+        │ c to chat                                                    │
+        └──────────────────────────────────────────────────────────────┘";
+
+    fn screen(app: &ClientApp) -> String {
+        let buffer = render_to_buffer(80, 24, |frame| {
+            render(frame, app, &Theme::default(), &LayoutOptions::default())
+        });
+        buffer_lines(&buffer).join("\n")
+    }
+
+    #[test]
+    fn test_render_screens() {
+        let mut app = ClientApp::new("localhost".to_string(), 8712);
+        app.enter_lobby("alice".to_string());
+        app.players = vec!["alice".to_string(), "bobby".to_string()];
+        assert_eq!(screen(&app).trim_matches('\n'), LOBBY.trim_matches('\n'));
+        app.starts_at = Some(std::time::Instant::now() + std::time::Duration::from_millis(90_500));
+        assert!(screen(&app).contains("Quiz starts in 1:31"));
+
+        app.enter_quiz("alice".to_string(), 2);
+        app.set_question(QuestionData {
+            index: 0,
+            text: "Pick b".to_string(),
+            code: Some("SELECT 'b';".to_string()),
+            code_language: Some("sql".to_string()),
+            options: ["a", "b", "c", "d"].map(String::from),
+            multiple: false,
+        });
+        let quiz = screen(&app);
+        let lines: Vec<&str> = quiz.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        assert!(lines[0].starts_with("Question 1 of 2   ───"), "{}", quiz);
+        assert!(lines[2].starts_with("│ Pick b"), "{}", quiz);
+        assert!(lines.iter().any(|line| line.starts_with("│ > A) a")), "{}", quiz);
+        assert!(quiz.contains("┌ SQL ─"), "{}", quiz);
+
+        app.speed_bonus_window = Some(std::time::Duration::from_secs(30));
+        let quiz = screen(&app);
+        assert!(quiz.contains("Speed bonus 30s   ───"), "{}", quiz);
+
+        // Study mode: A was sent, B was right
+        app.show_feedback(false, vec![1]);
+        let quiz = screen(&app);
+        assert!(quiz.contains("│ ✗ A) a"), "{}", quiz);
+        assert!(quiz.contains("│ ✓ B) b"), "{}", quiz);
+        assert!(quiz.contains("Incorrect  ·  correct answer: B"), "{}", quiz);
+        assert!(quiz.contains("press any key to continue"), "{}", quiz);
+        app.clear_feedback();
+
+        // Answering offline, the next question comes without the server
+        app.speed_bonus_window = None;
+        let question = |index: usize, text: &str| QuestionData {
+            index,
+            text: text.to_string(),
+            code: None,
+            code_language: None,
+            options: ["a", "b", "c", "d"].map(String::from),
+            multiple: false,
+        };
+        app.questions = vec![question(0, "Pick b"), question(1, "Pick c")];
+        app.reconnecting = Some(3);
+        let quiz = screen(&app);
+        assert!(quiz.contains("│ Pick b"), "{}", quiz);
+        assert!(quiz.contains("OFFLINE  Answers are sent once the connection is back"), "{}", quiz);
+        let answer = |question_index| SubmittedAnswer {
+            question_index,
+            answers: vec![1],
+            confidence: None,
+            seconds: Some(2.0),
+        };
+        assert!(app.answer_offline(answer(0)));
+        assert!(!app.answer_offline(answer(0)));
+        assert!(screen(&app).contains("Question 2 of 2"));
+        assert!(app.answer_offline(answer(1)));
+        assert!(matches!(app.state, ClientState::Waiting { .. }));
+        assert_eq!(app.given.len(), 2);
+        app.reconnecting = None;
+
+        app.state = ClientState::disconnected("Kicked by host".to_string());
+        let disconnected = screen(&app);
+        assert!(disconnected.contains("Kicked by host"), "{}", disconnected);
+        assert!(disconnected.contains("Press [Q] to exit"), "{}", disconnected);
+    }
+}
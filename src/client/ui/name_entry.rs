@@ -4,9 +4,10 @@ use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
 use crate::client::state::{ClientApp, ClientState};
+use crate::theme::Theme;
 
 /// Render the name entry screen.
-pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
+pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
     let ClientState::NameEntry { input, error } = &app.state else {
         return;
     };
@@ -22,18 +23,18 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
         Line::from(""),
         Line::from(Span::styled(
             "RUST QUIZ",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
         Line::from(Span::styled(
             format!("Connected to {}", app.server_addr()),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.correct),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Enter your name: ", Style::default().fg(Color::White)),
-            Span::styled(input, Style::default().fg(Color::Yellow)),
-            Span::styled("_", Style::default().fg(Color::Yellow)),
+            Span::styled("Enter your name: ", Style::default().fg(theme.text)),
+            Span::styled(input, Style::default().fg(theme.highlight)),
+            Span::styled("_", Style::default().fg(theme.highlight)),
         ]),
         Line::from(""),
     ];
@@ -41,7 +42,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
     if let Some(err) = error {
         content.push(Line::from(Span::styled(
             err.clone(),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.incorrect),
         )));
     } else {
         content.push(Line::from(""));
@@ -50,7 +51,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
     content.push(Line::from(""));
     content.push(Line::from(Span::styled(
         "[Enter] to join  ·  [Q] to quit",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.dimmed),
     )));
 
     let widget = Paragraph::new(content).alignment(Alignment::Center);
@@ -0,0 +1,44 @@
+//! Overlay shown while the host has paused the quiz.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::theme::Theme;
+
+const WIDTH: u16 = 40;
+const HEIGHT: u16 = 7;
+
+/// Draw the "quiz paused" notice over the quiz.
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(HEIGHT),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, popup, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(WIDTH),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "QUIZ PAUSED",
+            Style::default().fg(theme.highlight).bold(),
+        )),
+        Line::from(""),
+        Line::from("Waiting for the host to resume...".fg(theme.text)),
+        Line::from("q quit".fg(theme.dimmed)),
+    ];
+
+    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.highlight),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
@@ -1,17 +1,38 @@
 //! Quiz screen for the client.
 
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, LineGauge, Padding, Paragraph, Wrap};
+
+use crate::client::state::{ClientApp, ClientState, Feedback};
+use crate::highlight;
+use crate::layout::LayoutOptions;
+use crate::models::format_answer;
+use crate::protocol::{PlayerProgress, StandingEntry};
+use crate::text;
+use crate::theme::Theme;
 
-use crate::client::state::{ClientApp, ClientState};
+/// Width of the race and standings sidebar.
+const STANDINGS_WIDTH: u16 = 32;
+/// Narrowest screen that shows the sidebar.
+const STANDINGS_MIN_SCREEN_WIDTH: u16 = 100;
 
 /// Render the quiz screen.
-pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    app: &ClientApp,
+    theme: &Theme,
+    layout: &LayoutOptions,
+) {
     let ClientState::Quiz {
         current_question,
         current_index,
         total,
         selected_option,
+        checked_options,
         ..
     } = &app.state
     else {
@@ -19,90 +40,212 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
     };
 
     let Some(question) = current_question else {
+        if let Some(ends) = app.countdown_ends {
+            render_countdown(frame, area, ends, theme);
+            return;
+        }
         // Waiting for question
         let waiting = Paragraph::new("Waiting for question...")
             .alignment(Alignment::Center)
-            .fg(Color::Yellow);
+            .fg(theme.highlight);
         frame.render_widget(waiting, area);
         return;
     };
 
-    let has_code = question.code.is_some();
+    let has_sidebar = !app.standings.is_empty() || !app.progress.is_empty();
+    let area = if area.width >= STANDINGS_MIN_SCREEN_WIDTH && has_sidebar {
+        let [main, sidebar] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(STANDINGS_WIDTH)])
+                .areas(area);
+        let sidebar = sidebar.inner(Margin::new(layout.margin, layout.margin));
+        // The race takes up to half the sidebar, one row per player
+        let race_height = (app.progress.len() as u16 + 2).min(sidebar.height / 2);
+        let [race, standings] =
+            Layout::vertical([Constraint::Length(race_height), Constraint::Fill(1)])
+                .areas(sidebar);
+        let you = app.state.username().unwrap_or_default();
+        render_race(frame, race, &app.progress, you, *total, theme);
+        render_standings(frame, standings, &app.standings, *total, theme);
+        main
+    } else {
+        area
+    };
+
+    let code_height = question.code.as_deref().map(|code| layout.code_height(code));
+    let has_code = code_height.is_some();
 
-    let chunks = if has_code {
+    let chunks = if let Some(code_height) = code_height {
         Layout::vertical([
-            Constraint::Length(3),  // Progress
-            Constraint::Length(5),  // Question text
-            Constraint::Length(10), // Code block
-            Constraint::Min(8),     // Options
-            Constraint::Length(2),  // Controls
+            Constraint::Length(3),                  // Progress
+            Constraint::Length(5),                  // Question text
+            Constraint::Length(code_height),        // Code block
+            Constraint::Min(layout.options_height), // Options
+            Constraint::Length(2),                  // Controls
         ])
-        .margin(1)
+        .margin(layout.margin)
         .split(area)
     } else {
         Layout::vertical([
-            Constraint::Length(3), // Progress
-            Constraint::Length(7), // Question text
-            Constraint::Min(8),    // Options
-            Constraint::Length(2), // Controls
+            Constraint::Length(3),                  // Progress
+            Constraint::Length(7),                  // Question text
+            Constraint::Min(layout.options_height), // Options
+            Constraint::Length(2),                  // Controls
         ])
-        .margin(1)
+        .margin(layout.margin)
         .split(area)
     };
 
-    render_progress(frame, chunks[0], *current_index, *total);
-    render_question_text(frame, chunks[1], &question.text);
+    render_progress(frame, chunks[0], app, *current_index, *total, theme);
+    render_question_text(frame, chunks[1], &question.text, theme);
 
+    let checked = question.multiple.then_some(checked_options);
+    let feedback = app.feedback.as_ref();
+    let options = &question.options;
     if has_code {
-        render_code_block(frame, chunks[2], question.code.as_deref().unwrap_or(""));
-        render_options(frame, chunks[3], &question.options, *selected_option);
-        render_controls(frame, chunks[4]);
+        let code = question.code.as_deref().unwrap_or("");
+        render_code_block(frame, chunks[2], code, question.code_language.as_deref(), theme);
+        render_options(frame, chunks[3], options, *selected_option, checked, feedback, theme);
+        render_controls(frame, chunks[4], app, question.multiple, theme);
     } else {
-        render_options(frame, chunks[2], &question.options, *selected_option);
-        render_controls(frame, chunks[3]);
+        render_options(frame, chunks[2], options, *selected_option, checked, feedback, theme);
+        render_controls(frame, chunks[3], app, question.multiple, theme);
     }
 }
 
-fn render_progress(frame: &mut Frame, area: Rect, current: usize, total: usize) {
-    let progress_text = format!("Question {} of {}", current + 1, total);
+/// Full-screen count of the seconds left before the first question.
+fn render_countdown(frame: &mut Frame, area: Rect, ends: Instant, theme: &Theme) {
+    let left = ends.saturating_duration_since(Instant::now());
+    let seconds = left.as_secs_f64().ceil() as u64;
 
-    let widget = Paragraph::new(progress_text)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::Cyan).bold());
+    let content = vec![
+        Line::from(Span::styled("GET READY", Style::default().fg(theme.accent).bold())),
+        Line::from(""),
+        Line::from(Span::styled(
+            if seconds > 0 { seconds.to_string() } else { "GO!".to_string() },
+            Style::default().fg(theme.highlight).bold(),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "The first question is on its way",
+            Style::default().fg(theme.dimmed),
+        )),
+    ];
+
+    let [centered] = Layout::vertical([Constraint::Length(5)])
+        .flex(Flex::Center)
+        .areas(area);
+    let widget = Paragraph::new(content).alignment(Alignment::Center);
+    frame.render_widget(widget, centered);
+}
+
+/// The question number, on a gauge of the questions answered so far, over
+/// the time left to earn the speed bonus.
+fn render_progress(
+    frame: &mut Frame,
+    area: Rect,
+    app: &ClientApp,
+    current: usize,
+    total: usize,
+    theme: &Theme,
+) {
+    let bonus = app.speed_bonus_left();
+    let [row, bonus_row] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(if bonus.is_some() { 1 } else { 0 }),
+    ])
+    .flex(Flex::Center)
+    .areas(area);
+    if let Some((left, window)) = bonus {
+        render_bonus_countdown(frame, bonus_row, left, window, theme);
+    }
+
+    let ratio = if total > 0 { current as f64 / total as f64 } else { 0.0 };
+
+    let widget = LineGauge::default()
+        .ratio(ratio.min(1.0))
+        .label(Span::styled(
+            format!("Question {} of {}  ", current + 1, total),
+            Style::default().fg(theme.accent).bold(),
+        ))
+        .filled_style(Style::default().fg(theme.accent))
+        .unfilled_style(Style::default().fg(theme.dimmed));
+
+    frame.render_widget(widget, row);
+}
+
+/// Gauge shrinking over the speed bonus window, red in its final seconds.
+fn render_bonus_countdown(
+    frame: &mut Frame,
+    area: Rect,
+    left: Duration,
+    window: Duration,
+    theme: &Theme,
+) {
+    let ratio = if window.is_zero() {
+        0.0
+    } else {
+        left.as_secs_f64() / window.as_secs_f64()
+    };
+    let label = if left.is_zero() {
+        "No speed bonus left  ".to_string()
+    } else {
+        format!("Speed bonus {}s  ", left.as_secs_f64().ceil() as u64)
+    };
+    let color = theme.countdown(left, window);
 
+    let widget = LineGauge::default()
+        .ratio(ratio.clamp(0.0, 1.0))
+        .label(Span::styled(label, Style::default().fg(color)))
+        .filled_style(Style::default().fg(color))
+        .unfilled_style(Style::default().fg(theme.dimmed));
     frame.render_widget(widget, area);
 }
 
-fn render_question_text(frame: &mut Frame, area: Rect, text: &str) {
+fn render_question_text(frame: &mut Frame, area: Rect, text: &str, theme: &Theme) {
     let widget = Paragraph::new(text)
         .wrap(Wrap { trim: true })
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.dimmed))
                 .padding(Padding::horizontal(1)),
         );
 
     frame.render_widget(widget, area);
 }
 
-fn render_code_block(frame: &mut Frame, area: Rect, code: &str) {
-    let widget = Paragraph::new(code)
-        .style(Style::default().fg(Color::Yellow))
+fn render_code_block(
+    frame: &mut Frame,
+    area: Rect,
+    code: &str,
+    language: Option<&str>,
+    theme: &Theme,
+) {
+    let label = language.map_or_else(|| "Code".to_string(), highlight::label);
+    let widget = Paragraph::new(highlight::highlight(code, language, theme))
+        .style(Style::default().fg(theme.code))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(" Code ")
-                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme.dimmed))
+                .title(format!(" {} ", label))
+                .title_style(Style::default().fg(theme.accent))
                 .padding(Padding::horizontal(1)),
         );
 
     frame.render_widget(widget, area);
 }
 
-fn render_options(frame: &mut Frame, area: Rect, options: &[String; 4], selected: usize) {
+fn render_options(
+    frame: &mut Frame,
+    area: Rect,
+    options: &[String; 4],
+    selected: usize,
+    checked: Option<&[bool; 4]>,
+    feedback: Option<&Feedback>,
+    theme: &Theme,
+) {
     let option_labels = ['A', 'B', 'C', 'D'];
 
     let lines: Vec<Line> = options
@@ -110,19 +253,91 @@ fn render_options(frame: &mut Frame, area: Rect, options: &[String; 4], selected
         .enumerate()
         .map(|(i, opt)| {
             let is_selected = i == selected;
-            let prefix = if is_selected { "> " } else { "  " };
             let label = option_labels[i];
 
-            let style = if is_selected {
-                Style::default().fg(Color::Yellow).bold()
+            // Once the verdict is in, mark the right options and a wrong
+            // pick instead of showing the cursor
+            let (marker, style) = match feedback {
+                Some(feedback) if feedback.correct_answer.contains(&i) => {
+                    (theme.option_marker(true), Style::default().fg(theme.correct).bold())
+                }
+                Some(feedback) if feedback.answer.contains(&i) => {
+                    (theme.option_marker(false), Style::default().fg(theme.incorrect).bold())
+                }
+                Some(_) => (" ", Style::default().fg(theme.dimmed)),
+                None if is_selected => (">", Style::default().fg(theme.highlight).bold()),
+                None => (" ", Style::default().fg(theme.text)),
+            };
+
+            let mut spans = vec![Span::styled(format!("{} ", marker), style)];
+            if let Some(checked) = checked {
+                let checkbox = if checked[i] { "[x] " } else { "[ ] " };
+                spans.push(Span::styled(checkbox, style));
+            }
+            spans.push(Span::styled(format!("{}) ", label), style));
+            spans.push(Span::styled(opt.clone(), style));
+            Line::from(spans)
+        })
+        .collect();
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(if checked.is_some() {
+                " Options (select all that apply) "
             } else {
-                Style::default().fg(Color::White)
+                " Options "
+            })
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
+
+/// A bar for each player showing how far through the quiz they are.
+fn render_race(
+    frame: &mut Frame,
+    area: Rect,
+    progress: &[PlayerProgress],
+    you: &str,
+    total: usize,
+    theme: &Theme,
+) {
+    if progress.is_empty() {
+        return;
+    }
+
+    // Leave room for the borders, padding, name and count
+    let count_width = 2 * total.to_string().len() + 1;
+    let bar_width = (area.width as usize).saturating_sub(4 + 11 + count_width + 1);
+    let max_display = (area.height as usize).saturating_sub(2);
+
+    let lines: Vec<Line> = progress
+        .iter()
+        .take(max_display)
+        .map(|player| {
+            let name_style = if player.username == you {
+                Style::default().fg(theme.correct).bold()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let filled = (player.answered * bar_width).checked_div(total).unwrap_or(0);
+            let bar_color = if player.answered >= total {
+                theme.correct
+            } else {
+                theme.highlight
             };
 
             Line::from(vec![
-                Span::styled(prefix, style),
-                Span::styled(format!("{}) ", label), style),
-                Span::styled(opt.clone(), style),
+                Span::styled(text::pad(&text::truncate(&player.username, 10), 11), name_style),
+                Span::styled("█".repeat(filled), Style::default().fg(bar_color)),
+                Span::styled("░".repeat(bar_width - filled), Style::default().fg(theme.dimmed)),
+                Span::styled(
+                    format!(" {:>1$}", format!("{}/{}", player.answered, total), count_width),
+                    Style::default().fg(theme.dimmed),
+                ),
             ])
         })
         .collect();
@@ -130,19 +345,126 @@ fn render_options(frame: &mut Frame, area: Rect, options: &[String; 4], selected
     let widget = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
-            .title(" Options ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(" Race ")
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(1)),
     );
 
     frame.render_widget(widget, area);
 }
 
-fn render_controls(frame: &mut Frame, area: Rect) {
-    let widget = Paragraph::new("j/k or arrows to select  ·  Enter/Space to submit  ·  q quit")
+fn render_standings(
+    frame: &mut Frame,
+    area: Rect,
+    standings: &[StandingEntry],
+    total: usize,
+    theme: &Theme,
+) {
+    // Leave room for the borders and the header
+    let max_display = (area.height as usize).saturating_sub(3);
+    let mut shown: Vec<_> = standings.iter().take(max_display).collect();
+    // Always show the player's own place, in the last row if need be
+    if let Some(you) = standings.iter().skip(max_display).find(|e| e.is_you)
+        && let Some(last) = shown.last_mut()
+    {
+        *last = you;
+    }
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("    {}{:>5} {:>4}", text::pad("Player", 12), "Done", "Pts"),
+        Style::default().fg(theme.dimmed),
+    ))];
+    lines.extend(shown.into_iter().map(|entry| {
+        let name_style = if entry.is_you {
+            Style::default().fg(theme.correct).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let progress = if entry.finished {
+            "all".to_string()
+        } else {
+            format!("{}/{}", entry.answered, total)
+        };
+
+        Line::from(vec![
+            Span::styled(format!("{:>2}. ", entry.rank), Style::default().fg(theme.dimmed)),
+            Span::styled(text::pad(&entry.username, 12), name_style),
+            Span::styled(format!("{:>5}", progress), Style::default().fg(theme.dimmed)),
+            Span::styled(format!(" {:>4}", entry.score), Style::default().fg(theme.highlight)),
+        ])
+    }));
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(" Standings ")
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
+
+fn render_controls(
+    frame: &mut Frame,
+    area: Rect,
+    app: &ClientApp,
+    is_multiple: bool,
+    theme: &Theme,
+) {
+    if app.confirming {
+        let prompt = format!(
+            "Submit {}?  Enter/y to send  ·  Esc/n to change",
+            format_answer(&app.answer_to_submit())
+        );
+        let widget = Paragraph::new(prompt)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.highlight).bold());
+        frame.render_widget(widget, area);
+        return;
+    }
+    if let Some(feedback) = &app.feedback {
+        let verdict = if feedback.correct {
+            Line::from(Span::styled("Correct!", Style::default().fg(theme.correct).bold()))
+        } else {
+            Line::from(vec![
+                Span::styled("Incorrect", Style::default().fg(theme.incorrect).bold()),
+                Span::styled(
+                    format!("  ·  correct answer: {}", format_answer(&feedback.correct_answer)),
+                    Style::default().fg(theme.dimmed),
+                ),
+            ])
+        };
+        let keys = Line::from(Span::styled(
+            "press any key to continue  ·  q quit",
+            Style::default().fg(theme.dimmed),
+        ));
+        let widget = Paragraph::new(vec![verdict, keys]).alignment(Alignment::Center);
+        frame.render_widget(widget, area);
+        return;
+    }
+    if app.choosing_confidence {
+        let prompt = format!(
+            "How sure are you of {}?  s sure  ·  u unsure  ·  Esc to change",
+            format_answer(&app.answer_to_submit())
+        );
+        let widget = Paragraph::new(prompt)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.highlight).bold());
+        frame.render_widget(widget, area);
+        return;
+    }
+
+    let text = if is_multiple {
+        "j/k or arrows to select  ·  Space to toggle  ·  Enter to submit  ·  q quit"
+    } else {
+        "j/k or arrows to select  ·  Enter/Space to submit  ·  q quit"
+    };
+    let widget = Paragraph::new(text)
         .alignment(Alignment::Center)
-        .fg(Color::DarkGray);
+        .fg(theme.dimmed);
 
     frame.render_widget(widget, area);
 }
@@ -0,0 +1,62 @@
+//! Overlay shown while the client tries to reconnect to the server.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::theme::Theme;
+
+const WIDTH: u16 = 40;
+const HEIGHT: u16 = 7;
+
+/// Draw the "connection lost" notice over the current screen.
+pub fn render(frame: &mut Frame, area: Rect, attempt: u32, theme: &Theme) {
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(HEIGHT),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, popup, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(WIDTH),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "CONNECTION LOST",
+            Style::default().fg(theme.incorrect).bold(),
+        )),
+        Line::from(""),
+        Line::from(format!("Reconnecting (attempt {})...", attempt).fg(theme.text)),
+        Line::from("q quit".fg(theme.dimmed)),
+    ];
+
+    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.incorrect),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+/// Draw a line at the bottom of the screen saying the connection is lost,
+/// for players who can keep answering meanwhile.
+pub fn render_offline(frame: &mut Frame, area: Rect, attempt: u32, theme: &Theme) {
+    let [_, row] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+    let line = Line::from(vec![
+        Span::styled(" OFFLINE ", Style::default().fg(theme.incorrect).bold()),
+        Span::styled(
+            format!(
+                " Answers are sent once the connection is back (attempt {})",
+                attempt
+            ),
+            Style::default().fg(theme.dimmed),
+        ),
+    ]);
+    frame.render_widget(Clear, row);
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), row);
+}
@@ -4,25 +4,55 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Block, Paragraph};
 
 use crate::client::state::{ClientApp, ClientState};
+use crate::layout::LayoutOptions;
+use crate::terminal;
+use crate::theme::Theme;
 
-use super::{lobby, name_entry, quiz, results};
+use super::{
+    announcement, lobby, name_entry, paused, quiz, reconnecting, results, waiting,
+};
+
+/// Smallest terminal the client screens fit in.
+const MIN_SIZE: Size = Size::new(60, 20);
 
 /// Render the client UI based on current state.
-pub fn render(frame: &mut Frame, app: &ClientApp) {
+pub fn render(frame: &mut Frame, app: &ClientApp, theme: &Theme, layout: &LayoutOptions) {
     let area = frame.area();
     frame.render_widget(Block::default().bg(Color::Reset), area);
+    if terminal::render_too_small(frame, MIN_SIZE, theme) {
+        return;
+    }
 
     match &app.state {
-        ClientState::Connecting => render_connecting(frame, area, app),
-        ClientState::NameEntry { .. } => name_entry::render(frame, area, app),
-        ClientState::Lobby { .. } => lobby::render(frame, area, app),
-        ClientState::Quiz { .. } => quiz::render(frame, area, app),
-        ClientState::Results { .. } => results::render(frame, area, app),
-        ClientState::Disconnected { message } => render_disconnected(frame, area, message),
+        ClientState::Connecting => render_connecting(frame, area, app, theme),
+        ClientState::NameEntry { .. } => name_entry::render(frame, area, app, theme),
+        ClientState::Lobby { .. } => lobby::render(frame, area, app, theme),
+        ClientState::Quiz { .. } => quiz::render(frame, area, app, theme, layout),
+        ClientState::Waiting { .. } => waiting::render(frame, area, app, theme),
+        ClientState::Results { .. } => results::render(frame, area, app, theme),
+        ClientState::Disconnected { message } => render_disconnected(frame, area, message, theme),
+    }
+
+    if app.paused && matches!(app.state, ClientState::Quiz { .. }) {
+        paused::render(frame, area, theme);
+    }
+
+    if let Some((text, received)) = &app.announcement
+        && received.elapsed() < announcement::DURATION
+    {
+        announcement::render(frame, area, text, theme);
+    }
+
+    if let Some(attempt) = app.reconnecting {
+        if app.answers_offline() {
+            reconnecting::render_offline(frame, area, attempt, theme);
+        } else {
+            reconnecting::render(frame, area, attempt, theme);
+        }
     }
 }
 
-fn render_connecting(frame: &mut Frame, area: Rect, app: &ClientApp) {
+fn render_connecting(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
     let chunks = Layout::vertical([
         Constraint::Percentage(40),
         Constraint::Length(7),
@@ -34,12 +64,12 @@ fn render_connecting(frame: &mut Frame, area: Rect, app: &ClientApp) {
         Line::from(""),
         Line::from(Span::styled(
             "RUST QUIZ",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
         Line::from(Span::styled(
             format!("Connecting to {}...", app.server_addr()),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         )),
         Line::from(""),
     ];
@@ -48,7 +78,7 @@ fn render_connecting(frame: &mut Frame, area: Rect, app: &ClientApp) {
     frame.render_widget(widget, chunks[1]);
 }
 
-fn render_disconnected(frame: &mut Frame, area: Rect, message: &str) {
+fn render_disconnected(frame: &mut Frame, area: Rect, message: &str, theme: &Theme) {
     let chunks = Layout::vertical([
         Constraint::Percentage(40),
         Constraint::Length(9),
@@ -60,18 +90,18 @@ fn render_disconnected(frame: &mut Frame, area: Rect, message: &str) {
         Line::from(""),
         Line::from(Span::styled(
             "RUST QUIZ",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
         Line::from(Span::styled(
             message,
-            Style::default().fg(Color::Red).bold(),
+            Style::default().fg(theme.incorrect).bold(),
         )),
         Line::from(""),
         Line::from(""),
         Line::from(Span::styled(
             "Press [Q] to exit",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dimmed),
         )),
         Line::from(""),
     ];
@@ -1,20 +1,31 @@
 //! Results screen for the client.
 
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+use ratatui::widgets::{
+    Block, Borders, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
 
 use crate::client::state::{ClientApp, ClientState};
+use crate::models::{Calibration, Grades};
+use crate::protocol::AnswerResult;
+use crate::text;
+use crate::theme::Theme;
+
+use super::chat;
 
 const QUESTION_PREVIEW_LENGTH: usize = 45;
 
 /// Render the results screen.
-pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
+pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
     let ClientState::Results {
         score,
         total,
+        seconds,
         answers,
         leaderboard,
+        grades,
         scroll,
+        wrong_only,
     } = &app.state
     else {
         return;
@@ -29,106 +40,187 @@ pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp) {
     .margin(1)
     .split(area);
 
-    render_score_summary(frame, chunks[0], *score, *total);
-    render_answers(frame, chunks[1], answers, *scroll);
-    render_leaderboard(frame, chunks[2], leaderboard);
-    render_controls(frame, chunks[3]);
+    let calibration = calibration(answers);
+    let summary = Summary {
+        score: *score,
+        total: *total,
+        seconds: *seconds,
+        calibration,
+    };
+    render_score_summary(frame, chunks[0], &summary, grades, theme);
+    render_answers(frame, chunks[1], answers, *scroll, *wrong_only, theme);
+    let columns = Layout::horizontal([
+        Constraint::Percentage(50), // Leaderboard
+        Constraint::Percentage(50), // Chat
+    ])
+    .split(chunks[2]);
+
+    render_leaderboard(frame, columns[0], leaderboard, theme);
+    chat::render(frame, columns[1], app, theme);
+    render_controls(frame, chunks[3], theme);
 }
 
-fn render_score_summary(frame: &mut Frame, area: Rect, score: usize, total: usize) {
+/// The player's own result, shown above the answers.
+struct Summary {
+    score: usize,
+    total: usize,
+    seconds: Option<f64>,
+    calibration: Option<Calibration>,
+}
+
+fn render_score_summary(
+    frame: &mut Frame,
+    area: Rect,
+    summary: &Summary,
+    grades: &Grades,
+    theme: &Theme,
+) {
+    let Summary {
+        score,
+        total,
+        seconds,
+        calibration,
+    } = *summary;
     let percentage = if total > 0 {
         (score as f64 / total as f64) * 100.0
     } else {
         0.0
     };
 
-    let grade_color = match percentage as u32 {
-        90..=100 => Color::Green,
-        70..=89 => Color::Cyan,
-        50..=69 => Color::Yellow,
-        _ => Color::Red,
-    };
+    let grade_color = theme.grade(grades.band(percentage));
+    let grade = grades.grade(percentage).map(|grade| grade.label.as_str());
 
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
             "RESULTS",
-            Style::default().fg(Color::Cyan).bold(),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("{} / {}  ({:.0}%)", score, total, percentage),
-            Style::default().fg(grade_color).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("{} / {}  ({:.0}%)", score, total, percentage),
+                Style::default().fg(grade_color).bold(),
+            ),
+            Span::styled(
+                grade.map(|label| format!("  ·  {}", label)).unwrap_or_default(),
+                Style::default().fg(grade_color).bold(),
+            ),
+            Span::styled(
+                seconds.map(|s| format!("  in {}", text::seconds(s))).unwrap_or_default(),
+                Style::default().fg(theme.dimmed),
+            ),
+        ]),
+        Line::from(
+            calibration
+                .map(|calibration| calibration.summary())
+                .unwrap_or_default()
+                .fg(theme.dimmed),
+        ),
     ];
 
     let widget = Paragraph::new(content).alignment(Alignment::Center).block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Color::DarkGray),
+            .border_style(theme.dimmed),
     );
 
     frame.render_widget(widget, area);
 }
 
+/// How often sure and unsure answers were right, if the host asked.
+fn calibration(answers: &[AnswerResult]) -> Option<Calibration> {
+    if answers.iter().all(|answer| answer.confidence.is_none()) {
+        return None;
+    }
+    Some(Calibration::new(answers.iter().filter_map(|answer| {
+        answer.confidence.map(|confidence| (confidence, answer.is_correct))
+    })))
+}
+
 fn render_answers(
     frame: &mut Frame,
     area: Rect,
-    answers: &[crate::protocol::AnswerResult],
+    answers: &[AnswerResult],
     scroll: usize,
+    wrong_only: bool,
+    theme: &Theme,
 ) {
     let lines: Vec<Line> = answers
         .iter()
         .enumerate()
+        .filter(|(_, answer)| !wrong_only || !answer.is_correct)
         .map(|(index, answer)| {
-            let (symbol, color) = if answer.is_correct {
-                ("+", Color::Green)
-            } else {
-                ("-", Color::Red)
-            };
+            let color = if answer.is_correct { theme.correct } else { theme.incorrect };
 
-            let preview = truncate_question(&answer.question_text);
+            let preview = text::truncate(&answer.question_text, QUESTION_PREVIEW_LENGTH);
 
             Line::from(vec![
-                Span::styled(format!(" {} ", symbol), Style::default().fg(color)),
+                Span::styled(
+                    format!(" {} ", theme.verdict(answer.is_correct)),
+                    Style::default().fg(color),
+                ),
                 Span::styled(
                     format!("{:2}. ", index + 1),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.dimmed),
+                ),
+                Span::styled(
+                    format!("{:>6}  ", answer.seconds.map(text::seconds).unwrap_or_default()),
+                    Style::default().fg(theme.dimmed),
                 ),
-                Span::styled(preview, Style::default().fg(Color::Gray)),
+                Span::styled(preview, Style::default().fg(theme.muted)),
             ])
         })
         .collect();
 
+    let content_length = lines.len();
     let widget = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(" Your Answers ")
-                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme.dimmed))
+                .title(if wrong_only { " Wrong Answers " } else { " Your Answers " })
+                .title_style(Style::default().fg(theme.accent))
                 .padding(Padding::horizontal(1)),
         )
         .scroll((scroll as u16, 0));
 
     frame.render_widget(widget, area);
+
+    let height = area.height.saturating_sub(2) as usize;
+    if content_length > height {
+        let mut state = ScrollbarState::new(content_length.saturating_sub(1))
+            .position(scroll)
+            .viewport_content_length(height);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .style(Style::default().fg(theme.dimmed));
+        // Over the block's right border, between the corners
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut state,
+        );
+    }
 }
 
 fn render_leaderboard(
     frame: &mut Frame,
     area: Rect,
     leaderboard: &[crate::protocol::LeaderboardEntry],
+    theme: &Theme,
 ) {
     let lines: Vec<Line> = leaderboard
         .iter()
         .take(5) // Show top 5
         .map(|entry| {
             let rank_style = match entry.rank {
-                1 => Style::default().fg(Color::Yellow).bold(),
-                2 => Style::default().fg(Color::White),
+                1 => Style::default().fg(theme.highlight).bold(),
+                2 => Style::default().fg(theme.text),
                 3 => Style::default().fg(Color::LightRed),
-                _ => Style::default().fg(Color::DarkGray),
+                _ => Style::default().fg(theme.dimmed),
             };
 
             let you_marker = if entry.is_you { " <- You" } else { "" };
@@ -142,18 +234,22 @@ fn render_leaderboard(
             Line::from(vec![
                 Span::styled(format!("  {}. ", entry.rank), rank_style),
                 Span::styled(
-                    format!("{:<14}", entry.username),
+                    text::pad(&entry.username, 14),
                     if entry.is_you {
-                        Style::default().fg(Color::Green).bold()
+                        Style::default().fg(theme.correct).bold()
                     } else {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(theme.text)
                     },
                 ),
                 Span::styled(
                     format!("{}/{} ({:.0}%)", entry.score, entry.total, pct),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.dimmed),
+                ),
+                Span::styled(
+                    entry.seconds.map(|s| format!(" {}", text::seconds(s))).unwrap_or_default(),
+                    Style::default().fg(theme.dimmed),
                 ),
-                Span::styled(you_marker, Style::default().fg(Color::Green)),
+                Span::styled(you_marker, Style::default().fg(theme.correct)),
             ])
         })
         .collect();
@@ -161,29 +257,24 @@ fn render_leaderboard(
     let widget = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(" Leaderboard ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(1)),
     );
 
     frame.render_widget(widget, area);
 }
 
-fn render_controls(frame: &mut Frame, area: Rect) {
-    let widget = Paragraph::new("j/k scroll  ·  q quit")
-        .alignment(Alignment::Center)
-        .fg(Color::DarkGray);
+fn render_controls(frame: &mut Frame, area: Rect, theme: &Theme) {
+    // Players who stay are brought back to the lobby if the host restarts
+    let widget = Paragraph::new(vec![
+        Line::from("j/k/pgup/pgdn scroll  ·  w wrong only  ·  c chat  ·  q quit"),
+        Line::from("Stay to play again if the host starts another round".italic()),
+    ])
+    .alignment(Alignment::Center)
+    .fg(theme.dimmed);
 
     frame.render_widget(widget, area);
 }
 
-fn truncate_question(text: &str) -> String {
-    let char_count = text.chars().count();
-    if char_count > QUESTION_PREVIEW_LENGTH {
-        let truncated: String = text.chars().take(QUESTION_PREVIEW_LENGTH).collect();
-        format!("{}...", truncated)
-    } else {
-        text.to_string()
-    }
-}
@@ -0,0 +1,63 @@
+//! Screen shown after answering every question, until results arrive.
+
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Gauge, Paragraph};
+
+use crate::client::state::{ClientApp, ClientState};
+use crate::theme::Theme;
+
+/// Render the waiting screen.
+pub fn render(frame: &mut Frame, area: Rect, app: &ClientApp, theme: &Theme) {
+    let ClientState::Waiting {
+        finished, players, ..
+    } = &app.state
+    else {
+        return;
+    };
+
+    let [content_area, gauge_area, hint_area] = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .flex(Flex::Center)
+    .areas(area);
+
+    let content = vec![
+        Line::from(Span::styled(
+            "You finished!",
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{}/{} players done", finished, players),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Results appear once everyone finishes or the host stops the quiz",
+            Style::default().fg(theme.dimmed),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(content).alignment(Alignment::Center), content_area);
+
+    let [gauge_area] = Layout::horizontal([Constraint::Max(40)])
+        .flex(Flex::Center)
+        .areas(gauge_area);
+    let ratio = if *players > 0 {
+        (*finished as f64 / *players as f64).min(1.0)
+    } else {
+        0.0
+    };
+    let gauge = Gauge::default()
+        .ratio(ratio)
+        .label("")
+        .gauge_style(Style::default().fg(theme.correct).bg(theme.dimmed));
+    frame.render_widget(gauge, gauge_area);
+
+    let hint = Paragraph::new("\nq quit")
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(hint, hint_area);
+}
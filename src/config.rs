@@ -0,0 +1,130 @@
+//! Defaults for the command line, read from a config file.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::keymap::Keymap;
+
+/// Settings used when the command line doesn't give them.
+///
+/// Read from TOML, where every field is optional:
+///
+/// ```toml
+/// questions = "https://example.com/questions.json"
+/// quizzes = ["ownership.json", "traits.toml"]
+/// theme = "light"
+/// port = 9000
+/// username = "ferris"
+///
+/// [keys]
+/// up = "w"
+/// down = "x"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Question file or URL to play or serve.
+    pub questions: Option<PathBuf>,
+    /// Question files to choose from on the welcome screen, used instead of
+    /// `questions` when playing without a file.
+    pub quizzes: Vec<PathBuf>,
+    /// Theme preset name or theme file.
+    pub theme: Option<String>,
+    /// Port to serve on and to join.
+    pub port: Option<u16>,
+    /// Name filled in when joining a server.
+    pub username: Option<String>,
+    /// Extra single-player key bindings.
+    pub keys: Keymap,
+}
+
+/// Error loading the config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The config file could not be read.
+    Io(io::Error),
+    /// The config file is not valid TOML or has an invalid setting.
+    Parse(toml::de::Error),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "Failed to read config: {}", e),
+            ConfigError::Parse(e) => write!(f, "Failed to parse config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            ConfigError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(err: io::Error) -> Self {
+        ConfigError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(err: toml::de::Error) -> Self {
+        ConfigError::Parse(err)
+    }
+}
+
+impl Config {
+    /// Parse a config from TOML.
+    pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Load a config file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// `config.toml` in the user's config directory, next to `theme.toml`.
+    ///
+    /// Returns `None` if the platform has no config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-quiz").join("config.toml"))
+    }
+
+    /// The config from [`default_path`](Self::default_path) if that file
+    /// exists, otherwise an empty config.
+    pub fn load_default() -> Result<Self, ConfigError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load(path),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml("port = 9000\nusername = \"ferris\"\n[keys]\nup = \"w\"")
+            .unwrap();
+        assert_eq!(config.port, Some(9000));
+        assert_eq!(config.username.as_deref(), Some("ferris"));
+        assert_eq!(config.keys.translate(KeyCode::Char('w')), KeyCode::Up);
+        assert!(config.questions.is_none());
+
+        assert!(matches!(
+            Config::from_toml("[keys]\nup = \"page up\""),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+}
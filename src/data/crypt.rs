@@ -0,0 +1,108 @@
+//! Passphrase-encrypted question files.
+//!
+//! An encrypted file starts with a fixed header, then a random salt and
+//! nonce, then the original JSON or TOML encrypted with AES-256-GCM. The key
+//! comes from the passphrase through PBKDF2-HMAC-SHA256, so guessing
+//! passphrases is slow.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use sha2::Sha256;
+
+use super::loader::LoadError;
+
+/// Start of every encrypted file.
+const MAGIC: &[u8] = b"rust-quiz encrypted v1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+#[cfg(not(test))]
+const PBKDF2_ROUNDS: u32 = 200_000;
+#[cfg(test)]
+const PBKDF2_ROUNDS: u32 = 1_000;
+
+/// Encrypt the contents of a question file with `passphrase`.
+///
+/// The result can be loaded with
+/// [`load_bank_encrypted`](super::load_bank_encrypted).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// let content = std::fs::read("questions.json")?;
+/// std::fs::write("questions.json.enc", rust_quiz::data::encrypt(&content, "hunter2"))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn encrypt(content: &[u8], passphrase: &str) -> Vec<u8> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), content)
+        .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+
+    let mut data = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    data
+}
+
+/// Decrypt data made by [`encrypt`].
+///
+/// A wrong passphrase and a damaged file both give `LoadError::Decrypt`;
+/// the two can't be told apart.
+pub(crate) fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, LoadError> {
+    let data = data.strip_prefix(MAGIC).ok_or(LoadError::Decrypt)?;
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(LoadError::Decrypt);
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    Aes256Gcm::new(&derive_key(passphrase, salt))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| LoadError::Decrypt)
+}
+
+/// Whether `data` is an encrypted question file.
+pub(crate) fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Whether the file at `path` is an encrypted question file, e.g. to ask
+/// for a passphrase before loading it.
+pub fn is_encrypted_file<P: AsRef<Path>>(path: P) -> io::Result<bool> {
+    let mut header = Vec::with_capacity(MAGIC.len());
+    File::open(path)?
+        .take(MAGIC.len() as u64)
+        .read_to_end(&mut header)?;
+    Ok(is_encrypted(&header))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key<Aes256Gcm> {
+    let mut key = Key::<Aes256Gcm>::default();
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let content = br#"[{"text": "Q?", "correct_answer": 2}]"#;
+        let data = encrypt(content, "correct horse");
+
+        assert!(is_encrypted(&data));
+        assert!(!data.windows(14).any(|window| window == b"correct_answer"));
+        assert_eq!(decrypt(&data, "correct horse").unwrap(), content);
+        assert!(matches!(decrypt(&data, "wrong horse"), Err(LoadError::Decrypt)));
+        assert!(matches!(decrypt(content, "correct horse"), Err(LoadError::Decrypt)));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::models::Question;
+
+use super::LoadError;
+
+/// Keep only the questions that have at least one of the given tags.
+///
+/// Tags are compared case-insensitively. An empty `tags` slice keeps every
+/// question.
+///
+/// # Returns
+///
+/// The matching questions, or `LoadError::NoMatchingTags` if none match.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::filter_questions_by_tags;
+/// use rust_quiz::load_questions_from_json;
+///
+/// let questions = load_questions_from_json("questions.json").expect("Failed to load");
+/// let ownership = filter_questions_by_tags(questions, &["ownership"]).expect("No matches");
+/// ```
+pub fn filter_questions_by_tags<S: AsRef<str>>(
+    questions: Vec<Question>,
+    tags: &[S],
+) -> Result<Vec<Question>, LoadError> {
+    if tags.is_empty() {
+        return Ok(questions);
+    }
+
+    let filtered: Vec<Question> = questions
+        .into_iter()
+        .filter(|question| question.has_any_tag(tags))
+        .collect();
+
+    if filtered.is_empty() {
+        return Err(LoadError::NoMatchingTags);
+    }
+
+    Ok(filtered)
+}
@@ -0,0 +1,98 @@
+//! Comments and trailing commas in JSON question files.
+
+/// Blank out `//` and `/* */` comments and trailing commas, so the rest
+/// parses as plain JSON.
+///
+/// Removed bytes become spaces and line breaks are kept, so parse errors
+/// point at the same line and column as in the original file.
+pub(crate) fn strip_jsonc(content: &str) -> String {
+    let mut bytes = content.as_bytes().to_vec();
+    blank_comments(&mut bytes);
+    blank_trailing_commas(&mut bytes);
+    // Only whole characters were replaced, with ASCII spaces
+    String::from_utf8(bytes).expect("stripping comments keeps UTF-8 valid")
+}
+
+fn blank_comments(bytes: &mut [u8]) {
+    let mut in_string = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        if in_string {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        let end = match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => {
+                in_string = true;
+                i + 1
+            }
+            (b'/', Some(b'/')) => (i..bytes.len())
+                .find(|&j| bytes[j] == b'\n')
+                .unwrap_or(bytes.len()),
+            (b'/', Some(b'*')) => (i + 2..bytes.len().saturating_sub(1))
+                .find(|&j| &bytes[j..j + 2] == b"*/")
+                .map_or(bytes.len(), |j| j + 2),
+            _ => i + 1,
+        };
+        if bytes[i] == b'/' {
+            for byte in &mut bytes[i..end] {
+                if !matches!(byte, b'\n' | b'\r') {
+                    *byte = b' ';
+                }
+            }
+        }
+        i = end;
+    }
+}
+
+fn blank_trailing_commas(bytes: &mut [u8]) {
+    let mut in_string = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_string => i += 1,
+            b'"' => in_string = !in_string,
+            b',' if !in_string => {
+                let next = bytes[i + 1..].iter().find(|byte| !byte.is_ascii_whitespace());
+                if matches!(next, Some(b']' | b'}')) {
+                    bytes[i] = b' ';
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let content = r#"[
+  // Ownership basics
+  {
+    "text": "Is // a comment in \"strings\"?", /* no */
+    "options": ["a", "b", "c", "d",],
+  },
+]"#;
+        let stripped = strip_jsonc(content);
+        assert_eq!(stripped.len(), content.len());
+        assert_eq!(stripped.lines().count(), content.lines().count());
+        let value: serde_json::Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value[0]["text"], "Is // a comment in \"strings\"?");
+        assert_eq!(value[0]["options"][3], "d");
+
+        // Errors keep their position in the original file
+        let broken = strip_jsonc("[\n  /* ü */ {\"text\": }\n]");
+        let error = serde_json::from_str::<serde_json::Value>(&broken).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 21));
+    }
+}
@@ -1,25 +1,67 @@
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 
-use crate::models::Question;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Question, QuizMeta};
+
+use super::crypt::{decrypt, is_encrypted};
+use super::jsonc::strip_jsonc;
+use super::parse::{self, ParseError};
+use super::validate::{validate_questions, ValidationIssue};
 
 /// Error type for loading questions.
 #[derive(Debug)]
 pub enum LoadError {
     /// Failed to read the file.
     Io(std::io::Error),
-    /// Failed to parse the JSON.
-    Parse(serde_json::Error),
+    /// Failed to parse the JSON or TOML.
+    Parse(ParseError),
+    /// Failed to fetch the file over HTTP(S).
+    Http(Box<ureq::Error>),
     /// The questions file is empty.
     Empty,
+    /// No questions match the requested tags.
+    NoMatchingTags,
+    /// The questions failed validation.
+    Invalid(Vec<ValidationIssue>),
+    /// The file is encrypted and has to be loaded with a passphrase.
+    Encrypted,
+    /// The passphrase is wrong or the encrypted file is damaged.
+    Decrypt,
+    /// Failed to load a file named by an `include` entry.
+    Include { path: PathBuf, error: Box<LoadError> },
+    /// A file includes itself, directly or through other files.
+    IncludeCycle,
+    /// None of the files offered to choose from is a question file.
+    NoQuizzes,
 }
 
 impl std::fmt::Display for LoadError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LoadError::Io(e) => write!(f, "Failed to read file: {}", e),
-            LoadError::Parse(e) => write!(f, "Failed to parse JSON: {}", e),
+            LoadError::Parse(e) => write!(f, "Failed to parse {}: {}", e.format(), e),
+            LoadError::Http(e) => write!(f, "Failed to fetch questions: {}", e),
             LoadError::Empty => write!(f, "Questions file must contain at least one question"),
+            LoadError::NoMatchingTags => write!(f, "No questions match the selected tags"),
+            LoadError::Invalid(issues) => {
+                write!(f, "Invalid questions file:")?;
+                for issue in issues {
+                    write!(f, "\n  {}", issue)?;
+                }
+                Ok(())
+            }
+            LoadError::Encrypted => {
+                write!(f, "Questions file is encrypted and needs a passphrase")
+            }
+            LoadError::Decrypt => write!(f, "Wrong passphrase or damaged questions file"),
+            LoadError::Include { path, error } => write!(f, "In {}: {}", path.display(), error),
+            LoadError::IncludeCycle => {
+                write!(f, "File includes itself, directly or through other files")
+            }
+            LoadError::NoQuizzes => write!(f, "None of the files is a question file"),
         }
     }
 }
@@ -29,7 +71,15 @@ impl std::error::Error for LoadError {
         match self {
             LoadError::Io(e) => Some(e),
             LoadError::Parse(e) => Some(e),
-            LoadError::Empty => None,
+            LoadError::Http(e) => Some(e.as_ref()),
+            LoadError::Include { error, .. } => Some(error.as_ref()),
+            LoadError::Empty
+            | LoadError::NoMatchingTags
+            | LoadError::Invalid(_)
+            | LoadError::Encrypted
+            | LoadError::Decrypt
+            | LoadError::IncludeCycle
+            | LoadError::NoQuizzes => None,
         }
     }
 }
@@ -40,12 +90,72 @@ impl From<std::io::Error> for LoadError {
     }
 }
 
-impl From<serde_json::Error> for LoadError {
-    fn from(err: serde_json::Error) -> Self {
+impl From<ParseError> for LoadError {
+    fn from(err: ParseError) -> Self {
         LoadError::Parse(err)
     }
 }
 
+impl From<ureq::Error> for LoadError {
+    fn from(err: ureq::Error) -> Self {
+        LoadError::Http(Box::new(err))
+    }
+}
+
+/// Questions loaded from a file, with the file's [`QuizMeta`].
+///
+/// This is also the top-level layout of a question file with metadata. TOML
+/// documents must be tables, so TOML files always use it, with questions in
+/// a `[[questions]]` array. JSON files may also be a bare list of questions.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct QuestionBank {
+    #[serde(default)]
+    pub meta: QuizMeta,
+    pub questions: Vec<Question>,
+}
+
+impl QuestionBank {
+    /// A bank of `questions` without metadata.
+    pub fn new(questions: Vec<Question>) -> Self {
+        Self {
+            meta: QuizMeta::default(),
+            questions,
+        }
+    }
+
+    /// Set the bank's title, time limit and other metadata.
+    pub fn with_meta(mut self, meta: QuizMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+}
+
+/// A question file as written, before includes are resolved.
+#[derive(Deserialize)]
+struct RawBank {
+    #[serde(default)]
+    meta: QuizMeta,
+    questions: Vec<Entry>,
+}
+
+impl From<QuestionBank> for RawBank {
+    fn from(bank: QuestionBank) -> Self {
+        Self {
+            meta: bank.meta,
+            questions: bank.questions.into_iter().map(|q| Entry::Question(Box::new(q))).collect(),
+        }
+    }
+}
+
+/// An entry of a question list: a question, or another question file to
+/// take all questions from, e.g. `{"include": "ownership.json"}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Include { include: PathBuf },
+    Question(Box<Question>),
+}
+
 /// Load questions from a JSON file.
 ///
 /// # Arguments
@@ -54,7 +164,9 @@ impl From<serde_json::Error> for LoadError {
 ///
 /// # Returns
 ///
-/// A vector of questions on success, or a `LoadError` on failure.
+/// A vector of questions on success, or a `LoadError` on failure. Banks that
+/// fail [`validate_questions`](super::validate_questions) are rejected with
+/// `LoadError::Invalid`.
 ///
 /// # Example
 ///
@@ -64,12 +176,425 @@ impl From<serde_json::Error> for LoadError {
 /// let questions = load_questions_from_json("questions.json").expect("Failed to load");
 /// ```
 pub fn load_questions_from_json<P: AsRef<Path>>(path: P) -> Result<Vec<Question>, LoadError> {
-    let json_content = fs::read_to_string(path)?;
-    let questions: Vec<Question> = serde_json::from_str(&json_content)?;
+    load_bank_from_json(path).map(|bank| bank.questions)
+}
+
+/// Load questions and metadata from a JSON file.
+///
+/// Like [`load_questions_from_json`], but also accepts and returns a
+/// `meta` object:
+///
+/// ```json
+/// {
+///   "meta": { "title": "Ownership", "pass_threshold": 70, "time_limit": 600 },
+///   "questions": [ ... ]
+/// }
+/// ```
+pub fn load_bank_from_json<P: AsRef<Path>>(path: P) -> Result<QuestionBank, LoadError> {
+    let path = path.as_ref();
+    let raw = parse_json_raw(&read_plain(path)?)?;
+    check_bank(resolve_file(raw, path)?)
+}
+
+/// Load questions and metadata from a file made by
+/// [`encrypt`](super::encrypt).
+///
+/// The decrypted file is TOML if `path` ends in `.toml` or `.toml.enc`, and
+/// JSON otherwise. A wrong passphrase gives `LoadError::Decrypt`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::load_bank_encrypted;
+///
+/// let bank = load_bank_encrypted("questions.json.enc", "hunter2").expect("Failed to load");
+/// ```
+pub fn load_bank_encrypted<P: AsRef<Path>>(
+    path: P,
+    passphrase: &str,
+) -> Result<QuestionBank, LoadError> {
+    let path = path.as_ref();
+    let content = decrypt(&fs::read(path)?, passphrase)?;
+    let content = String::from_utf8(content).map_err(|_| LoadError::Decrypt)?;
+
+    let inner = match path.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("enc") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+    let raw = if is_toml(&inner) {
+        parse_toml_raw(&content)?
+    } else {
+        parse_json_raw(&content)?
+    };
+    check_bank(resolve_file(raw, path)?)
+}
+
+/// Read a question file that isn't encrypted.
+fn read_plain(path: &Path) -> Result<String, LoadError> {
+    let content = fs::read(path)?;
+    if is_encrypted(&content) {
+        return Err(LoadError::Encrypted);
+    }
+    String::from_utf8(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+}
+
+/// Fetch questions from a JSON file served over HTTP(S).
+///
+/// The response body must use the same format as [`load_questions_from_json`].
+/// Non-2xx responses are reported as `LoadError::Http`.
+///
+/// # Arguments
+///
+/// * `url` - URL of the JSON file containing questions.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::load_questions_from_url;
+///
+/// let questions = load_questions_from_url("https://example.com/questions.json")
+///     .expect("Failed to fetch");
+/// ```
+pub fn load_questions_from_url(url: &str) -> Result<Vec<Question>, LoadError> {
+    load_bank_from_url(url).map(|bank| bank.questions)
+}
+
+/// Fetch questions and metadata from a JSON file served over HTTP(S).
+///
+/// The response body must use the same format as [`load_bank_from_json`].
+pub fn load_bank_from_url(url: &str) -> Result<QuestionBank, LoadError> {
+    let json_content = ureq::get(url).call()?.into_string()?;
+    parse_json_bank(&json_content)
+}
+
+/// Parse a downloaded bank, which can't include other files.
+fn parse_json_bank(content: &str) -> Result<QuestionBank, LoadError> {
+    check_bank(resolve(parse_json_raw(content)?, None, &mut Vec::new())?)
+}
+
+fn parse_json_raw(content: &str) -> Result<RawBank, LoadError> {
+    let content = &strip_jsonc(content);
+    let raw = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).map(|questions| RawBank {
+            meta: QuizMeta::default(),
+            questions,
+        })
+    } else {
+        serde_json::from_str(content)
+    };
+    // An untagged entry only says it matched neither a question nor an
+    // include, so report the error from reading plain questions instead.
+    raw.or_else(|_| parse_json_plain(content).map(RawBank::from))
+}
+
+fn parse_json_plain(content: &str) -> Result<QuestionBank, LoadError> {
+    // Checking the first character keeps serde's error messages specific,
+    // which an untagged enum of both layouts would not.
+    if content.trim_start().starts_with('[') {
+        Ok(QuestionBank {
+            meta: QuizMeta::default(),
+            questions: parse::from_json(content)?,
+        })
+    } else {
+        Ok(parse::from_json(content)?)
+    }
+}
+
+fn parse_toml_raw(content: &str) -> Result<RawBank, LoadError> {
+    toml::from_str(content).or_else(|_| Ok(parse::from_toml::<QuestionBank>(content)?.into()))
+}
+
+/// Resolve the includes of the question file at `path`.
+fn resolve_file(raw: RawBank, path: &Path) -> Result<QuestionBank, LoadError> {
+    let mut stack: Vec<PathBuf> = fs::canonicalize(path).into_iter().collect();
+    resolve(raw, Some(path), &mut stack)
+}
+
+/// Replace include entries with the questions of the files they name,
+/// relative to `path`, the file the entries came from. Images are made
+/// relative to it too. `stack` has the files being loaded, to catch cycles.
+fn resolve(
+    raw: RawBank,
+    path: Option<&Path>,
+    stack: &mut Vec<PathBuf>,
+) -> Result<QuestionBank, LoadError> {
+    let mut questions = Vec::new();
+    for entry in raw.questions {
+        match entry {
+            Entry::Question(mut question) => {
+                if let (Some(path), Some(image)) = (path, &question.image) {
+                    let image = path.parent().unwrap_or(Path::new("")).join(image);
+                    question.image = Some(image.to_string_lossy().into_owned());
+                }
+                questions.push(*question);
+            }
+            Entry::Include { include } => {
+                let Some(path) = path else {
+                    let error = io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "only local question files can include others",
+                    );
+                    return Err(LoadError::Include {
+                        path: include,
+                        error: Box::new(error.into()),
+                    });
+                };
+                let included = path.parent().unwrap_or(Path::new("")).join(include);
+                let bank = load_included(&included, stack).map_err(|error| LoadError::Include {
+                    path: included,
+                    error: Box::new(error),
+                })?;
+                questions.extend(bank.questions);
+            }
+        }
+    }
+    Ok(QuestionBank {
+        meta: raw.meta,
+        questions,
+    })
+}
+
+/// Load an included file, JSON or TOML by extension. Its metadata is
+/// dropped.
+fn load_included(path: &Path, stack: &mut Vec<PathBuf>) -> Result<QuestionBank, LoadError> {
+    let canonical = fs::canonicalize(path)?;
+    if stack.contains(&canonical) {
+        return Err(LoadError::IncludeCycle);
+    }
+    let content = read_plain(path)?;
+    let raw = if is_toml(path) {
+        parse_toml_raw(&content)?
+    } else {
+        parse_json_raw(&content)?
+    };
 
-    if questions.is_empty() {
+    stack.push(canonical);
+    let bank = resolve(raw, Some(path), stack);
+    stack.pop();
+    bank
+}
+
+/// Read a JSON or TOML question file, chosen by extension, without
+/// rejecting empty or invalid banks, e.g. to fix them in an editor.
+///
+/// Files with includes are refused, since saving them would copy the
+/// included questions in.
+pub(crate) fn load_bank_unchecked(path: &Path) -> Result<QuestionBank, LoadError> {
+    let content = read_plain(path)?;
+    let raw = if is_toml(path) {
+        parse_toml_raw(&content)?
+    } else {
+        parse_json_raw(&content)?
+    };
+    if raw.questions.iter().any(|entry| matches!(entry, Entry::Include { .. })) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "files with includes can't be edited; edit the included files instead",
+        )
+        .into());
+    }
+    resolve(raw, None, &mut Vec::new())
+}
+
+/// Whether `path` names a TOML file rather than JSON.
+pub(crate) fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+/// Load questions from a TOML file.
+///
+/// Questions are declared as a `[[questions]]` array of tables with the same
+/// fields as the JSON format. Multi-line code snippets can use TOML's
+/// triple-quoted strings.
+///
+/// # Arguments
+///
+/// * `path` - Path to the TOML file containing questions.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::load_questions_from_toml;
+///
+/// let questions = load_questions_from_toml("questions.toml").expect("Failed to load");
+/// ```
+pub fn load_questions_from_toml<P: AsRef<Path>>(path: P) -> Result<Vec<Question>, LoadError> {
+    load_bank_from_toml(path).map(|bank| bank.questions)
+}
+
+/// Load questions and metadata from a TOML file.
+///
+/// Like [`load_questions_from_toml`], but also accepts and returns a
+/// `[meta]` table.
+pub fn load_bank_from_toml<P: AsRef<Path>>(path: P) -> Result<QuestionBank, LoadError> {
+    let path = path.as_ref();
+    let raw = parse_toml_raw(&read_plain(path)?)?;
+    check_bank(resolve_file(raw, path)?)
+}
+
+
+fn check_bank(bank: QuestionBank) -> Result<QuestionBank, LoadError> {
+    if bank.questions.is_empty() {
         return Err(LoadError::Empty);
     }
 
-    Ok(questions)
+    let issues = validate_questions(&bank.questions);
+    if !issues.is_empty() {
+        return Err(LoadError::Invalid(issues));
+    }
+
+    Ok(bank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CorrectAnswer, Grades};
+
+    fn parse_toml_bank(content: &str) -> Result<QuestionBank, LoadError> {
+        check_bank(resolve(parse_toml_raw(content)?, None, &mut Vec::new())?)
+    }
+
+    #[test]
+    fn test_parse_toml_bank() {
+        let content = r#"
+[[questions]]
+text = "What does this function return?"
+code = """
+fn example() -> i32 {
+    42
+}"""
+options = ["0", "42", "Compile error", "None"]
+correct_answer = 1
+
+[[questions]]
+text = "Which keyword declares a constant?"
+options = ["let", "static", "const", "mut"]
+correct_answer = 2
+"#;
+
+        let questions = parse_toml_bank(content).unwrap().questions;
+        assert_eq!(questions.len(), 2);
+        assert_eq!(
+            questions[0].code.as_deref(),
+            Some("fn example() -> i32 {\n    42\n}")
+        );
+        assert!(questions[1].code.is_none());
+        assert_eq!(questions[1].correct_answer, CorrectAnswer::Single(2));
+    }
+
+    #[test]
+    fn test_parse_toml_questions_empty() {
+        assert!(matches!(
+            parse_toml_bank("questions = []"),
+            Err(LoadError::Empty)
+        ));
+        assert!(matches!(
+            parse_toml_bank("questions = 1"),
+            Err(LoadError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_json_checkbox_question() {
+        let content = r#"[
+            {"text": "Q?", "code": null, "options": ["a", "b", "c", "d"], "correct_answer": [2, 0]}
+        ]"#;
+        let questions = parse_json_bank(content).unwrap().questions;
+        assert!(questions[0].is_multiple());
+        assert!(questions[0].is_correct(&[0, 2]));
+        assert!(!questions[0].is_correct(&[0]));
+        assert!(!questions[0].is_correct(&[0, 1, 2]));
+    }
+
+    #[test]
+    fn test_parse_json_questions_invalid() {
+        let content = r#"[
+            {"text": "Q?", "code": null, "options": ["a", "b", "c", "d"], "correct_answer": 7}
+        ]"#;
+        assert!(matches!(
+            parse_json_bank(content),
+            Err(LoadError::Invalid(issues)) if issues.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta() {
+        let content = r#"{
+            "meta": {"title": "Ownership", "author": "Ferris", "pass_threshold": 70, "time_limit": 600},
+            "questions": [
+                {"text": "Q?", "code": null, "options": ["a", "b", "c", "d"], "correct_answer": 0}
+            ]
+        }"#;
+        let bank = parse_json_bank(content).unwrap();
+        assert_eq!(bank.meta.title.as_deref(), Some("Ownership"));
+        assert_eq!(bank.meta.passed(7, 10), Some(true));
+        assert_eq!(bank.meta.passed(6, 10), Some(false));
+        assert_eq!(bank.meta.time_limit_secs, Some(600));
+        assert_eq!(bank.questions.len(), 1);
+
+        let content = r#"
+[meta]
+title = "Traits"
+version = "1.2"
+
+[[meta.grades]]
+min = 50
+label = "Pass"
+
+[[meta.grades]]
+min = 0
+label = "Fail"
+
+[[questions]]
+text = "Q?"
+options = ["a", "b", "c", "d"]
+correct_answer = 0
+"#;
+        let bank = parse_toml_bank(content).unwrap();
+        assert_eq!(bank.meta.byline().as_deref(), Some("v1.2"));
+        assert_eq!(bank.meta.passed(0, 10), None);
+        let grades = &bank.meta.grades;
+        assert_eq!(grades.grade(50.0).map(|grade| grade.label.as_str()), Some("Pass"));
+        assert_eq!(grades.grade(49.0).map(|grade| grade.label.as_str()), Some("Fail"));
+        assert_eq!((grades.band(100.0), grades.band(10.0)), (0, 3));
+        assert_eq!(Grades::default().band(75.0), 1);
+    }
+
+    #[test]
+    fn test_load_includes() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-include-{}", std::process::id()));
+        fs::create_dir_all(dir.join("topics")).unwrap();
+        let question = r#"{"text": "Main?", "options": ["a", "b", "c", "d"], "correct_answer": 0}"#;
+        fs::write(
+            dir.join("main.json"),
+            format!(r#"[{}, {{"include": "topics/traits.toml"}}]"#, question),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("topics/traits.toml"),
+            "[meta]\ntitle = \"Traits\"\n\n[[questions]]\ntext = \"Traits?\"\n\
+             options = [\"a\", \"b\", \"c\", \"d\"]\ncorrect_answer = 1\n\
+             image = \"traits.png\"\n",
+        )
+        .unwrap();
+        let bank = load_bank_from_json(dir.join("main.json")).unwrap();
+        assert_eq!(bank.questions.len(), 2);
+        assert_eq!(bank.questions[1].text, "Traits?");
+        assert!(bank.meta.title.is_none());
+        let image = dir.join("topics").join("traits.png");
+        assert_eq!(bank.questions[1].image.as_deref(), image.to_str());
+
+        fs::write(
+            dir.join("topics/traits.toml"),
+            "[[questions]]\ninclude = \"../main.json\"\n",
+        )
+        .unwrap();
+        let error = load_bank_from_json(dir.join("main.json")).err().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        // main.json -> traits.toml -> main.json
+        let message = error.to_string();
+        assert_eq!(message.matches("In ").count(), 2);
+        assert!(message.ends_with("File includes itself, directly or through other files"));
+    }
 }
@@ -1,3 +1,29 @@
+mod crypt;
+mod filter;
+mod jsonc;
 mod loader;
+mod parse;
+mod sample;
+mod srs;
+mod validate;
+mod writer;
 
-pub use loader::{load_questions_from_json, LoadError};
+pub(crate) use loader::load_bank_unchecked;
+pub(crate) use sample::seeded_rng;
+pub use crypt::{encrypt, is_encrypted_file};
+pub use filter::filter_questions_by_tags;
+pub use loader::{
+    load_bank_encrypted, load_bank_from_json, load_bank_from_toml, load_bank_from_url,
+    load_questions_from_json, load_questions_from_toml, load_questions_from_url, LoadError,
+    QuestionBank,
+};
+pub use parse::ParseError;
+pub use sample::{
+    sample_pools_with_rng, sample_questions, sample_questions_with_rng, shuffle_options_with_rng,
+    Sampling,
+};
+pub use srs::{
+    question_weights, sample_questions_adaptive, sample_questions_adaptive_with_rng,
+};
+pub use validate::{validate_questions, ValidationIssue};
+pub use writer::{save_bank, SaveError};
@@ -0,0 +1,200 @@
+//! Parsing question files with errors that say where they went wrong.
+
+use std::fmt::Write as _;
+
+use serde::de::DeserializeOwned;
+use serde_path_to_error::{Path, Segment};
+
+/// A question file that isn't valid JSON or TOML, or whose values don't fit
+/// the question format.
+///
+/// Says which question and field the problem is in where that is known,
+/// e.g. `Question 14, options: invalid length 3, expected an array of
+/// length 4 at line 120 column 16`.
+#[derive(Debug)]
+pub struct ParseError {
+    /// Index of the question the error is in, 0-based.
+    pub question: Option<usize>,
+    /// Field the error is in, within the question if there is one, e.g.
+    /// `options[2]` or `meta.time_limit`.
+    pub field: Option<String>,
+    /// Line of the error, 1-based.
+    pub line: Option<usize>,
+    /// Column of the error in bytes, 1-based.
+    pub column: Option<usize>,
+    /// What went wrong, without the location.
+    pub message: String,
+    source: Source,
+}
+
+#[derive(Debug)]
+enum Source {
+    Json(serde_json::Error),
+    Toml(Box<toml::de::Error>),
+}
+
+impl ParseError {
+    /// `"JSON"` or `"TOML"`.
+    pub fn format(&self) -> &'static str {
+        match self.source {
+            Source::Json(_) => "JSON",
+            Source::Toml(_) => "TOML",
+        }
+    }
+
+    fn from_toml(err: toml::de::Error, content: &str) -> Self {
+        let (line, column) = match err.span() {
+            Some(span) => {
+                let before = &content[..span.start.min(content.len())];
+                let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+                (Some(before.matches('\n').count() + 1), Some(before.len() - line_start + 1))
+            }
+            None => (None, None),
+        };
+        Self {
+            question: None,
+            field: None,
+            line,
+            column,
+            message: err.message().to_string(),
+            source: Source::Toml(Box::new(err)),
+        }
+    }
+
+    fn with_path(mut self, path: &Path) -> Self {
+        let segments: Vec<&Segment> = path.iter().collect();
+        // Questions are either the whole file or its `questions` list
+        let rest = match segments.as_slice() {
+            [Segment::Map { key }, Segment::Seq { index }, rest @ ..] if key == "questions" => {
+                self.question = Some(*index);
+                rest
+            }
+            [Segment::Seq { index }, rest @ ..] => {
+                self.question = Some(*index);
+                rest
+            }
+            rest => rest,
+        };
+
+        let mut field = String::new();
+        for segment in rest {
+            match segment {
+                Segment::Seq { index } => {
+                    let _ = write!(field, "[{}]", index);
+                }
+                Segment::Map { key } | Segment::Enum { variant: key } => {
+                    if !field.is_empty() {
+                        field.push('.');
+                    }
+                    field.push_str(key);
+                }
+                Segment::Unknown => {}
+            }
+        }
+        self.field = (!field.is_empty()).then_some(field);
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.question, &self.field) {
+            (Some(question), Some(field)) => write!(f, "Question {}, {}: ", question + 1, field)?,
+            (Some(question), None) => write!(f, "Question {}: ", question + 1)?,
+            (None, Some(field)) => write!(f, "{}: ", field)?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)?;
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " at line {} column {}", line, column)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.source {
+            Source::Json(e) => Some(e),
+            Source::Toml(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(err: serde_json::Error) -> Self {
+        let (line, column) = match err.line() {
+            0 => (None, None),
+            line => (Some(line), Some(err.column())),
+        };
+        // serde_json puts the location at the end of its message
+        let mut message = err.to_string();
+        let location = format!(" at line {} column {}", err.line(), err.column());
+        if message.ends_with(&location) {
+            message.truncate(message.len() - location.len());
+        }
+        Self {
+            question: None,
+            field: None,
+            line,
+            column,
+            message,
+            source: Source::Json(err),
+        }
+    }
+}
+
+/// Deserialize JSON, keeping track of where in the document an error is.
+pub(crate) fn from_json<T: DeserializeOwned>(content: &str) -> Result<T, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let path = e.path().clone();
+        ParseError::from(e.into_inner()).with_path(&path)
+    })?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserialize TOML, keeping track of where in the document an error is.
+pub(crate) fn from_toml<T: DeserializeOwned>(content: &str) -> Result<T, ParseError> {
+    serde_path_to_error::deserialize(toml::Deserializer::new(content)).map_err(|e| {
+        let path = e.path().clone();
+        ParseError::from_toml(e.into_inner(), content).with_path(&path)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::QuestionBank;
+    use crate::models::Question;
+
+    #[test]
+    fn test_error_names_question_and_field() {
+        let content = r#"[
+  {"text": "Q1", "options": ["a", "b", "c", "d"], "correct_answer": 0},
+  {"text": "Q2", "options": ["a", "b", "c"], "correct_answer": 0}
+]"#;
+        let error = from_json::<Vec<Question>>(content).err().unwrap();
+        assert_eq!(error.question, Some(1));
+        assert_eq!(error.field.as_deref(), Some("options"));
+        assert_eq!(error.line, Some(3));
+        assert!(error.to_string().starts_with("Question 2, options: "));
+
+        let content = r#"
+[[questions]]
+text = "Q1"
+options = ["a", "b", "c", "d"]
+correct_answer = 0
+
+[[questions]]
+text = "Q2"
+options = ["a", "b", "c", "d"]
+correct_answer = "b"
+"#;
+        let error = from_toml::<QuestionBank>(content).err().unwrap();
+        assert_eq!(error.question, Some(1));
+        assert_eq!(error.field.as_deref(), Some("correct_answer"));
+        assert_eq!((error.line, error.column), (Some(10), Some(18)));
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+use super::LoadError;
+use crate::models::{CorrectAnswer, Pool, Question};
+
+/// How to pick a subset of questions from a larger bank.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// Every question is equally likely to be picked.
+    #[default]
+    Uniform,
+    /// Each tag keeps roughly its share of the bank, grouping questions by
+    /// their first tag. Untagged questions form their own group.
+    ByTag,
+}
+
+/// Pick at most `limit` random questions from a bank.
+///
+/// Picked questions keep their order from the bank. If the bank has `limit`
+/// questions or fewer, it is returned unchanged. At least one question is
+/// always kept.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::{sample_questions, Sampling};
+/// use rust_quiz::load_questions_from_json;
+///
+/// let questions = load_questions_from_json("questions.json").expect("Failed to load");
+/// let session = sample_questions(questions, 20, Sampling::ByTag);
+/// ```
+pub fn sample_questions(questions: Vec<Question>, limit: usize, sampling: Sampling) -> Vec<Question> {
+    sample_questions_with_rng(questions, limit, sampling, &mut rand::rng())
+}
+
+/// Randomness for picking and ordering questions: the same every time for a
+/// given `seed`, or fresh without one.
+pub(crate) fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rand::rng()),
+    }
+}
+
+/// Like [`sample_questions`], drawing randomness from `rng`.
+pub fn sample_questions_with_rng<R: Rng + ?Sized>(
+    questions: Vec<Question>,
+    limit: usize,
+    sampling: Sampling,
+    rng: &mut R,
+) -> Vec<Question> {
+    let limit = limit.max(1);
+    if questions.len() <= limit {
+        return questions;
+    }
+
+    let mut picked = match sampling {
+        Sampling::Uniform => index::sample(rng, questions.len(), limit).into_vec(),
+        Sampling::ByTag => sample_indices_by_tag(&questions, limit, rng),
+    };
+    picked.sort_unstable();
+
+    let mut picked = picked.into_iter().peekable();
+    questions
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, question)| picked.next_if_eq(&i).map(|_| question))
+        .collect()
+}
+
+/// Assemble a quiz from the bank following `pools`, as set in the file's
+/// [`QuizMeta::pools`](crate::models::QuizMeta::pools).
+///
+/// Picked questions keep their order from the bank. Without pools the bank
+/// is returned unchanged.
+///
+/// # Returns
+///
+/// The picked questions, or `LoadError::NoMatchingTags` if no pool matches
+/// any question.
+pub fn sample_pools_with_rng<R: Rng + ?Sized>(
+    questions: Vec<Question>,
+    pools: &[Pool],
+    rng: &mut R,
+) -> Result<Vec<Question>, LoadError> {
+    if pools.is_empty() {
+        return Ok(questions);
+    }
+
+    let mut picked = vec![false; questions.len()];
+    for pool in pools {
+        let tags: Vec<String> = match &pool.tag {
+            Some(tag) => vec![tag.to_lowercase()],
+            None => questions
+                .iter()
+                .flat_map(|question| question.tags.iter().map(|tag| tag.to_lowercase()))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        };
+        for tag in tags {
+            let candidates: Vec<usize> = (0..questions.len())
+                .filter(|&i| !picked[i] && questions[i].has_any_tag(&[&tag]))
+                .collect();
+            let count = pool.count.min(candidates.len());
+            for i in index::sample(rng, candidates.len(), count) {
+                picked[candidates[i]] = true;
+            }
+        }
+    }
+
+    if !picked.contains(&true) {
+        return Err(LoadError::NoMatchingTags);
+    }
+    Ok(questions
+        .into_iter()
+        .zip(picked)
+        .filter_map(|(question, picked)| picked.then_some(question))
+        .collect())
+}
+
+/// Put each question's options in a random order, keeping the correct
+/// answer on the same option texts.
+pub fn shuffle_options_with_rng<R: Rng + ?Sized>(
+    questions: Vec<Question>,
+    rng: &mut R,
+) -> Vec<Question> {
+    questions
+        .into_iter()
+        .map(|mut question| {
+            let mut order = [0, 1, 2, 3];
+            order.shuffle(rng);
+            // `order[new]` is the old index of the option now at `new`.
+            let moved = |old: usize| order.iter().position(|&from| from == old).unwrap_or(old);
+            question.options = order.map(|old| question.options[old].clone());
+            question.correct_answer = match &question.correct_answer {
+                CorrectAnswer::Single(index) => CorrectAnswer::Single(moved(*index)),
+                CorrectAnswer::Multiple(indices) => {
+                    CorrectAnswer::Multiple(indices.iter().map(|&index| moved(index)).collect())
+                }
+            };
+            question
+        })
+        .collect()
+}
+
+/// Allocate `limit` picks across tag groups proportionally to group size,
+/// handing leftover picks to the groups with the largest remainders (larger
+/// groups first on ties).
+fn sample_indices_by_tag<R: Rng + ?Sized>(
+    questions: &[Question],
+    limit: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let total = questions.len();
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, question) in questions.iter().enumerate() {
+        let tag = question
+            .tags
+            .first()
+            .map(|tag| tag.to_lowercase())
+            .unwrap_or_default();
+        groups.entry(tag).or_default().push(i);
+    }
+
+    let mut quotas: Vec<(usize, usize, &Vec<usize>)> = groups
+        .values()
+        .map(|members| {
+            let share = limit * members.len();
+            (share / total, share % total, members)
+        })
+        .collect();
+
+    let allocated: usize = quotas.iter().map(|(quota, _, _)| quota).sum();
+    let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        (quotas[b].1, quotas[b].2.len()).cmp(&(quotas[a].1, quotas[a].2.len()))
+    });
+    for &group in by_remainder.iter().take(limit - allocated) {
+        quotas[group].0 += 1;
+    }
+
+    quotas
+        .into_iter()
+        .flat_map(|(quota, _, members)| {
+            index::sample(rng, members.len(), quota)
+                .into_iter()
+                .map(|i| members[i])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::testing::tagged_questions;
+
+    #[test]
+    fn test_sample_questions_uniform() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let questions = tagged_questions(&["a"; 10]);
+
+        let sampled = sample_questions_with_rng(questions, 4, Sampling::Uniform, &mut rng);
+        assert_eq!(sampled.len(), 4);
+
+        let numbers: Vec<usize> = sampled
+            .iter()
+            .map(|q| q.text[9..q.text.len() - 1].parse().unwrap())
+            .collect();
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_sample_questions_by_tag() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut tags = vec!["ownership"; 6];
+        tags.extend(["traits"; 3]);
+        tags.push("macros");
+        let questions = tagged_questions(&tags);
+
+        let sampled = sample_questions_with_rng(questions, 5, Sampling::ByTag, &mut rng);
+        let count = |tag: &str| sampled.iter().filter(|q| q.tags[0] == tag).count();
+
+        assert_eq!(sampled.len(), 5);
+        assert_eq!(count("ownership"), 3);
+        assert_eq!(count("traits"), 2);
+        assert_eq!(count("macros"), 0);
+    }
+
+    #[test]
+    fn test_shuffle_options_keeps_correct_answer() {
+        let mut questions = tagged_questions(&["a", "b"]);
+        questions[1].correct_answer = CorrectAnswer::Multiple(vec![1, 3]);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let shuffled = shuffle_options_with_rng(questions, &mut rng);
+        let correct = |question: &Question| -> Vec<String> {
+            let mut texts: Vec<String> = question
+                .correct_answer
+                .indices()
+                .into_iter()
+                .map(|index| question.options[index].clone())
+                .collect();
+            texts.sort();
+            texts
+        };
+        assert_eq!(correct(&shuffled[0]), ["a"]);
+        assert_eq!(correct(&shuffled[1]), ["b", "d"]);
+    }
+
+    #[test]
+    fn test_sample_pools() {
+        let questions = tagged_questions(&["easy", "easy", "easy", "hard", "hard", "misc"]);
+        let mut rng = StdRng::seed_from_u64(3);
+        let pools = [
+            Pool { tag: Some("Hard".to_string()), count: 5 },
+            Pool { tag: Some("easy".to_string()), count: 2 },
+        ];
+
+        let picked = sample_pools_with_rng(questions.clone(), &pools, &mut rng).unwrap();
+        let tags: Vec<&str> = picked.iter().map(|q| q.tags[0].as_str()).collect();
+        assert_eq!(tags, ["easy", "easy", "hard", "hard"]);
+
+        let each_tag = [Pool { tag: None, count: 1 }];
+        let picked = sample_pools_with_rng(questions.clone(), &each_tag, &mut rng).unwrap();
+        assert_eq!(picked.len(), 3);
+
+        let unknown = [Pool { tag: Some("medium".to_string()), count: 1 }];
+        assert!(sample_pools_with_rng(questions, &unknown, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use rand::seq::index;
+use rand::Rng;
+
+use crate::history::HistoryEntry;
+use crate::models::Question;
+
+/// Weight of a question that has never been asked: twice that of one missed
+/// in the latest run, so new material gets covered.
+const NEW_QUESTION_WEIGHT: f64 = 2.0;
+
+/// Longest streak of correct answers that still lowers a question's weight.
+const MAX_STREAK: u32 = 5;
+
+/// How a question fared in past runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ReviewRecord {
+    /// Correct answers in a row, counting back from the latest time asked.
+    streak: u32,
+    /// Runs since the question was last asked.
+    runs_since_seen: usize,
+}
+
+/// How likely each question is to be picked, from the player's history.
+///
+/// Questions are matched to past runs by their text. The weight halves for
+/// every correct answer in a row and grows with the number of runs since the
+/// question was last asked, so missed questions come back soon while
+/// well-known ones still resurface now and then. Questions that were never
+/// asked weigh the most.
+pub fn question_weights(questions: &[Question], history: &[HistoryEntry]) -> Vec<f64> {
+    let records = review_records(history);
+
+    questions
+        .iter()
+        .map(|question| match records.get(question.text.as_str()) {
+            Some(record) => {
+                let decay = 2f64.powi(record.streak.min(MAX_STREAK) as i32);
+                (1 + record.runs_since_seen) as f64 / decay
+            }
+            None => NEW_QUESTION_WEIGHT,
+        })
+        .collect()
+}
+
+/// Pick at most `limit` questions, favouring ones the player struggles with.
+///
+/// Uses [`question_weights`] to weight the draw. Picked questions keep their
+/// order from the bank, and at least one question is always kept.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::sample_questions_adaptive;
+/// use rust_quiz::{load_questions_from_json, History};
+///
+/// let questions = load_questions_from_json("questions.json").expect("Failed to load");
+/// let history = History::open_default()
+///     .and_then(|history| history.load().ok())
+///     .unwrap_or_default();
+/// let session = sample_questions_adaptive(questions, 20, &history);
+/// ```
+pub fn sample_questions_adaptive(
+    questions: Vec<Question>,
+    limit: usize,
+    history: &[HistoryEntry],
+) -> Vec<Question> {
+    sample_questions_adaptive_with_rng(questions, limit, history, &mut rand::rng())
+}
+
+/// Like [`sample_questions_adaptive`], drawing randomness from `rng`.
+pub fn sample_questions_adaptive_with_rng<R: Rng + ?Sized>(
+    questions: Vec<Question>,
+    limit: usize,
+    history: &[HistoryEntry],
+    rng: &mut R,
+) -> Vec<Question> {
+    let limit = limit.max(1);
+    if questions.len() <= limit {
+        return questions;
+    }
+
+    let weights = question_weights(&questions, history);
+    // Weights are always positive and finite, so sampling can't fail.
+    let mut picked = index::sample_weighted(rng, questions.len(), |i| weights[i], limit)
+        .expect("question weights are positive")
+        .into_vec();
+    picked.sort_unstable();
+
+    let mut picked = picked.into_iter().peekable();
+    questions
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, question)| picked.next_if_eq(&i).map(|_| question))
+        .collect()
+}
+
+fn review_records(history: &[HistoryEntry]) -> HashMap<&str, ReviewRecord> {
+    let mut records: HashMap<&str, ReviewRecord> = HashMap::new();
+
+    for entry in history {
+        for record in records.values_mut() {
+            record.runs_since_seen += 1;
+        }
+        for text in &entry.correct {
+            let record = records.entry(text.as_str()).or_default();
+            record.streak += 1;
+            record.runs_since_seen = 0;
+        }
+        for text in &entry.missed {
+            let record = records.entry(text.as_str()).or_default();
+            record.streak = 0;
+            record.runs_since_seen = 0;
+        }
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::question;
+
+    fn run(correct: &[&str], missed: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            date: String::new(),
+            quiz: "questions.json".to_string(),
+            score: correct.len(),
+            max_score: correct.len() + missed.len(),
+            duration_secs: 0,
+            tags: Vec::new(),
+            missed: missed.iter().map(|text| text.to_string()).collect(),
+            correct: correct.iter().map(|text| text.to_string()).collect(),
+            flashcards: false,
+        }
+    }
+
+    #[test]
+    fn test_question_weights() {
+        let questions = ["Known?", "Missed?", "New?", "Stale?"].map(question);
+        let history = [
+            run(&["Known?", "Stale?"], &["Missed?"]),
+            run(&["Known?"], &[]),
+            run(&["Known?"], &["Missed?"]),
+        ];
+
+        assert_eq!(
+            question_weights(&questions, &history),
+            vec![1.0 / 8.0, 1.0, NEW_QUESTION_WEIGHT, 3.0 / 2.0]
+        );
+    }
+}
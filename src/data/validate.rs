@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use crate::models::Question;
+
+/// A problem found in a question bank.
+///
+/// Question and option indices are 0-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// `correct_answer` does not point at one of the options, so the question
+    /// can never be answered correctly.
+    CorrectAnswerOutOfRange {
+        question: usize,
+        correct_answer: usize,
+    },
+    /// A checkbox question lists no correct options.
+    NoCorrectAnswer { question: usize },
+    /// An option is empty or only whitespace.
+    EmptyOption { question: usize, option: usize },
+    /// An option has the same text as an earlier option of the same question.
+    DuplicateOption {
+        question: usize,
+        option: usize,
+        first: usize,
+    },
+    /// A question has the same text and code as an earlier question.
+    DuplicateQuestion { question: usize, first: usize },
+}
+
+impl ValidationIssue {
+    /// Index of the question the issue is about.
+    pub fn question(&self) -> usize {
+        match *self {
+            ValidationIssue::CorrectAnswerOutOfRange { question, .. }
+            | ValidationIssue::NoCorrectAnswer { question }
+            | ValidationIssue::EmptyOption { question, .. }
+            | ValidationIssue::DuplicateOption { question, .. }
+            | ValidationIssue::DuplicateQuestion { question, .. } => question,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::CorrectAnswerOutOfRange {
+                question,
+                correct_answer,
+            } => write!(
+                f,
+                "Question {}: correct_answer {} is out of range",
+                question + 1,
+                correct_answer
+            ),
+            ValidationIssue::NoCorrectAnswer { question } => {
+                write!(f, "Question {}: correct_answer is empty", question + 1)
+            }
+            ValidationIssue::EmptyOption { question, option } => write!(
+                f,
+                "Question {}: option {} is empty",
+                question + 1,
+                option + 1
+            ),
+            ValidationIssue::DuplicateOption {
+                question,
+                option,
+                first,
+            } => write!(
+                f,
+                "Question {}: option {} duplicates option {}",
+                question + 1,
+                option + 1,
+                first + 1
+            ),
+            ValidationIssue::DuplicateQuestion { question, first } => write!(
+                f,
+                "Question {} duplicates question {}",
+                question + 1,
+                first + 1
+            ),
+        }
+    }
+}
+
+/// Check a question bank for mistakes that would make the quiz unfair or
+/// confusing.
+///
+/// Returns every issue found, in question order. An empty vector means the
+/// bank is valid.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::validate_questions;
+/// use rust_quiz::load_questions_from_json;
+///
+/// let questions = load_questions_from_json("questions.json").expect("Failed to load");
+/// assert!(validate_questions(&questions).is_empty());
+/// ```
+pub fn validate_questions(questions: &[Question]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut seen_questions: HashMap<(&str, Option<&str>), usize> = HashMap::new();
+
+    for (index, question) in questions.iter().enumerate() {
+        let key = (question.text.trim(), question.code.as_deref());
+        if let Some(&first) = seen_questions.get(&key) {
+            issues.push(ValidationIssue::DuplicateQuestion {
+                question: index,
+                first,
+            });
+        } else {
+            seen_questions.insert(key, index);
+        }
+
+        let correct_answers = question.correct_answer.indices();
+        if correct_answers.is_empty() {
+            issues.push(ValidationIssue::NoCorrectAnswer { question: index });
+        }
+        for correct_answer in correct_answers {
+            if correct_answer >= question.options.len() {
+                issues.push(ValidationIssue::CorrectAnswerOutOfRange {
+                    question: index,
+                    correct_answer,
+                });
+            }
+        }
+
+        for (option_index, option) in question.options.iter().enumerate() {
+            if option.trim().is_empty() {
+                issues.push(ValidationIssue::EmptyOption {
+                    question: index,
+                    option: option_index,
+                });
+                continue;
+            }
+
+            let first = question.options[..option_index]
+                .iter()
+                .position(|earlier| earlier.trim() == option.trim());
+            if let Some(first) = first {
+                issues.push(ValidationIssue::DuplicateOption {
+                    question: index,
+                    option: option_index,
+                    first,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CorrectAnswer;
+
+    #[test]
+    fn test_validate_questions_valid() {
+        let questions = vec![
+            Question::new("First?", ["a", "b", "c", "d"], CorrectAnswer::Single(0)),
+            Question::new("Second?", ["a", "b", "c", "d"], CorrectAnswer::Multiple(vec![1, 3])),
+        ];
+        assert!(validate_questions(&questions).is_empty());
+    }
+
+    #[test]
+    fn test_validate_questions_issues() {
+        let questions = vec![
+            Question::new("First?", ["a", "b", "c", "d"], CorrectAnswer::Single(4)),
+            Question::new("Second?", ["a", " ", "c", "a"], CorrectAnswer::Single(0)),
+            Question::new("First?", ["a", "b", "c", "d"], CorrectAnswer::Multiple(vec![])),
+            Question::new("Fourth?", ["a", "b", "c", "d"], CorrectAnswer::Multiple(vec![0, 5])),
+        ];
+
+        assert_eq!(
+            validate_questions(&questions),
+            vec![
+                ValidationIssue::CorrectAnswerOutOfRange {
+                    question: 0,
+                    correct_answer: 4,
+                },
+                ValidationIssue::EmptyOption {
+                    question: 1,
+                    option: 1,
+                },
+                ValidationIssue::DuplicateOption {
+                    question: 1,
+                    option: 3,
+                    first: 0,
+                },
+                ValidationIssue::DuplicateQuestion {
+                    question: 2,
+                    first: 0,
+                },
+                ValidationIssue::NoCorrectAnswer { question: 2 },
+                ValidationIssue::CorrectAnswerOutOfRange {
+                    question: 3,
+                    correct_answer: 5,
+                },
+            ]
+        );
+    }
+}
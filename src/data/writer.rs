@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::QuizMeta;
+
+use super::loader::{is_toml, QuestionBank};
+
+/// Error type for saving questions.
+#[derive(Debug)]
+pub enum SaveError {
+    /// Failed to write the file.
+    Io(std::io::Error),
+    /// Failed to encode the JSON.
+    Json(serde_json::Error),
+    /// Failed to encode the TOML.
+    Toml(toml::ser::Error),
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "Failed to write file: {}", e),
+            SaveError::Json(e) => write!(f, "Failed to encode JSON: {}", e),
+            SaveError::Toml(e) => write!(f, "Failed to encode TOML: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(e) => Some(e),
+            SaveError::Json(e) => Some(e),
+            SaveError::Toml(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+impl From<toml::ser::Error> for SaveError {
+    fn from(err: toml::ser::Error) -> Self {
+        SaveError::Toml(err)
+    }
+}
+
+/// Write a question bank as JSON, or as TOML if `path` ends in `.toml`.
+///
+/// Optional fields left at their defaults are omitted. A JSON bank without
+/// metadata is written as a bare list of questions.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::{load_bank_from_json, save_bank};
+///
+/// let mut bank = load_bank_from_json("questions.json").expect("Failed to load");
+/// bank.meta.title = Some("Rust Basics".to_string());
+/// save_bank(&bank, "questions.json").expect("Failed to save");
+/// ```
+pub fn save_bank<P: AsRef<Path>>(bank: &QuestionBank, path: P) -> Result<(), SaveError> {
+    let path = path.as_ref();
+    if is_toml(path) {
+        bank.save_toml(path)
+    } else {
+        bank.save_json(path)
+    }
+}
+
+impl QuestionBank {
+    /// Encode the bank as pretty-printed JSON, as a bare list of questions
+    /// if it has no metadata.
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        let json = if self.meta == QuizMeta::default() {
+            serde_json::to_string_pretty(&self.questions)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+        Ok(json + "\n")
+    }
+
+    /// Encode the bank as TOML, with questions in a `[[questions]]` array.
+    pub fn to_toml(&self) -> Result<String, SaveError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Write the bank as JSON, whatever the extension of `path`.
+    ///
+    /// The file can be read back with
+    /// [`load_bank_from_json`](super::load_bank_from_json), as long as the
+    /// questions pass [`validate_questions`](super::validate_questions).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{Question, QuestionBank, QuizMeta};
+    ///
+    /// let options = ["Nothing", "8 bytes", "A page", "4 KiB"];
+    /// let questions = vec![
+    ///     Question::new("What does `Vec::new` allocate?", options, 0).with_tags(["collections"]),
+    /// ];
+    /// let meta = QuizMeta { title: Some("Collections".to_string()), ..QuizMeta::default() };
+    /// QuestionBank::new(questions).with_meta(meta).save_json("collections.json")?;
+    /// # Ok::<(), rust_quiz::data::SaveError>(())
+    /// ```
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Write the bank as TOML, whatever the extension of `path`.
+    ///
+    /// The file can be read back with
+    /// [`load_bank_from_toml`](super::load_bank_from_toml).
+    pub fn save_toml<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{load_bank_from_json, load_bank_from_toml};
+    use crate::models::Question;
+
+    #[test]
+    fn test_save_bank_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-writer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let options = ["0", "42", "Compile error", "None"];
+        let mut bank = QuestionBank::new(vec![
+            Question::new("What does this function return?", options, vec![1, 2])
+                .with_code("fn example() -> i32 {\n    42\n}")
+                .with_tags(["functions"]),
+        ]);
+
+        save_bank(&bank, dir.join("bare.json")).unwrap();
+        let json = fs::read_to_string(dir.join("bare.json")).unwrap();
+        assert!(json.starts_with('['));
+        assert!(!json.contains("points") && !json.contains("hint"));
+
+        bank.meta.title = Some("Functions".to_string());
+        save_bank(&bank, dir.join("bank.json")).unwrap();
+        // The format is chosen by the method, not the extension
+        bank.save_toml(dir.join("bank.txt")).unwrap();
+        let from_json = load_bank_from_json(dir.join("bank.json")).unwrap();
+        let from_toml = load_bank_from_toml(dir.join("bank.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        for loaded in [from_json, from_toml] {
+            assert_eq!(loaded.meta, bank.meta);
+            assert_eq!(loaded.questions[0].code, bank.questions[0].code);
+            assert_eq!(loaded.questions[0].correct_answer, bank.questions[0].correct_answer);
+        }
+    }
+}
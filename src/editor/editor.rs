@@ -0,0 +1,161 @@
+//! Event loop and key handling of the question editor.
+
+use std::io;
+use std::path::Path;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::backend::Backend;
+use ratatui::Terminal;
+
+use crate::data::{load_bank_unchecked, LoadError, QuestionBank};
+use crate::terminal;
+use crate::theme::Theme;
+use crate::QuizError;
+
+use super::state::{Editor, Mode};
+use super::ui;
+
+/// Open the question file at `path` in the editor, drawn with `theme`.
+///
+/// A missing file starts an empty bank, created on the first save. JSON or
+/// TOML is picked by the file extension, as when loading.
+pub fn run<P: AsRef<Path>>(path: P, theme: Theme) -> Result<(), QuizError> {
+    let mut editor = open(path.as_ref())?;
+
+    let mut terminal = terminal::init().map_err(QuizError::Terminal)?;
+    let result = run_event_loop(&mut terminal, &mut editor, &theme);
+    terminal::restore().map_err(QuizError::Terminal)?;
+    result
+}
+
+/// Open the question file at `path` in the editor as [`run`] does, but on a
+/// terminal the caller has set up, e.g. one drawing to stderr.
+///
+/// Keys are still read with crossterm. Raw mode and the alternate screen
+/// are left to the caller.
+pub fn run_on<P: AsRef<Path>, B: Backend>(
+    terminal: &mut Terminal<B>,
+    path: P,
+    theme: Theme,
+) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
+    let mut editor = open(path.as_ref())?;
+    run_event_loop(terminal, &mut editor, &theme)
+}
+
+/// Load the bank to edit, or start an empty one if the file is missing.
+fn open(path: &Path) -> Result<Editor, LoadError> {
+    let bank = match load_bank_unchecked(path) {
+        Ok(bank) => bank,
+        Err(LoadError::Io(e)) if e.kind() == io::ErrorKind::NotFound => QuestionBank::default(),
+        Err(e) => return Err(e),
+    };
+    Ok(Editor::new(path.into(), bank))
+}
+
+fn run_event_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    editor: &mut Editor,
+    theme: &Theme,
+) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
+    loop {
+        terminal
+            .draw(|frame| ui::render(frame, editor, theme))
+            .map_err(terminal::backend_error::<B>)?;
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let should_quit = handle_input(editor, key);
+                if should_quit {
+                    break;
+                }
+            }
+            Event::Resize(..) => terminal::handle_resize(terminal)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle a key press. Returns true if the editor should close.
+fn handle_input(editor: &mut Editor, key: KeyEvent) -> bool {
+    editor.status = None;
+    match editor.mode {
+        Mode::List => handle_list_input(editor, key.code),
+        Mode::Edit { .. } => {
+            handle_edit_input(editor, key);
+            false
+        }
+        Mode::ConfirmDelete => {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => editor.delete_selected(),
+                _ => editor.mode = Mode::List,
+            }
+            false
+        }
+        Mode::ConfirmQuit => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let saved = editor.save();
+                if !saved {
+                    editor.mode = Mode::List;
+                }
+                saved
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => true,
+            _ => {
+                editor.mode = Mode::List;
+                false
+            }
+        },
+    }
+}
+
+fn handle_list_input(editor: &mut Editor, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => editor.select_next(),
+        KeyCode::Up | KeyCode::Char('k') => editor.select_previous(),
+        KeyCode::Char('J') => editor.move_selected(true),
+        KeyCode::Char('K') => editor.move_selected(false),
+        KeyCode::Enter | KeyCode::Char('e') => editor.edit_selected(),
+        KeyCode::Char('n') | KeyCode::Char('a') => editor.add_question(),
+        KeyCode::Char('d') | KeyCode::Delete if !editor.bank.questions.is_empty() => {
+            editor.mode = Mode::ConfirmDelete;
+        }
+        KeyCode::Char('s') => {
+            editor.save();
+        }
+        KeyCode::Char('q') | KeyCode::Esc => {
+            if !editor.modified {
+                return true;
+            }
+            editor.mode = Mode::ConfirmQuit;
+        }
+        _ => {}
+    }
+    false
+}
+
+fn handle_edit_input(editor: &mut Editor, key: KeyEvent) {
+    let Mode::Edit { form, .. } = &mut editor.mode else {
+        return;
+    };
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    match key.code {
+        KeyCode::Char('s') if ctrl => editor.apply_edit(),
+        KeyCode::Esc => editor.cancel_edit(),
+        KeyCode::Tab | KeyCode::Down => form.next_field(),
+        KeyCode::BackTab | KeyCode::Up => form.previous_field(),
+        KeyCode::Enter if form.is_multiline() => form.push('\n'),
+        KeyCode::Enter => form.next_field(),
+        KeyCode::Backspace => form.pop(),
+        KeyCode::Char(c) if !ctrl => form.push(c),
+        _ => {}
+    }
+}
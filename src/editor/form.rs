@@ -0,0 +1,224 @@
+//! A question being edited, one text field per property.
+
+use crate::data::{validate_questions, ValidationIssue};
+use crate::models::{format_answer, CorrectAnswer, Question, OPTION_LABELS};
+
+pub(super) const TEXT: usize = 0;
+pub(super) const CODE: usize = 1;
+pub(super) const LANGUAGE: usize = 2;
+/// First of the four option fields.
+pub(super) const OPTIONS: usize = 3;
+pub(super) const CORRECT: usize = 7;
+pub(super) const TAGS: usize = 8;
+pub(super) const POINTS: usize = 9;
+pub(super) const EXPLANATION: usize = 10;
+pub(super) const HINT: usize = 11;
+pub(super) const IMAGE: usize = 12;
+
+/// Labels of the fields, in display order.
+pub(super) const LABELS: [&str; 13] = [
+    "Question",
+    "Code",
+    "Language",
+    "Option A",
+    "Option B",
+    "Option C",
+    "Option D",
+    "Correct",
+    "Tags",
+    "Points",
+    "Explanation",
+    "Hint",
+    "Image",
+];
+
+/// Edit buffers for one question, plus the problems found in them.
+pub(super) struct Form {
+    pub(super) values: [String; 13],
+    pub(super) focus: usize,
+    /// Field index and message for each problem from the last
+    /// [`build_question`](Self::build_question).
+    pub(super) errors: Vec<(usize, String)>,
+}
+
+impl Form {
+    pub(super) fn new() -> Self {
+        Self {
+            values: Default::default(),
+            focus: TEXT,
+            errors: Vec::new(),
+        }
+    }
+
+    pub(super) fn from_question(question: &Question) -> Self {
+        let mut form = Self::new();
+        form.values[TEXT] = question.text.clone();
+        form.values[CODE] = question.code.clone().unwrap_or_default();
+        form.values[LANGUAGE] = question.code_language.clone().unwrap_or_default();
+        for (index, option) in question.options.iter().enumerate() {
+            form.values[OPTIONS + index] = option.clone();
+        }
+        form.values[CORRECT] = format_answer(&question.correct_answer.indices());
+        if question.is_multiple() && !form.values[CORRECT].contains(',') {
+            form.values[CORRECT].push(',');
+        }
+        form.values[TAGS] = question.tags.join(", ");
+        form.values[POINTS] = question.points.to_string();
+        form.values[EXPLANATION] = question.explanation.clone().unwrap_or_default();
+        form.values[HINT] = question.hint.clone().unwrap_or_default();
+        form.values[IMAGE] = question.image.clone().unwrap_or_default();
+        form
+    }
+
+    /// Whether the focused field takes several lines.
+    pub(super) fn is_multiline(&self) -> bool {
+        self.focus == CODE
+    }
+
+    pub(super) fn next_field(&mut self) {
+        self.focus = (self.focus + 1) % LABELS.len();
+    }
+
+    pub(super) fn previous_field(&mut self) {
+        self.focus = (self.focus + LABELS.len() - 1) % LABELS.len();
+    }
+
+    pub(super) fn push(&mut self, c: char) {
+        self.values[self.focus].push(c);
+    }
+
+    pub(super) fn pop(&mut self) {
+        self.values[self.focus].pop();
+    }
+
+    /// The first problem with `field`, if any.
+    pub(super) fn error(&self, field: usize) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|(index, _)| *index == field)
+            .map(|(_, message)| message.as_str())
+    }
+
+    /// Build the question, or record what is wrong with each field and
+    /// return `None`.
+    pub(super) fn build_question(&mut self) -> Option<Question> {
+        self.errors.clear();
+
+        if self.values[TEXT].trim().is_empty() {
+            self.errors.push((TEXT, "Required".to_string()));
+        }
+        let correct_answer = match parse_correct_answer(&self.values[CORRECT]) {
+            Ok(answer) => Some(answer),
+            Err(message) => {
+                self.errors.push((CORRECT, message));
+                None
+            }
+        };
+        let points = match self.values[POINTS].trim() {
+            "" => Some(1),
+            points => match points.parse() {
+                Ok(points) => Some(points),
+                Err(_) => {
+                    self.errors.push((POINTS, "Must be a whole number".to_string()));
+                    None
+                }
+            },
+        };
+
+        let question = Question {
+            text: self.values[TEXT].trim().to_string(),
+            code: optional(&self.values[CODE]),
+            code_language: optional(&self.values[LANGUAGE]),
+            options: std::array::from_fn(|index| self.values[OPTIONS + index].trim().to_string()),
+            correct_answer: correct_answer.unwrap_or(CorrectAnswer::Single(0)),
+            tags: self.values[TAGS]
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect(),
+            points: points.unwrap_or(1),
+            explanation: optional(&self.values[EXPLANATION]),
+            hint: optional(&self.values[HINT]),
+            image: optional(&self.values[IMAGE]),
+        };
+
+        for issue in validate_questions(std::slice::from_ref(&question)) {
+            match issue {
+                ValidationIssue::EmptyOption { option, .. } => {
+                    self.errors.push((OPTIONS + option, "Required".to_string()));
+                }
+                ValidationIssue::DuplicateOption { option, first, .. } => {
+                    self.errors.push((
+                        OPTIONS + option,
+                        format!("Same as option {}", OPTION_LABELS[first]),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        self.errors.is_empty().then_some(question)
+    }
+}
+
+/// `None` for a blank field, so it is left out of the file.
+fn optional(value: &str) -> Option<String> {
+    // Keep the code's indentation, but not blank lines around it
+    let value = value.trim_matches('\n').trim_end();
+    (!value.trim().is_empty()).then(|| value.to_string())
+}
+
+/// Parse option letters such as `B` or `A, C`.
+///
+/// A single letter makes a regular question. Several letters, or a comma as
+/// in `B,`, make a checkbox question.
+fn parse_correct_answer(value: &str) -> Result<CorrectAnswer, String> {
+    let mut indices = Vec::new();
+    for letter in value.split([',', ' ']).filter(|letter| !letter.is_empty()) {
+        let index = OPTION_LABELS
+            .iter()
+            .position(|label| letter.eq_ignore_ascii_case(&label.to_string()))
+            .ok_or_else(|| format!("Unknown option '{}', use letters A-D", letter))?;
+        indices.push(index);
+    }
+
+    match indices.as_slice() {
+        [] => Err("Required, e.g. B or A, C".to_string()),
+        [index] if !value.contains(',') => Ok(CorrectAnswer::Single(*index)),
+        _ => Ok(CorrectAnswer::Multiple(indices)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_round_trip_and_errors() {
+        let mut form = Form::new();
+        form.values[TEXT] = "Which are integers?".to_string();
+        form.values[CODE] = "\nlet x = 1;\n".to_string();
+        for (index, option) in ["i32", "f64", "u8", "i32"].iter().enumerate() {
+            form.values[OPTIONS + index] = option.to_string();
+        }
+        form.values[CORRECT] = "a, x".to_string();
+        form.values[POINTS] = "two".to_string();
+
+        assert!(form.build_question().is_none());
+        assert!(form.error(CORRECT).is_some());
+        assert!(form.error(POINTS).is_some());
+        assert_eq!(form.error(OPTIONS + 3), Some("Same as option A"));
+
+        form.values[OPTIONS + 3] = "bool".to_string();
+        form.values[CORRECT] = "a, c".to_string();
+        form.values[POINTS] = String::new();
+        let question = form.build_question().unwrap();
+        assert_eq!(question.correct_answer, CorrectAnswer::Multiple(vec![0, 2]));
+        assert_eq!(question.code.as_deref(), Some("let x = 1;"));
+        assert_eq!(question.points, 1);
+
+        let form = Form::from_question(&question);
+        assert_eq!(form.values[CORRECT], "A, C");
+    }
+}
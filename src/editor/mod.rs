@@ -0,0 +1,12 @@
+//! Question bank editor.
+//!
+//! A terminal UI for browsing, adding, changing and deleting the questions
+//! of a JSON or TOML file, so code snippets don't have to be escaped by hand.
+
+#[allow(clippy::module_inception)]
+mod editor;
+mod form;
+mod state;
+mod ui;
+
+pub use editor::{run, run_on};
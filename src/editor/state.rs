@@ -0,0 +1,147 @@
+//! Editor state: the question bank being edited and what is on screen.
+
+use std::path::PathBuf;
+
+use crate::data::{save_bank, validate_questions, QuestionBank, ValidationIssue};
+
+use super::form::Form;
+
+/// What the editor is showing.
+pub(super) enum Mode {
+    /// Browsing the list of questions.
+    List,
+    /// Editing a question; `index` is `None` for a new one.
+    Edit {
+        form: Box<Form>,
+        index: Option<usize>,
+    },
+    /// Asking whether to delete the selected question.
+    ConfirmDelete,
+    /// Asking whether to save before quitting.
+    ConfirmQuit,
+}
+
+pub(super) struct Editor {
+    pub(super) path: PathBuf,
+    pub(super) bank: QuestionBank,
+    pub(super) selected: usize,
+    pub(super) mode: Mode,
+    /// Whether there are changes not yet written to `path`.
+    pub(super) modified: bool,
+    /// One-off message shown in the footer, e.g. after saving.
+    pub(super) status: Option<String>,
+}
+
+impl Editor {
+    pub(super) fn new(path: PathBuf, bank: QuestionBank) -> Self {
+        Self {
+            path,
+            bank,
+            selected: 0,
+            mode: Mode::List,
+            modified: false,
+            status: None,
+        }
+    }
+
+    /// Problems with the bank as a whole, such as duplicate questions.
+    pub(super) fn issues(&self) -> Vec<ValidationIssue> {
+        validate_questions(&self.bank.questions)
+    }
+
+    pub(super) fn select_next(&mut self) {
+        if self.selected + 1 < self.bank.questions.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(super) fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(super) fn edit_selected(&mut self) {
+        if let Some(question) = self.bank.questions.get(self.selected) {
+            self.mode = Mode::Edit {
+                form: Box::new(Form::from_question(question)),
+                index: Some(self.selected),
+            };
+        }
+    }
+
+    pub(super) fn add_question(&mut self) {
+        self.mode = Mode::Edit {
+            form: Box::new(Form::new()),
+            index: None,
+        };
+    }
+
+    /// Put the edited question into the bank if its fields are valid.
+    pub(super) fn apply_edit(&mut self) {
+        let Mode::Edit { form, index } = &mut self.mode else {
+            return;
+        };
+        let Some(question) = form.build_question() else {
+            return;
+        };
+
+        match *index {
+            Some(index) => self.bank.questions[index] = question,
+            None => {
+                self.bank.questions.push(question);
+                self.selected = self.bank.questions.len() - 1;
+            }
+        }
+        self.modified = true;
+        self.mode = Mode::List;
+    }
+
+    pub(super) fn cancel_edit(&mut self) {
+        self.mode = Mode::List;
+    }
+
+    pub(super) fn delete_selected(&mut self) {
+        if self.selected < self.bank.questions.len() {
+            self.bank.questions.remove(self.selected);
+            self.selected = self.selected.min(self.bank.questions.len().saturating_sub(1));
+            self.modified = true;
+        }
+        self.mode = Mode::List;
+    }
+
+    /// Swap the selected question with the one below (`down`) or above.
+    pub(super) fn move_selected(&mut self, down: bool) {
+        let target = if down {
+            self.selected + 1
+        } else {
+            match self.selected.checked_sub(1) {
+                Some(target) => target,
+                None => return,
+            }
+        };
+        if target < self.bank.questions.len() {
+            self.bank.questions.swap(self.selected, target);
+            self.selected = target;
+            self.modified = true;
+        }
+    }
+
+    /// Write the bank back to its file. Returns whether it was saved.
+    pub(super) fn save(&mut self) -> bool {
+        match save_bank(&self.bank, &self.path) {
+            Ok(()) => {
+                self.modified = false;
+                let issues = self.issues().len();
+                self.status = Some(match issues {
+                    0 => format!("Saved {}", self.path.display()),
+                    1 => "Saved, but 1 problem remains".to_string(),
+                    _ => format!("Saved, but {} problems remain", issues),
+                });
+                true
+            }
+            Err(e) => {
+                self.status = Some(e.to_string());
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Drawing the question editor.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph},
+};
+
+use crate::terminal;
+use crate::text;
+use crate::theme::Theme;
+
+use super::form::{Form, CODE, LABELS};
+use super::state::{Editor, Mode};
+
+/// Smallest terminal the editor fits in.
+const MIN_SIZE: Size = Size::new(60, 16);
+
+/// Columns taken by a field label, including the gap after it.
+const LABEL_WIDTH: usize = 13;
+
+pub(super) fn render(frame: &mut Frame, editor: &Editor, theme: &Theme) {
+    let area = frame.area();
+    frame.render_widget(Block::default().bg(Color::Reset), area);
+    if terminal::render_too_small(frame, MIN_SIZE, theme) {
+        return;
+    }
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+    ])
+    .split(area);
+
+    render_header(frame, chunks[0], editor, theme);
+    match &editor.mode {
+        Mode::Edit { form, .. } => render_form(frame, chunks[1], form, theme),
+        _ => render_list(frame, chunks[1], editor, theme),
+    }
+    render_footer(frame, chunks[2], editor, theme);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, editor: &Editor, theme: &Theme) {
+    let count = match editor.bank.questions.len() {
+        1 => "1 question".to_string(),
+        count => format!("{} questions", count),
+    };
+    let mut spans = vec![
+        Span::styled(editor.path.display().to_string(), Style::default().fg(theme.text)),
+        Span::styled(format!("  ·  {}", count), Style::default().fg(theme.dimmed)),
+    ];
+    if editor.modified {
+        spans.push(Span::styled("  ·  modified", Style::default().fg(theme.highlight)));
+    }
+    if let Mode::Edit { index, .. } = &editor.mode {
+        let editing = match index {
+            Some(index) => format!("  ·  editing question {}", index + 1),
+            None => "  ·  new question".to_string(),
+        };
+        spans.push(Span::styled(editing, Style::default().fg(theme.accent)));
+    }
+
+    let header = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.dimmed)
+            .title(" Question Editor ")
+            .title_style(Style::default().fg(theme.accent).bold())
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(header, area);
+}
+
+fn render_list(frame: &mut Frame, area: Rect, editor: &Editor, theme: &Theme) {
+    let questions = &editor.bank.questions;
+    if questions.is_empty() {
+        let empty = Paragraph::new("No questions yet. Press n to add one.")
+            .style(Style::default().fg(theme.dimmed).italic())
+            .alignment(Alignment::Center)
+            .block(Block::default().padding(Padding::top(area.height / 2)));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let issues = editor.issues();
+    let preview_width = (area.width as usize).saturating_sub(12);
+    let lines: Vec<Line> = questions
+        .iter()
+        .enumerate()
+        .map(|(index, question)| {
+            let is_selected = index == editor.selected;
+            let has_issue = issues.iter().any(|issue| issue.question() == index);
+            let marker = if is_selected { ">" } else { " " };
+            let text_style = if is_selected {
+                Style::default().fg(theme.text).bold()
+            } else {
+                Style::default().fg(theme.muted)
+            };
+
+            let mut spans = vec![
+                Span::styled(marker, Style::default().fg(theme.accent).bold()),
+                Span::styled(
+                    if has_issue { "! " } else { "  " },
+                    Style::default().fg(theme.incorrect).bold(),
+                ),
+                Span::styled(format!("{:3}. ", index + 1), Style::default().fg(theme.dimmed)),
+                Span::styled(text::truncate(&question.text, preview_width), text_style),
+            ];
+            if !question.tags.is_empty() {
+                spans.push(Span::styled(
+                    format!("  [{}]", question.tags.join(", ")),
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    // Keep the selected question in view.
+    let scroll = editor
+        .selected
+        .saturating_sub(area.height.saturating_sub(1) as usize);
+    let widget = Paragraph::new(lines)
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(widget, area);
+}
+
+fn render_form(frame: &mut Frame, area: Rect, form: &Form, theme: &Theme) {
+    let value_width = (area.width as usize).saturating_sub(LABEL_WIDTH + 4);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut focus_line = 0;
+
+    for (field, label) in LABELS.iter().enumerate() {
+        let is_focused = field == form.focus;
+        if is_focused {
+            focus_line = lines.len();
+        }
+        let label_style = if is_focused {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.dimmed)
+        };
+        let value_style = Style::default().fg(if field == CODE { theme.code } else { theme.text });
+        let cursor = Span::styled(
+            if is_focused { "█" } else { "" },
+            Style::default().fg(theme.accent),
+        );
+        let label = Span::styled(text::pad(label, LABEL_WIDTH), label_style);
+        let value = &form.values[field];
+
+        if field == CODE {
+            lines.push(Line::from(label));
+            let code_lines: Vec<&str> = value.split('\n').collect();
+            let last = code_lines.len() - 1;
+            for (index, code_line) in code_lines.into_iter().enumerate() {
+                let mut spans = vec![
+                    Span::raw("  "),
+                    Span::styled(text::truncate(code_line, value_width), value_style),
+                ];
+                if index == last {
+                    spans.push(cursor.clone());
+                }
+                lines.push(Line::from(spans));
+            }
+            if is_focused {
+                focus_line = lines.len() - 1;
+            }
+        } else {
+            let shown = if is_focused {
+                text::truncate_start(value, value_width)
+            } else {
+                text::truncate(value, value_width)
+            };
+            lines.push(Line::from(vec![label, Span::styled(shown, value_style), cursor]));
+        }
+
+        if let Some(error) = form.error(field) {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", " ".repeat(LABEL_WIDTH), error),
+                Style::default().fg(theme.incorrect),
+            )));
+        }
+    }
+
+    // Keep the focused field in view.
+    let height = area.height.saturating_sub(2) as usize;
+    let scroll = focus_line.saturating_sub(height.saturating_sub(2));
+    let widget = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.dimmed)
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect, editor: &Editor, theme: &Theme) {
+    let status = match &editor.mode {
+        Mode::ConfirmDelete => Line::from(
+            format!("Delete question {}? y/n", editor.selected + 1).fg(theme.highlight),
+        ),
+        Mode::ConfirmQuit => {
+            Line::from("Save changes before quitting? y/n  ·  esc cancel".fg(theme.highlight))
+        }
+        Mode::Edit { form, .. } if !form.errors.is_empty() => {
+            Line::from("Fix the marked fields to continue".fg(theme.incorrect))
+        }
+        _ => match &editor.status {
+            Some(status) => Line::from(status.as_str().fg(theme.highlight)),
+            None => selected_issue(editor)
+                .map(|issue| Line::from(issue.fg(theme.incorrect)))
+                .unwrap_or_default(),
+        },
+    };
+    let keys = match &editor.mode {
+        Mode::Edit { .. } => {
+            "tab/↑↓ field  ·  enter newline in code  ·  ctrl+s done  ·  esc cancel"
+        }
+        _ => {
+            "↑↓ select  ·  enter edit  ·  n new  ·  d delete  ·  J/K move  ·  s save  ·  q quit"
+        }
+    };
+
+    let widget = Paragraph::new(vec![status, Line::from(keys.fg(theme.dimmed))])
+        .alignment(Alignment::Center);
+    frame.render_widget(widget, area);
+}
+
+/// The first problem with the selected question, if any.
+fn selected_issue(editor: &Editor) -> Option<String> {
+    if !matches!(editor.mode, Mode::List) {
+        return None;
+    }
+    editor
+        .issues()
+        .into_iter()
+        .find(|issue| issue.question() == editor.selected)
+        .map(|issue| issue.to_string())
+}
@@ -0,0 +1,703 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::models::{max_score, normalize_answer, Calibration, Confidence, Question, QuizMeta};
+use crate::session::SavedSession;
+
+/// The rules of a quiz run, without any user interface.
+///
+/// Tracks which question is current, the answers given, skipped questions,
+/// hints, timing and scoring. The terminal UI drives it through [`App`], but
+/// it can be driven directly as well, e.g. from a GUI or from tests.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_quiz::{CorrectAnswer, Question, QuizEngine};
+///
+/// let question = Question {
+///     text: "Which keyword declares a constant?".to_string(),
+///     code: None,
+///     code_language: None,
+///     options: ["let", "static", "const", "mut"].map(String::from),
+///     correct_answer: CorrectAnswer::Single(2),
+///     tags: Vec::new(),
+///     points: 1,
+///     explanation: None,
+///     hint: None,
+///     image: None,
+/// };
+///
+/// let mut engine = QuizEngine::new(vec![question]);
+/// engine.start();
+/// assert_eq!(engine.answer(vec![2]), Some(true));
+///
+/// let result = engine.result().expect("quiz is finished");
+/// assert_eq!(result.score, 1);
+/// ```
+///
+/// [`App`]: crate::App
+pub struct QuizEngine {
+    questions: Vec<Question>,
+    current_question_index: usize,
+    /// Indices of questions still to be answered, current question first.
+    question_queue: VecDeque<usize>,
+    /// Questions that were skipped and are still waiting in the queue.
+    skipped: Vec<bool>,
+    answers: Vec<Option<Vec<usize>>>,
+    /// Questions whose hint was revealed.
+    hints_used: Vec<bool>,
+    time_limit: Option<Duration>,
+    started_at: Option<Instant>,
+    /// Time spent before the session was saved and resumed.
+    resumed_elapsed: Duration,
+    /// Set once the quiz has ended.
+    time_taken: Option<Duration>,
+    exam_mode: bool,
+    /// Points deducted from a question's score when its hint was revealed.
+    hint_penalty: usize,
+    /// Whether answers are given with a [`Confidence`] that weights them.
+    confidence_mode: bool,
+    confidences: Vec<Option<Confidence>>,
+    /// How far into the quiz each answer was last saved.
+    answered_at: Vec<Option<Duration>>,
+}
+
+/// Summary of a finished quiz run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizResult {
+    /// Points earned, after hint penalties.
+    pub score: usize,
+    pub max_score: usize,
+    /// Number of questions answered correctly.
+    pub correct: usize,
+    /// Number of questions answered at all.
+    pub answered: usize,
+    pub total: usize,
+    pub time_taken: Duration,
+    /// Score per tag as `(tag, score, max_score)`, sorted by tag.
+    pub tag_scores: Vec<(String, usize, usize)>,
+    /// How often sure and unsure answers were right, in confidence mode.
+    pub calibration: Option<Calibration>,
+}
+
+impl QuizResult {
+    /// Score as a percentage of the maximum score.
+    pub fn percentage(&self) -> f64 {
+        if self.max_score > 0 {
+            self.score as f64 / self.max_score as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl QuizEngine {
+    pub fn new(questions: Vec<Question>) -> Self {
+        let num_questions = questions.len();
+
+        Self {
+            questions,
+            current_question_index: 0,
+            question_queue: (0..num_questions).collect(),
+            skipped: vec![false; num_questions],
+            answers: vec![None; num_questions],
+            hints_used: vec![false; num_questions],
+            time_limit: None,
+            started_at: None,
+            resumed_elapsed: Duration::ZERO,
+            time_taken: None,
+            exam_mode: false,
+            hint_penalty: 0,
+            confidence_mode: false,
+            confidences: vec![None; num_questions],
+            answered_at: vec![None; num_questions],
+        }
+    }
+
+    /// Rebuild a quiz in progress from a saved session.
+    ///
+    /// The quiz is started right away, with the time already spent counted
+    /// towards any time limit.
+    pub fn from_saved_session(session: SavedSession) -> Self {
+        let mut engine = Self::new(session.questions);
+        let num_questions = engine.questions.len();
+        let fit = |mut flags: Vec<bool>| {
+            flags.resize(num_questions, false);
+            flags
+        };
+
+        engine.question_queue = session
+            .queue
+            .into_iter()
+            .filter(|&index| index < num_questions)
+            .collect();
+        engine.answers = session.answers;
+        engine.answers.resize(num_questions, None);
+        engine.skipped = fit(session.skipped);
+        engine.hints_used = fit(session.hints_used);
+        engine.time_limit = session.time_limit_secs.map(Duration::from_secs);
+        engine.exam_mode = session.exam_mode;
+        engine.hint_penalty = session.hint_penalty;
+        engine.confidence_mode = session.confidence_mode;
+        engine.confidences = session.confidences;
+        engine.confidences.resize(num_questions, None);
+        engine.answered_at = vec![None; num_questions];
+
+        engine.start();
+        engine.resumed_elapsed = Duration::from_secs(session.elapsed_secs);
+        if session.current_question < num_questions {
+            engine.current_question_index = session.current_question;
+        }
+        if !engine.exam_mode && engine.question_queue.is_empty() {
+            engine.finish();
+        }
+        engine
+    }
+
+    /// Snapshot the quiz in progress so it can be resumed later.
+    pub fn to_saved_session(&self, source: &str) -> SavedSession {
+        // An answered question may still be shown (e.g. with instant
+        // feedback), so resume at the next one in the queue.
+        let current_question = match self.question_queue.front() {
+            Some(&next) if !self.exam_mode => next,
+            _ => self.current_question_index,
+        };
+
+        SavedSession {
+            source: source.to_string(),
+            questions: self.questions.clone(),
+            current_question,
+            queue: self.question_queue.iter().copied().collect(),
+            answers: self.answers.clone(),
+            skipped: self.skipped.clone(),
+            hints_used: self.hints_used.clone(),
+            elapsed_secs: self.elapsed().as_secs(),
+            time_limit_secs: self.time_limit.map(|limit| limit.as_secs()),
+            exam_mode: self.exam_mode,
+            instant_feedback: false,
+            hint_penalty: self.hint_penalty,
+            confidence_mode: self.confidence_mode,
+            confidences: self.confidences.clone(),
+            meta: QuizMeta::default(),
+        }
+    }
+
+    /// Replace the questions, keeping settings such as the time limit.
+    ///
+    /// Progress is reset as if the quiz was restarted.
+    pub fn replace_questions(&mut self, questions: Vec<Question>) {
+        self.questions = questions;
+        self.reset();
+    }
+
+    /// Clear all progress, keeping the questions and settings.
+    pub fn reset(&mut self) {
+        let num_questions = self.questions.len();
+        self.current_question_index = 0;
+        self.question_queue = (0..num_questions).collect();
+        self.skipped = vec![false; num_questions];
+        self.answers = vec![None; num_questions];
+        self.hints_used = vec![false; num_questions];
+        self.confidences = vec![None; num_questions];
+        self.answered_at = vec![None; num_questions];
+        self.started_at = None;
+        self.resumed_elapsed = Duration::ZERO;
+        self.time_taken = None;
+    }
+
+    /// Set a time budget for the whole quiz.
+    ///
+    /// When it runs out, unanswered questions count as wrong and the quiz
+    /// ends on the next [`tick`](Self::tick).
+    pub fn set_time_limit(&mut self, time_limit: Option<Duration>) {
+        self.time_limit = time_limit;
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub fn is_exam_mode(&self) -> bool {
+        self.exam_mode
+    }
+
+    /// Enable exam mode, where answers can be revised until the quiz is
+    /// finished explicitly with [`finish`](Self::finish).
+    pub fn set_exam_mode(&mut self, exam_mode: bool) {
+        self.exam_mode = exam_mode;
+    }
+
+    pub fn hint_penalty(&self) -> usize {
+        self.hint_penalty
+    }
+
+    /// Deduct `hint_penalty` points from questions answered with a hint.
+    ///
+    /// A question never scores below zero.
+    pub fn set_hint_penalty(&mut self, hint_penalty: usize) {
+        self.hint_penalty = hint_penalty;
+    }
+
+    pub fn is_confidence_mode(&self) -> bool {
+        self.confidence_mode
+    }
+
+    /// Weight answers by how sure the player is, as given to
+    /// [`record_answer_with_confidence`](Self::record_answer_with_confidence).
+    ///
+    /// A sure answer earns twice its points when correct and loses its
+    /// points when wrong; answers without a confidence count as unsure. The
+    /// total score never goes below zero.
+    pub fn set_confidence_mode(&mut self, confidence_mode: bool) {
+        self.confidence_mode = confidence_mode;
+    }
+
+    /// Start the clock.
+    pub fn start(&mut self) {
+        self.started_at = Some(Instant::now());
+        self.time_taken = None;
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time_taken.is_some()
+    }
+
+    /// End the quiz and score the answers given so far.
+    pub fn finish(&mut self) {
+        if !self.is_finished() {
+            self.time_taken = Some(self.elapsed());
+        }
+    }
+
+    /// Time spent since the quiz started, frozen once it ends.
+    pub fn elapsed(&self) -> Duration {
+        self.time_taken
+            .or_else(|| {
+                self.started_at
+                    .map(|started| started.elapsed() + self.resumed_elapsed)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Time left before the time limit expires, if there is one.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.time_limit
+            .map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    /// Total time taken, available once the quiz has ended.
+    pub fn time_taken(&self) -> Option<Duration> {
+        self.time_taken
+    }
+
+    /// End the quiz if the time limit expired.
+    ///
+    /// Returns true if this call ended the quiz.
+    pub fn tick(&mut self) -> bool {
+        let expired = self.is_started()
+            && !self.is_finished()
+            && self.remaining_time() == Some(Duration::ZERO);
+        if expired {
+            self.finish();
+        }
+        expired
+    }
+
+    pub fn current_question(&self) -> &Question {
+        &self.questions[self.current_question_index]
+    }
+
+    /// Index of the current question in [`questions`](Self::questions).
+    pub fn current_question_index(&self) -> usize {
+        self.current_question_index
+    }
+
+    pub fn current_question_number(&self) -> usize {
+        self.current_question_index + 1
+    }
+
+    pub fn total_questions(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn questions(&self) -> &[Question] {
+        &self.questions
+    }
+
+    pub fn answers(&self) -> &[Option<Vec<usize>>] {
+        &self.answers
+    }
+
+    /// Get the saved answer for the current question, if any.
+    pub fn current_answer(&self) -> Option<&[usize]> {
+        self.answers
+            .get(self.current_question_index)
+            .and_then(|answer| answer.as_deref())
+    }
+
+    /// Check if the current question has a saved answer.
+    pub fn is_current_answered(&self) -> bool {
+        self.current_answer().is_some()
+    }
+
+    /// Number of questions with a saved answer.
+    pub fn answered_count(&self) -> usize {
+        self.answers.iter().filter(|answer| answer.is_some()).count()
+    }
+
+    /// Number of skipped questions still waiting to be answered.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.iter().filter(|&&skipped| skipped).count()
+    }
+
+    /// Answer the current question and move on.
+    ///
+    /// Outside exam mode the next question is the front of the queue, and
+    /// answering the last one finishes the quiz. In exam mode the answer can
+    /// still be changed later and the quiz moves to the following question.
+    ///
+    /// Returns whether the answer is correct, or `None` if it was ignored
+    /// because it is empty or the quiz has finished.
+    pub fn answer(&mut self, answer: Vec<usize>) -> Option<bool> {
+        let is_correct = self.record_answer(answer)?;
+        self.advance();
+        Some(is_correct)
+    }
+
+    /// Save an answer for the current question without moving on.
+    ///
+    /// Call [`advance`](Self::advance) to go to the next question, e.g. after
+    /// showing whether the answer was correct.
+    pub fn record_answer(&mut self, answer: Vec<usize>) -> Option<bool> {
+        let answer = normalize_answer(answer);
+        if answer.is_empty() || self.is_finished() {
+            return None;
+        }
+
+        let index = self.current_question_index;
+        let is_correct = self.questions[index].is_correct(&answer);
+        self.answers[index] = Some(answer);
+        self.confidences[index] = None;
+        self.answered_at[index] = Some(self.elapsed());
+        self.skipped[index] = false;
+        self.question_queue.retain(|&queued| queued != index);
+        Some(is_correct)
+    }
+
+    /// Save an answer for the current question along with how sure the
+    /// player is of it, without moving on.
+    pub fn record_answer_with_confidence(
+        &mut self,
+        answer: Vec<usize>,
+        confidence: Confidence,
+    ) -> Option<bool> {
+        let index = self.current_question_index;
+        let is_correct = self.record_answer(answer)?;
+        self.confidences[index] = Some(confidence);
+        Some(is_correct)
+    }
+
+    /// How sure the player was of each answer, in question order.
+    pub fn confidences(&self) -> &[Option<Confidence>] {
+        &self.confidences
+    }
+
+    /// How far into the quiz each answer was last saved, in question order.
+    ///
+    /// Answers given before a session was saved and resumed have no time.
+    pub fn answered_at(&self) -> &[Option<Duration>] {
+        &self.answered_at
+    }
+
+    /// Move on after answering.
+    ///
+    /// Goes to the front of the queue, finishing when it is empty. In exam
+    /// mode goes to the following question instead.
+    pub fn advance(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        if self.exam_mode {
+            self.next_question();
+            return;
+        }
+
+        match self.question_queue.front() {
+            Some(&next) => self.current_question_index = next,
+            None => self.finish(),
+        }
+    }
+
+    /// Defer the current question to the end of the queue.
+    ///
+    /// Does nothing in exam mode or when it is the last question left.
+    /// Returns true if the question was skipped.
+    pub fn skip(&mut self) -> bool {
+        if self.exam_mode || self.question_queue.len() < 2 || self.is_finished() {
+            return false;
+        }
+
+        if let Some(index) = self.question_queue.pop_front() {
+            self.skipped[index] = true;
+            self.question_queue.push_back(index);
+        }
+        if let Some(&next) = self.question_queue.front() {
+            self.current_question_index = next;
+        }
+        true
+    }
+
+    /// Move to the next question in exam mode.
+    ///
+    /// Returns true if the current question changed.
+    pub fn next_question(&mut self) -> bool {
+        let can_move = self.exam_mode && self.current_question_index + 1 < self.questions.len();
+        if can_move {
+            self.current_question_index += 1;
+        }
+        can_move
+    }
+
+    /// Move to the previous question in exam mode.
+    ///
+    /// Returns true if the current question changed.
+    pub fn previous_question(&mut self) -> bool {
+        let can_move = self.exam_mode && self.current_question_index > 0;
+        if can_move {
+            self.current_question_index -= 1;
+        }
+        can_move
+    }
+
+    /// Reveal the hint of the current question, if it has one.
+    pub fn reveal_hint(&mut self) -> Option<&str> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let index = self.current_question_index;
+        let hint = self.questions[index].hint.as_deref()?;
+        self.hints_used[index] = true;
+        Some(hint)
+    }
+
+    /// Check if the hint of the current question has been revealed.
+    pub fn is_hint_revealed(&self) -> bool {
+        self.hints_used
+            .get(self.current_question_index)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Which questions had their hint revealed, in question order.
+    pub fn hints_used(&self) -> &[bool] {
+        &self.hints_used
+    }
+
+    /// Sum of points earned on correctly answered questions, less points
+    /// lost on sure answers that were wrong in confidence mode.
+    pub fn calculate_score(&self) -> usize {
+        let indices = 0..self.questions.len();
+        let earned: usize = indices.clone().map(|index| self.question_score(index)).sum();
+        earned.saturating_sub(indices.map(|index| self.question_penalty(index)).sum())
+    }
+
+    /// Points earned on a question, after any hint penalty.
+    pub fn question_score(&self, index: usize) -> usize {
+        let mut points = self.questions[index].points_for(self.answers[index].as_deref());
+        if self.confidence_mode {
+            points = self.confidence(index).reward(points);
+        }
+        if self.hints_used[index] {
+            points.saturating_sub(self.hint_penalty)
+        } else {
+            points
+        }
+    }
+
+    /// Points lost on a question by a wrong answer the player was sure of.
+    pub fn question_penalty(&self, index: usize) -> usize {
+        let question = &self.questions[index];
+        match &self.answers[index] {
+            Some(answer) if self.confidence_mode && !question.is_correct(answer) => {
+                self.confidence(index).penalty(question.points)
+            }
+            _ => 0,
+        }
+    }
+
+    fn confidence(&self, index: usize) -> Confidence {
+        self.confidences[index].unwrap_or(Confidence::Unsure)
+    }
+
+    /// Maximum achievable score.
+    pub fn max_score(&self) -> usize {
+        let points = max_score(&self.questions);
+        if self.confidence_mode { points * 2 } else { points }
+    }
+
+    /// How often sure and unsure answers were right, in confidence mode.
+    pub fn calibration(&self) -> Option<Calibration> {
+        if !self.confidence_mode {
+            return None;
+        }
+        let answers = self.questions.iter().enumerate().filter_map(|(index, question)| {
+            let answer = self.answers[index].as_deref()?;
+            Some((self.confidence(index), question.is_correct(answer)))
+        });
+        Some(Calibration::new(answers))
+    }
+
+    /// Score broken down per tag as `(tag, score, max_score)`, sorted by tag.
+    ///
+    /// Questions with several tags count towards each of them.
+    pub fn score_by_tag(&self) -> Vec<(String, usize, usize)> {
+        let mut scores: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+        let mut penalties: BTreeMap<&str, usize> = BTreeMap::new();
+        let weight = if self.confidence_mode { 2 } else { 1 };
+
+        for (index, question) in self.questions.iter().enumerate() {
+            let points = self.question_score(index);
+            let penalty = self.question_penalty(index);
+            for tag in &question.tags {
+                let entry = scores.entry(tag.as_str()).or_default();
+                entry.0 += points;
+                entry.1 += question.points * weight;
+                *penalties.entry(tag.as_str()).or_default() += penalty;
+            }
+        }
+
+        scores
+            .into_iter()
+            .map(|(tag, (score, max))| (tag.to_string(), score.saturating_sub(penalties[tag]), max))
+            .collect()
+    }
+
+    /// Summary of the run, available once the quiz has finished.
+    pub fn result(&self) -> Option<QuizResult> {
+        let time_taken = self.time_taken?;
+        let correct = self
+            .questions
+            .iter()
+            .zip(&self.answers)
+            .filter(|(question, answer)| {
+                answer.as_deref().is_some_and(|answer| question.is_correct(answer))
+            })
+            .count();
+
+        Some(QuizResult {
+            score: self.calculate_score(),
+            max_score: self.max_score(),
+            correct,
+            answered: self.answered_count(),
+            total: self.questions.len(),
+            time_taken,
+            tag_scores: self.score_by_tag(),
+            calibration: self.calibration(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::questions;
+
+    #[test]
+    fn test_skip_moves_to_end_of_queue() {
+        let mut engine = QuizEngine::new(questions(3));
+        engine.start();
+
+        assert!(engine.skip());
+        assert_eq!(engine.current_question_number(), 2);
+        assert_eq!(engine.skipped_count(), 1);
+
+        assert_eq!(engine.answer(vec![0]), Some(false));
+        assert_eq!(engine.answer(vec![0]), Some(false));
+        assert_eq!(engine.current_question_number(), 1);
+
+        assert!(!engine.skip());
+        assert_eq!(engine.answer(vec![]), None);
+        assert_eq!(engine.answer(vec![1]), Some(true));
+        assert_eq!(engine.skipped_count(), 0);
+        assert!(engine.is_finished());
+        assert_eq!(engine.answer(vec![1]), None);
+
+        let result = engine.result().unwrap();
+        assert_eq!((result.score, result.correct, result.answered), (1, 1, 3));
+    }
+
+    #[test]
+    fn test_exam_mode_revise_and_finish() {
+        let mut engine = QuizEngine::new(questions(3));
+        engine.set_exam_mode(true);
+        engine.start();
+
+        engine.answer(vec![1]);
+        assert_eq!(engine.current_question_number(), 2);
+        assert!(engine.previous_question());
+        engine.answer(vec![2]);
+        assert_eq!(engine.answers()[0], Some(vec![2]));
+
+        assert!(engine.next_question());
+        assert!(!engine.next_question());
+        assert!(!engine.is_finished());
+        assert!(engine.result().is_none());
+
+        engine.finish();
+        assert_eq!(engine.result().unwrap().score, 0);
+    }
+
+    #[test]
+    fn test_hint_penalty() {
+        let mut qs = questions(2);
+        qs[0].points = 3;
+        qs[0].hint = Some("Not a".to_string());
+        let mut engine = QuizEngine::new(qs);
+        engine.set_hint_penalty(2);
+        engine.start();
+
+        assert_eq!(engine.reveal_hint(), Some("Not a"));
+        assert!(engine.is_hint_revealed());
+        engine.answer(vec![1]);
+
+        assert_eq!(engine.reveal_hint(), None);
+        assert!(!engine.is_hint_revealed());
+        engine.answer(vec![1]);
+
+        assert_eq!(engine.hints_used(), &[true, false]);
+        assert_eq!(engine.calculate_score(), 2);
+    }
+
+    #[test]
+    fn test_confidence_mode() {
+        let mut qs = questions(3);
+        qs[2].points = 5;
+        let mut engine = QuizEngine::new(qs);
+        engine.set_confidence_mode(true);
+        engine.start();
+
+        engine.record_answer_with_confidence(vec![1], Confidence::Sure);
+        engine.advance();
+        engine.record_answer_with_confidence(vec![0], Confidence::Unsure);
+        engine.advance();
+        assert_eq!(engine.calculate_score(), 2);
+        engine.record_answer_with_confidence(vec![0], Confidence::Sure);
+        engine.advance();
+
+        let result = engine.result().unwrap();
+        assert_eq!((result.score, result.max_score), (0, 14));
+        let calibration = result.calibration.unwrap();
+        assert_eq!((calibration.sure, calibration.sure_correct), (2, 1));
+        assert_eq!(
+            calibration.summary(),
+            "Sure: 2 answers, 50% right · Unsure: 1 answer, 0% right"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Flashcard study: each question is shown with its answer hidden until the
+//! player reveals it and says whether they knew it. Nothing is scored; the
+//! ratings go to the history for the spaced-repetition sampling.
+
+use std::time::{Duration, Instant};
+
+/// Progress through a study session, one card per question.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deck {
+    card: usize,
+    revealed: bool,
+    /// Whether the player knew each card, once rated.
+    known: Vec<Option<bool>>,
+    started_at: Option<Instant>,
+    /// Set once the last card is rated.
+    time_taken: Option<Duration>,
+}
+
+impl Deck {
+    /// A deck of `cards` cards, none rated yet.
+    pub fn new(cards: usize) -> Self {
+        Self {
+            known: vec![None; cards],
+            ..Self::default()
+        }
+    }
+
+    /// Start the clock.
+    pub fn start(&mut self) {
+        self.started_at = Some(Instant::now());
+    }
+
+    /// Index of the card on screen.
+    pub fn card(&self) -> usize {
+        self.card
+    }
+
+    /// Whether the answer of the card on screen is shown.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Show the answer of the card on screen.
+    pub fn reveal(&mut self) {
+        if !self.is_finished() {
+            self.revealed = true;
+        }
+    }
+
+    /// Record whether the player knew the card on screen and go to the next
+    /// one. Ignored until the answer is revealed.
+    pub fn rate(&mut self, knew: bool) {
+        if !self.revealed || self.is_finished() {
+            return;
+        }
+        self.known[self.card] = Some(knew);
+        self.card += 1;
+        self.revealed = false;
+        if self.is_finished() {
+            self.time_taken = self.started_at.map(|started| started.elapsed());
+        }
+    }
+
+    /// Whether every card has been rated.
+    pub fn is_finished(&self) -> bool {
+        self.card >= self.known.len()
+    }
+
+    /// Whether the player knew each card, in question order.
+    pub fn known(&self) -> &[Option<bool>] {
+        &self.known
+    }
+
+    /// Number of cards the player knew.
+    pub fn known_count(&self) -> usize {
+        self.known.iter().filter(|&&known| known == Some(true)).count()
+    }
+
+    /// Time spent on the deck, available once it is finished.
+    pub fn time_taken(&self) -> Option<Duration> {
+        self.time_taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_after_reveal() {
+        let mut deck = Deck::new(2);
+        deck.start();
+
+        deck.rate(true);
+        assert_eq!(deck.card(), 0);
+        deck.reveal();
+        deck.rate(true);
+        deck.reveal();
+        deck.rate(false);
+
+        assert!(deck.is_finished() && deck.time_taken().is_some());
+        assert_eq!(deck.known(), &[Some(true), Some(false)]);
+        assert_eq!(deck.known_count(), 1);
+    }
+}
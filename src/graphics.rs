@@ -0,0 +1,315 @@
+//! Drawing question images with the kitty graphics protocol or sixel.
+//!
+//! ratatui only draws text, so images are written to the terminal after
+//! each frame, over the blank box the quiz screen leaves for them. Which
+//! protocol to use is guessed from the environment, and can be set with
+//! [`GRAPHICS_VAR`]. Terminals with neither get a placeholder instead.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+
+use base64::Engine;
+use crossterm::{cursor::MoveTo, queue};
+use ratatui::layout::Rect;
+
+/// Environment variable choosing the protocol: `kitty`, `sixel` or `none`.
+pub(crate) const GRAPHICS_VAR: &str = "RUST_QUIZ_GRAPHICS";
+
+/// Size of a cell in pixels when the terminal doesn't say.
+const DEFAULT_CELL: (u32, u32) = (8, 16);
+
+/// Largest piece of an image sent in one kitty escape sequence.
+const KITTY_CHUNK: usize = 4096;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+/// Keeps track of the image on screen, to draw each one once and take it
+/// down when the screen changes.
+pub(crate) struct Graphics {
+    protocol: Protocol,
+    /// Path and area of the image drawn last.
+    shown: Option<(String, Rect)>,
+}
+
+impl Graphics {
+    /// Graphics for the terminal the quiz runs in, if it can show images.
+    pub(crate) fn detect() -> Option<Self> {
+        let protocol = match env::var(GRAPHICS_VAR) {
+            Ok(choice) if choice.eq_ignore_ascii_case("kitty") => Some(Protocol::Kitty),
+            Ok(choice) if choice.eq_ignore_ascii_case("sixel") => Some(Protocol::Sixel),
+            Ok(_) => None,
+            Err(_) => {
+                let term = env::var("TERM").unwrap_or_default();
+                let program = env::var("TERM_PROGRAM").unwrap_or_default();
+                if env::var_os("KITTY_WINDOW_ID").is_some()
+                    || matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+                    || matches!(program.as_str(), "WezTerm" | "ghostty")
+                {
+                    Some(Protocol::Kitty)
+                } else if ["foot", "mlterm", "contour"].iter().any(|t| term.starts_with(t))
+                    || term.contains("sixel")
+                {
+                    Some(Protocol::Sixel)
+                } else {
+                    None
+                }
+            }
+        };
+        protocol.map(|protocol| Self {
+            protocol,
+            shown: None,
+        })
+    }
+
+    /// Take down the image on screen unless it is `wanted`.
+    ///
+    /// Returns true if the screen must be cleared and drawn again to do so,
+    /// as sixel images stay until the text under them is redrawn.
+    pub(crate) fn hide_unless(&mut self, wanted: Option<(&str, Rect)>) -> io::Result<bool> {
+        if self.is_shown(wanted) || self.shown.take().is_none() {
+            return Ok(false);
+        }
+        match self.protocol {
+            Protocol::Kitty => {
+                delete_kitty_images()?;
+                Ok(false)
+            }
+            Protocol::Sixel => Ok(true),
+        }
+    }
+
+    /// Draw `wanted` unless it is already on screen.
+    pub(crate) fn show(&mut self, wanted: Option<(&str, Rect)>) -> io::Result<()> {
+        let Some((path, area)) = wanted else {
+            return Ok(());
+        };
+        if area.is_empty() || self.is_shown(wanted) {
+            return Ok(());
+        }
+        // Remembered even if drawing fails, so it isn't tried every frame
+        self.shown = Some((path.to_string(), area));
+
+        let png = fs::read(path)?;
+        let size = png_size(&png)?;
+        let (cols, rows) = fit(size, area, cell_size());
+        let x = area.x + area.width.saturating_sub(cols) / 2;
+        let y = area.y + area.height.saturating_sub(rows) / 2;
+
+        let mut out = io::stdout().lock();
+        queue!(out, MoveTo(x, y))?;
+        match self.protocol {
+            Protocol::Kitty => write_kitty(&mut out, &png, cols, rows)?,
+            Protocol::Sixel => {
+                let (cell_width, cell_height) = cell_size();
+                let width = (u32::from(cols) * cell_width).min(size.0);
+                let height = (u32::from(rows) * cell_height).min(size.1);
+                out.write_all(&encode_sixel(&decode_png(&png)?, width, height))?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Forget the image on screen after the screen was cleared, so it is
+    /// drawn again.
+    pub(crate) fn reset(&mut self) -> io::Result<()> {
+        self.shown = None;
+        match self.protocol {
+            Protocol::Kitty => delete_kitty_images(),
+            Protocol::Sixel => Ok(()),
+        }
+    }
+
+    fn is_shown(&self, wanted: Option<(&str, Rect)>) -> bool {
+        let shown = self.shown.as_ref().map(|(path, area)| (path.as_str(), *area));
+        shown == wanted
+    }
+}
+
+/// Width and height of a PNG image in pixels, from its header.
+fn png_size(png: &[u8]) -> io::Result<(u32, u32)> {
+    if png.len() < 24 || !png.starts_with(PNG_SIGNATURE) || &png[12..16] != b"IHDR" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a PNG image"));
+    }
+    let read = |at: usize| u32::from_be_bytes([png[at], png[at + 1], png[at + 2], png[at + 3]]);
+    Ok((read(16), read(20)))
+}
+
+/// Width and height of a cell in pixels.
+fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.columns > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns).max(1),
+            u32::from(size.height / size.rows).max(1),
+        ),
+        _ => DEFAULT_CELL,
+    }
+}
+
+/// Columns and rows an image of `size` pixels takes in `area`, keeping its
+/// aspect ratio and never enlarging it.
+fn fit(size: (u32, u32), area: Rect, cell: (u32, u32)) -> (u16, u16) {
+    let (width, height) = (f64::from(size.0.max(1)), f64::from(size.1.max(1)));
+    let scale = (f64::from(u32::from(area.width) * cell.0) / width)
+        .min(f64::from(u32::from(area.height) * cell.1) / height)
+        .min(1.0);
+    let cells = |pixels: f64, cell: u32, max: u16| {
+        ((pixels * scale / f64::from(cell)).ceil() as u16).clamp(1, max.max(1))
+    };
+    (cells(width, cell.0, area.width), cells(height, cell.1, area.height))
+}
+
+/// Send a PNG image to show at the cursor, scaled to `cols` by `rows` cells.
+fn write_kitty(out: &mut impl Write, png: &[u8], cols: u16, rows: u16) -> io::Result<()> {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        // The cursor stays put, and the terminal doesn't reply
+        if i == 0 {
+            write!(out, "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};", cols, rows, more)?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+    Ok(())
+}
+
+fn delete_kitty_images() -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+    out.flush()
+}
+
+/// An image as rows of RGBA pixels.
+struct Pixels {
+    width: u32,
+    height: u32,
+    rgba: Vec<[u8; 4]>,
+}
+
+fn decode_png(png: &[u8]) -> io::Result<Pixels> {
+    let invalid = |e: png::DecodingError| io::Error::new(io::ErrorKind::InvalidData, e);
+    let mut decoder = png::Decoder::new(png);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(invalid)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(invalid)?;
+
+    let samples = info.color_type.samples();
+    let rgba = buf[..info.buffer_size()]
+        .chunks_exact(samples)
+        .map(|pixel| match *pixel {
+            [v] => [v, v, v, 255],
+            [v, a] => [v, v, v, a],
+            [r, g, b] => [r, g, b, 255],
+            [r, g, b, a] => [r, g, b, a],
+            _ => [0; 4],
+        })
+        .collect();
+    Ok(Pixels {
+        width: info.width,
+        height: info.height,
+        rgba,
+    })
+}
+
+/// Encode `pixels` scaled to `width` by `height` as sixel, in a palette of
+/// 216 colors. Transparent pixels are left as they were.
+fn encode_sixel(pixels: &Pixels, width: u32, height: u32) -> Vec<u8> {
+    let (width, height) = (width.max(1), height.max(1));
+    // Nearest neighbour is plenty for diagrams
+    let color = |x: u32, y: u32| {
+        let source_x = (u64::from(x) * u64::from(pixels.width) / u64::from(width)) as usize;
+        let source_y = (u64::from(y) * u64::from(pixels.height) / u64::from(height)) as usize;
+        let [r, g, b, a] = pixels.rgba[source_y * pixels.width as usize + source_x];
+        let level = |v: u8| (u16::from(v) * 5 + 127) / 255;
+        (a >= 128).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as usize)
+    };
+
+    let mut out = String::new();
+    // Writing to a String never fails
+    let _ = write!(out, "\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for index in 0..216 {
+        let percent = |level: usize| level * 20;
+        let (r, g, b) = (index / 36, index / 6 % 6, index % 6);
+        let _ = write!(out, "#{};2;{};{};{}", index, percent(r), percent(g), percent(b));
+    }
+
+    for top in (0..height).step_by(6) {
+        // For each color used in this band of six rows, which of them to
+        // paint in each column
+        let mut bands: Vec<Option<Vec<u8>>> = vec![None; 216];
+        for x in 0..width {
+            for dy in 0..6.min(height - top) {
+                if let Some(index) = color(x, top + dy) {
+                    let band = bands[index].get_or_insert_with(|| vec![0; width as usize]);
+                    band[x as usize] |= 1 << dy;
+                }
+            }
+        }
+        for (index, band) in bands.iter().enumerate() {
+            if let Some(band) = band {
+                let _ = write!(out, "#{}", index);
+                write_sixel_run(&mut out, band);
+                out.push('$');
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out.into_bytes()
+}
+
+/// Write one color's columns of a band, run-length encoded, up to the last
+/// one painted.
+fn write_sixel_run(out: &mut String, band: &[u8]) {
+    let end = band.iter().rposition(|&bits| bits != 0).map_or(0, |i| i + 1);
+    let band = &band[..end];
+    let mut i = 0;
+    while i < band.len() {
+        let run = band[i..].iter().take_while(|&&bits| bits == band[i]).count();
+        let c = char::from(63 + band[i]);
+        if run > 3 {
+            let _ = write!(out, "!{}{}", run, c);
+        } else {
+            (0..run).for_each(|_| out.push(c));
+        }
+        i += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_and_encode() {
+        assert_eq!(fit((800, 400), Rect::new(0, 0, 60, 10), (8, 16)), (40, 10));
+        assert_eq!(fit((16, 16), Rect::new(0, 0, 60, 10), (8, 16)), (2, 1));
+
+        // Two pixels across, red then transparent
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 2, 1);
+        encoder.set_color(png::ColorType::Rgba);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[255, 0, 0, 255, 0, 0, 0, 0]).unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(png_size(&png).unwrap(), (2, 1));
+        assert!(png_size(b"GIF89a").is_err());
+        let sixel = String::from_utf8(encode_sixel(&decode_png(&png).unwrap(), 4, 1)).unwrap();
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;4;1"));
+        // Red is color 180, painted in the top row of the first two columns
+        assert!(sixel.ends_with("#180@@$-\x1b\\"), "{:?}", sixel);
+    }
+}
@@ -0,0 +1,262 @@
+//! Keyword, string and comment highlighting for code snippets.
+//!
+//! Not a parser: each line is split into words, quoted strings and comments
+//! with a few rules per language, which is enough for quiz-sized snippets.
+//! Unknown languages are shown in the plain code color.
+
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+use crate::theme::Theme;
+
+/// How to pick out the parts of one language.
+struct Language {
+    /// Name shown on the code block's border.
+    label: &'static str,
+    /// Names it goes by in question files, lowercase.
+    names: &'static [&'static str],
+    keywords: &'static [&'static str],
+    /// Whether `SELECT` is as much a keyword as `select`.
+    ignore_case: bool,
+    line_comment: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+const RUST: Language = Language {
+    label: "Rust",
+    names: &["rust", "rs"],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    ignore_case: false,
+    line_comment: &["//"],
+    block_comment: Some(("/*", "*/")),
+    // Not `'`, which also starts lifetimes
+    quotes: &['"'],
+};
+
+const LANGUAGES: [Language; 6] = [
+    RUST,
+    Language {
+        label: "Python",
+        names: &["python", "py"],
+        keywords: &[
+            "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+            "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+            "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return",
+            "True", "try", "while", "with", "yield",
+        ],
+        ignore_case: false,
+        line_comment: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+    },
+    Language {
+        label: "Shell",
+        names: &["shell", "sh", "bash", "zsh", "console"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+            "in", "local", "readonly", "return", "then", "until", "while",
+        ],
+        ignore_case: false,
+        line_comment: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+    },
+    Language {
+        label: "TOML",
+        names: &["toml"],
+        keywords: &["true", "false"],
+        ignore_case: false,
+        line_comment: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+    },
+    Language {
+        label: "SQL",
+        names: &["sql"],
+        keywords: &[
+            "all", "alter", "and", "as", "asc", "between", "by", "create", "delete", "desc",
+            "distinct", "drop", "exists", "from", "group", "having", "in", "index", "inner",
+            "insert", "into", "is", "join", "key", "left", "like", "limit", "not", "null", "on",
+            "or", "order", "outer", "primary", "references", "right", "select", "set", "table",
+            "union", "update", "values", "where",
+        ],
+        ignore_case: true,
+        line_comment: &["--"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['\''],
+    },
+    Language {
+        label: "JSON",
+        names: &["json"],
+        keywords: &["true", "false", "null"],
+        ignore_case: false,
+        line_comment: &[],
+        block_comment: None,
+        quotes: &['"'],
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Plain,
+    Keyword,
+    String,
+    Comment,
+}
+
+fn find(language: &str) -> Option<&'static Language> {
+    let language = language.trim().to_lowercase();
+    LANGUAGES.iter().find(|known| known.names.contains(&language.as_str()))
+}
+
+/// Name to put on the border of a code block in `language`, e.g. `Python`
+/// for `py`. Unknown languages are shown as written.
+pub(crate) fn label(language: &str) -> String {
+    find(language).map_or_else(|| language.trim().to_string(), |known| known.label.to_string())
+}
+
+/// Lines of `code` styled as `language`, or as Rust without one.
+pub(crate) fn highlight<'a>(
+    code: &'a str,
+    language: Option<&str>,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    let language = match language {
+        Some(language) => find(language),
+        None => Some(&RUST),
+    };
+    let style = |kind| match kind {
+        Kind::Plain => Style::default().fg(theme.code),
+        Kind::Keyword => Style::default().fg(theme.accent).bold(),
+        Kind::String => Style::default().fg(theme.correct),
+        Kind::Comment => Style::default().fg(theme.dimmed).italic(),
+    };
+
+    let mut in_comment = false;
+    code.lines()
+        .map(|line| {
+            let spans = match language {
+                Some(language) => tokens(line, language, &mut in_comment)
+                    .into_iter()
+                    .map(|(text, kind)| Span::styled(text, style(kind)))
+                    .collect(),
+                None => vec![Span::styled(line, style(Kind::Plain))],
+            };
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Split a line into runs of one kind. `in_comment` carries a block comment
+/// over to the next line.
+fn tokens<'a>(
+    line: &'a str,
+    language: &Language,
+    in_comment: &mut bool,
+) -> Vec<(&'a str, Kind)> {
+    let mut tokens: Vec<(usize, usize, Kind)> = Vec::new();
+    let mut at = 0;
+    while at < line.len() {
+        let rest = &line[at..];
+        let (len, kind) = if *in_comment {
+            let end = language
+                .block_comment
+                .and_then(|(_, end)| rest.find(end).map(|i| i + end.len()));
+            *in_comment = end.is_none();
+            (end.unwrap_or(rest.len()), Kind::Comment)
+        } else if language.line_comment.iter().any(|start| rest.starts_with(start)) {
+            (rest.len(), Kind::Comment)
+        } else if let Some((start, _)) =
+            language.block_comment.filter(|(start, _)| rest.starts_with(start))
+        {
+            *in_comment = true;
+            (start.len(), Kind::Comment)
+        } else if let Some(quote) = rest.chars().next().filter(|c| language.quotes.contains(c)) {
+            (quoted_len(rest, quote), Kind::String)
+        } else if rest.starts_with(is_word_char) {
+            let len = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            let word = &rest[..len];
+            let is_keyword = language.keywords.iter().any(|keyword| {
+                *keyword == word || language.ignore_case && keyword.eq_ignore_ascii_case(word)
+            });
+            (len, if is_keyword { Kind::Keyword } else { Kind::Plain })
+        } else {
+            (rest.chars().next().map_or(1, char::len_utf8), Kind::Plain)
+        };
+
+        match tokens.last_mut() {
+            Some((_, end, last)) if *last == kind => *end += len,
+            _ => tokens.push((at, at + len, kind)),
+        }
+        at += len;
+    }
+    tokens.into_iter().map(|(start, end, kind)| (&line[start..end], kind)).collect()
+}
+
+/// Length of the string starting at `text`, up to and including its closing
+/// `quote` or the end of the line.
+fn quoted_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return i + c.len_utf8(),
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let kinds = |line, language| {
+            let language = find(language).unwrap();
+            tokens(line, language, &mut false)
+        };
+        assert_eq!(
+            kinds("let s = \"a \\\" b\"; // done", "rust"),
+            vec![
+                ("let", Kind::Keyword),
+                (" s = ", Kind::Plain),
+                ("\"a \\\" b\"", Kind::String),
+                ("; ", Kind::Plain),
+                ("// done", Kind::Comment),
+            ]
+        );
+        assert_eq!(
+            kinds("SELECT name FROM users -- all", "SQL"),
+            vec![
+                ("SELECT", Kind::Keyword),
+                (" name ", Kind::Plain),
+                ("FROM", Kind::Keyword),
+                (" users ", Kind::Plain),
+                ("-- all", Kind::Comment),
+            ]
+        );
+
+        let mut in_comment = false;
+        tokens("x /* open", &RUST, &mut in_comment);
+        assert!(in_comment);
+        let closed = tokens("closed */ fn", &RUST, &mut in_comment);
+        assert_eq!(closed[0], ("closed */", Kind::Comment));
+        assert!(!in_comment);
+
+        assert_eq!(label("py"), "Python");
+        assert_eq!(label("Haskell"), "Haskell");
+    }
+}
@@ -0,0 +1,339 @@
+//! Persistent record of finished quiz runs.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::QuizEngine;
+use crate::flashcards::Deck;
+use crate::models::Question;
+
+/// One finished quiz run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the run finished, as an RFC 3339 timestamp.
+    pub date: String,
+    /// The question file or URL the quiz was loaded from.
+    pub quiz: String,
+    pub score: usize,
+    pub max_score: usize,
+    /// Time taken, in seconds.
+    pub duration_secs: u64,
+    /// Score per tag.
+    #[serde(default)]
+    pub tags: Vec<TagScore>,
+    /// Text of the questions answered wrong or left unanswered.
+    #[serde(default)]
+    pub missed: Vec<String>,
+    /// Text of the questions answered correctly.
+    #[serde(default)]
+    pub correct: Vec<String>,
+    /// Whether this was a flashcard study session, where `correct` holds
+    /// the cards the player knew and the score counts them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flashcards: bool,
+}
+
+/// Score on the questions with one tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagScore {
+    pub tag: String,
+    pub score: usize,
+    pub max_score: usize,
+}
+
+impl HistoryEntry {
+    /// Record the result of a finished quiz run.
+    pub fn from_engine(engine: &QuizEngine, quiz: &str) -> Self {
+        let (correct, missed): (Vec<_>, Vec<_>) = engine
+            .questions()
+            .iter()
+            .zip(engine.answers())
+            .partition(|(question, answer)| {
+                answer.as_deref().is_some_and(|answer| question.is_correct(answer))
+            });
+        let texts = |questions: Vec<(&Question, _)>| {
+            questions
+                .into_iter()
+                .map(|(question, _)| question.text.clone())
+                .collect()
+        };
+
+        Self {
+            date: Local::now().to_rfc3339(),
+            quiz: quiz.to_string(),
+            score: engine.calculate_score(),
+            max_score: engine.max_score(),
+            duration_secs: engine.time_taken().unwrap_or_default().as_secs(),
+            tags: engine
+                .score_by_tag()
+                .into_iter()
+                .map(|(tag, score, max_score)| TagScore {
+                    tag,
+                    score,
+                    max_score,
+                })
+                .collect(),
+            missed: texts(missed),
+            correct: texts(correct),
+            flashcards: false,
+        }
+    }
+
+    /// Record a finished flashcard study session over `questions`.
+    ///
+    /// Cards the player didn't know count as missed, so the adaptive
+    /// sampling brings them back sooner.
+    pub fn from_deck(questions: &[Question], deck: &Deck, quiz: &str) -> Self {
+        let (correct, missed): (Vec<_>, Vec<_>) = questions
+            .iter()
+            .zip(deck.known())
+            .partition(|(_, known)| **known == Some(true));
+        let texts = |cards: Vec<(&Question, _)>| {
+            cards.into_iter().map(|(question, _)| question.text.clone()).collect()
+        };
+
+        Self {
+            date: Local::now().to_rfc3339(),
+            quiz: quiz.to_string(),
+            score: deck.known_count(),
+            max_score: questions.len(),
+            duration_secs: deck.time_taken().unwrap_or_default().as_secs(),
+            tags: Vec::new(),
+            missed: texts(missed),
+            correct: texts(correct),
+            flashcards: true,
+        }
+    }
+
+    /// The finish time in local time, if the stored date is valid.
+    pub fn local_date(&self) -> Option<DateTime<Local>> {
+        DateTime::parse_from_rfc3339(&self.date)
+            .ok()
+            .map(|date| date.with_timezone(&Local))
+    }
+
+    /// Score as a percentage of the maximum score.
+    pub fn percentage(&self) -> f64 {
+        if self.max_score > 0 {
+            self.score as f64 / self.max_score as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Aggregates over a list of past runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryStats {
+    pub attempts: usize,
+    /// Average score percentage.
+    pub average: f64,
+    /// Best score percentage.
+    pub best: f64,
+    /// Score percentage of each run, oldest first.
+    pub trend: Vec<f64>,
+    /// Accuracy per tag as `(tag, percentage)`, weakest first.
+    pub tag_accuracy: Vec<(String, f64)>,
+    /// Questions missed most often as `(text, times missed)`, most missed
+    /// first.
+    pub most_missed: Vec<(String, usize)>,
+}
+
+impl HistoryStats {
+    /// Aggregate runs given oldest first.
+    ///
+    /// Flashcard study sessions are left out, as they aren't scored.
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let entries: Vec<&HistoryEntry> =
+            entries.iter().filter(|entry| !entry.flashcards).collect();
+        if entries.is_empty() {
+            return Self::default();
+        }
+
+        let trend: Vec<f64> = entries.iter().map(|entry| entry.percentage()).collect();
+        let average = trend.iter().sum::<f64>() / trend.len() as f64;
+        let best = trend.iter().copied().fold(0.0, f64::max);
+
+        let mut tags: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        let mut missed: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in &entries {
+            for tag in &entry.tags {
+                let totals = tags.entry(tag.tag.as_str()).or_default();
+                totals.0 += tag.score;
+                totals.1 += tag.max_score;
+            }
+            for text in &entry.missed {
+                *missed.entry(text.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut tag_accuracy: Vec<(String, f64)> = tags
+            .into_iter()
+            .filter(|(_, (_, max_score))| *max_score > 0)
+            .map(|(tag, (score, max_score))| {
+                (tag.to_string(), score as f64 / max_score as f64 * 100.0)
+            })
+            .collect();
+        tag_accuracy.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut most_missed: Vec<(String, usize)> = missed
+            .into_iter()
+            .map(|(text, count)| (text.to_string(), count))
+            .collect();
+        // Stable sort keeps ties in alphabetical order.
+        most_missed.sort_by_key(|(_, count)| Reverse(*count));
+
+        Self {
+            attempts: entries.len(),
+            average,
+            best,
+            trend,
+            tag_accuracy,
+            most_missed,
+        }
+    }
+}
+
+/// A history file with one JSON entry per line, oldest first.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    /// Use the history file at `path`.
+    pub fn at<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Use `history.jsonl` in the user's data directory, e.g.
+    /// `~/.local/share/rust-quiz/` on Linux.
+    ///
+    /// Returns `None` if the platform has no data directory.
+    pub fn open_default() -> Option<Self> {
+        dirs::data_dir().map(|dir| Self::at(dir.join("rust-quiz").join("history.jsonl")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read all entries, oldest first.
+    ///
+    /// A missing file means no history yet. Lines that can't be parsed are
+    /// skipped so one bad line doesn't hide the rest.
+    pub fn load(&self) -> io::Result<Vec<HistoryEntry>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Append an entry, creating the file and its directory if needed.
+    pub fn append(&self, entry: &HistoryEntry) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_append_and_load() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-history-{}", std::process::id()));
+        let history = History::at(dir.join("nested").join("history.jsonl"));
+        assert!(history.load().unwrap().is_empty());
+
+        let entry = HistoryEntry {
+            date: "2024-03-01T10:00:00+00:00".to_string(),
+            quiz: "questions.json".to_string(),
+            score: 3,
+            max_score: 4,
+            duration_secs: 95,
+            tags: Vec::new(),
+            missed: Vec::new(),
+            correct: Vec::new(),
+            flashcards: false,
+        };
+        history.append(&entry).unwrap();
+        fs::write(
+            history.path(),
+            format!("{}not json\n", fs::read_to_string(history.path()).unwrap()),
+        )
+        .unwrap();
+        history.append(&entry).unwrap();
+
+        let entries = history.load().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(entries, vec![entry.clone(), entry]);
+        assert_eq!(entries[0].percentage(), 75.0);
+        assert!(entries[0].local_date().is_some());
+    }
+
+    #[test]
+    fn test_history_stats() {
+        let entry = |score: usize, tag_score: usize, missed: &[&str]| HistoryEntry {
+            date: String::new(),
+            quiz: "questions.json".to_string(),
+            score,
+            max_score: 4,
+            duration_secs: 60,
+            tags: vec![
+                TagScore {
+                    tag: "traits".to_string(),
+                    score: tag_score,
+                    max_score: 2,
+                },
+                TagScore {
+                    tag: "macros".to_string(),
+                    score: score - tag_score,
+                    max_score: 2,
+                },
+            ],
+            missed: missed.iter().map(|text| text.to_string()).collect(),
+            correct: Vec::new(),
+            flashcards: false,
+        };
+
+        let mut study = entry(0, 0, &["Q4"]);
+        study.flashcards = true;
+        let stats = HistoryStats::from_entries(&[
+            entry(1, 0, &["Q1", "Q2", "Q3"]),
+            study,
+            entry(3, 1, &["Q2"]),
+        ]);
+        assert_eq!(stats.attempts, 2);
+        assert_eq!(stats.average, 50.0);
+        assert_eq!(stats.best, 75.0);
+        assert_eq!(stats.trend, vec![25.0, 75.0]);
+        assert_eq!(
+            stats.tag_accuracy,
+            vec![("traits".to_string(), 25.0), ("macros".to_string(), 75.0)]
+        );
+        assert_eq!(stats.most_missed[0], ("Q2".to_string(), 2));
+        assert_eq!(stats.most_missed.len(), 3);
+    }
+}
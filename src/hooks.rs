@@ -0,0 +1,55 @@
+//! Callbacks run as a quiz progresses, e.g. to log answers elsewhere.
+
+use crate::engine::QuizResult;
+use crate::models::Question;
+
+/// A question was put on screen.
+pub struct QuestionShown<'a> {
+    pub question: &'a Question,
+    /// 1-based position of the question in the quiz.
+    pub number: usize,
+    pub total: usize,
+}
+
+/// An answer was submitted for a question.
+pub struct AnswerSubmitted<'a> {
+    pub question: &'a Question,
+    /// 1-based position of the question in the quiz.
+    pub number: usize,
+    /// Indices of the chosen options, sorted.
+    pub answer: &'a [usize],
+    pub is_correct: bool,
+    pub hint_used: bool,
+}
+
+type QuestionShownHook = Box<dyn FnMut(&QuestionShown)>;
+type AnswerSubmittedHook = Box<dyn FnMut(&AnswerSubmitted)>;
+type FinishedHook = Box<dyn FnMut(&QuizResult)>;
+
+/// Registered callbacks, run in the order they were added.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    pub(crate) on_question_shown: Vec<QuestionShownHook>,
+    pub(crate) on_answer_submitted: Vec<AnswerSubmittedHook>,
+    pub(crate) on_finished: Vec<FinishedHook>,
+}
+
+impl Hooks {
+    pub(crate) fn question_shown(&mut self, event: &QuestionShown) {
+        for hook in &mut self.on_question_shown {
+            hook(event);
+        }
+    }
+
+    pub(crate) fn answer_submitted(&mut self, event: &AnswerSubmitted) {
+        for hook in &mut self.on_answer_submitted {
+            hook(event);
+        }
+    }
+
+    pub(crate) fn finished(&mut self, result: &QuizResult) {
+        for hook in &mut self.on_finished {
+            hook(result);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Extra key bindings for the single-player quiz.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+
+/// Keys that act like a built-in key, on top of the defaults.
+///
+/// Deserializes from a table of action names to keys, e.g. to move with `w`
+/// and `x` and quit with Esc:
+///
+/// ```toml
+/// up = "w"
+/// down = "x"
+/// quit = "esc"
+/// ```
+///
+/// Actions are `up`, `down`, `next`, `previous`, `select`, `toggle`,
+/// `back`, `skip`, `hint`, `finish`, `quit`, `history`, `stats`, `review`,
+/// `export`, `moodle`, `restart` and `help`. Keys are single characters or
+/// `enter`, `esc`, `space`, `tab`, `backspace`, `up`, `down`, `left` and
+/// `right`.
+///
+/// A bound key stops doing what it did by default, e.g. binding `up = "s"`
+/// leaves skipping on `S` only.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "BTreeMap<String, String>")]
+pub struct Keymap {
+    keys: HashMap<KeyCode, KeyCode>,
+}
+
+impl Keymap {
+    /// The built-in key that `key` stands for.
+    pub fn translate(&self, key: KeyCode) -> KeyCode {
+        self.keys.get(&key).copied().unwrap_or(key)
+    }
+
+    /// Make `key` act like the default key of `action`.
+    pub fn bind(&mut self, action: &str, key: &str) -> Result<(), String> {
+        let target = action_key(action).ok_or_else(|| format!("unknown action '{}'", action))?;
+        let key = parse_key(key).ok_or_else(|| format!("unknown key '{}'", key))?;
+        self.keys.insert(key, target);
+        Ok(())
+    }
+}
+
+impl TryFrom<BTreeMap<String, String>> for Keymap {
+    type Error = String;
+
+    fn try_from(bindings: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        let mut keymap = Self::default();
+        for (action, key) in &bindings {
+            keymap.bind(action, key)?;
+        }
+        Ok(keymap)
+    }
+}
+
+/// The default key of an action.
+fn action_key(action: &str) -> Option<KeyCode> {
+    let key = match action {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "next" => KeyCode::Right,
+        "previous" => KeyCode::Left,
+        "select" => KeyCode::Enter,
+        "toggle" => KeyCode::Char(' '),
+        "back" => KeyCode::Esc,
+        "skip" => KeyCode::Char('s'),
+        "hint" | "history" => KeyCode::Char('h'),
+        "finish" => KeyCode::Char('f'),
+        "quit" => KeyCode::Char('q'),
+        "stats" => KeyCode::Char('s'),
+        "review" => KeyCode::Enter,
+        "export" => KeyCode::Char('e'),
+        "moodle" => KeyCode::Char('m'),
+        "restart" => KeyCode::Char('r'),
+        "help" => KeyCode::Char('?'),
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let key = match key.to_lowercase().as_str() {
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings() {
+        let keymap: Keymap = toml::from_str("up = \"w\"\nquit = \"esc\"").unwrap();
+        assert_eq!(keymap.translate(KeyCode::Char('w')), KeyCode::Up);
+        assert_eq!(keymap.translate(KeyCode::Esc), KeyCode::Char('q'));
+        assert_eq!(keymap.translate(KeyCode::Char('j')), KeyCode::Char('j'));
+
+        assert!(toml::from_str::<Keymap>("jump = \"w\"").is_err());
+        assert!(toml::from_str::<Keymap>("up = \"page up\"").is_err());
+    }
+}
@@ -0,0 +1,47 @@
+//! Sizing of the quiz screens.
+
+/// How the quiz screen divides its space, in terminal rows.
+///
+/// The code block grows with the snippet up to `code_max_height`, and the
+/// answer options get whatever is left, but never less than
+/// `options_height`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::{LayoutOptions, Quiz};
+///
+/// let quiz = Quiz::from_json("questions.json")?.layout(LayoutOptions {
+///     code_max_height: 30,
+///     ..LayoutOptions::default()
+/// });
+/// # Ok::<(), rust_quiz::QuizError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutOptions {
+    /// Tallest the code block may grow, borders included. Longer snippets
+    /// are cut off.
+    pub code_max_height: u16,
+    /// Fewest rows kept for the answer options; each option takes two.
+    pub options_height: u16,
+    /// Blank rows and columns around the quiz screen.
+    pub margin: u16,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            code_max_height: 20,
+            options_height: 8,
+            margin: 1,
+        }
+    }
+}
+
+impl LayoutOptions {
+    /// Rows to give a code block showing `code`, borders included.
+    pub fn code_height(&self, code: &str) -> u16 {
+        let lines = u16::try_from(code.lines().count()).unwrap_or(u16::MAX);
+        lines.saturating_add(2).min(self.code_max_height)
+    }
+}
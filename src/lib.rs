@@ -9,7 +9,7 @@
 //!
 //! fn main() -> Result<(), rust_quiz::QuizError> {
 //!     // Load questions from a JSON file
-//!     let quiz = Quiz::from_json("questions.json")?;
+//!     let mut quiz = Quiz::from_json("questions.json")?;
 //!
 //!     // Run the quiz in the terminal
 //!     quiz.run()?;
@@ -18,53 +18,147 @@
 //! }
 //! ```
 //!
+//! ## Without a Terminal
+//!
+//! [`QuizEngine`] has the quiz logic on its own, to drive a quiz from a GUI
+//! or from tests.
+//!
 //! ## Server Mode (Host)
 //!
 //! ```bash
 //! rust-quiz serve --port 8712 --questions questions.json
 //! ```
 //!
+//! [`QuizServer`] hosts from another program, with or without the host's
+//! terminal interface.
+//!
 //! ## Client Mode (Player)
 //!
 //! ```bash
 //! rust-quiz connect --host localhost --port 8712
 //! ```
+//!
+//! [`QuizClient`] joins from another program, e.g. to test a quiz with
+//! scripted players.
 
+mod alert;
 mod app;
+mod catalog;
+pub mod certificate;
 pub mod client;
+pub mod config;
 pub mod data;
+pub mod editor;
+mod engine;
+mod flashcards;
+mod graphics;
+mod highlight;
+pub mod history;
+mod hooks;
+pub mod keymap;
+pub mod layout;
 mod models;
+pub mod moodle;
+mod playlist;
 pub mod protocol;
+pub mod report;
 pub mod server;
+pub mod session;
 pub mod terminal;
+#[cfg(test)]
+mod testing;
+mod text;
+pub mod theme;
 mod ui;
+mod watch;
 
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, EventStream, KeyCode, KeyEventKind};
+use futures_util::StreamExt;
+use ratatui::backend::Backend;
+use ratatui::{Frame, Terminal};
+use alert::Alerter;
+use graphics::Graphics;
+use watch::{Origin, Selection, Watcher};
 
+pub use alert::Alerts;
 pub use app::App;
-pub use data::{load_questions_from_json, LoadError};
-pub use models::{AppState, Question};
+pub use catalog::QuizEntry;
+pub use config::{Config, ConfigError};
+pub use engine::{QuizEngine, QuizResult};
+pub use flashcards::Deck;
+pub use data::{
+    filter_questions_by_tags, load_bank_encrypted, load_bank_from_json, load_bank_from_toml,
+    load_bank_from_url, load_questions_from_json, load_questions_from_toml, load_questions_from_url,
+    sample_questions, sample_questions_adaptive, validate_questions, LoadError, ParseError,
+    QuestionBank, Sampling, ValidationIssue,
+};
+pub use history::{History, HistoryEntry, HistoryStats, TagScore};
+pub use hooks::{AnswerSubmitted, QuestionShown};
+pub use keymap::Keymap;
+pub use layout::LayoutOptions;
+pub use models::{
+    AppState, Calibration, Confidence, CorrectAnswer, Grade, Grades, Pool, Question, QuizMeta,
+};
+pub use playlist::{Playlist, PlaylistError};
+pub use session::SavedSession;
+pub use theme::{Theme, ThemeError};
+pub use ui::render_to_buffer;
 pub use protocol::{
     AnswerResult, ClientMessage, LeaderboardEntry, ServerMessage, DEFAULT_PORT,
 };
+pub use client::{JoinOptions, QuizClient};
+pub use server::{QuizServer, ServeOptions};
+
+/// How often the local event loop redraws while waiting for input.
+const TICK_RATE: Duration = Duration::from_millis(250);
+/// Most recently played quizzes offered on the welcome screen.
+const RECENT_QUIZZES: usize = 5;
+/// Questions Page Up and Page Down move the results selection by.
+const RESULT_PAGE: usize = 10;
 
 /// Error type for quiz operations.
 #[derive(Debug)]
 pub enum QuizError {
     /// Error loading questions from file.
     Load(LoadError),
+    /// The questions failed validation.
+    Invalid(Vec<ValidationIssue>),
     /// IO error during quiz execution.
     Io(io::Error),
+    /// The terminal could not be set up, drawn on or restored.
+    Terminal(io::Error),
+    /// A saved session could not be parsed.
+    Session(serde_json::Error),
+    /// The server could not listen, or the client could not connect.
+    Network(NetworkError),
+    /// The server's TLS certificate or key could not be used.
+    Tls(String),
+    /// A file the server reads or writes, such as its blocklist or saved
+    /// state, could not be.
+    File { path: PathBuf, error: io::Error },
 }
 
 impl std::fmt::Display for QuizError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             QuizError::Load(e) => write!(f, "Failed to load questions: {}", e),
+            QuizError::Invalid(issues) => {
+                write!(f, "Invalid questions:")?;
+                for issue in issues {
+                    write!(f, "\n  {}", issue)?;
+                }
+                Ok(())
+            }
             QuizError::Io(e) => write!(f, "IO error: {}", e),
+            QuizError::Terminal(e) => write!(f, "Terminal error: {}", e),
+            QuizError::Session(e) => write!(f, "Failed to read saved session: {}", e),
+            QuizError::Network(e) => write!(f, "{}", e),
+            QuizError::Tls(message) => write!(f, "{}", message),
+            QuizError::File { path, error } => write!(f, "{}: {}", path.display(), error),
         }
     }
 }
@@ -73,14 +167,21 @@ impl std::error::Error for QuizError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             QuizError::Load(e) => Some(e),
-            QuizError::Io(e) => Some(e),
+            QuizError::Io(e) | QuizError::Terminal(e) => Some(e),
+            QuizError::Session(e) => Some(e),
+            QuizError::Network(e) => Some(e),
+            QuizError::File { error, .. } => Some(error),
+            QuizError::Invalid(_) | QuizError::Tls(_) => None,
         }
     }
 }
 
 impl From<LoadError> for QuizError {
     fn from(err: LoadError) -> Self {
-        QuizError::Load(err)
+        match err {
+            LoadError::Invalid(issues) => QuizError::Invalid(issues),
+            err => QuizError::Load(err),
+        }
     }
 }
 
@@ -90,9 +191,106 @@ impl From<io::Error> for QuizError {
     }
 }
 
+impl From<NetworkError> for QuizError {
+    fn from(err: NetworkError) -> Self {
+        QuizError::Network(err)
+    }
+}
+
+/// Why a server or client couldn't get on the network.
+#[derive(Debug)]
+pub enum NetworkError {
+    /// The server could not listen on `port`.
+    Bind { port: u16, error: io::Error },
+    /// The client could not connect to the server at `url`.
+    Connect {
+        url: String,
+        error: Box<tokio_tungstenite::tungstenite::Error>,
+    },
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::Bind { port, error } => {
+                write!(f, "Failed to listen on port {}: {}", port, error)
+            }
+            NetworkError::Connect { url, error } => {
+                write!(f, "Failed to connect to {}: {}", url, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NetworkError::Bind { error, .. } => Some(error),
+            NetworkError::Connect { error, .. } => Some(&**error),
+        }
+    }
+}
+
 /// A quiz instance that can be run in the terminal.
 pub struct Quiz {
     app: App,
+    storage: Storage,
+    theme: Theme,
+    layout: LayoutOptions,
+    alerts: Alerts,
+    keymap: Keymap,
+    /// The question file, if the quiz was loaded from a local one.
+    origin: Option<Origin>,
+    /// Changes made to the file's question list, in order.
+    selections: Vec<Selection>,
+    /// Seed for sampling and shuffling, so runs can be repeated.
+    seed: Option<u64>,
+    /// Pools from the file's metadata not drawn yet. They are drawn on the
+    /// first change to the question list or when the quiz runs, so that an
+    /// earlier [`Quiz::seed`] applies to them.
+    pools: Vec<Pool>,
+    /// Time limit given with [`Quiz::time_limit`], which also applies to a
+    /// quiz chosen on the welcome screen.
+    time_limit: Option<Duration>,
+    watch: bool,
+}
+
+/// Where a quiz keeps data that outlives a run.
+struct Storage {
+    /// Where the questions came from, as recorded in the history.
+    source: String,
+    history: Option<History>,
+    session_file: Option<PathBuf>,
+    /// Whether the quiz was resumed from `session_file`.
+    resumed: bool,
+    /// Checks the question file for edits, if asked to with
+    /// [`Quiz::watch`].
+    watcher: Option<Watcher>,
+    /// Where to save a certificate on passing, if asked to with
+    /// [`Quiz::certificate`].
+    certificate: Option<CertificateFile>,
+    /// Whether this is a [`Quiz::strict_exam`]: progress can't be saved and
+    /// an attempt report is written on finishing.
+    strict: bool,
+    /// How to read a quiz chosen on the welcome screen, either one of
+    /// several offered with [`Quiz::from_files`] or one played recently.
+    picker: Option<Picker>,
+}
+
+/// The quiz's settings to apply to whichever question file is chosen.
+struct Picker {
+    selections: Vec<Selection>,
+    seed: Option<u64>,
+    /// Time limit set on the quiz, which takes precedence over the file's.
+    time_limit: Option<Duration>,
+    watch: bool,
+}
+
+/// A certificate to issue when the player passes.
+struct CertificateFile {
+    path: PathBuf,
+    name: String,
+    key: Vec<u8>,
 }
 
 impl Quiz {
@@ -100,6 +298,26 @@ impl Quiz {
     pub fn new(questions: Vec<Question>) -> Self {
         Self {
             app: App::with_questions(questions),
+            storage: Storage {
+                source: "custom".to_string(),
+                history: None,
+                session_file: None,
+                resumed: false,
+                watcher: None,
+                certificate: None,
+                strict: false,
+                picker: None,
+            },
+            theme: Theme::default(),
+            layout: LayoutOptions::default(),
+            alerts: Alerts::default(),
+            keymap: Keymap::default(),
+            origin: None,
+            selections: Vec::new(),
+            seed: None,
+            pools: Vec::new(),
+            time_limit: None,
+            watch: false,
         }
     }
 
@@ -117,21 +335,697 @@ impl Quiz {
     /// let quiz = Quiz::from_json("questions.json").expect("Failed to load quiz");
     /// ```
     pub fn from_json<P: AsRef<Path>>(path: P) -> Result<Self, QuizError> {
-        let questions = load_questions_from_json(path)?;
-        Ok(Self::new(questions))
+        let origin = Origin::Json(path.as_ref().into());
+        let bank = load_bank_from_json(path)?;
+        Ok(Self::from_bank(bank).with_origin(origin))
+    }
+
+    /// Load a quiz from a TOML file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the TOML file containing questions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_toml("questions.toml").expect("Failed to load quiz");
+    /// ```
+    pub fn from_toml<P: AsRef<Path>>(path: P) -> Result<Self, QuizError> {
+        let origin = Origin::Toml(path.as_ref().into());
+        let bank = load_bank_from_toml(path)?;
+        Ok(Self::from_bank(bank).with_origin(origin))
+    }
+
+    /// Load a quiz from a question file encrypted with
+    /// [`data::encrypt`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_encrypted("questions.json.enc", "hunter2")
+    ///     .expect("Failed to load quiz");
+    /// ```
+    pub fn from_encrypted<P: AsRef<Path>>(path: P, passphrase: &str) -> Result<Self, QuizError> {
+        let origin = Origin::Encrypted {
+            path: path.as_ref().into(),
+            passphrase: passphrase.to_string(),
+        };
+        let bank = load_bank_encrypted(path, passphrase)?;
+        Ok(Self::from_bank(bank).with_origin(origin))
+    }
+
+    /// Load a quiz from a JSON file served over HTTP(S).
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the JSON file containing questions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_url("https://example.com/questions.json")
+    ///     .expect("Failed to load quiz");
+    /// ```
+    pub fn from_url(url: &str) -> Result<Self, QuizError> {
+        let bank = load_bank_from_url(url)?;
+        Ok(Self::from_bank(bank).with_source(url.to_string()))
+    }
+
+    /// Offer a choice of question files on the welcome screen, showing
+    /// each one's title and number of questions. The chosen file is read
+    /// again when the player starts it, and tag filters, sampling and
+    /// shuffling apply to it then.
+    ///
+    /// Files that can't be read as JSON or TOML question files, such as
+    /// encrypted ones, are left out, and a single file that can is played
+    /// as with [`Quiz::from_json`]. Returns [`LoadError::NoQuizzes`] if
+    /// none can.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let mut quiz = Quiz::from_files(&["ownership.json", "traits.toml"])?;
+    /// quiz.run()?;
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn from_files<P: AsRef<Path>>(paths: &[P]) -> Result<Self, QuizError> {
+        let mut quizzes = catalog::read_entries(paths);
+        match quizzes.len() {
+            0 => Err(LoadError::NoQuizzes.into()),
+            1 => {
+                let path = quizzes.remove(0).path;
+                if path.extension().is_some_and(|ext| ext == "toml") {
+                    Self::from_toml(path)
+                } else {
+                    Self::from_json(path)
+                }
+            }
+            _ => {
+                let mut quiz = Self::new(Vec::new());
+                quiz.app.set_quizzes(quizzes);
+                Ok(quiz)
+            }
+        }
+    }
+
+    /// Run the quizzes of `playlist` one after the other, with each one's
+    /// score and the total shown on the welcome screen after the last.
+    ///
+    /// Each question file is read when its turn comes, and tag filters,
+    /// sampling and shuffling apply to it then, as with
+    /// [`Quiz::from_files`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{Playlist, Quiz};
+    ///
+    /// let playlist = Playlist::load("workshop.toml")?;
+    /// Quiz::from_playlist(playlist).run()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_playlist(playlist: Playlist) -> Self {
+        let mut quiz = Self::new(Vec::new());
+        quiz.app.set_playlist(playlist);
+        quiz
+    }
+
+    /// Offer the JSON and TOML question files in `dir` to choose from, as
+    /// with [`Quiz::from_files`].
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, QuizError> {
+        Self::from_files(&catalog::question_files(dir.as_ref())?)
+    }
+
+    /// Keep only questions tagged with at least one of `tags`.
+    ///
+    /// Tags are compared case-insensitively. Returns an error if no question
+    /// matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.filter_tags(&["ownership"])?;
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn filter_tags<S: AsRef<str>>(self, tags: &[S]) -> Result<Self, QuizError> {
+        let tags = tags.iter().map(|tag| tag.as_ref().to_string()).collect();
+        self.select(Selection::Tags(tags))
+    }
+
+    /// Pick and order questions the same way on every run with the same
+    /// `seed`, e.g. to give a class the same randomized paper or to
+    /// reproduce a bug report.
+    ///
+    /// Affects the pools in the file's metadata, and [`Quiz::sample`],
+    /// [`Quiz::sample_adaptive`] and [`Quiz::shuffle`] called after it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{Quiz, Sampling};
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.seed(42).sample(20, Sampling::Uniform);
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Keep at most `limit` randomly picked questions.
+    ///
+    /// Use [`Sampling::ByTag`] to keep each tag's share of the bank.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{Quiz, Sampling};
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.sample(20, Sampling::Uniform);
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn sample(self, limit: usize, sampling: Sampling) -> Self {
+        self.select(Selection::Sample(limit, sampling))
+            .expect("sampling never fails")
+    }
+
+    /// Ask the questions in random order.
+    pub fn shuffle(self) -> Self {
+        let mut quiz = self.select(Selection::Shuffle).expect("shuffling never fails");
+        quiz.app.set_shuffled(true);
+        quiz
+    }
+
+    /// Give the whole quiz a time budget.
+    ///
+    /// The remaining time is shown in the quiz header, and the quiz ends
+    /// when it runs out.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.time_limit(Duration::from_secs(600));
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self.app.set_time_limit(Some(time_limit));
+        self
+    }
+
+    /// Let players move between questions and revise answers until they
+    /// finish the quiz.
+    ///
+    /// Enter saves an answer, left/right (or `p`/`n`) navigate, and `f`
+    /// finishes and scores the quiz.
+    pub fn exam_mode(mut self) -> Self {
+        self.app.set_exam_mode(true);
+        self
+    }
+
+    /// Run the quiz as a strict exam with a `time_limit` for the whole quiz.
+    ///
+    /// Combines [`exam_mode`](Self::exam_mode) without instant feedback,
+    /// the time limit, and a random order for both the questions and their
+    /// options. The quiz can't be saved and resumed midway, and on finishing
+    /// a plain-text attempt report with the time of every answer is written
+    /// to `quiz-attempt-<timestamp>.txt` in the working directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use rust_quiz::Quiz;
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.strict_exam(Duration::from_secs(1800));
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn strict_exam(self, time_limit: Duration) -> Self {
+        let mut quiz = self
+            .exam_mode()
+            .time_limit(time_limit)
+            .shuffle()
+            .select(Selection::ShuffleOptions)
+            .expect("shuffling never fails");
+        quiz.app.set_instant_feedback(false);
+        quiz.storage.strict = true;
+        quiz
+    }
+
+    /// Study the questions as flashcards instead of taking the quiz.
+    ///
+    /// Each card shows a question with its answer hidden until Enter is
+    /// pressed; the player then says whether they knew it with `y` or `n`.
+    /// Nothing is scored, but the ratings are recorded in the history, so
+    /// [`sample_adaptive`](Self::sample_adaptive) brings back the cards the
+    /// player didn't know.
+    pub fn flashcards(mut self) -> Self {
+        self.app.set_flashcards(true);
+        self
+    }
+
+    /// Ask how sure the player is of each answer after submitting it.
+    ///
+    /// A correct answer the player is sure of earns double points and a
+    /// wrong one costs the question's points; the results show how often
+    /// sure and unsure answers were right.
+    pub fn confidence_mode(mut self) -> Self {
+        self.app.set_confidence_mode(true);
+        self
+    }
+
+    /// Deduct `points` from a question's score when its hint is revealed.
+    ///
+    /// Hints are free by default.
+    pub fn hint_penalty(mut self, points: usize) -> Self {
+        self.app.set_hint_penalty(points);
+        self
+    }
+
+    /// Show whether each answer was correct right after submitting it.
+    ///
+    /// The correct answer and the question's explanation, if any, stay on
+    /// screen until a key is pressed. Ignored in exam mode.
+    pub fn instant_feedback(mut self) -> Self {
+        self.app.set_instant_feedback(true);
+        self
+    }
+
+    /// Append each finished run to `history`, and list past runs on the
+    /// welcome screen with `h`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{History, Quiz};
+    ///
+    /// let mut quiz = Quiz::from_json("questions.json")?;
+    /// if let Some(history) = History::open_default() {
+    ///     quiz = quiz.history(history)?;
+    /// }
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn history(mut self, history: History) -> Result<Self, QuizError> {
+        self.app.set_history(history.load()?);
+        self.storage.history = Some(history);
+        Ok(self)
+    }
+
+    /// Offer to save progress to `path` when quitting mid-quiz, so it can be
+    /// picked up later with [`resume`](Self::resume).
+    pub fn session_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.storage.session_file = Some(path.into());
+        self
+    }
+
+    /// Save a [`Certificate`](certificate::Certificate) for `name`, signed
+    /// with `key`, to `path` when the player passes.
+    ///
+    /// Passing takes the `pass_threshold` or `pass_score` set in the quiz
+    /// metadata; without either, no certificate is saved.
+    pub fn certificate<P: Into<PathBuf>>(mut self, path: P, name: &str, key: &[u8]) -> Self {
+        self.storage.certificate = Some(CertificateFile {
+            path: path.into(),
+            name: name.to_string(),
+            key: key.to_vec(),
+        });
+        self
+    }
+
+    /// Continue a quiz saved when quitting mid-quiz.
+    ///
+    /// The quiz picks up at the question it was left on, with the answers,
+    /// settings and time spent restored. Quitting again offers to save back
+    /// to `path`, and the file is removed once the quiz is finished.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let mut quiz = Quiz::resume("session.json")?;
+    /// quiz.run()?;
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn resume<P: AsRef<Path>>(path: P) -> Result<Self, QuizError> {
+        let session = SavedSession::load(&path)?;
+        let source = session.source().to_string();
+        let mut quiz = Self {
+            app: App::from_saved_session(session),
+            ..Self::new(Vec::new())
+        }
+        .with_source(source)
+        .session_file(path.as_ref());
+        quiz.storage.resumed = true;
+        Ok(quiz)
+    }
+
+    /// Keep at most `limit` questions, favouring ones missed in past runs.
+    ///
+    /// Uses the history attached with [`history`](Self::history), so call
+    /// that first. Without history every question is equally likely.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{History, Quiz};
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?
+    ///     .history(History::at("history.jsonl"))?
+    ///     .sample_adaptive(20);
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn sample_adaptive(self, limit: usize) -> Self {
+        self.select(Selection::Adaptive(limit))
+            .expect("sampling never fails")
+    }
+
+    /// Offer to reload the question file on the welcome screen when it
+    /// changes on disk, e.g. while writing questions in another window.
+    ///
+    /// The reloaded questions go through the same tag filter, sampling and
+    /// shuffling as when the quiz was built. Only quizzes loaded from a local
+    /// file can be watched; for others this does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let mut quiz = Quiz::from_toml("questions.toml")?.watch();
+    /// quiz.run()?;
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn watch(mut self) -> Self {
+        self.watch = true;
+        self
+    }
+
+    /// Draw the quiz with `theme` instead of the default dark palette.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::{Quiz, Theme};
+    ///
+    /// let quiz = Quiz::from_json("questions.json")?.theme(Theme::light());
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Size the code block, answer options and margins of the quiz screen.
+    pub fn layout(mut self, layout: LayoutOptions) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Ring the bell or flash the screen when an answer is submitted and
+    /// when the time limit is about to run out.
+    pub fn alerts(mut self, alerts: Alerts) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    /// Accept extra keys on top of the default bindings.
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Call `hook` each time a new question is put on screen.
+    pub fn on_question_shown(mut self, hook: impl FnMut(&QuestionShown) + 'static) -> Self {
+        self.app.on_question_shown(hook);
+        self
+    }
+
+    /// Call `hook` each time an answer is submitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// let mut quiz = Quiz::from_json("questions.json")?.on_answer_submitted(|event| {
+    ///     eprintln!("{}: {}", event.question.text, event.is_correct);
+    /// });
+    /// quiz.run()?;
+    /// # Ok::<(), rust_quiz::QuizError>(())
+    /// ```
+    pub fn on_answer_submitted(mut self, hook: impl FnMut(&AnswerSubmitted) + 'static) -> Self {
+        self.app.on_answer_submitted(hook);
+        self
+    }
+
+    /// Call `hook` with the result when the quiz ends.
+    pub fn on_finished(mut self, hook: impl FnMut(&QuizResult) + 'static) -> Self {
+        self.app.on_finished(hook);
+        self
+    }
+
+    /// A quiz with the bank's questions, using its metadata such as the
+    /// default time limit and pools.
+    fn from_bank(bank: QuestionBank) -> Self {
+        let mut quiz = Self::new(bank.questions);
+        quiz.pools = bank.meta.pools.clone();
+        quiz.app.set_time_limit(bank.meta.time_limit());
+        quiz.app.set_meta(bank.meta);
+        quiz
+    }
+
+    fn with_source(mut self, source: String) -> Self {
+        self.storage.source = source;
+        self
+    }
+
+    fn with_origin(mut self, origin: Origin) -> Self {
+        self.storage.source = origin.path().display().to_string();
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Change the question list, and remember the change for reloading.
+    ///
+    /// While a quiz is still to be chosen, the change is only remembered.
+    fn select(mut self, selection: Selection) -> Result<Self, QuizError> {
+        if !self.app.quizzes().is_empty() || self.app.playlist().is_some() {
+            self.selections.push(selection);
+            return Ok(self);
+        }
+        self.draw_pools()?;
+        self.apply(selection)?;
+        Ok(self)
+    }
+
+    /// Assemble the questions from the file's pools, if not done yet.
+    fn draw_pools(&mut self) -> Result<(), QuizError> {
+        if self.pools.is_empty() {
+            return Ok(());
+        }
+        let pools = std::mem::take(&mut self.pools);
+        self.apply(Selection::Pools(pools))
+    }
+
+    fn apply(&mut self, selection: Selection) -> Result<(), QuizError> {
+        let questions = self.app.engine().questions().to_vec();
+        let questions = selection.apply(questions, self.app.history(), self.seed)?;
+        self.app.replace_questions(questions);
+        self.selections.push(selection);
+        Ok(())
     }
 
     /// Run the quiz in the terminal.
     ///
     /// This will take over the terminal, display the quiz UI, and return
     /// when the user quits.
-    pub fn run(mut self) -> Result<(), QuizError> {
-        let mut term = terminal::init()?;
-        let result = run_event_loop(&mut term, &mut self.app);
-        terminal::restore()?;
+    pub fn run(&mut self) -> Result<(), QuizError> {
+        let mut term = terminal::init().map_err(QuizError::Terminal)?;
+        let result = self.run_loop(&mut term, Graphics::detect());
+        terminal::restore().map_err(QuizError::Terminal)?;
+        result
+    }
+
+    /// Run the quiz on a terminal the caller has set up, e.g. one drawing
+    /// to stderr or a TTY handle, and return when the user quits.
+    ///
+    /// Keys are still read with crossterm. Unlike [`Quiz::run`], raw mode
+    /// and the alternate screen are left to the caller, and question images
+    /// are shown as placeholders.
+    pub fn run_on<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        self.run_loop(terminal, None)
+    }
+
+    fn run_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        graphics: Option<Graphics>,
+    ) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        self.prepare()?;
+
+        let mut event_loop = self.event_loop(graphics);
+        loop {
+            event_loop.draw(terminal)?;
+            // Poll with a timeout so timers keep updating without input
+            if event::poll(TICK_RATE)? && event_loop.handle(terminal, event::read()?)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Get the questions and storage ready for running.
+    fn prepare(&mut self) -> Result<(), QuizError> {
+        self.draw_pools()?;
+        if self.watch
+            && let Some(origin) = self.origin.take()
+        {
+            self.storage.watcher = Some(Watcher::new(origin, self.selections.clone(), self.seed));
+        }
+        // A chosen file's own pools are drawn first, in place of these
+        let selections = self
+            .selections
+            .iter()
+            .filter(|selection| !matches!(selection, Selection::Pools(_)))
+            .cloned();
+        self.storage.picker = Some(Picker {
+            selections: std::iter::once(Selection::Pools(Vec::new())).chain(selections).collect(),
+            seed: self.seed,
+            time_limit: self.time_limit,
+            watch: self.watch,
+        });
+        refresh_recent_quizzes(&mut self.app, &self.storage);
+        Ok(())
+    }
+
+    /// Run the quiz in the terminal without blocking a tokio runtime, and
+    /// return when the user quits.
+    ///
+    /// Keys are read from crossterm's `EventStream`, so the quiz can run
+    /// beside other tasks on the same thread. The future isn't `Send`, as
+    /// the hooks needn't be, so await it directly or with
+    /// `tokio::task::spawn_local` rather than `tokio::spawn`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rust_quiz::Quiz;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), rust_quiz::QuizError> {
+    ///     let mut quiz = Quiz::from_json("questions.json")?;
+    ///     quiz.run_async().await
+    /// }
+    /// ```
+    pub async fn run_async(&mut self) -> Result<(), QuizError> {
+        let mut term = terminal::init().map_err(QuizError::Terminal)?;
+        let result = self.run_async_loop(&mut term, Graphics::detect()).await;
+        terminal::restore().map_err(QuizError::Terminal)?;
         result
     }
 
+    /// [`Quiz::run_on`] without blocking a tokio runtime, as
+    /// [`Quiz::run_async`] is to [`Quiz::run`].
+    pub async fn run_async_on<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        self.run_async_loop(terminal, None).await
+    }
+
+    async fn run_async_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        graphics: Option<Graphics>,
+    ) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        self.prepare()?;
+
+        let mut event_loop = self.event_loop(graphics);
+        let mut events = EventStream::new();
+        let mut ticks = tokio::time::interval(TICK_RATE);
+        loop {
+            event_loop.draw(terminal)?;
+            // Redraw on every tick so timers keep updating without input
+            tokio::select! {
+                event = events.next() => {
+                    let Some(event) = event else {
+                        return Ok(());
+                    };
+                    if event_loop.handle(terminal, event?)? {
+                        return Ok(());
+                    }
+                }
+                _ = ticks.tick() => {}
+            }
+        }
+    }
+
+    /// Draw the current screen once, without reading any keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{backend::TestBackend, Terminal};
+    /// use rust_quiz::{Question, Quiz};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let quiz = Quiz::new(vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)]);
+    /// let mut terminal = Terminal::new(TestBackend::new(80, 24))?;
+    /// quiz.draw(&mut terminal)?;
+    /// assert!(terminal.backend().to_string().contains("RUST QUIZ"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw<B: Backend>(&self, terminal: &mut Terminal<B>) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        terminal
+            .draw(|frame| ui::render(frame, &self.app, &self.theme, &self.layout))
+            .map_err(terminal::backend_error::<B>)?;
+        Ok(())
+    }
+
+    fn event_loop(&mut self, graphics: Option<Graphics>) -> EventLoop<'_> {
+        self.app.set_graphics(graphics.is_some());
+        EventLoop {
+            app: &mut self.app,
+            storage: &mut self.storage,
+            theme: &self.theme,
+            layout: &self.layout,
+            keymap: &self.keymap,
+            alerter: Alerter::new(self.alerts),
+            graphics,
+            was_finished: false,
+        }
+    }
+
     /// Get a reference to the underlying app for custom handling.
     pub fn app(&self) -> &App {
         &self.app
@@ -143,45 +1037,347 @@ impl Quiz {
     }
 }
 
-fn run_event_loop(terminal: &mut terminal::AppTerminal, app: &mut App) -> Result<(), QuizError> {
-    loop {
-        terminal.draw(|frame| ui::render(frame, app))?;
+/// What the local event loop works on, whether it blocks on input or
+/// awaits it.
+struct EventLoop<'a> {
+    app: &'a mut App,
+    storage: &'a mut Storage,
+    theme: &'a Theme,
+    layout: &'a LayoutOptions,
+    keymap: &'a Keymap,
+    alerter: Alerter,
+    /// How to show question images, if the terminal can.
+    graphics: Option<Graphics>,
+    was_finished: bool,
+}
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
-                continue;
-            }
+impl EventLoop<'_> {
+    /// Catch up on timers and file changes, then draw the screen.
+    fn draw<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        let app = &mut *self.app;
+        app.tick();
+        if let Some(watcher) = &mut self.storage.watcher
+            && watcher.poll()
+        {
+            app.offer_reload();
+        }
+        if app.take_alert() {
+            self.alerter.alert()?;
+        }
+
+        let is_finished = match app.state {
+            AppState::Passed | AppState::Result | AppState::Review => true,
+            AppState::Flashcards => app.deck().is_some_and(Deck::is_finished),
+            _ => false,
+        };
+        if is_finished && !self.was_finished {
+            record_finished_run(app, self.storage);
+            refresh_recent_quizzes(app, self.storage);
+        }
+        self.was_finished = is_finished;
+
+        let alerter = &self.alerter;
+        let render = |frame: &mut Frame| {
+            ui::render(frame, app, self.theme, self.layout);
+            alerter.render(frame);
+        };
+        let completed = terminal.draw(render).map_err(terminal::backend_error::<B>)?;
 
-            if handle_input(app, key.code) {
-                break;
+        let Some(graphics) = &mut self.graphics else {
+            return Ok(());
+        };
+        let image = ui::image_area(completed.area, app, self.layout)
+            .map(|(path, area)| (path.to_string(), area));
+        let wanted = image.as_ref().map(|(path, area)| (path.as_str(), *area));
+        if graphics.hide_unless(wanted).map_err(QuizError::Terminal)? {
+            terminal.clear().map_err(terminal::backend_error::<B>)?;
+            terminal.draw(render).map_err(terminal::backend_error::<B>)?;
+        }
+        if let Err(e) = graphics.show(wanted) {
+            // Show placeholders from now on rather than blank boxes
+            self.graphics = None;
+            app.set_graphics(false);
+            let path = wanted.map_or("", |(path, _)| path);
+            app.set_status_message(format!("Can't show image {}: {}", path, e));
+        }
+        Ok(())
+    }
+
+    /// Handle a terminal event. Returns true if the app should exit.
+    fn handle<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        event: Event,
+    ) -> Result<bool, QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let key = self.keymap.translate(key.code);
+                if handle_input(self.app, key, self.storage) {
+                    return Ok(true);
+                }
+                if self.app.take_reload_request() {
+                    reload_questions(self.app, self.storage);
+                }
+                if let Some(entry) = self.app.take_quiz_request() {
+                    start_chosen_quiz(self.app, self.storage, &entry);
+                }
+            }
+            Event::Resize(..) => {
+                terminal::handle_resize(terminal)?;
+                // Images drawn outside ratatui went with the old screen
+                if let Some(graphics) = &mut self.graphics {
+                    graphics.reset().map_err(QuizError::Terminal)?;
+                }
             }
+            _ => {}
         }
+        Ok(false)
+    }
+}
+
+/// Remember a finished run, both on screen and in the history file, drop
+/// the saved session it was resumed from, and issue any certificate earned.
+fn record_finished_run(app: &mut App, storage: &Storage) {
+    let entry = match app.deck() {
+        Some(deck) => HistoryEntry::from_deck(app.engine().questions(), deck, &storage.source),
+        None => HistoryEntry::from_engine(app.engine(), &storage.source),
+    };
+    app.record_playlist_score(entry.score, entry.max_score);
+    if let Some(history) = &storage.history
+        && let Err(e) = history.append(&entry)
+    {
+        app.set_status_message(format!("Failed to save history: {}", e));
+    }
+    app.push_history(entry);
+
+    if storage.strict {
+        write_attempt_report(app);
+    }
+
+    if storage.resumed
+        && let Some(path) = &storage.session_file
+        && let Err(e) = std::fs::remove_file(path)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        app.set_status_message(format!("Failed to remove saved session: {}", e));
     }
 
-    Ok(())
+    if app.state == AppState::Passed
+        && let Some(file) = &storage.certificate
+    {
+        let engine = app.engine();
+        let quiz = app.meta().title.as_deref().unwrap_or(&storage.source);
+        let certificate = certificate::Certificate::issue(
+            &file.name,
+            quiz,
+            engine.calculate_score(),
+            engine.max_score(),
+            &file.key,
+        );
+        app.set_status_message(match certificate.save(&file.path) {
+            Ok(()) => format!("Certificate saved to {}", file.path.display()),
+            Err(e) => format!("Failed to save certificate: {}", e),
+        });
+    }
+}
+
+/// Replace the quiz's questions with a fresh read of the question file.
+fn reload_questions(app: &mut App, storage: &Storage) {
+    let Some(watcher) = &storage.watcher else {
+        return;
+    };
+    match watcher.reload(app.history()) {
+        Ok(bank) => {
+            let count = bank.questions.len();
+            app.replace_questions(bank.questions);
+            app.set_meta(bank.meta);
+            app.set_status_message(match count {
+                1 => "Reloaded 1 question".to_string(),
+                count => format!("Reloaded {} questions", count),
+            });
+        }
+        Err(e) => {
+            // Keep validation issues on the one status line
+            let error = e.to_string().replace("\n  ", " · ");
+            app.set_status_message(format!("Failed to reload questions: {}", error));
+        }
+    }
+}
+
+/// Read the question file chosen on the welcome screen and start its quiz.
+fn start_chosen_quiz(app: &mut App, storage: &mut Storage, entry: &QuizEntry) {
+    let Some(picker) = &storage.picker else {
+        return;
+    };
+    let origin = Origin::for_file(&entry.path);
+    let source = origin.path().display().to_string();
+    // Read through a watcher, which applies the quiz's selections
+    let watcher = Watcher::new(origin, picker.selections.clone(), picker.seed);
+    match watcher.reload(app.history()) {
+        Ok(bank) => {
+            app.set_time_limit(picker.time_limit.or(bank.meta.time_limit()));
+            app.replace_questions(bank.questions);
+            app.set_meta(bank.meta);
+            storage.source = source;
+            storage.watcher = picker.watch.then_some(watcher);
+            refresh_recent_quizzes(app, storage);
+            app.start_quiz();
+        }
+        Err(e) => {
+            let error = e.to_string().replace("\n  ", " · ");
+            app.set_status_message(format!("Failed to load {}: {}", entry.title, error));
+        }
+    }
+}
+
+/// List the other question files in the history on the welcome screen,
+/// unless choosing between files offered together or running a playlist.
+fn refresh_recent_quizzes(app: &mut App, storage: &Storage) {
+    if storage.history.is_some() && app.quizzes().is_empty() && app.playlist().is_none() {
+        let quizzes = catalog::recent_quizzes(app.history(), &storage.source, RECENT_QUIZZES);
+        app.set_recent_quizzes(quizzes);
+    }
 }
 
 /// Returns true if the app should exit.
-fn handle_input(app: &mut App, key: KeyCode) -> bool {
+fn handle_input(app: &mut App, key: KeyCode, storage: &Storage) -> bool {
+    let can_save = storage.session_file.is_some() && !storage.strict;
+    if app.is_showing_help() {
+        if matches!(key, KeyCode::Char('?') | KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+            app.toggle_help();
+        }
+        return false;
+    }
+    if key == KeyCode::Char('?') && app.state != AppState::QuitPrompt {
+        app.toggle_help();
+        return false;
+    }
     match app.state {
         AppState::Welcome => handle_welcome_input(app, key),
-        AppState::Quiz => handle_quiz_input(app, key),
+        AppState::History => handle_history_input(app, key),
+        AppState::Stats => handle_stats_input(app, key),
+        AppState::Quiz => handle_quiz_input(app, key, can_save),
+        AppState::Feedback => handle_feedback_input(app, key, can_save),
+        AppState::QuitPrompt => handle_quit_prompt_input(app, key, storage),
+        AppState::Passed => handle_passed_input(app, key),
         AppState::Result => handle_result_input(app, key),
+        AppState::Review => handle_review_input(app, key),
+        AppState::Flashcards => handle_flashcards_input(app, key),
     }
 }
 
 fn handle_welcome_input(app: &mut App, key: KeyCode) -> bool {
     match key {
+        KeyCode::Enter if app.playlist().is_some_and(Playlist::is_finished) => {
+            app.restart_playlist();
+            false
+        }
+        KeyCode::Enter if app.chosen_quiz().is_some() => {
+            app.request_quiz();
+            false
+        }
         KeyCode::Enter => {
             app.start_quiz();
             false
         }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.select_next_quiz();
+            false
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.select_previous_quiz();
+            false
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            app.show_history();
+            false
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.show_stats();
+            false
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.request_reload();
+            false
+        }
         KeyCode::Char('q') | KeyCode::Char('Q') => true,
         _ => false,
     }
 }
 
-fn handle_quiz_input(app: &mut App, key: KeyCode) -> bool {
+fn handle_history_input(app: &mut App, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.scroll_history_down();
+            false
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.scroll_history_up();
+            false
+        }
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Char('H') => {
+            app.close_history();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => true,
+        _ => false,
+    }
+}
+
+fn handle_stats_input(app: &mut App, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.close_stats();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => true,
+        _ => false,
+    }
+}
+
+/// Quit right away, or ask first when progress can be saved.
+fn quit_quiz(app: &mut App, can_save: bool) -> bool {
+    if can_save {
+        app.prompt_quit();
+        false
+    } else {
+        true
+    }
+}
+
+fn handle_quit_prompt_input(app: &mut App, key: KeyCode, storage: &Storage) -> bool {
+    match key {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            let Some(path) = &storage.session_file else {
+                return true;
+            };
+            match app.to_saved_session(&storage.source).save(path) {
+                Ok(()) => true,
+                Err(e) => {
+                    app.set_status_message(format!("Failed to save session: {}", e));
+                    false
+                }
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => true,
+        KeyCode::Esc => {
+            app.cancel_quit();
+            false
+        }
+        _ => false,
+    }
+}
+
+fn handle_quiz_input(app: &mut App, key: KeyCode, can_save: bool) -> bool {
+    if app.is_choosing_confidence() {
+        return handle_confidence_input(app, key, can_save);
+    }
     match key {
         KeyCode::Up | KeyCode::Char('k') => {
             app.select_previous_option();
@@ -191,10 +1387,102 @@ fn handle_quiz_input(app: &mut App, key: KeyCode) -> bool {
             app.select_next_option();
             false
         }
+        KeyCode::Char(' ') if app.current_question().is_multiple() => {
+            app.toggle_selected_option();
+            false
+        }
         KeyCode::Enter | KeyCode::Char(' ') => {
             app.submit_answer();
             false
         }
+        KeyCode::Right | KeyCode::Char('n') => {
+            app.next_question();
+            false
+        }
+        KeyCode::Left | KeyCode::Char('p') => {
+            app.previous_question();
+            false
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            app.finish_quiz();
+            false
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.skip_question();
+            false
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            app.reveal_hint();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => quit_quiz(app, can_save),
+        _ => false,
+    }
+}
+
+fn handle_confidence_input(app: &mut App, key: KeyCode, can_save: bool) -> bool {
+    match key {
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.submit_with_confidence(Confidence::Sure);
+            false
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            app.submit_with_confidence(Confidence::Unsure);
+            false
+        }
+        KeyCode::Esc => {
+            app.cancel_confidence();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => quit_quiz(app, can_save),
+        _ => false,
+    }
+}
+
+fn handle_feedback_input(app: &mut App, key: KeyCode, can_save: bool) -> bool {
+    match key {
+        KeyCode::Char('q') | KeyCode::Char('Q') => quit_quiz(app, can_save),
+        _ => {
+            app.continue_after_feedback();
+            false
+        }
+    }
+}
+
+fn handle_flashcards_input(app: &mut App, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            app.reveal_card();
+            false
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.rate_card(true);
+            false
+        }
+        // Once every card is rated, n moves on through a playlist
+        KeyCode::Char('n') | KeyCode::Char('N') if app.current_card().is_none() => {
+            app.next_in_playlist();
+            false
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.rate_card(false);
+            false
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') if app.current_card().is_none() => {
+            app.restart();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => true,
+        _ => false,
+    }
+}
+
+fn handle_passed_input(app: &mut App, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            app.show_results();
+            false
+        }
         KeyCode::Char('q') | KeyCode::Char('Q') => true,
         _ => false,
     }
@@ -203,17 +1491,121 @@ fn handle_quiz_input(app: &mut App, key: KeyCode) -> bool {
 fn handle_result_input(app: &mut App, key: KeyCode) -> bool {
     match key {
         KeyCode::Down | KeyCode::Char('j') => {
-            app.scroll_results_down();
+            app.select_next_result();
             false
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            app.scroll_results_up();
+            app.select_previous_result();
+            false
+        }
+        KeyCode::PageDown => {
+            app.select_next_results(RESULT_PAGE);
+            false
+        }
+        KeyCode::PageUp => {
+            app.select_previous_results(RESULT_PAGE);
+            false
+        }
+        KeyCode::Home => {
+            app.select_first_result();
+            false
+        }
+        KeyCode::End => {
+            app.select_last_result();
+            false
+        }
+        KeyCode::Enter => {
+            app.open_review();
+            false
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            app.toggle_answers();
+            false
+        }
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            app.toggle_wrong_only();
+            false
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            if !app.retake_missed() {
+                app.set_status_message("Every question was answered correctly");
+            }
+            false
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            export_report(app);
+            false
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            export_moodle_xml(app);
             false
         }
         KeyCode::Char('r') | KeyCode::Char('R') => {
             app.restart();
             false
         }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.next_in_playlist();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => true,
+        _ => false,
+    }
+}
+
+/// Save an HTML report of the finished quiz in the working directory.
+fn export_report(app: &mut App) {
+    let path = format!(
+        "quiz-report-{}.html",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let message = match report::write_html_report(app.engine(), &path) {
+        Ok(()) => format!("Report saved to {}", path),
+        Err(e) => format!("Failed to save report: {}", e),
+    };
+    app.set_status_message(message);
+}
+
+/// Save the strict exam's attempt report in the working directory.
+fn write_attempt_report(app: &mut App) {
+    let finished_at = chrono::Local::now();
+    let path = format!("quiz-attempt-{}.txt", finished_at.format("%Y%m%d-%H%M%S"));
+    let message = match report::write_attempt_report(app.engine(), finished_at, &path) {
+        Ok(()) => format!("Attempt report saved to {}", path),
+        Err(e) => format!("Failed to save attempt report: {}", e),
+    };
+    app.set_status_message(message);
+}
+
+/// Save the quiz's questions with the given answers as Moodle XML in the
+/// working directory.
+fn export_moodle_xml(app: &mut App) {
+    let path = format!(
+        "quiz-moodle-{}.xml",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let xml = moodle::moodle_xml_with_results(app.engine(), app.meta());
+    let message = match std::fs::write(&path, xml) {
+        Ok(()) => format!("Moodle XML saved to {}", path),
+        Err(e) => format!("Failed to save Moodle XML: {}", e),
+    };
+    app.set_status_message(message);
+}
+
+fn handle_review_input(app: &mut App, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Right | KeyCode::Char('n') | KeyCode::Down | KeyCode::Char('j') => {
+            app.select_next_result();
+            false
+        }
+        KeyCode::Left | KeyCode::Char('p') | KeyCode::Up | KeyCode::Char('k') => {
+            app.select_previous_result();
+            false
+        }
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace => {
+            app.close_review();
+            false
+        }
         KeyCode::Char('q') | KeyCode::Char('Q') => true,
         _ => false,
     }
@@ -1,35 +1,404 @@
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use rust_quiz::protocol::DEFAULT_PORT;
+use rust_quiz::client::JoinOptions;
+use rust_quiz::data::is_encrypted_file;
+use rust_quiz::server::{BotAccuracy, ServeOptions, TlsConfig, UsernamePolicy};
+use rust_quiz::{Alerts, Config, LayoutOptions, QuestionBank, Sampling, Theme, ThemeError};
 
 #[derive(Parser)]
 #[command(name = "rust-quiz")]
 #[command(about = "A terminal-based quiz application with multiplayer support")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Path to questions JSON file (for local mode)
-    #[arg(short, long, default_value = "questions.json")]
-    questions: PathBuf,
+    /// Without a subcommand, play locally as with `play`
+    #[command(flatten)]
+    play: PlayArgs,
+
+    /// Color theme: `dark`, `light`, `high-contrast` or a path to a theme TOML file
+    /// [default: ~/.config/rust-quiz/theme.toml if present, else dark]
+    #[arg(long, value_name = "THEME", global = true)]
+    theme: Option<String>,
+
+    /// Tallest the code block may grow, in rows [default: 20]
+    #[arg(long, value_name = "ROWS", global = true)]
+    code_height: Option<u16>,
+
+    /// Fewest rows kept for the answer options [default: 8]
+    #[arg(long, value_name = "ROWS", global = true)]
+    options_height: Option<u16>,
+
+    /// Blank space around the quiz screen [default: 1]
+    #[arg(long, value_name = "CELLS", global = true)]
+    margin: Option<u16>,
+
+    /// Ring the terminal bell when answering, when time is running out, and
+    /// when a multiplayer quiz starts or the host announces something
+    #[arg(long, global = true)]
+    bell: bool,
+
+    /// Flash the screen on the same events as --bell
+    #[arg(long, global = true)]
+    flash: bool,
+
+    /// Send a desktop notification when a multiplayer quiz starts or the
+    /// host announces something, in terminals that support OSC 9
+    #[arg(long, global = true)]
+    notify: bool,
+}
+
+/// Options for a single-player quiz.
+#[derive(Args)]
+struct PlayArgs {
+    /// Path or http(s) URL to the questions file, or a directory of them to
+    /// choose from [default: `quizzes` or `questions` from the config file,
+    /// else questions.json]
+    #[arg(value_name = "FILE")]
+    file: Option<PathBuf>,
+
+    /// Same as FILE
+    #[arg(short, long, value_name = "FILE", conflicts_with = "file")]
+    questions: Option<PathBuf>,
+
+    /// Play the quizzes listed in a playlist TOML file one after the other,
+    /// with every score and the total at the end
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["file", "questions"])]
+    playlist: Option<PathBuf>,
+
+    /// Only use questions with this tag (repeatable)
+    #[arg(short, long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Ask at most this many randomly picked questions
+    #[arg(short = 'n', long, value_name = "N")]
+    limit: Option<NonZeroUsize>,
+
+    /// Keep each tag's share of the bank when using --limit
+    #[arg(long, requires = "limit")]
+    by_tag: bool,
+
+    /// Favour questions missed in past runs when using --limit
+    #[arg(long, requires = "limit", conflicts_with_all = ["by_tag", "no_history"])]
+    adaptive: bool,
+
+    /// Ask the questions in random order
+    #[arg(long)]
+    shuffle: bool,
+
+    /// Pick and order questions the same way on every run with this seed
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// Time budget for the whole quiz, in seconds
+    #[arg(long, visible_alias = "timer", value_name = "SECONDS")]
+    time_limit: Option<NonZeroU64>,
+
+    /// Allow revisiting and changing answers until finishing with `f`
+    ///
+    /// `--exam=strict` also asks questions and options in random order,
+    /// needs --time-limit, can't be saved midway and writes an attempt
+    /// report with the time of every answer.
+    #[arg(
+        long,
+        value_name = "MODE",
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "open",
+        requires_if("strict", "time_limit")
+    )]
+    exam: Option<ExamMode>,
+
+    /// Show the correct answer right after each question
+    #[arg(long, conflicts_with = "exam")]
+    feedback: bool,
+
+    /// Points deducted from a question when its hint is revealed
+    #[arg(long, value_name = "POINTS", default_value_t = 0)]
+    hint_penalty: usize,
+
+    /// Study the questions as flashcards: reveal each answer and say
+    /// whether you knew it, without a score
+    #[arg(
+        long,
+        conflicts_with_all = ["exam", "feedback", "time_limit", "hint_penalty", "confidence"]
+    )]
+    flashcards: bool,
+
+    /// Say how sure you are of each answer: sure answers score double when
+    /// right and lose points when wrong
+    #[arg(long)]
+    confidence: bool,
+
+    /// Don't record this run in the score history
+    #[arg(long)]
+    no_history: bool,
+
+    /// Offer to reload the questions on the welcome screen when FILE changes
+    #[arg(long)]
+    watch: bool,
+
+    /// Save a signed certificate to FILE on passing the quiz
+    ///
+    /// The key is read from RUST_QUIZ_CERTIFICATE_KEY; check certificates
+    /// with `rust-quiz verify`.
+    #[arg(long, value_name = "FILE")]
+    certificate: Option<PathBuf>,
+
+    /// Name on the certificate [default: `username` from the config file]
+    #[arg(long, value_name = "NAME", requires = "certificate")]
+    name: Option<String>,
+
+    /// Continue the quiz saved when quitting mid-quiz
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "file", "questions", "tags", "limit", "shuffle", "seed", "time_limit", "exam",
+            "feedback", "hint_penalty", "flashcards", "confidence", "watch",
+        ]
+    )]
+    resume: bool,
+}
+
+impl PlayArgs {
+    fn questions<'a>(&'a self, config: &'a Config) -> &'a Path {
+        self.file
+            .as_deref()
+            .or(self.questions.as_deref())
+            .or(config.questions.as_deref())
+            .unwrap_or(Path::new(DEFAULT_QUESTIONS))
+    }
+}
+
+/// How `--exam` runs the quiz.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExamMode {
+    /// Revise answers until finishing
+    Open,
+    /// Open, plus a random order, no saving midway and an attempt report
+    Strict,
+}
+
+/// Question file used when neither the command line nor the config names one.
+const DEFAULT_QUESTIONS: &str = "questions.json";
+
+impl Cli {
+    fn layout(&self) -> LayoutOptions {
+        let default = LayoutOptions::default();
+        LayoutOptions {
+            code_max_height: self.code_height.unwrap_or(default.code_max_height),
+            options_height: self.options_height.unwrap_or(default.options_height),
+            margin: self.margin.unwrap_or(default.margin),
+        }
+    }
+
+    fn alerts(&self) -> Alerts {
+        Alerts {
+            bell: self.bell,
+            flash: self.flash,
+            notify: self.notify,
+        }
+    }
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Play a quiz on your own (the default)
+    Play(PlayArgs),
+
     /// Start a quiz server
     Serve {
-        /// Port to listen on
-        #[arg(short, long, default_value_t = DEFAULT_PORT)]
-        port: u16,
+        /// Port to listen on [default: `port` from the config file, else 8712]
+        #[arg(short, long)]
+        port: Option<u16>,
 
         /// Path to questions JSON file
+        /// [default: `questions` from the config file, else questions.json]
         #[arg(short, long)]
-        questions: PathBuf,
+        questions: Option<PathBuf>,
+
+        /// Only use questions with this tag (repeatable)
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// TLS certificate (PEM, may be a chain), to serve over wss://
+        #[arg(long, value_name = "FILE", requires = "key")]
+        cert: Option<PathBuf>,
+
+        /// TLS private key (PEM) for --cert
+        #[arg(long, value_name = "FILE", requires = "cert")]
+        key: Option<PathBuf>,
+
+        /// Most players each room takes at once; others are turned away
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        max_players: Option<u32>,
+
+        /// Give faster correct answers more points, up to double
+        #[arg(long)]
+        speed_bonus: bool,
+
+        /// Have players press Enter twice to send each answer
+        #[arg(long)]
+        confirm_answers: bool,
+
+        /// Have players say how sure they are of each answer: sure answers
+        /// score double when right and lose points when wrong
+        #[arg(long)]
+        confidence: bool,
+
+        /// Tell players whether each answer was right as soon as they send
+        /// it (study mode), instead of only in the results (exam mode)
+        #[arg(long)]
+        feedback: bool,
+
+        /// Send players every question when the quiz starts, so they keep
+        /// answering through Wi-Fi dropouts and catch up on reconnecting
+        #[arg(long)]
+        offline: bool,
+
+        /// What to do when a player picks a username someone in the room has:
+        /// `reject` it, `suffix` a number (alice-2), or `replace` a
+        /// disconnected player of that name, dropping their answers
+        #[arg(long, value_name = "POLICY", default_value = "reject")]
+        duplicate_names: UsernamePolicy,
+
+        /// Refuse usernames containing any word in this file (one per line)
+        #[arg(long, value_name = "FILE")]
+        blocklist: Option<PathBuf>,
+
+        /// Seconds to count down before the first question; 0 to start at once
+        #[arg(long, value_name = "SECS", default_value_t = 5)]
+        countdown: u32,
+
+        /// Pick the same questions on every `limit` with this seed
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+
+        /// Also serve quiz progress and metrics over HTTP on this port
+        #[arg(long, value_name = "PORT")]
+        http_port: Option<u16>,
+
+        /// Serve the HTTP API on this address instead of only this machine,
+        /// e.g. 0.0.0.0 for every interface
+        #[arg(long, value_name = "ADDR", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+        http_address: IpAddr,
+
+        /// Close connections that pick no username within this many
+        /// seconds; 0 to wait forever
+        #[arg(long, value_name = "SECS", default_value_t = 120)]
+        join_timeout: u64,
+
+        /// Give up on players disconnected for this many minutes, freeing
+        /// their username but keeping their answers; 0 to wait forever
+        #[arg(long, value_name = "MINS", default_value_t = 30)]
+        abandon_after: u64,
+
+        /// Add this many simulated players to the first room
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        bots: usize,
+
+        /// Percent of questions bots answer right, e.g. 70, or a range such
+        /// as 40-90 to give each bot its own
+        #[arg(long, value_name = "PCT", default_value = "50-90")]
+        bot_accuracy: BotAccuracy,
+
+        /// Add a JSON line to this file for every join, question sent,
+        /// answer and host command
+        #[arg(long, value_name = "FILE")]
+        transcript: Option<PathBuf>,
+
+        /// Save the rooms, players and answers to this file every few
+        /// seconds and on shutdown
+        #[arg(long, value_name = "FILE")]
+        state_file: Option<PathBuf>,
+
+        /// Restore the rooms, players and answers saved in the state file
+        #[arg(long, requires = "state_file", conflicts_with_all = ["questions", "tags"])]
+        resume: bool,
+    },
+
+    /// Join a quiz server
+    Join {
+        /// Server address, with wss:// for a server using TLS and /CODE
+        /// for a room other than the first; the port defaults to `port`
+        /// from the config file, else 8712
+        #[arg(value_name = "[wss://]HOST[:PORT][/CODE]", value_parser = parse_address)]
+        address: ServerAddress,
+
+        /// Exchange compact MessagePack messages instead of JSON
+        #[arg(long)]
+        binary: bool,
+    },
+
+    /// Play back a session recorded with `serve --transcript` in the host's
+    /// screens
+    ///
+    /// Space pauses, Left and Right step through events, + and - change the
+    /// speed, Tab switches between the lobby, analytics and leaderboard, u
+    /// shows each player in turn, r each room, l the log, and q quits.
+    Replay {
+        /// Transcript to play back
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Times faster than the session happened, from 0.25 to 64
+        #[arg(long, value_name = "X", default_value_t = 1.0)]
+        speed: f64,
+    },
+
+    /// Browse, add, change and delete the questions of a JSON or TOML file
+    Edit {
+        /// Question file to edit; created on the first save if missing
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+
+    /// Convert a question file to Moodle XML for importing into a course
+    Export {
+        /// Question file to convert; may be encrypted
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Where to write the XML [default: FILE with an .xml extension]
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
     },
 
-    /// Connect to a quiz server
+    /// Encrypt a question file so the answers can't be read from it
+    ///
+    /// The passphrase is read from RUST_QUIZ_PASSPHRASE, or typed in.
+    /// Encrypted files are played and served like plain ones, after asking
+    /// for the passphrase.
+    Encrypt {
+        /// Question file to encrypt
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Where to write the encrypted file [default: FILE with .enc appended]
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Check that a certificate saved with --certificate is genuine
+    ///
+    /// The key it was signed with is read from RUST_QUIZ_CERTIFICATE_KEY.
+    Verify {
+        /// Certificate to check
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+
+    /// Connect to a quiz server (same as `join`)
+    #[command(hide = true)]
     Connect {
         /// Server host address
         #[arg(short = 'H', long)]
@@ -41,13 +410,156 @@ enum Commands {
     },
 }
 
+/// Host and, if given, port and room code of a quiz server.
+#[derive(Clone)]
+struct ServerAddress {
+    host: String,
+    port: Option<u16>,
+    room: Option<String>,
+}
+
+/// Parse `[SCHEME://]HOST[:PORT][/CODE]`, keeping the scheme in the host.
+/// IPv6 hosts need brackets to take a port, e.g. `[::1]:9000`.
+fn parse_address(address: &str) -> Result<ServerAddress, String> {
+    let (scheme, address) = match address.split_once("://") {
+        Some((scheme @ ("ws" | "wss"), rest)) => (Some(scheme), rest),
+        Some((scheme, _)) => return Err(format!("unsupported scheme '{}', use ws or wss", scheme)),
+        None => (None, address),
+    };
+    let (address, room) = match address.split_once('/') {
+        Some((address, room)) if !room.is_empty() => (address, Some(room.to_string())),
+        Some((address, _)) => (address, None),
+        None => (address, None),
+    };
+    let (host, port) = match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            let port = port
+                .parse()
+                .map_err(|_| format!("invalid port '{}'", port))?;
+            (host, Some(port))
+        }
+        _ => (address, None),
+    };
+    if host.is_empty() {
+        return Err("missing host".to_string());
+    }
+    Ok(ServerAddress {
+        host: match scheme {
+            Some(scheme) => format!("{}://{}", scheme, host),
+            None => host.to_string(),
+        },
+        port,
+        room,
+    })
+}
+
 fn main() {
     let cli = Cli::parse();
 
+    let config = match Config::load_default() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let theme = match load_theme(cli.theme.as_deref().or(config.theme.as_deref())) {
+        Ok(theme) => theme,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let layout = cli.layout();
+    let alerts = cli.alerts();
+
+    let default_port = config.port.unwrap_or(DEFAULT_PORT);
+
     let result = match cli.command {
-        Some(Commands::Serve { port, questions }) => run_server(port, questions),
-        Some(Commands::Connect { host, port }) => run_client(host, port),
-        None => run_local(cli.questions),
+        Some(Commands::Serve {
+            port,
+            questions,
+            tags,
+            cert,
+            key,
+            max_players,
+            speed_bonus,
+            confirm_answers,
+            confidence,
+            feedback,
+            offline,
+            duplicate_names,
+            blocklist,
+            countdown,
+            seed,
+            http_port,
+            http_address,
+            join_timeout,
+            abandon_after,
+            bots,
+            bot_accuracy,
+            transcript,
+            state_file,
+            resume,
+        }) => {
+            let questions = questions
+                .or(config.questions)
+                .unwrap_or_else(|| DEFAULT_QUESTIONS.into());
+            let options = ServeOptions {
+                port: port.unwrap_or(default_port),
+                tags,
+                // clap makes --cert and --key come together
+                tls: cert.zip(key).map(|(cert, key)| TlsConfig { cert, key }),
+                resume,
+                max_players: max_players.map(|max| max as usize),
+                speed_bonus,
+                confirm_answers,
+                confidence_mode: confidence,
+                feedback,
+                offline,
+                username_policy: duplicate_names,
+                blocklist,
+                countdown,
+                seed,
+                http_port,
+                http_address,
+                join_timeout: (join_timeout > 0).then(|| Duration::from_secs(join_timeout)),
+                abandon_after: (abandon_after > 0).then(|| Duration::from_secs(abandon_after * 60)),
+                bots,
+                bot_accuracy,
+                transcript,
+                state_file,
+                ..ServeOptions::default()
+            };
+            run_server(questions, theme, options)
+        }
+        Some(Commands::Join { address, binary }) => {
+            let port = address.port.unwrap_or(default_port);
+            let options = JoinOptions {
+                room: address.room,
+                username: config.username,
+                binary,
+            };
+            run_client(address.host, port, theme, layout, alerts, options)
+        }
+        Some(Commands::Connect { host, port }) => {
+            let options = JoinOptions {
+                username: config.username,
+                ..JoinOptions::default()
+            };
+            run_client(host, port, theme, layout, alerts, options)
+        }
+        Some(Commands::Replay { file, speed }) => {
+            rust_quiz::server::replay(file, speed, theme).map_err(Into::into)
+        }
+        Some(Commands::Edit { file }) => rust_quiz::editor::run(file, theme).map_err(Into::into),
+        Some(Commands::Export { file, output }) => run_export(file, output),
+        Some(Commands::Encrypt { file, output }) => run_encrypt(file, output),
+        Some(Commands::Verify { file }) => run_verify(file),
+        Some(Commands::Play(args)) => run_local(args, &config, theme, layout, alerts),
+        None => run_local(cli.play, &config, theme, layout, alerts),
     };
 
     if let Err(e) = result {
@@ -56,29 +568,311 @@ fn main() {
     }
 }
 
-/// Run in local mode (single player, existing behavior).
-fn run_local(questions_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    use rust_quiz::Quiz;
+/// Pick the named theme, or the user's theme file.
+fn load_theme(theme: Option<&str>) -> Result<Theme, ThemeError> {
+    match theme {
+        None => Theme::load_default(),
+        Some(path) if Path::new(path).is_file() => Theme::load(path),
+        Some(name) => Theme::preset(name),
+    }
+}
+
+/// Environment variable holding the passphrase of encrypted question files.
+const PASSPHRASE_VAR: &str = "RUST_QUIZ_PASSPHRASE";
+
+/// Passphrase from `RUST_QUIZ_PASSPHRASE`, or typed in without echo.
+fn read_passphrase(prompt: &str) -> io::Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    eprint!("{}", prompt);
+    io::stderr().flush()?;
+    crossterm::terminal::enable_raw_mode()?;
+    let passphrase = read_hidden_line();
+    crossterm::terminal::disable_raw_mode()?;
+    eprintln!();
+    passphrase
+}
+
+fn read_hidden_line() -> io::Result<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+    let mut line = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(line),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Esc => return Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled")),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"));
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Passphrase for an encrypted file at `path`, or `None` if it is plain.
+///
+/// An unreadable file also gives `None`, so loading it reports the error.
+fn passphrase_for(path: &Path) -> io::Result<Option<String>> {
+    if !is_encrypted_file(path).unwrap_or(false) {
+        return Ok(None);
+    }
+    let prompt = format!("Passphrase for {}: ", path.display());
+    read_passphrase(&prompt).map(Some)
+}
+
+/// Load a local question file, asking for the passphrase if it is encrypted.
+fn load_bank(path: &Path) -> Result<QuestionBank, Box<dyn std::error::Error>> {
+    use rust_quiz::{load_bank_encrypted, load_bank_from_json, load_bank_from_toml};
+
+    let bank = match passphrase_for(path)? {
+        Some(passphrase) => load_bank_encrypted(path, &passphrase)?,
+        None if path.extension().is_some_and(|ext| ext == "toml") => load_bank_from_toml(path)?,
+        None => load_bank_from_json(path)?,
+    };
+    Ok(bank)
+}
+
+/// Write an encrypted copy of the question file.
+fn run_encrypt(file: PathBuf, output: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    if is_encrypted_file(&file)? {
+        return Err(format!("{} is encrypted already", file.display()).into());
+    }
+    // Refuse broken files
+    load_bank(&file)?;
 
-    let quiz = Quiz::from_json(&questions_path)?;
-    quiz.run()?;
+    let passphrase = read_passphrase("New passphrase: ")?;
+    if std::env::var_os(PASSPHRASE_VAR).is_none()
+        && read_passphrase("Repeat passphrase: ")? != passphrase
+    {
+        return Err("Passphrases don't match".into());
+    }
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".into());
+    }
+
+    let output = output.unwrap_or_else(|| {
+        let mut name = file.clone().into_os_string();
+        name.push(".enc");
+        name.into()
+    });
+    let content = std::fs::read(&file)?;
+    std::fs::write(&output, rust_quiz::data::encrypt(&content, &passphrase))?;
+    println!("Wrote {}", output.display());
+    Ok(())
+}
+
+/// Write the question file as Moodle XML.
+fn run_export(file: PathBuf, output: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    let bank = load_bank(&file)?;
+    let output = output.unwrap_or_else(|| file.with_extension("xml"));
+    rust_quiz::moodle::write_moodle_xml(&bank, &output)?;
+    let count = match bank.questions.len() {
+        1 => "1 question".to_string(),
+        count => format!("{} questions", count),
+    };
+    println!("Wrote {} to {}", count, output.display());
+    Ok(())
+}
+
+/// Key certificates are signed with, from `RUST_QUIZ_CERTIFICATE_KEY`.
+fn certificate_key() -> Result<String, String> {
+    use rust_quiz::certificate::KEY_VAR;
+
+    match std::env::var(KEY_VAR) {
+        Ok(key) if !key.is_empty() => Ok(key),
+        _ => Err(format!("Set {} to the key certificates are signed with", KEY_VAR)),
+    }
+}
+
+/// Check a certificate's signature and say who it was issued to.
+fn run_verify(file: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let key = certificate_key()?;
+    let certificate = rust_quiz::certificate::Certificate::load(&file)?;
+    if !certificate.verify(key.as_bytes()) {
+        return Err(format!("{} is not a genuine certificate", file.display()).into());
+    }
+    println!(
+        "Genuine: {} passed {} with {}/{} on {}",
+        certificate.name,
+        certificate.quiz,
+        certificate.score,
+        certificate.max_score,
+        certificate.date
+    );
+    Ok(())
+}
+
+/// Have `quiz` save a certificate on passing, if asked to.
+fn with_certificate(
+    quiz: rust_quiz::Quiz,
+    args: &PlayArgs,
+    config: &Config,
+) -> Result<rust_quiz::Quiz, Box<dyn std::error::Error>> {
+    let Some(path) = &args.certificate else {
+        return Ok(quiz);
+    };
+    let name = args
+        .name
+        .as_deref()
+        .or(config.username.as_deref())
+        .ok_or("--certificate needs --name, or a username in the config file")?;
+    Ok(quiz.certificate(path, name, certificate_key()?.as_bytes()))
+}
+
+/// Run in local mode (single player).
+fn run_local(
+    args: PlayArgs,
+    config: &Config,
+    theme: Theme,
+    layout: LayoutOptions,
+    alerts: Alerts,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use rust_quiz::{History, Playlist, Quiz, SavedSession};
+
+    let session_file = SavedSession::default_path();
+    if args.resume {
+        let path = session_file.ok_or("No data directory to load the session from")?;
+        let mut quiz = Quiz::resume(path)?
+            .theme(theme)
+            .layout(layout)
+            .alerts(alerts)
+            .keymap(config.keys.clone());
+        if !args.no_history
+            && let Some(history) = History::open_default()
+        {
+            quiz = quiz.history(history)?;
+        }
+        with_certificate(quiz, &args, config)?.run()?;
+        return Ok(());
+    }
+
+    let questions = args.questions(config);
+    let offered = args.file.is_none() && args.questions.is_none() && !config.quizzes.is_empty();
+    let mut encrypted = false;
+    let quiz = match questions.to_str() {
+        _ if let Some(playlist) = &args.playlist => Quiz::from_playlist(Playlist::load(playlist)?),
+        _ if offered => Quiz::from_files(&config.quizzes)?,
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+            Quiz::from_url(url)?
+        }
+        _ if questions.is_dir() => Quiz::from_dir(questions)?,
+        _ if let Some(passphrase) = passphrase_for(questions)? => {
+            encrypted = true;
+            Quiz::from_encrypted(questions, &passphrase)?
+        }
+        _ if questions.extension().is_some_and(|ext| ext == "toml") => {
+            Quiz::from_toml(questions)?
+        }
+        _ => Quiz::from_json(questions)?,
+    };
+    // Before anything changes the question list, so the file's pools use it
+    let quiz = match args.seed {
+        Some(seed) => quiz.seed(seed),
+        None => quiz,
+    };
+    let mut quiz = quiz
+        .filter_tags(&args.tags)?
+        .theme(theme)
+        .layout(layout)
+        .alerts(alerts)
+        .keymap(config.keys.clone());
+    if !args.no_history
+        && let Some(history) = History::open_default()
+    {
+        quiz = quiz.history(history)?;
+    }
+    if let Some(limit) = args.limit
+        && args.adaptive
+    {
+        quiz = quiz.sample_adaptive(limit.get());
+    } else if let Some(limit) = args.limit {
+        let sampling = if args.by_tag {
+            Sampling::ByTag
+        } else {
+            Sampling::Uniform
+        };
+        quiz = quiz.sample(limit.get(), sampling);
+    }
+    if args.shuffle {
+        quiz = quiz.shuffle();
+    }
+    let time_limit = args.time_limit.map(|secs| Duration::from_secs(secs.get()));
+    match (args.exam, time_limit) {
+        (Some(ExamMode::Strict), Some(time_limit)) => quiz = quiz.strict_exam(time_limit),
+        (exam, time_limit) => {
+            if let Some(time_limit) = time_limit {
+                quiz = quiz.time_limit(time_limit);
+            }
+            if exam.is_some() {
+                quiz = quiz.exam_mode();
+            }
+        }
+    }
+    if args.feedback {
+        quiz = quiz.instant_feedback();
+    }
+    if args.hint_penalty > 0 {
+        quiz = quiz.hint_penalty(args.hint_penalty);
+    }
+    if args.flashcards {
+        quiz = quiz.flashcards();
+    }
+    if args.confidence {
+        quiz = quiz.confidence_mode();
+    }
+    if args.watch {
+        quiz = quiz.watch();
+    }
+    // A saved session would hold the questions in plain text
+    if let Some(path) = session_file
+        && !encrypted
+    {
+        quiz = quiz.session_file(path);
+    }
+    with_certificate(quiz, &args, config)?.run()?;
     Ok(())
 }
 
 /// Run as a server host.
-fn run_server(port: u16, questions_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+fn run_server(
+    questions_path: PathBuf,
+    theme: Theme,
+    mut options: ServeOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
     use rust_quiz::server;
 
+    // The saved questions are used when resuming
+    if !options.resume {
+        options.passphrase = passphrase_for(&questions_path)?;
+    }
     let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(server::run(port, questions_path))?;
+    rt.block_on(server::run(questions_path, theme, options))?;
     Ok(())
 }
 
 /// Run as a client connecting to a server.
-fn run_client(host: String, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+fn run_client(
+    host: String,
+    port: u16,
+    theme: Theme,
+    layout: LayoutOptions,
+    alerts: Alerts,
+    options: JoinOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
     use rust_quiz::client;
 
     let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(client::run(host, port))?;
+    rt.block_on(client::run(host, port, theme, layout, alerts, options))?;
     Ok(())
 }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// How sure a player is of an answer, in confidence mode.
+///
+/// Being sure doubles the points for a correct answer but costs the
+/// question's points when wrong; being unsure scores as usual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    Sure,
+    Unsure,
+}
+
+impl Confidence {
+    /// Points won by a correct answer worth `points`.
+    pub fn reward(self, points: usize) -> usize {
+        match self {
+            Confidence::Sure => points * 2,
+            Confidence::Unsure => points,
+        }
+    }
+
+    /// Points lost by a wrong answer to a question worth `points`.
+    pub fn penalty(self, points: usize) -> usize {
+        match self {
+            Confidence::Sure => points,
+            Confidence::Unsure => 0,
+        }
+    }
+}
+
+/// How often answers given with each confidence were right.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub sure: usize,
+    pub sure_correct: usize,
+    pub unsure: usize,
+    pub unsure_correct: usize,
+}
+
+impl Calibration {
+    /// Count answers given as `(confidence, is_correct)`.
+    pub fn new(answers: impl IntoIterator<Item = (Confidence, bool)>) -> Self {
+        let mut calibration = Self::default();
+        for (confidence, is_correct) in answers {
+            let (count, correct) = match confidence {
+                Confidence::Sure => (&mut calibration.sure, &mut calibration.sure_correct),
+                Confidence::Unsure => (&mut calibration.unsure, &mut calibration.unsure_correct),
+            };
+            *count += 1;
+            *correct += usize::from(is_correct);
+        }
+        calibration
+    }
+
+    /// One line for the results, e.g.
+    /// `Sure: 4 answers, 75% right · Unsure: 2 answers, 50% right`.
+    pub fn summary(&self) -> String {
+        let part = |label, count: usize, correct: usize| {
+            if count == 0 {
+                format!("{}: no answers", label)
+            } else {
+                let percent = correct as f64 / count as f64 * 100.0;
+                let answers = if count == 1 { "answer" } else { "answers" };
+                format!("{}: {} {}, {:.0}% right", label, count, answers, percent)
+            }
+        };
+        format!(
+            "{} · {}",
+            part("Sure", self.sure, self.sure_correct),
+            part("Unsure", self.unsure, self.unsure_correct)
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Optional details about a question file as a whole.
+///
+/// In JSON the file is then an object with `meta` and `questions` fields
+/// instead of a bare list of questions; in TOML it is a `[meta]` table next
+/// to the `[[questions]]`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuizMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Lowest score that passes, as a percentage of the maximum score.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_threshold: Option<f64>,
+    /// Lowest score that passes, in points.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_score: Option<usize>,
+    /// Default time budget for the whole quiz, in seconds.
+    #[serde(rename = "time_limit", skip_serializing_if = "Option::is_none")]
+    pub time_limit_secs: Option<u64>,
+    /// Labels given to scores, e.g. letter grades or "Pass" and "Fail".
+    #[serde(skip_serializing_if = "Grades::is_empty")]
+    pub grades: Grades,
+    /// Rules for assembling each quiz from part of the bank, e.g. 5 easy,
+    /// 10 medium and 5 hard questions. Without pools every question is used.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pools: Vec<Pool>,
+}
+
+/// Pick `count` random questions tagged `tag`, e.g.
+/// `{"tag": "hard", "count": 5}`, or `count` from each tag in the bank when
+/// there is no `tag`.
+///
+/// A question is picked at most once, even if it has several tags.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pool {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Every matching question is picked if there are fewer.
+    pub count: usize,
+}
+
+/// A label earned by scoring at least `min` percent of the maximum score.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Grade {
+    pub min: f64,
+    pub label: String,
+}
+
+/// How scores are graded, in any order, e.g.
+/// `[{"min": 70, "label": "Pass"}, {"min": 0, "label": "Fail"}]`.
+///
+/// Scores are colored by their grade, best in the theme's `correct` color
+/// and worst in `incorrect`. Without grades they are colored in bands at
+/// 90%, 70% and 50%, and get no label.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Grades(pub Vec<Grade>);
+
+/// Where scores are colored when there are no grades.
+const DEFAULT_BANDS: [f64; 3] = [90.0, 70.0, 50.0];
+
+impl Grades {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The grade a score of `percentage` earns: the one with the highest
+    /// `min` it reaches.
+    pub fn grade(&self, percentage: f64) -> Option<&Grade> {
+        self.0
+            .iter()
+            .filter(|grade| percentage >= grade.min)
+            .max_by(|a, b| a.min.total_cmp(&b.min))
+    }
+
+    /// How a score of `percentage` ranks, from 0 for the best grade to 3
+    /// for the worst or for no grade at all, as [`Theme::grade`] colors it.
+    ///
+    /// [`Theme::grade`]: crate::theme::Theme::grade
+    pub fn band(&self, percentage: f64) -> usize {
+        if self.is_empty() {
+            return DEFAULT_BANDS
+                .iter()
+                .position(|&min| percentage >= min)
+                .unwrap_or(DEFAULT_BANDS.len());
+        }
+        let Some(grade) = self.grade(percentage) else {
+            return 3;
+        };
+        let better = self.0.iter().filter(|other| other.min > grade.min).count();
+        if better > 0 && better + 1 == self.0.len() { 3 } else { better.min(2) }
+    }
+}
+
+impl QuizMeta {
+    /// The default time budget, if the file sets one.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit_secs.map(Duration::from_secs)
+    }
+
+    /// Whether `score` out of `max_score` passes, if the file sets a
+    /// `pass_threshold` or `pass_score`. With both, it has to reach both.
+    pub fn passed(&self, score: usize, max_score: usize) -> Option<bool> {
+        if self.pass_threshold.is_none() && self.pass_score.is_none() {
+            return None;
+        }
+        let percentage = if max_score > 0 {
+            score as f64 / max_score as f64 * 100.0
+        } else {
+            0.0
+        };
+        Some(
+            self.pass_threshold.is_none_or(|threshold| percentage >= threshold)
+                && self.pass_score.is_none_or(|min| score >= min),
+        )
+    }
+
+    /// "by author · v1.0", or `None` if neither is set.
+    pub fn byline(&self) -> Option<String> {
+        let author = self.author.as_ref().map(|author| format!("by {}", author));
+        let version = self.version.as_ref().map(|version| format!("v{}", version));
+        match (author, version) {
+            (Some(author), Some(version)) => Some(format!("{}  ·  {}", author, version)),
+            (author, version) => author.or(version),
+        }
+    }
+}
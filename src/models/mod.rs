@@ -1,5 +1,11 @@
+mod confidence;
+mod meta;
 mod question;
 mod state;
 
-pub use question::Question;
+pub use confidence::{Calibration, Confidence};
+pub use meta::{Grade, Grades, Pool, QuizMeta};
+pub use question::{
+    format_answer, max_score, normalize_answer, CorrectAnswer, Question, OPTION_LABELS,
+};
 pub use state::AppState;
@@ -1,9 +1,211 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+/// Labels used when displaying option indices.
+pub const OPTION_LABELS: [char; 4] = ['A', 'B', 'C', 'D'];
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Question {
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
+    /// Language of `code`, e.g. `python` or `toml`, picking how it is
+    /// highlighted and labelling it. Rust when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_language: Option<String>,
     pub options: [String; 4],
-    pub correct_answer: usize,
+    pub correct_answer: CorrectAnswer,
+    /// Categories used for filtering and per-tag score breakdowns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Points awarded for a correct answer.
+    #[serde(default = "default_points", skip_serializing_if = "is_default_points")]
+    pub points: usize,
+    /// Optional explanation shown after answering in instant feedback mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Optional hint the player can reveal while answering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Optional PNG picture or diagram shown with the question, drawn with
+    /// terminal graphics where the terminal supports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+fn default_points() -> usize {
+    1
+}
+
+fn is_default_points(points: &usize) -> bool {
+    *points == default_points()
+}
+
+/// The correct answer of a question.
+///
+/// Deserializes from either a single index (`"correct_answer": 1`) or a list
+/// of indices (`"correct_answer": [0, 2]`). A list makes the question a
+/// checkbox question where every listed option must be selected.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CorrectAnswer {
+    /// Exactly one option is correct.
+    Single(usize),
+    /// Several options are correct and all must be selected.
+    Multiple(Vec<usize>),
+}
+
+impl CorrectAnswer {
+    /// Check if this is a checkbox (multiple-answer) question.
+    pub fn is_multiple(&self) -> bool {
+        matches!(self, Self::Multiple(_))
+    }
+
+    /// Get the correct option indices, sorted and deduplicated.
+    pub fn indices(&self) -> Vec<usize> {
+        match self {
+            Self::Single(index) => vec![*index],
+            Self::Multiple(indices) => normalize_answer(indices.clone()),
+        }
+    }
+
+    /// Check if a submitted set of option indices is exactly correct.
+    pub fn matches(&self, answer: &[usize]) -> bool {
+        self.indices() == normalize_answer(answer.to_vec())
+    }
+}
+
+impl From<usize> for CorrectAnswer {
+    fn from(index: usize) -> Self {
+        Self::Single(index)
+    }
+}
+
+impl From<Vec<usize>> for CorrectAnswer {
+    fn from(indices: Vec<usize>) -> Self {
+        Self::Multiple(indices)
+    }
+}
+
+impl Question {
+    /// Create a question worth one point, without code, tags, explanation,
+    /// hint or image.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_quiz::Question;
+    ///
+    /// let options = ["let", "static", "const", "mut"];
+    /// let question = Question::new("Which keyword declares a constant?", options, 2)
+    ///     .with_tags(["variables"])
+    ///     .with_explanation("`const` values are inlined wherever they are used.");
+    /// assert!(question.is_correct(&[2]));
+    /// ```
+    pub fn new<S: Into<String>>(
+        text: impl Into<String>,
+        options: [S; 4],
+        correct_answer: impl Into<CorrectAnswer>,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            code: None,
+            code_language: None,
+            options: options.map(Into::into),
+            correct_answer: correct_answer.into(),
+            tags: Vec::new(),
+            points: default_points(),
+            explanation: None,
+            hint: None,
+            image: None,
+        }
+    }
+
+    /// Set the code snippet shown with the question.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Set the language of the code snippet, e.g. `python`.
+    pub fn with_code_language(mut self, language: impl Into<String>) -> Self {
+        self.code_language = Some(language.into());
+        self
+    }
+
+    /// Set the question's tags.
+    pub fn with_tags<S: Into<String>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the points awarded for a correct answer.
+    pub fn with_points(mut self, points: usize) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Set the explanation shown after answering.
+    pub fn with_explanation(mut self, explanation: impl Into<String>) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    /// Set the hint the player can reveal.
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Set the image shown with the question.
+    pub fn with_image(mut self, path: impl Into<String>) -> Self {
+        self.image = Some(path.into());
+        self
+    }
+
+    /// Check if this is a checkbox (multiple-answer) question.
+    pub fn is_multiple(&self) -> bool {
+        self.correct_answer.is_multiple()
+    }
+
+    /// Check if a submitted set of option indices is exactly correct.
+    pub fn is_correct(&self, answer: &[usize]) -> bool {
+        self.correct_answer.matches(answer)
+    }
+
+    /// Points earned for a submitted answer (0 if wrong or unanswered).
+    pub fn points_for(&self, answer: Option<&[usize]>) -> usize {
+        if answer.is_some_and(|answer| self.is_correct(answer)) {
+            self.points
+        } else {
+            0
+        }
+    }
+
+    /// Check if the question has any of the given tags (case-insensitive).
+    pub fn has_any_tag<S: AsRef<str>>(&self, tags: &[S]) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tags.iter().any(|wanted| tag.eq_ignore_ascii_case(wanted.as_ref())))
+    }
+}
+
+/// Maximum achievable score for a set of questions.
+pub fn max_score(questions: &[Question]) -> usize {
+    questions.iter().map(|question| question.points).sum()
+}
+
+/// Sort and deduplicate a set of selected option indices.
+pub fn normalize_answer(mut answer: Vec<usize>) -> Vec<usize> {
+    answer.sort_unstable();
+    answer.dedup();
+    answer
+}
+
+/// Format a set of option indices as labels, e.g. `A, C`.
+pub fn format_answer(answer: &[usize]) -> String {
+    let labels: Vec<String> = answer
+        .iter()
+        .map(|&index| OPTION_LABELS.get(index).copied().unwrap_or('?').to_string())
+        .collect();
+    labels.join(", ")
 }
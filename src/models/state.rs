@@ -2,6 +2,20 @@
 pub enum AppState {
     #[default]
     Welcome,
+    /// Listing past quiz runs.
+    History,
+    /// Aggregated statistics over past runs.
+    Stats,
     Quiz,
+    /// Showing whether the last answer was correct (instant feedback mode).
+    Feedback,
     Result,
+    /// Congratulating a player who passed, before the results.
+    Passed,
+    /// Reviewing a single question from the results.
+    Review,
+    /// Asking whether to save progress before quitting mid-quiz.
+    QuitPrompt,
+    /// Studying the questions as flashcards, and the summary once done.
+    Flashcards,
 }
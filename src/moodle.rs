@@ -0,0 +1,227 @@
+//! Exporting question banks as Moodle XML.
+//!
+//! Every question becomes a `multichoice` question, so a quiz tried out in
+//! the terminal can be imported into a Moodle course as it is. Checkbox
+//! questions split the grade between their correct options and take it away
+//! again for wrong ones, as Moodle's own multiple-answer questions do.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::data::QuestionBank;
+use crate::engine::QuizEngine;
+use crate::models::{format_answer, Question, QuizMeta};
+use crate::report::escape_html;
+use crate::text;
+
+/// Longest question name, in columns. Moodle shows it in the question bank
+/// list, so the start of the question text is enough.
+const NAME_WIDTH: usize = 60;
+
+/// Render a question bank as a Moodle XML document.
+///
+/// A bank with a title puts its questions into a category of that name.
+pub fn moodle_xml(bank: &QuestionBank) -> String {
+    let mut xml = header(&bank.meta);
+    for question in &bank.questions {
+        push_question(&mut xml, question);
+    }
+    xml.push_str("</quiz>\n");
+    xml
+}
+
+/// Render the questions of a finished quiz as a Moodle XML document, with
+/// the player's results in XML comments.
+///
+/// Moodle can't import attempts, so the score and each given answer are
+/// kept as comments next to the questions; the file still imports like the
+/// one from [`moodle_xml`].
+pub fn moodle_xml_with_results(engine: &QuizEngine, meta: &QuizMeta) -> String {
+    let score = engine.calculate_score();
+    let total = engine.max_score();
+    let percentage = if total > 0 {
+        score as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    let mut xml = header(meta);
+    let _ = writeln!(
+        xml,
+        "  <!-- Results: {} / {} ({:.0}%), {} -->",
+        score,
+        total,
+        percentage,
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    );
+    for (index, question) in engine.questions().iter().enumerate() {
+        let answer = match engine.answers()[index].as_deref() {
+            Some(answer) if question.is_correct(answer) => {
+                format!("{} (correct)", format_answer(answer))
+            }
+            Some(answer) => format!("{} (incorrect)", format_answer(answer)),
+            None => "none".to_string(),
+        };
+        let _ = writeln!(
+            xml,
+            "  <!-- Answered: {}, {} / {} points -->",
+            answer,
+            engine.question_score(index),
+            question.points
+        );
+        push_question(&mut xml, question);
+    }
+    xml.push_str("</quiz>\n");
+    xml
+}
+
+/// Write [`moodle_xml`] to a file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::data::load_bank_from_json;
+///
+/// let bank = load_bank_from_json("questions.json")?;
+/// rust_quiz::moodle::write_moodle_xml(&bank, "questions.xml")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_moodle_xml<P: AsRef<Path>>(bank: &QuestionBank, path: P) -> io::Result<()> {
+    fs::write(path, moodle_xml(bank))
+}
+
+fn header(meta: &QuizMeta) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<quiz>\n");
+    if let Some(title) = &meta.title {
+        let _ = writeln!(
+            xml,
+            "  <question type=\"category\">\n    \
+             <category><text>$course$/top/{}</text></category>\n  </question>",
+            escape_html(&title.replace('/', "//"))
+        );
+    }
+    xml
+}
+
+fn push_question(xml: &mut String, question: &Question) {
+    let correct = question.correct_answer.indices();
+    let wrong = question.options.len() - correct.len();
+
+    xml.push_str("  <question type=\"multichoice\">\n");
+    let _ = writeln!(
+        xml,
+        "    <name><text>{}</text></name>",
+        escape_html(&text::truncate(&question.text, NAME_WIDTH))
+    );
+    let mut text = format!("<p>{}</p>", escape_html(&question.text));
+    if let Some(code) = &question.code {
+        let _ = write!(text, "<pre><code>{}</code></pre>", escape_html(code));
+    }
+    push_html(xml, "questiontext", &text);
+    if let Some(explanation) = &question.explanation {
+        push_html(xml, "generalfeedback", &format!("<p>{}</p>", escape_html(explanation)));
+    }
+    let _ = writeln!(xml, "    <defaultgrade>{}</defaultgrade>", question.points);
+    let _ = writeln!(xml, "    <single>{}</single>", !question.is_multiple());
+    // Options may refer to each other by letter, e.g. "Both A and B"
+    xml.push_str("    <shuffleanswers>false</shuffleanswers>\n");
+    xml.push_str("    <answernumbering>ABCD</answernumbering>\n");
+
+    for (index, option) in question.options.iter().enumerate() {
+        let fraction = if correct.contains(&index) {
+            100.0 / correct.len() as f64
+        } else if question.is_multiple() {
+            -100.0 / wrong.max(1) as f64
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            xml,
+            "    <answer fraction=\"{}\" format=\"html\">\n      \
+             <text><![CDATA[<p>{}</p>]]></text>\n    </answer>",
+            format_fraction(fraction),
+            escape_html(option)
+        );
+    }
+
+    if let Some(hint) = &question.hint {
+        push_html(xml, "hint", &format!("<p>{}</p>", escape_html(hint)));
+    }
+    if !question.tags.is_empty() {
+        xml.push_str("    <tags>\n");
+        for tag in &question.tags {
+            let _ = writeln!(xml, "      <tag><text>{}</text></tag>", escape_html(tag));
+        }
+        xml.push_str("    </tags>\n");
+    }
+    xml.push_str("  </question>\n");
+}
+
+/// Add an element holding HTML. The HTML is escaped already, so it can't
+/// contain the `]]>` that would end the CDATA section.
+fn push_html(xml: &mut String, element: &str, html: &str) {
+    let _ = writeln!(
+        xml,
+        "    <{element} format=\"html\">\n      <text><![CDATA[{html}]]></text>\n    </{element}>"
+    );
+}
+
+/// Moodle only accepts grade fractions from a fixed list, such as
+/// 33.33333; one to four correct options always give one of them.
+fn format_fraction(fraction: f64) -> String {
+    let formatted = format!("{:.5}", fraction);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CorrectAnswer;
+
+    #[test]
+    fn test_moodle_xml() {
+        let question = Question {
+            text: "Which types are Copy?".to_string(),
+            code: Some("let s: &str = \"]]>\";".to_string()),
+            code_language: None,
+            options: ["i32", "String", "&str", "Vec<u8>"].map(String::from),
+            correct_answer: CorrectAnswer::Multiple(vec![0, 2]),
+            tags: vec!["traits".to_string()],
+            points: 2,
+            explanation: None,
+            hint: None,
+            image: None,
+        };
+        let bank = QuestionBank {
+            meta: QuizMeta {
+                title: Some("Rust & Traits".to_string()),
+                ..QuizMeta::default()
+            },
+            questions: vec![question.clone()],
+        };
+
+        let xml = moodle_xml(&bank);
+        assert!(xml.contains("<category><text>$course$/top/Rust &amp; Traits</text>"));
+        assert!(xml.contains("<single>false</single>"));
+        assert!(xml.contains("<defaultgrade>2</defaultgrade>"));
+        assert!(xml.contains("<answer fraction=\"50\" format=\"html\">"));
+        assert!(xml.contains("<answer fraction=\"-50\" format=\"html\">"));
+        assert!(xml.contains("<p>Vec&lt;u8&gt;</p>"));
+        assert!(xml.contains("&quot;]]&gt;&quot;"));
+        assert!(xml.contains("<tag><text>traits</text></tag>"));
+        assert_eq!(format_fraction(100.0 / 3.0), "33.33333");
+
+        let mut engine = QuizEngine::new(vec![question]);
+        engine.start();
+        engine.answer(vec![0]);
+        let xml = moodle_xml_with_results(&engine, &QuizMeta::default());
+        assert!(xml.contains("<!-- Results: 0 / 2 (0%)"));
+        assert!(xml.contains("<!-- Answered: A (incorrect), 0 / 2 points -->"));
+        assert!(!xml.contains("type=\"category\""));
+    }
+}
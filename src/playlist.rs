@@ -0,0 +1,215 @@
+//! Quizzes run back to back, with a report of every score at the end.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::catalog::QuizEntry;
+use crate::data::LoadError;
+
+/// Question files played one after the other, and how each went.
+///
+/// Read from TOML, with the files relative to the playlist:
+///
+/// ```toml
+/// title = "Rust workshop"
+/// quizzes = ["ownership.json", "traits.toml", "async.json"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playlist {
+    title: Option<String>,
+    quizzes: Vec<QuizEntry>,
+    /// Index of the quiz being played, or the number of quizzes once every
+    /// one was.
+    current: usize,
+    /// Score and maximum score of each quiz's first finished run.
+    scores: Vec<Option<(usize, usize)>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPlaylist {
+    title: Option<String>,
+    quizzes: Vec<PathBuf>,
+}
+
+/// Error loading a playlist.
+#[derive(Debug)]
+pub enum PlaylistError {
+    /// The playlist could not be read.
+    Io(io::Error),
+    /// The playlist is not valid TOML.
+    Parse(toml::de::Error),
+    /// The playlist lists no quizzes.
+    Empty,
+    /// A quiz in the playlist could not be loaded.
+    Quiz { path: PathBuf, error: Box<LoadError> },
+}
+
+impl std::fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlaylistError::Io(e) => write!(f, "Failed to read playlist: {}", e),
+            PlaylistError::Parse(e) => write!(f, "Failed to parse playlist: {}", e),
+            PlaylistError::Empty => write!(f, "Playlist must list at least one quiz"),
+            PlaylistError::Quiz { path, error } => write!(f, "In {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for PlaylistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlaylistError::Io(e) => Some(e),
+            PlaylistError::Parse(e) => Some(e),
+            PlaylistError::Quiz { error, .. } => Some(error.as_ref()),
+            PlaylistError::Empty => None,
+        }
+    }
+}
+
+impl From<io::Error> for PlaylistError {
+    fn from(err: io::Error) -> Self {
+        PlaylistError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for PlaylistError {
+    fn from(err: toml::de::Error) -> Self {
+        PlaylistError::Parse(err)
+    }
+}
+
+impl Playlist {
+    /// A playlist of `quizzes`, none played yet.
+    pub fn new(title: Option<String>, quizzes: Vec<QuizEntry>) -> Self {
+        Self {
+            title,
+            scores: vec![None; quizzes.len()],
+            quizzes,
+            current: 0,
+        }
+    }
+
+    /// Load a playlist, reading the title and question count of each quiz.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PlaylistError> {
+        let path = path.as_ref();
+        let raw: RawPlaylist = toml::from_str(&fs::read_to_string(path)?)?;
+        if raw.quizzes.is_empty() {
+            return Err(PlaylistError::Empty);
+        }
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let quizzes = raw
+            .quizzes
+            .iter()
+            .map(|quiz| {
+                let path = dir.join(quiz);
+                QuizEntry::read(&path).map_err(|error| PlaylistError::Quiz {
+                    path,
+                    error: Box::new(error),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(raw.title, quizzes))
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn quizzes(&self) -> &[QuizEntry] {
+        &self.quizzes
+    }
+
+    /// Index of the quiz being played, or the number of quizzes once every
+    /// one was.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The quiz being played, until every one was.
+    pub fn current_quiz(&self) -> Option<&QuizEntry> {
+        self.quizzes.get(self.current)
+    }
+
+    /// Whether the quiz being played is the last one.
+    pub fn is_last(&self) -> bool {
+        self.current + 1 == self.quizzes.len()
+    }
+
+    /// Whether every quiz was played.
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.quizzes.len()
+    }
+
+    /// Score and maximum score of each quiz, once played.
+    pub fn scores(&self) -> &[Option<(usize, usize)>] {
+        &self.scores
+    }
+
+    /// Total score and maximum score of the quizzes played so far.
+    pub fn total(&self) -> (usize, usize) {
+        self.scores
+            .iter()
+            .flatten()
+            .fold((0, 0), |(score, max), (s, m)| (score + s, max + m))
+    }
+
+    /// Record the score of the quiz being played. Only its first finished
+    /// run counts, not retakes.
+    pub fn record(&mut self, score: usize, max_score: usize) {
+        if let Some(slot @ None) = self.scores.get_mut(self.current) {
+            *slot = Some((score, max_score));
+        }
+    }
+
+    /// Move on to the next quiz.
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1).min(self.quizzes.len());
+    }
+
+    /// Start over from the first quiz, forgetting the scores.
+    pub fn restart(&mut self) {
+        *self = Self::new(self.title.take(), std::mem::take(&mut self.quizzes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_play_through() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-playlist-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let question = r#"{"text": "Q", "options": ["a", "b", "c", "d"], "correct_answer": 0}"#;
+        fs::write(dir.join("one.json"), format!("[{}]", question)).unwrap();
+        fs::write(dir.join("two.json"), format!("[{}]", question)).unwrap();
+        let path = dir.join("workshop.toml");
+        fs::write(&path, "title = \"Workshop\"\nquizzes = [\"one.json\", \"two.json\"]").unwrap();
+        let mut playlist = Playlist::load(&path).unwrap();
+        fs::write(&path, "quizzes = [\"one.json\", \"missing.json\"]").unwrap();
+        let missing = Playlist::load(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(missing, Err(PlaylistError::Quiz { .. })));
+        assert_eq!(playlist.title(), Some("Workshop"));
+        assert_eq!(playlist.current_quiz().map(|quiz| quiz.title.as_str()), Some("one"));
+
+        playlist.record(1, 1);
+        playlist.record(0, 1);
+        playlist.advance();
+        assert!(playlist.is_last());
+        playlist.record(0, 1);
+        playlist.advance();
+        assert!(playlist.is_finished());
+        assert_eq!(playlist.scores(), &[Some((1, 1)), Some((0, 1))]);
+        assert_eq!(playlist.total(), (1, 2));
+
+        playlist.restart();
+        assert_eq!(playlist.current(), 0);
+        assert_eq!(playlist.total(), (0, 0));
+    }
+}
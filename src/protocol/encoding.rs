@@ -0,0 +1,99 @@
+//! Encoding messages as WebSocket frames.
+//!
+//! Messages are JSON text frames unless the client offers the
+//! [`MSGPACK_PROTOCOL`] subprotocol when connecting and the server accepts
+//! it, after which both sides send MessagePack binary frames. Either side
+//! reads both kinds of frame, whatever was agreed.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio_tungstenite::tungstenite::Message;
+
+/// WebSocket subprotocol a client offers to get MessagePack frames.
+pub const MSGPACK_PROTOCOL: &str = "rust-quiz.msgpack";
+
+/// How messages are encoded on the wire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// JSON text frames, readable in any WebSocket tool.
+    #[default]
+    Json,
+    /// MessagePack binary frames, smaller and quicker to parse.
+    MessagePack,
+}
+
+impl Encoding {
+    /// The encoding to use given the `Sec-WebSocket-Protocol` header of a
+    /// handshake, e.g. `rust-quiz.msgpack, chat`.
+    pub fn negotiate(protocols: Option<&str>) -> Self {
+        let offered = protocols
+            .into_iter()
+            .flat_map(|protocols| protocols.split(','))
+            .any(|protocol| protocol.trim() == MSGPACK_PROTOCOL);
+        if offered { Encoding::MessagePack } else { Encoding::Json }
+    }
+
+    /// Encode a message as a frame.
+    pub fn encode<T: Serialize>(self, msg: &T) -> Message {
+        match self {
+            Encoding::Json => Message::Text(serde_json::to_string(msg).unwrap().into()),
+            // Named fields, so `#[serde(default)]` works as it does for JSON
+            Encoding::MessagePack => Message::Binary(rmp_serde::to_vec_named(msg).unwrap().into()),
+        }
+    }
+}
+
+/// Decode a text frame as JSON or a binary frame as MessagePack.
+///
+/// Returns `None` for other frames and for frames that don't hold a message.
+pub fn decode<T: DeserializeOwned>(frame: &Message) -> Option<T> {
+    match frame {
+        Message::Text(text) => serde_json::from_str(text).ok(),
+        Message::Binary(bytes) => rmp_serde::from_slice(bytes).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Confidence, Grades};
+    use crate::protocol::{AnswerResult, ServerMessage};
+
+    #[test]
+    fn test_round_trip() {
+        let msg = ServerMessage::QuizResults {
+            score: 2,
+            total: 3,
+            seconds: Some(4.5),
+            answers: vec![AnswerResult {
+                question_index: 0,
+                question_text: "Pick b".to_string(),
+                your_answer: vec![1],
+                correct_answer: vec![1],
+                is_correct: true,
+                options: ["a", "b", "c", "d"].map(String::from),
+                seconds: None,
+                confidence: Some(Confidence::Sure),
+            }],
+            leaderboard: Vec::new(),
+            grades: Grades::default(),
+        };
+
+        let json = Encoding::Json.encode(&msg);
+        let binary = Encoding::MessagePack.encode(&msg);
+        assert!(binary.is_binary() && binary.len() < json.len());
+        for frame in [json, binary] {
+            let Some(ServerMessage::QuizResults { answers, seconds, .. }) = decode(&frame) else {
+                panic!("{:?} did not decode", frame);
+            };
+            assert_eq!(answers[0].question_text, "Pick b");
+            assert_eq!(seconds, Some(4.5));
+        }
+        assert!(decode::<ServerMessage>(&Message::Binary(vec![0xc1].into())).is_none());
+
+        assert_eq!(Encoding::negotiate(None), Encoding::Json);
+        let offered = format!("chat, {}", MSGPACK_PROTOCOL);
+        assert_eq!(Encoding::negotiate(Some(&offered)), Encoding::MessagePack);
+    }
+}
@@ -1,9 +1,12 @@
 //! Protocol messages for client-server communication.
 //!
-//! All messages are serialized as JSON over WebSocket.
+//! Messages are sent over WebSocket as JSON, or as MessagePack when the
+//! client asks for it (see [`Encoding`](super::Encoding)).
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::{Confidence, Grades, Question};
+
 /// Messages sent from client to server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -12,10 +15,36 @@ pub enum ClientMessage {
     Join { username: String },
 
     /// Client submits an answer for the current question.
+    ///
+    /// `answers` holds a single index for regular questions and every ticked
+    /// index for checkbox questions.
     SubmitAnswer {
         question_index: usize,
-        answer: usize,
+        answers: Vec<usize>,
+        /// How sure the player is, when the host asks for it.
+        #[serde(default)]
+        confidence: Option<Confidence>,
     },
+
+    /// Client sends a chat message to the room.
+    Chat { text: String },
+
+    /// Client sends every answer given while answering offline, oldest
+    /// first, after reconnecting; ones the server already has are skipped.
+    SyncAnswers { answers: Vec<SubmittedAnswer> },
+}
+
+/// An answer given while answering offline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmittedAnswer {
+    pub question_index: usize,
+    pub answers: Vec<usize>,
+    /// How sure the player is, when the host asks for it.
+    #[serde(default)]
+    pub confidence: Option<Confidence>,
+    /// How long the question was on screen before the player answered.
+    #[serde(default)]
+    pub seconds: Option<f64>,
 }
 
 /// Messages sent from server to client.
@@ -25,8 +54,24 @@ pub enum ServerMessage {
     /// Connection accepted, waiting for Join message.
     ConnectionAck,
 
+    /// No room on the server has the code the client asked for.
+    RoomNotFound { code: String },
+
     /// Username accepted, client is now in lobby.
-    JoinAccepted { username: String },
+    JoinAccepted {
+        username: String,
+        /// Whether the host wants answers confirmed before they are sent.
+        #[serde(default)]
+        confirm_answers: bool,
+        /// Whether the host wants to know how sure players are of each
+        /// answer.
+        #[serde(default)]
+        confidence_mode: bool,
+        /// Seconds a correct answer keeps earning a speed bonus, when the
+        /// host turned it on.
+        #[serde(default)]
+        speed_bonus_window: Option<u64>,
+    },
 
     /// Username rejected (taken, invalid length, etc.).
     JoinRejected { reason: String },
@@ -35,30 +80,117 @@ pub enum ServerMessage {
     ReconnectAccepted {
         username: String,
         current_question: usize,
+        /// Whether the host wants answers confirmed before they are sent.
+        #[serde(default)]
+        confirm_answers: bool,
+        /// Whether the host wants to know how sure players are of each
+        /// answer.
+        #[serde(default)]
+        confidence_mode: bool,
+        /// Seconds a correct answer keeps earning a speed bonus, when the
+        /// host turned it on.
+        #[serde(default)]
+        speed_bonus_window: Option<u64>,
+        /// Every question of the quiz in progress, when the host lets
+        /// players answer offline.
+        #[serde(default)]
+        questions: Vec<QuizQuestion>,
     },
 
+    /// The host will start the quiz in this many seconds.
+    StartsIn { seconds: u32 },
+
+    /// The host called off the scheduled start.
+    StartCancelled,
+
     /// Quiz is starting.
-    QuizStart { total_questions: usize },
+    QuizStart {
+        total_questions: usize,
+        /// Every question, without the answers, when the host lets players
+        /// answer offline; they then move on by themselves and only the
+        /// first question is sent again.
+        #[serde(default)]
+        questions: Vec<QuizQuestion>,
+    },
+
+    /// The first question follows in this many seconds.
+    Countdown { seconds: u32 },
 
     /// Next question to answer.
     Question {
         index: usize,
         text: String,
         code: Option<String>,
+        /// Language of `code`, Rust when not set.
+        #[serde(default)]
+        code_language: Option<String>,
         options: [String; 4],
+        /// Whether several options may be selected (checkbox question).
+        #[serde(default)]
+        multiple: bool,
     },
 
+    /// Whether the answer just submitted was right, and the right one, sent
+    /// before what comes next when the host turned on feedback.
+    AnswerFeedback {
+        correct: bool,
+        correct_answer: Vec<usize>,
+    },
+
+    /// How many players taking the quiz have finished, sent to those who
+    /// have while they wait for results.
+    ProgressSummary { finished: usize, players: usize },
+
     /// Quiz complete with results.
     QuizResults {
+        /// Points earned.
         score: usize,
+        /// Maximum achievable points.
         total: usize,
+        /// Seconds from the first question to the last answer.
+        #[serde(default)]
+        seconds: Option<f64>,
         answers: Vec<AnswerResult>,
         leaderboard: Vec<LeaderboardEntry>,
+        /// How the quiz grades scores, to label and color this one.
+        #[serde(default)]
+        grades: Grades,
     },
 
+    /// Interim standings, sent to players still answering whenever anyone
+    /// answers a question.
+    Standings { standings: Vec<StandingEntry> },
+
+    /// How far each player taking the quiz has got, sent to everyone in
+    /// the room whenever anyone answers.
+    Progress { players: Vec<PlayerProgress> },
+
+    /// Usernames of everyone connected to the room, sent to players
+    /// whenever someone joins or leaves.
+    LobbyUpdate { players: Vec<String> },
+
+    /// Chat message from a player in the room.
+    Chat { username: String, text: String },
+
+    /// Chat message refused (muted, mid-quiz, etc.).
+    ChatRejected { reason: String },
+
     /// Client has been kicked by host.
     Kicked { reason: String },
 
+    /// Message from the host to everyone in the room.
+    Announcement { text: String },
+
+    /// Host paused the quiz; answers are refused until it resumes.
+    Paused,
+
+    /// Host resumed a paused quiz.
+    Resumed,
+
+    /// Host started another round; the player leaves the results for the
+    /// lobby and may play again.
+    ReturnToLobby { username: String },
+
     /// Host ended the quiz abruptly.
     HostEndedQuiz,
 
@@ -66,15 +198,64 @@ pub enum ServerMessage {
     ServerClosing,
 }
 
+impl ServerMessage {
+    /// Ask `question`, the `index`th of the quiz.
+    pub fn question(index: usize, question: &Question) -> Self {
+        ServerMessage::Question {
+            index,
+            text: question.text.clone(),
+            code: question.code.clone(),
+            code_language: question.code_language.clone(),
+            options: question.options.clone(),
+            multiple: question.is_multiple(),
+        }
+    }
+}
+
+/// A question sent ahead for answering offline, without its answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizQuestion {
+    pub index: usize,
+    pub text: String,
+    pub code: Option<String>,
+    /// Language of `code`, Rust when not set.
+    #[serde(default)]
+    pub code_language: Option<String>,
+    pub options: [String; 4],
+    /// Whether several options may be selected (checkbox question).
+    #[serde(default)]
+    pub multiple: bool,
+}
+
+impl QuizQuestion {
+    /// Send `question`, the `index`th of the quiz.
+    pub fn new(index: usize, question: &Question) -> Self {
+        Self {
+            index,
+            text: question.text.clone(),
+            code: question.code.clone(),
+            code_language: question.code_language.clone(),
+            options: question.options.clone(),
+            multiple: question.is_multiple(),
+        }
+    }
+}
+
 /// Result for a single answered question.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnswerResult {
     pub question_index: usize,
     pub question_text: String,
-    pub your_answer: usize,
-    pub correct_answer: usize,
+    pub your_answer: Vec<usize>,
+    pub correct_answer: Vec<usize>,
     pub is_correct: bool,
     pub options: [String; 4],
+    /// Seconds taken to answer.
+    #[serde(default)]
+    pub seconds: Option<f64>,
+    /// How sure the player was, when the host asked.
+    #[serde(default)]
+    pub confidence: Option<Confidence>,
 }
 
 /// Entry in the leaderboard.
@@ -82,8 +263,13 @@ pub struct AnswerResult {
 pub struct LeaderboardEntry {
     pub rank: usize,
     pub username: String,
+    /// Points earned.
     pub score: usize,
+    /// Maximum achievable points.
     pub total: usize,
+    /// Seconds from the first question to the last answer.
+    #[serde(default)]
+    pub seconds: Option<f64>,
     pub is_you: bool,
 }
 
@@ -91,11 +277,40 @@ pub struct LeaderboardEntry {
 pub const USERNAME_MIN_LENGTH: usize = 3;
 pub const USERNAME_MAX_LENGTH: usize = 16;
 
+/// A player's place in the interim standings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandingEntry {
+    pub rank: usize,
+    pub username: String,
+    /// Number of questions answered so far.
+    pub answered: usize,
+    /// Points earned so far.
+    pub score: usize,
+    /// Whether the player has answered every question.
+    pub finished: bool,
+    pub is_you: bool,
+}
+
+/// How far a player has got through the quiz.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerProgress {
+    pub username: String,
+    /// Number of questions answered so far.
+    pub answered: usize,
+}
+
+/// Longest chat message the server passes on, in characters.
+pub const CHAT_MAX_LENGTH: usize = 200;
+
 /// Default server port.
 pub const DEFAULT_PORT: u16 = 8712;
 
 /// Validates a username according to the rules.
 ///
+/// Leading and trailing whitespace is ignored. Within the name, spaces are
+/// the only whitespace allowed, and control and invisible formatting
+/// characters are refused, as they could scramble the screens showing it.
+///
 /// Returns `Ok(())` if valid, or `Err` with an error message.
 pub fn validate_username(username: &str) -> Result<(), &'static str> {
     let trimmed = username.trim();
@@ -108,9 +323,23 @@ pub fn validate_username(username: &str) -> Result<(), &'static str> {
         return Err("Username must be at most 16 characters");
     }
 
+    if trimmed.chars().any(|c| !is_printable(c)) {
+        return Err("Username may only contain printable characters");
+    }
+
     Ok(())
 }
 
+/// Whether `c` shows up as itself when printed: not a control character,
+/// whitespace other than a space, or a zero-width or direction mark.
+fn is_printable(c: char) -> bool {
+    let invisible = matches!(
+        c,
+        '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2069}' | '\u{FEFF}'
+    );
+    c == ' ' || !(c.is_control() || c.is_whitespace() || invisible)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +351,10 @@ mod tests {
         assert!(validate_username("ab").is_err());
         assert!(validate_username("abcdefghijklmnopq").is_err()); // 17 chars
         assert!(validate_username("  ab  ").is_err()); // trimmed = 2 chars
+        assert!(validate_username("  Ann Lee ").is_ok());
+        assert!(validate_username("\x1b[2Jbob").is_err());
+        assert!(validate_username("bob\tby").is_err());
+        assert!(validate_username("bob\u{202E}by").is_err());
     }
 
     #[test]
@@ -134,8 +367,14 @@ mod tests {
 
         let msg = ServerMessage::QuizStart {
             total_questions: 25,
+            questions: Vec::new(),
         };
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("\"type\":\"QuizStart\""));
+
+        // Servers that don't send questions ahead leave them out
+        let json = r#"{"type":"QuizStart","total_questions":3}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(msg, ServerMessage::QuizStart { questions, .. } if questions.is_empty()));
     }
 }
@@ -1,3 +1,5 @@
+mod encoding;
 mod messages;
 
+pub use encoding::*;
 pub use messages::*;
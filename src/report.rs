@@ -0,0 +1,309 @@
+//! Exporting finished quiz runs as standalone reports.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::engine::QuizEngine;
+use crate::models::{format_answer, OPTION_LABELS};
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; color: #222; }
+h1 { margin-bottom: 0.2rem; }
+.meta { color: #666; }
+.score { font-size: 2rem; font-weight: bold; }
+.tags td, .tags th { padding: 0.2rem 1rem 0.2rem 0; text-align: left; }
+.question { border: 1px solid #ddd; border-radius: 6px; padding: 0.8rem 1.2rem; margin: 1rem 0; }
+.question.correct { border-left: 6px solid #2e7d32; }
+.question.incorrect { border-left: 6px solid #c62828; }
+.verdict { font-weight: bold; }
+.correct .verdict, li.answer { color: #2e7d32; }
+.incorrect .verdict, li.wrong { color: #c62828; }
+pre { background: #f6f8fa; padding: 0.8rem; border-radius: 4px; overflow-x: auto; }
+ol.options { list-style: none; padding-left: 0; }
+li.answer, li.wrong { font-weight: bold; }
+.hint { color: #b26a00; }
+.explanation { color: #444; font-style: italic; }
+";
+
+/// Render the results of a finished quiz as a self-contained HTML page.
+///
+/// The page has the score summary, per-tag scores and every question with
+/// its code, options, the given answer and the correct answer. All styling is
+/// inline so the file can be shared on its own, e.g. as an email attachment.
+pub fn html_report(engine: &QuizEngine) -> String {
+    let score = engine.calculate_score();
+    let total = engine.max_score();
+    let percentage = if total > 0 {
+        score as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Rust Quiz Report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>\n</head>\n<body>", STYLE);
+
+    html.push_str("<h1>Rust Quiz Report</h1>\n");
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">{}</p>",
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    );
+    let _ = writeln!(
+        html,
+        "<p class=\"score\">{} / {} ({:.0}%)</p>",
+        score, total, percentage
+    );
+
+    let mut details = Vec::new();
+    if let Some(time_taken) = engine.time_taken() {
+        details.push(format!("Time {}", format_seconds(time_taken.as_secs())));
+    }
+    let hints = engine.hints_used().iter().filter(|&&used| used).count();
+    if hints > 0 {
+        details.push(format!("{} hint(s) used", hints));
+    }
+    if !details.is_empty() {
+        let _ = writeln!(html, "<p class=\"meta\">{}</p>", details.join(" · "));
+    }
+
+    let tag_scores = engine.score_by_tag();
+    if !tag_scores.is_empty() {
+        html.push_str("<table class=\"tags\">\n<tr><th>Tag</th><th>Score</th></tr>\n");
+        for (tag, tag_score, tag_total) in &tag_scores {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{} / {}</td></tr>",
+                escape_html(tag),
+                tag_score,
+                tag_total
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    for (index, question) in engine.questions().iter().enumerate() {
+        let answer = engine.answers()[index].as_deref();
+        let is_correct = answer.is_some_and(|answer| question.is_correct(answer));
+        let correct = question.correct_answer.indices();
+        let (class, verdict) = match answer {
+            _ if is_correct => ("correct", "Correct"),
+            Some(_) => ("incorrect", "Incorrect"),
+            None => ("incorrect", "Unanswered"),
+        };
+
+        let _ = writeln!(html, "<section class=\"question {}\">", class);
+        let _ = writeln!(
+            html,
+            "<h3>Question {} · <span class=\"verdict\">{}</span> · {}/{} points</h3>",
+            index + 1,
+            verdict,
+            engine.question_score(index),
+            question.points
+        );
+        let _ = writeln!(html, "<p>{}</p>", escape_html(&question.text));
+        if let Some(code) = &question.code {
+            let _ = writeln!(html, "<pre><code>{}</code></pre>", escape_html(code));
+        }
+
+        html.push_str("<ol class=\"options\">\n");
+        for (option_index, option) in question.options.iter().enumerate() {
+            let chosen = answer.is_some_and(|answer| answer.contains(&option_index));
+            let class = if correct.contains(&option_index) {
+                " class=\"answer\""
+            } else if chosen {
+                " class=\"wrong\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                html,
+                "<li{}>{}. {}{}</li>",
+                class,
+                OPTION_LABELS[option_index],
+                escape_html(option),
+                if chosen { " ← your answer" } else { "" }
+            );
+        }
+        html.push_str("</ol>\n");
+
+        let _ = writeln!(
+            html,
+            "<p>Your answer: {} · Correct answer: {}</p>",
+            answer.map_or_else(|| "none".to_string(), format_answer),
+            format_answer(&correct)
+        );
+        if engine.hints_used()[index]
+            && let Some(hint) = &question.hint
+        {
+            let _ = writeln!(html, "<p class=\"hint\">Hint used: {}</p>", escape_html(hint));
+        }
+        if let Some(explanation) = &question.explanation {
+            let _ = writeln!(
+                html,
+                "<p class=\"explanation\">{}</p>",
+                escape_html(explanation)
+            );
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Write [`html_report`] to a file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::Quiz;
+///
+/// let mut quiz = Quiz::from_json("questions.json")?;
+/// quiz.run()?;
+/// rust_quiz::report::write_html_report(quiz.app().engine(), "report.html")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_html_report<P: AsRef<Path>>(engine: &QuizEngine, path: P) -> io::Result<()> {
+    fs::write(path, html_report(engine))
+}
+
+/// Render a plain-text log of a finished attempt, for strict exams.
+///
+/// Lists when the attempt started and ended, the score, and for every
+/// question the time its answer was last saved and whether it was right.
+/// Times are local clock times worked back from `finished_at`, the moment
+/// the quiz ended.
+pub fn attempt_report(engine: &QuizEngine, finished_at: DateTime<Local>) -> String {
+    let time_taken = engine.time_taken().unwrap_or_else(|| engine.elapsed());
+    let started_at = finished_at - time_taken;
+    let clock = |offset: Duration| (started_at + offset).format("%H:%M:%S").to_string();
+
+    let mut report = String::from("Rust Quiz attempt report\n\n");
+    let _ = writeln!(report, "Started:  {}", started_at.format("%Y-%m-%d %H:%M:%S"));
+    let _ = write!(report, "Finished: {}", finished_at.format("%Y-%m-%d %H:%M:%S"));
+    let _ = write!(report, " ({}", format_seconds(time_taken.as_secs()));
+    if let Some(limit) = engine.time_limit() {
+        let _ = write!(report, " of {}", format_seconds(limit.as_secs()));
+    }
+    report.push_str(")\n");
+    let _ = writeln!(
+        report,
+        "Score:    {} / {}\n",
+        engine.calculate_score(),
+        engine.max_score()
+    );
+
+    for (index, question) in engine.questions().iter().enumerate() {
+        let answer = engine.answers()[index].as_deref();
+        let verdict = match answer {
+            Some(answer) if question.is_correct(answer) => "correct",
+            Some(_) => "incorrect",
+            None => "unanswered",
+        };
+        let _ = writeln!(
+            report,
+            "{:>3}. {:<8}  {:<10}  {:<10}  {}",
+            index + 1,
+            engine.answered_at()[index].map_or_else(|| "--".to_string(), clock),
+            answer.map_or_else(|| "-".to_string(), format_answer),
+            verdict,
+            question.text
+        );
+    }
+    report
+}
+
+/// Write [`attempt_report`] to a file.
+pub fn write_attempt_report<P: AsRef<Path>>(
+    engine: &QuizEngine,
+    finished_at: DateTime<Local>,
+    path: P,
+) -> io::Result<()> {
+    fs::write(path, attempt_report(engine, finished_at))
+}
+
+fn format_seconds(secs: u64) -> String {
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CorrectAnswer, Question};
+
+    #[test]
+    fn test_html_report_escapes_and_marks_answers() {
+        let question = Question {
+            text: "What is Vec<T>?".to_string(),
+            code: Some("let v: Vec<&str> = vec![];".to_string()),
+            code_language: None,
+            options: ["A list", "A <tree>", "A map", "A set"].map(String::from),
+            correct_answer: CorrectAnswer::Single(0),
+            tags: vec!["collections".to_string()],
+            points: 1,
+            explanation: None,
+            hint: None,
+            image: None,
+        };
+        let mut engine = QuizEngine::new(vec![question]);
+        engine.start();
+        engine.answer(vec![1]);
+
+        let html = html_report(&engine);
+        assert!(html.contains("What is Vec&lt;T&gt;?"));
+        assert!(html.contains("Vec&lt;&amp;str&gt;"));
+        assert!(html.contains("<li class=\"wrong\">B. A &lt;tree&gt; ← your answer</li>"));
+        assert!(html.contains("0 / 1 (0%)"));
+        assert!(html.contains("<td>collections</td>"));
+    }
+
+    #[test]
+    fn test_attempt_report_lists_answers() {
+        let question = |text: &str| Question {
+            text: text.to_string(),
+            code: None,
+            code_language: None,
+            options: ["a", "b", "c", "d"].map(String::from),
+            correct_answer: CorrectAnswer::Single(0),
+            tags: Vec::new(),
+            points: 1,
+            explanation: None,
+            hint: None,
+            image: None,
+        };
+        let mut engine = QuizEngine::new(vec![question("First"), question("Second")]);
+        engine.set_exam_mode(true);
+        engine.start();
+        engine.answer(vec![0]);
+        engine.finish();
+
+        let finished_at = Local::now();
+        let report = attempt_report(&engine, finished_at);
+        assert!(report.contains("Score:    1 / 2"));
+        assert!(report.contains("A           correct     First"));
+        assert!(report.contains("--        -           unanswered  Second"));
+        assert!(report.contains(&finished_at.format("%H:%M:%S").to_string()));
+    }
+}
@@ -0,0 +1,65 @@
+//! Words the host won't have in usernames.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Words refused anywhere in a username, matched ignoring case and any
+/// characters between the letters, so `B.a.d` is caught by `bad`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Blocklist {
+    words: Vec<String>,
+}
+
+impl Blocklist {
+    /// Read a blocklist file: one word per line, with blank lines and lines
+    /// starting with `#` skipped.
+    pub(crate) fn load(path: &Path) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub(crate) fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(letters)
+            .filter(|word| !word.is_empty())
+            .collect();
+        Self { words }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether `username` contains a blocked word.
+    pub(crate) fn blocks(&self, username: &str) -> bool {
+        let username = letters(username);
+        self.words.iter().any(|word| username.contains(word.as_str()))
+    }
+}
+
+/// The letters and digits of `text`, lowercased.
+fn letters(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks() {
+        let blocklist = Blocklist::parse("# words\n\nbad\n  Worse Word \n");
+        assert_eq!(blocklist.len(), 2);
+        assert!(blocklist.blocks("Bad Bob"));
+        assert!(blocklist.blocks("x_B.a.d_x"));
+        assert!(blocklist.blocks("worseword"));
+        assert!(!blocklist.blocks("alice"));
+        assert!(!Blocklist::default().blocks("bad"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Simulated players, to rehearse a big session or show off the analytics
+//! without volunteers.
+//!
+//! Bots join a room like any player and answer each question after a random
+//! pause, right as often as their accuracy says. They live inside the
+//! server, with no connection of their own.
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::models::{Confidence, Question};
+use crate::protocol::{ClientMessage, ServerMessage};
+
+use super::server::{handle_client_message, handle_join, SharedState};
+use super::state::{ServerState, UserSession};
+
+/// Most bots one `bots` command adds.
+pub const MAX_BOTS: usize = 500;
+/// Shortest and longest a bot thinks before answering.
+const THINK_TIME: (Duration, Duration) = (Duration::from_secs(2), Duration::from_secs(12));
+/// How long a bot waits before trying again while the quiz is paused.
+const PAUSE_RETRY: Duration = Duration::from_secs(1);
+/// Address bots are listed with; no real player connects from it, so
+/// banning a bot bans nobody else.
+const BOT_IP: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+/// Range of the share of questions bots answer right, in percent. Each bot
+/// gets its own accuracy, spread evenly over the range.
+///
+/// Written `70` for every bot alike, or `40-90` for a spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BotAccuracy {
+    pub low: u8,
+    pub high: u8,
+}
+
+impl Default for BotAccuracy {
+    fn default() -> Self {
+        Self { low: 50, high: 90 }
+    }
+}
+
+impl std::fmt::Display for BotAccuracy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.low == self.high {
+            write!(f, "{}%", self.low)
+        } else {
+            write!(f, "{}-{}%", self.low, self.high)
+        }
+    }
+}
+
+impl FromStr for BotAccuracy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let percent = |s: &str| match s.trim().trim_end_matches('%').parse::<u8>() {
+            Ok(percent) if percent <= 100 => Ok(percent),
+            _ => Err(format!("expected a percentage from 0 to 100, got '{}'", s)),
+        };
+        let (low, high) = match s.split_once('-') {
+            Some((low, high)) => (percent(low)?, percent(high)?),
+            None => (percent(s)?, percent(s)?),
+        };
+        if low > high {
+            return Err(format!("the range {} goes down", s));
+        }
+        Ok(Self { low, high })
+    }
+}
+
+/// Bots the host asked for, added by the server's timers.
+#[derive(Debug, Clone)]
+pub struct BotRequest {
+    /// Code of the room the bots join.
+    pub room: String,
+    pub count: usize,
+    pub accuracy: BotAccuracy,
+}
+
+/// Join `request.count` bots to their room and set them answering.
+pub(super) async fn add_bots(state: &SharedState, request: BotRequest) {
+    let mut rng = StdRng::from_rng(&mut rand::rng());
+    let mut guard = state.lock().await;
+    let Some(room) = guard.rooms.get_mut(&request.room) else {
+        return;
+    };
+
+    let mut number = 1;
+    for _ in 0..request.count {
+        while room.is_username_taken(&format!("bot-{}", number)) {
+            number += 1;
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        let session = UserSession::new(BOT_IP, tx);
+        let id = session.id;
+        room.sessions.insert(id, session);
+        handle_join(id, format!("bot-{}", number), room);
+
+        let percent = rng.random_range(request.accuracy.low..=request.accuracy.high);
+        let bot = Bot {
+            state: Arc::downgrade(state),
+            room: request.room.clone(),
+            session_id: id,
+            accuracy: f64::from(percent) / 100.0,
+            rng: StdRng::from_rng(&mut rng),
+        };
+        tokio::spawn(bot.run(rx));
+    }
+}
+
+/// A simulated player in a room.
+struct Bot {
+    /// Held weakly, so the bot ends with the server rather than keeping it
+    /// alive.
+    state: Weak<Mutex<ServerState>>,
+    room: String,
+    session_id: Uuid,
+    /// Chance of answering each question right.
+    accuracy: f64,
+    rng: StdRng,
+}
+
+impl Bot {
+    /// Answer each question sent until the bot is turned away, kicked or
+    /// the server closes.
+    async fn run(mut self, mut rx: mpsc::UnboundedReceiver<ServerMessage>) {
+        // The question to answer, and when
+        let mut pending: Option<(usize, Instant)> = None;
+        // Questions sent ahead, which the bot moves through by itself
+        let mut offline = 0;
+        loop {
+            let wake = pending.map_or_else(Instant::now, |(_, at)| at);
+            tokio::select! {
+                msg = rx.recv() => match msg {
+                    None
+                    | Some(ServerMessage::JoinRejected { .. })
+                    | Some(ServerMessage::Kicked { .. })
+                    | Some(ServerMessage::ServerClosing) => break,
+                    Some(ServerMessage::QuizStart { questions, .. }) => {
+                        offline = questions.len();
+                    }
+                    Some(ServerMessage::Question { index, .. }) => {
+                        pending = Some((index, self.think()));
+                    }
+                    Some(ServerMessage::HostEndedQuiz) => pending = None,
+                    Some(_) => {}
+                },
+                _ = tokio::time::sleep_until(wake), if pending.is_some() => {
+                    let Some(state) = self.state.upgrade() else {
+                        break;
+                    };
+                    let Some((index, _)) = pending else {
+                        continue;
+                    };
+                    match self.answer(&state, index).await {
+                        Some(msg) => {
+                            pending = (index + 1 < offline).then(|| (index + 1, self.think()));
+                            handle_client_message(self.session_id, &self.room, msg, &state).await;
+                        }
+                        None => pending = Some((index, Instant::now() + PAUSE_RETRY)),
+                    }
+                }
+            }
+        }
+
+        // Forget a bot that never got in
+        if let Some(state) = self.state.upgrade()
+            && let Some(room) = state.lock().await.rooms.get_mut(&self.room)
+            && room.sessions.get(&self.session_id).is_some_and(|s| s.username.is_none())
+        {
+            room.remove_session(self.session_id);
+        }
+    }
+
+    /// When to answer a question that arrives now.
+    fn think(&mut self) -> Instant {
+        Instant::now() + self.rng.random_range(THINK_TIME.0..=THINK_TIME.1)
+    }
+
+    /// The answer to question `index`, or `None` while the quiz is paused.
+    async fn answer(&mut self, state: &SharedState, index: usize) -> Option<ClientMessage> {
+        let state = state.lock().await;
+        let room = state.rooms.get(&self.room)?;
+        if room.paused {
+            return None;
+        }
+        let right = self.rng.random_bool(self.accuracy);
+        let answers = room
+            .questions
+            .get(index)
+            .map_or_else(|| vec![0], |question| pick_answer(question, right, &mut self.rng));
+        let confidence = room.confidence_mode.then(|| {
+            if self.rng.random_bool(self.accuracy) {
+                Confidence::Sure
+            } else {
+                Confidence::Unsure
+            }
+        });
+        Some(ClientMessage::SubmitAnswer {
+            question_index: index,
+            answers,
+            confidence,
+        })
+    }
+}
+
+/// The right answer to `question`, or else a wrong one picked at random.
+fn pick_answer<R: Rng + ?Sized>(question: &Question, right: bool, rng: &mut R) -> Vec<usize> {
+    let correct = question.correct_answer.indices();
+    if right {
+        return correct;
+    }
+    // Any option outside the correct ones is wrong, even for checkbox
+    // questions; with all four correct, one alone is too
+    (0..question.options.len())
+        .filter(|i| !correct.contains(i))
+        .choose(rng)
+        .map_or_else(|| vec![correct[0]], |wrong| vec![wrong])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accuracy_and_answers() {
+        assert_eq!("70".parse(), Ok(BotAccuracy { low: 70, high: 70 }));
+        assert_eq!("40-90%".parse(), Ok(BotAccuracy { low: 40, high: 90 }));
+        assert!("90-40".parse::<BotAccuracy>().is_err());
+        assert!("120".parse::<BotAccuracy>().is_err());
+        assert_eq!(BotAccuracy::default().to_string(), "50-90%");
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let single = Question::new("Pick b", ["a", "b", "c", "d"], 1);
+        let checkbox = Question::new("Pick a and c", ["a", "b", "c", "d"], vec![0, 2]);
+        assert_eq!(pick_answer(&single, true, &mut rng), [1]);
+        assert_eq!(pick_answer(&checkbox, true, &mut rng), [0, 2]);
+        for _ in 0..20 {
+            assert!(!single.is_correct(&pick_answer(&single, false, &mut rng)));
+            assert!(!checkbox.is_correct(&pick_answer(&checkbox, false, &mut rng)));
+        }
+    }
+}
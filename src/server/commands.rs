@@ -3,10 +3,20 @@
 //! Handles host commands like `start`, `kick`, `ban`, etc.
 
 use std::net::IpAddr;
+use std::path::Path;
+use std::time::Duration;
 
+use crate::data::{
+    load_bank_from_json, sample_pools_with_rng, sample_questions_with_rng, seeded_rng, Sampling,
+};
 use crate::protocol::ServerMessage;
 
-use super::state::{ServerState, ServerStatus, ServerView, UserStatus};
+use super::bots::{BotAccuracy, BotRequest, MAX_BOTS};
+use super::export::{export_analytics, export_results};
+use super::state::{Room, ServerState, ServerStatus, ServerView, UserStatus};
+
+/// Longest `start in` waits, in seconds.
+const MAX_START_DELAY: u32 = 3600;
 
 /// Result of executing a command.
 pub enum CommandResult {
@@ -30,14 +40,33 @@ pub fn execute_command(state: &mut ServerState, input: &str) -> CommandResult {
     let args = &parts[1..];
 
     match command.as_str() {
-        "start" => cmd_start(state),
-        "stop" => cmd_stop(state),
+        "start" => cmd_start(state, args),
+        "stop" => cmd_stop(state.room_mut()),
+        "restart" => cmd_restart(state, args),
+        "pause" => cmd_pause(state.room_mut()),
+        "resume" => cmd_resume(state.room_mut()),
+        "feedback" => cmd_feedback(state.room_mut(), args),
+        // Keep the message's spacing as typed
+        "announce" => cmd_announce(state.room_mut(), input[parts[0].len()..].trim()),
         "quit" | "exit" => cmd_quit(state),
-        "kick" => cmd_kick(state, args),
+        "kick" => cmd_kick(state.room_mut(), args),
+        "mute" => cmd_mute(state.room_mut(), args),
+        "unmute" => cmd_unmute(state.room_mut(), args),
         "ban" => cmd_ban(state, args),
         "unban" => cmd_unban(state, args),
+        "limit" => cmd_limit(state.room_mut(), args),
+        "load" => cmd_load(state.room_mut(), args),
+        "preview" => cmd_preview(state, args),
+        "drop" => cmd_drop(state, args),
+        "bots" => cmd_bots(state, args),
+        "results" => cmd_results(state.room(), args),
+        "export" => cmd_export(state.room(), args),
+        "room" => cmd_room(state, args),
+        "rooms" => cmd_rooms(state),
         "view" => cmd_view(state, args),
+        "leaderboard" | "lb" => cmd_leaderboard(state),
         "list" => cmd_list(state, args),
+        "log" => cmd_log(state),
         "help" | "?" => cmd_help(state),
         _ => CommandResult::Error(format!(
             "Unknown command: {}. Type 'help' for available commands.",
@@ -46,123 +75,140 @@ pub fn execute_command(state: &mut ServerState, input: &str) -> CommandResult {
     }
 }
 
-/// Start the quiz.
-fn cmd_start(state: &mut ServerState) -> CommandResult {
-    if state.status != ServerStatus::Lobby {
-        return CommandResult::Error("Quiz has already started.".to_string());
+/// Start the quiz now, schedule it with `start in <seconds>`, or call off a
+/// scheduled start with `start cancel`.
+fn cmd_start(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let room = state.room_mut();
+    match args {
+        [] => {}
+        ["in", seconds] => {
+            if room.status != ServerStatus::Lobby {
+                return CommandResult::Error("Quiz has already started.".to_string());
+            }
+            let seconds = match seconds.trim_end_matches('s').parse::<u32>() {
+                Ok(seconds) if (1..=MAX_START_DELAY).contains(&seconds) => seconds,
+                _ => {
+                    return CommandResult::Error(format!(
+                        "Invalid delay: {} (1 to {} seconds)",
+                        seconds, MAX_START_DELAY
+                    ))
+                }
+            };
+            room.schedule_start(Some(Duration::from_secs(seconds.into())));
+            return CommandResult::Ok(Some(format!(
+                "Quiz starts in {} seconds. Type 'start cancel' to call it off.",
+                seconds
+            )));
+        }
+        ["cancel"] => {
+            if room.starts_at.is_none() {
+                return CommandResult::Error("No start is scheduled.".to_string());
+            }
+            room.schedule_start(None);
+            return CommandResult::Ok(Some("Scheduled start called off.".to_string()));
+        }
+        _ => {
+            return CommandResult::Error(
+                "Usage: start | start in <seconds> | start cancel".to_string(),
+            )
+        }
     }
 
-    let named_count = state.named_user_count();
-    if named_count == 0 {
-        return CommandResult::Error("No users have joined yet.".to_string());
+    match room.start() {
+        Ok(count) => {
+            state.current_view = ServerView::Analytics;
+            CommandResult::Ok(Some(format!("Quiz started with {} users!", count)))
+        }
+        Err(reason) => CommandResult::Error(reason.to_string()),
     }
+}
 
-    // Initialize all users for the quiz
-    let num_questions = state.questions.len();
-    for session in state.sessions.values_mut() {
-        if session.username.is_some() && session.status == UserStatus::InLobby {
-            session.init_answers(num_questions);
-            session.status = UserStatus::Answering(0);
+/// Bring everyone back to the lobby for another round, optionally with a
+/// new `limit`.
+fn cmd_restart(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let room = state.room_mut();
+    match room.status {
+        ServerStatus::Lobby => {
+            return CommandResult::Error("Quiz hasn't started yet.".to_string());
+        }
+        // Once everyone has finished there is nothing left to stop
+        ServerStatus::InProgress
+            if room.sessions.values().any(|s| matches!(s.status, UserStatus::Answering(_))) =>
+        {
+            return CommandResult::Error("Quiz is still running. Type 'stop' first.".to_string());
         }
+        ServerStatus::InProgress | ServerStatus::Finished => {}
     }
 
-    state.status = ServerStatus::InProgress;
-    state.current_view = ServerView::Analytics;
-
-    // Broadcast quiz start
-    state.broadcast(ServerMessage::QuizStart {
-        total_questions: num_questions,
-    });
-
-    // Send first question to each user
-    if let Some(first_question) = state.questions.first() {
-        let msg = ServerMessage::Question {
-            index: 0,
-            text: first_question.text.clone(),
-            code: first_question.code.clone(),
-            options: first_question.options.clone(),
-        };
-        state.broadcast(msg);
+    room.new_round();
+    let mut message = format!(
+        "New round: {} users back in the lobby.",
+        room.sessions.values().filter(|s| s.status == UserStatus::InLobby).count()
+    );
+    if !args.is_empty() {
+        match cmd_limit(room, args) {
+            CommandResult::Ok(Some(limit)) => message = format!("{} {}", message, limit),
+            CommandResult::Error(e) => return CommandResult::Error(format!("{} {}", message, e)),
+            _ => {}
+        }
     }
 
-    CommandResult::Ok(Some(format!("Quiz started with {} users!", named_count)))
+    state.current_view = ServerView::Lobby;
+    CommandResult::Ok(Some(message))
 }
 
-/// Stop the quiz and send results to finished users.
-fn cmd_stop(state: &mut ServerState) -> CommandResult {
-    if state.status != ServerStatus::InProgress {
+/// Freeze the quiz for everyone until `resume`.
+fn cmd_pause(room: &mut Room) -> CommandResult {
+    if room.status != ServerStatus::InProgress {
         return CommandResult::Error("Quiz is not in progress.".to_string());
     }
-
-    state.status = ServerStatus::Finished;
-
-    // Send results to all finished users, HostEndedQuiz to others
-    let questions = state.questions.clone();
-    let session_ids: Vec<_> = state.sessions.keys().copied().collect();
-
-    // First pass: calculate scores and collect data
-    let mut results_to_send: Vec<(
-        uuid::Uuid,
-        usize,
-        String,
-        Vec<crate::protocol::AnswerResult>,
-    )> = Vec::new();
-    let mut host_ended_ids: Vec<uuid::Uuid> = Vec::new();
-
-    for id in &session_ids {
-        if let Some(session) = state.sessions.get_mut(id) {
-            if session.is_finished() {
-                // Calculate final score
-                session.score = Some(session.calculate_score(&questions));
-                let username = session.username.clone().unwrap_or_default();
-                let score = session.score.unwrap_or(0);
-
-                // Collect answer results
-                let answers: Vec<_> = session
-                    .answers
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(i, ans)| {
-                        let question = questions.get(i)?;
-                        let your_answer = (*ans)?;
-                        Some(crate::protocol::AnswerResult {
-                            question_index: i,
-                            question_text: question.text.clone(),
-                            your_answer,
-                            correct_answer: question.correct_answer,
-                            is_correct: your_answer == question.correct_answer,
-                            options: question.options.clone(),
-                        })
-                    })
-                    .collect();
-
-                results_to_send.push((*id, score, username, answers));
-            } else if session.is_connected() {
-                host_ended_ids.push(*id);
-            }
-        }
+    if room.paused {
+        return CommandResult::Error("Quiz is already paused.".to_string());
     }
 
-    // Second pass: send results (now we can generate leaderboards)
-    for (id, score, username, answers) in results_to_send {
-        let leaderboard = state.generate_leaderboard(&username);
-        if let Some(session) = state.sessions.get(&id) {
-            session.send(ServerMessage::QuizResults {
-                score,
-                total: questions.len(),
-                answers,
-                leaderboard,
-            });
-        }
+    room.paused = true;
+    room.broadcast(ServerMessage::Paused);
+    CommandResult::Ok(Some("Quiz paused. Type 'resume' to continue.".to_string()))
+}
+
+/// Let players answer again after `pause`.
+fn cmd_resume(room: &mut Room) -> CommandResult {
+    if !room.paused {
+        return CommandResult::Error("Quiz is not paused.".to_string());
     }
 
-    // Send HostEndedQuiz to non-finished users
-    for id in host_ended_ids {
-        if let Some(session) = state.sessions.get(&id) {
-            session.send(ServerMessage::HostEndedQuiz);
+    room.paused = false;
+    room.broadcast(ServerMessage::Resumed);
+    CommandResult::Ok(Some("Quiz resumed.".to_string()))
+}
+
+/// Tell players whether each answer was right as they send it (study mode),
+/// or only in the results (exam mode).
+fn cmd_feedback(room: &mut Room, args: &[&str]) -> CommandResult {
+    room.feedback = match args {
+        ["on"] => true,
+        ["off"] => false,
+        [] => {
+            let mode = if room.feedback { "on (study mode)" } else { "off (exam mode)" };
+            return CommandResult::Ok(Some(format!("Feedback is {}.", mode)));
         }
+        _ => return CommandResult::Error("Usage: feedback on | feedback off".to_string()),
+    };
+    CommandResult::Ok(Some(if room.feedback {
+        "Players now see whether each answer was right as they send it.".to_string()
+    } else {
+        "Players now find out how they did in the results.".to_string()
+    }))
+}
+
+/// Stop the quiz and send results to finished users.
+fn cmd_stop(room: &mut Room) -> CommandResult {
+    if room.status != ServerStatus::InProgress {
+        return CommandResult::Error("Quiz is not in progress.".to_string());
     }
 
+    room.finish_quiz();
+
     CommandResult::Ok(Some(
         "Quiz stopped. Results sent to finished users.".to_string(),
     ))
@@ -170,32 +216,74 @@ fn cmd_stop(state: &mut ServerState) -> CommandResult {
 
 /// Quit the server.
 fn cmd_quit(state: &mut ServerState) -> CommandResult {
-    // Send HostEndedQuiz to all connected users
-    state.broadcast_all(ServerMessage::HostEndedQuiz);
+    state.close_connections();
     state.should_quit = true;
     CommandResult::Quit
 }
 
+/// Show a message from the host on every client in the room.
+fn cmd_announce(room: &mut Room, text: &str) -> CommandResult {
+    if text.is_empty() {
+        return CommandResult::Error("Usage: announce <message>".to_string());
+    }
+
+    room.broadcast_all(ServerMessage::Announcement {
+        text: text.to_string(),
+    });
+    CommandResult::Ok(Some(format!("Announced: {}", text)))
+}
+
 /// Kick a user.
-fn cmd_kick(state: &mut ServerState, args: &[&str]) -> CommandResult {
+fn cmd_kick(room: &mut Room, args: &[&str]) -> CommandResult {
     if args.is_empty() {
         return CommandResult::Error("Usage: kick <username>".to_string());
     }
 
     let username = args[0];
 
-    if let Some(session) = state.get_user_by_name_mut(username) {
+    if let Some(session) = room.get_user_by_name_mut(username) {
         session.send(ServerMessage::Kicked {
             reason: "Kicked by host".to_string(),
         });
-        session.sender = None;
-        session.status = UserStatus::Disconnected;
+        session.disconnect();
+        room.broadcast_lobby();
         CommandResult::Ok(Some(format!("Kicked user: {}", username)))
     } else {
         CommandResult::Error(format!("User not found: {}", username))
     }
 }
 
+/// Stop a user's chat messages reaching the room.
+fn cmd_mute(room: &mut Room, args: &[&str]) -> CommandResult {
+    if args.is_empty() {
+        return CommandResult::Error("Usage: mute <username>".to_string());
+    }
+
+    let username = args[0];
+
+    if room.get_user_by_name(username).is_some() {
+        room.muted.insert(username.to_string());
+        CommandResult::Ok(Some(format!("Muted user: {}", username)))
+    } else {
+        CommandResult::Error(format!("User not found: {}", username))
+    }
+}
+
+/// Let a muted user chat again.
+fn cmd_unmute(room: &mut Room, args: &[&str]) -> CommandResult {
+    if args.is_empty() {
+        return CommandResult::Error("Usage: unmute <username>".to_string());
+    }
+
+    let username = args[0];
+
+    if room.muted.remove(username) {
+        CommandResult::Ok(Some(format!("Unmuted user: {}", username)))
+    } else {
+        CommandResult::Error(format!("User not muted: {}", username))
+    }
+}
+
 /// Ban a user (kick + ban IP).
 fn cmd_ban(state: &mut ServerState, args: &[&str]) -> CommandResult {
     if args.is_empty() {
@@ -204,17 +292,17 @@ fn cmd_ban(state: &mut ServerState, args: &[&str]) -> CommandResult {
 
     let username = args[0];
 
-    if let Some(session) = state.get_user_by_name(username) {
+    if let Some(session) = state.room().get_user_by_name(username) {
         let ip = session.ip_addr;
         state.banned_ips.insert(ip);
 
-        if let Some(session) = state.get_user_by_name_mut(username) {
+        if let Some(session) = state.room_mut().get_user_by_name_mut(username) {
             session.send(ServerMessage::Kicked {
                 reason: "Banned by host".to_string(),
             });
-            session.sender = None;
-            session.status = UserStatus::Disconnected;
+            session.disconnect();
         }
+        state.room().broadcast_lobby();
 
         CommandResult::Ok(Some(format!("Banned user: {} (IP: {})", username, ip)))
     } else {
@@ -241,6 +329,131 @@ fn cmd_unban(state: &mut ServerState, args: &[&str]) -> CommandResult {
     }
 }
 
+/// Pick a random subset of the question bank, or restore the full bank.
+fn cmd_limit(room: &mut Room, args: &[&str]) -> CommandResult {
+    if room.status != ServerStatus::Lobby {
+        return CommandResult::Error("Questions can only be changed in the lobby.".to_string());
+    }
+
+    let Some(arg) = args.first() else {
+        return CommandResult::Error("Usage: limit <count> [by-tag] | limit off".to_string());
+    };
+
+    if arg.to_lowercase() == "off" {
+        room.questions = room.question_bank.clone();
+        return CommandResult::Ok(Some(format!(
+            "Using all {} questions.",
+            room.questions.len()
+        )));
+    }
+
+    let limit = match arg.parse::<usize>() {
+        Ok(limit) if limit > 0 => limit,
+        _ => return CommandResult::Error(format!("Invalid question count: {}", arg)),
+    };
+
+    let sampling = match args.get(1).map(|a| a.to_lowercase()) {
+        None => Sampling::Uniform,
+        Some(mode) if mode == "by-tag" => Sampling::ByTag,
+        Some(mode) => return CommandResult::Error(format!("Unknown sampling mode: {}", mode)),
+    };
+
+    let mut rng = seeded_rng(room.seed);
+    room.questions =
+        sample_questions_with_rng(room.question_bank.clone(), limit, sampling, &mut rng);
+    CommandResult::Ok(Some(format!(
+        "Using {} of {} questions.",
+        room.questions.len(),
+        room.question_bank.len()
+    )))
+}
+
+/// Page through the room's questions, from the given one or the first.
+fn cmd_preview(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let count = state.room().questions.len();
+    let start = match args.first() {
+        None => 1,
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(number) if (1..=count).contains(&number) => number,
+            _ => return CommandResult::Error(format!("No question number {}", arg)),
+        },
+    };
+
+    if state.current_view != ServerView::Preview {
+        state.previous_view = Some(state.current_view.clone());
+        state.current_view = ServerView::Preview;
+    }
+    state.preview_scroll = start - 1;
+    CommandResult::Ok(None)
+}
+
+/// Leave a question out of the quiz before it starts.
+fn cmd_drop(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let room = state.room_mut();
+    if room.status != ServerStatus::Lobby {
+        return CommandResult::Error("Questions can only be changed in the lobby.".to_string());
+    }
+    let Some(arg) = args.first() else {
+        return CommandResult::Error("Usage: drop <question number>".to_string());
+    };
+    let count = room.questions.len();
+    let index = match arg.parse::<usize>() {
+        Ok(number) if (1..=count).contains(&number) => number - 1,
+        _ => return CommandResult::Error(format!("No question number {}", arg)),
+    };
+    if count == 1 {
+        return CommandResult::Error("Can't drop the only question.".to_string());
+    }
+
+    let Some(question) = room.drop_question(index) else {
+        return CommandResult::Error(format!("No question number {}", arg));
+    };
+    let left = room.questions.len();
+    state.preview_scroll = state.preview_scroll.min(left - 1);
+    CommandResult::Ok(Some(format!(
+        "Dropped question {} ({}); {} left.",
+        index + 1,
+        question.text,
+        left
+    )))
+}
+
+/// Add simulated players to the room.
+fn cmd_bots(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let Some(arg) = args.first() else {
+        return CommandResult::Error("Usage: bots <count> [accuracy%, e.g. 70 or 40-90]".to_string());
+    };
+    let count = match arg.parse::<usize>() {
+        Ok(count) if (1..=MAX_BOTS).contains(&count) => count,
+        _ => {
+            return CommandResult::Error(format!(
+                "Invalid bot count: {} (1 to {})",
+                arg, MAX_BOTS
+            ))
+        }
+    };
+    let accuracy = match args.get(1) {
+        None => state.bot_accuracy,
+        Some(arg) => match arg.parse::<BotAccuracy>() {
+            Ok(accuracy) => accuracy,
+            Err(e) => return CommandResult::Error(format!("Invalid accuracy: {}", e)),
+        },
+    };
+    if state.room().status == ServerStatus::Finished {
+        return CommandResult::Error("Quiz has finished.".to_string());
+    }
+
+    state.bot_requests.push(BotRequest {
+        room: state.current_room.clone(),
+        count,
+        accuracy,
+    });
+    CommandResult::Ok(Some(format!(
+        "Adding {} bots answering {} right.",
+        count, accuracy
+    )))
+}
+
 /// View a specific user or all users.
 fn cmd_view(state: &mut ServerState, args: &[&str]) -> CommandResult {
     if args.is_empty() || args[0].to_lowercase() == "all" {
@@ -248,7 +461,7 @@ fn cmd_view(state: &mut ServerState, args: &[&str]) -> CommandResult {
         CommandResult::Ok(Some("Viewing all users.".to_string()))
     } else {
         let username = args[0];
-        if state.get_user_by_name(username).is_some() {
+        if state.room().get_user_by_name(username).is_some() {
             state.current_view = ServerView::UserDetail(username.to_string());
             CommandResult::Ok(Some(format!("Viewing user: {}", username)))
         } else {
@@ -257,6 +470,12 @@ fn cmd_view(state: &mut ServerState, args: &[&str]) -> CommandResult {
     }
 }
 
+/// Rank everyone taking the quiz by points so far.
+fn cmd_leaderboard(state: &mut ServerState) -> CommandResult {
+    state.current_view = ServerView::Leaderboard;
+    CommandResult::Ok(None)
+}
+
 /// List users or bans.
 fn cmd_list(state: &mut ServerState, args: &[&str]) -> CommandResult {
     if args.first().is_some_and(|a| a.to_lowercase() == "bans") {
@@ -268,6 +487,7 @@ fn cmd_list(state: &mut ServerState, args: &[&str]) -> CommandResult {
         }
     } else {
         let users: Vec<String> = state
+            .room()
             .sessions
             .values()
             .filter_map(|s| {
@@ -277,6 +497,7 @@ fn cmd_list(state: &mut ServerState, args: &[&str]) -> CommandResult {
                     UserStatus::Answering(i) => format!("Q{}", i + 1),
                     UserStatus::Finished => "done".to_string(),
                     UserStatus::Disconnected => "disconnected".to_string(),
+                    UserStatus::Abandoned => "abandoned".to_string(),
                     UserStatus::Connected => "connecting".to_string(),
                 };
                 Some(format!("{} ({})", name, status_str))
@@ -291,7 +512,130 @@ fn cmd_list(state: &mut ServerState, args: &[&str]) -> CommandResult {
     }
 }
 
+/// Show the current room, open a new one, or switch to another.
+fn cmd_room(state: &mut ServerState, args: &[&str]) -> CommandResult {
+    let Some(arg) = args.first() else {
+        return CommandResult::Ok(Some(format!("In room {}.", state.current_room)));
+    };
+
+    let (code, message) = if arg.to_lowercase() == "new" {
+        let code = state.open_room();
+        let message = format!("Opened room {}. Players join with HOST/{}", code, code);
+        (code, message)
+    } else {
+        match state.find_room(Some(arg)) {
+            Some(code) => (code.clone(), format!("Switched to room {}.", code)),
+            None => return CommandResult::Error(format!("No room with code: {}", arg)),
+        }
+    };
+
+    state.current_room = code;
+    state.previous_view = None;
+    state.current_view = match state.room().status {
+        ServerStatus::Lobby => ServerView::Lobby,
+        ServerStatus::InProgress | ServerStatus::Finished => ServerView::Analytics,
+    };
+    CommandResult::Ok(Some(message))
+}
+
+/// Replace the room's questions with those in another file.
+fn cmd_load(room: &mut Room, args: &[&str]) -> CommandResult {
+    if room.status != ServerStatus::Lobby {
+        return CommandResult::Error("Questions can only be changed in the lobby.".to_string());
+    }
+    if args.is_empty() {
+        return CommandResult::Error("Usage: load <path>".to_string());
+    }
+
+    let path = args.join(" ");
+    let mut rng = seeded_rng(room.seed);
+    let loaded = load_bank_from_json(&path).and_then(|mut bank| {
+        bank.questions = sample_pools_with_rng(bank.questions, &bank.meta.pools, &mut rng)?;
+        Ok(bank)
+    });
+    match loaded {
+        Ok(bank) => {
+            room.questions = bank.questions.clone();
+            room.question_bank = bank.questions;
+            room.meta = bank.meta;
+            CommandResult::Ok(Some(format!(
+                "Loaded {} questions from {}",
+                room.questions.len(),
+                path
+            )))
+        }
+        Err(e) => CommandResult::Error(format!("{}: {}", path, e)),
+    }
+}
+
+/// Write the room's results to a file.
+fn cmd_results(room: &Room, args: &[&str]) -> CommandResult {
+    let path = match args {
+        ["export", path @ ..] if !path.is_empty() => path.join(" "),
+        _ => return CommandResult::Error("Usage: results export <path>".to_string()),
+    };
+    if room.status == ServerStatus::Lobby {
+        return CommandResult::Error("Quiz hasn't started yet.".to_string());
+    }
+
+    match export_results(room, Path::new(&path)) {
+        Ok(count) => CommandResult::Ok(Some(format!(
+            "Exported results of {} users to {}",
+            count, path
+        ))),
+        Err(e) => CommandResult::Error(format!("Failed to export results: {}", e)),
+    }
+}
+
+/// Save the room's raw answers for analysis.
+fn cmd_export(room: &Room, args: &[&str]) -> CommandResult {
+    let path = match args {
+        ["analytics", path @ ..] if !path.is_empty() => path.join(" "),
+        _ => return CommandResult::Error("Usage: export analytics <path>".to_string()),
+    };
+    if room.status == ServerStatus::Lobby {
+        return CommandResult::Error("Quiz hasn't started yet.".to_string());
+    }
+
+    match export_analytics(room, Path::new(&path)) {
+        Ok((rows, summary)) => CommandResult::Ok(Some(format!(
+            "Exported {} rows to {} and a summary to {}",
+            rows,
+            path,
+            summary.display()
+        ))),
+        Err(e) => CommandResult::Error(format!("Failed to export analytics: {}", e)),
+    }
+}
+
+/// List the open rooms.
+fn cmd_rooms(state: &mut ServerState) -> CommandResult {
+    let rooms: Vec<String> = state
+        .rooms
+        .values()
+        .map(|room| {
+            let status = match room.status {
+                ServerStatus::Lobby => "lobby",
+                ServerStatus::InProgress if room.paused => "paused",
+                ServerStatus::InProgress => "in progress",
+                ServerStatus::Finished => "finished",
+            };
+            format!("{} ({}, {} named)", room.code, status, room.named_user_count())
+        })
+        .collect();
+    CommandResult::Ok(Some(format!("Rooms: {}", rooms.join(", "))))
+}
+
 /// Show help by switching to Help view.
+fn cmd_log(state: &mut ServerState) -> CommandResult {
+    if state.current_view != ServerView::Log {
+        state.previous_view = Some(state.current_view.clone());
+        state.current_view = ServerView::Log;
+    }
+    state.log_scroll = 0;
+    CommandResult::Ok(None)
+}
+
 fn cmd_help(state: &mut ServerState) -> CommandResult {
     // Save current view so we can return to it
     state.previous_view = Some(state.current_view.clone());
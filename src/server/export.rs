@@ -0,0 +1,332 @@
+//! Writing a room's results to a file for grading.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::models::format_answer;
+
+use super::state::{self, Room};
+
+/// Columns of the CSV before the answer to each question.
+const CSV_COLUMNS: [&str; 6] = ["username", "score", "correct", "answered", "finished", "seconds"];
+
+/// Columns of the analytics CSV, one row per player per question.
+const ANALYTICS_COLUMNS: [&str; 6] =
+    ["username", "question", "answer", "correct", "answered_at", "seconds"];
+
+/// Columns of the analytics summary, one row per question.
+const SUMMARY_COLUMNS: [&str; 7] = [
+    "question",
+    "text",
+    "answered",
+    "correct",
+    "percent_correct",
+    "average_seconds",
+    "most_common_answer",
+];
+
+/// Everything `results export` writes as JSON.
+#[derive(Debug, Serialize)]
+struct ResultsFile<'a> {
+    room: &'a str,
+    title: Option<&'a str>,
+    exported_at: String,
+    questions: usize,
+    max_score: usize,
+    players: Vec<PlayerResult>,
+}
+
+/// One player's results.
+#[derive(Debug, Serialize)]
+struct PlayerResult {
+    username: String,
+    score: usize,
+    correct: usize,
+    answered: usize,
+    finished: bool,
+    /// Seconds from the first question to the last answer.
+    seconds: Option<f64>,
+    answers: Vec<AnswerRecord>,
+}
+
+/// One answer of a player.
+#[derive(Debug, Serialize)]
+struct AnswerRecord {
+    /// Question number, from 1.
+    question: usize,
+    /// Option letters given, e.g. `"A, C"`, or `None` if unanswered.
+    answer: Option<String>,
+    correct: bool,
+    /// Seconds spent on this question.
+    seconds: Option<f64>,
+}
+
+/// Write the results of everyone who took the quiz in `room`, as CSV if
+/// `path` ends in `.csv` and JSON otherwise. Returns the number of players.
+///
+/// The CSV has a row per player with their answer to each question in
+/// columns `Q1`, `Q2`, ..., for opening in a spreadsheet.
+pub(crate) fn export_results(
+    room: &Room,
+    path: &Path,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let players = player_results(room);
+    let count = players.len();
+
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    if is_csv {
+        fs::write(path, to_csv(&players, room.questions.len())?)?;
+    } else {
+        fs::write(path, to_json(room, players)? + "\n")?;
+    }
+    Ok(count)
+}
+
+/// The results of everyone who took the quiz in `room` as JSON, as
+/// `results export` writes them.
+pub(crate) fn results_json(room: &Room) -> serde_json::Result<String> {
+    to_json(room, player_results(room))
+}
+
+fn to_json(room: &Room, players: Vec<PlayerResult>) -> serde_json::Result<String> {
+    let file = ResultsFile {
+        room: &room.code,
+        title: room.meta.title.as_deref(),
+        exported_at: chrono::Local::now().to_rfc3339(),
+        questions: room.questions.len(),
+        max_score: room.max_score(),
+        players,
+    };
+    serde_json::to_string_pretty(&file)
+}
+
+/// Results of everyone who took the quiz, best score first.
+fn player_results(room: &Room) -> Vec<PlayerResult> {
+    let mut players: Vec<_> = room
+        .sessions
+        .values()
+        .filter(|s| !s.answers.is_empty())
+        .filter_map(|session| {
+            let username = session.username.clone()?;
+            let times = session.answer_times();
+            let answers = room
+                .questions
+                .iter()
+                .enumerate()
+                .map(|(i, question)| {
+                    let answer = session.answers.get(i).cloned().flatten();
+                    AnswerRecord {
+                        question: i + 1,
+                        correct: answer.as_ref().is_some_and(|a| question.is_correct(a)),
+                        answer: answer.map(|a| format_answer(&a)),
+                        seconds: times.get(i).copied().flatten().map(state::seconds),
+                    }
+                })
+                .collect();
+
+            Some(PlayerResult {
+                username,
+                score: session.calculate_score(&room.questions, room.speed_bonus),
+                correct: session.correct_count(&room.questions),
+                answered: session.answered_count(),
+                finished: session.is_finished(),
+                seconds: session.total_time().map(state::seconds),
+                answers,
+            })
+        })
+        .collect();
+
+    players.sort_by(|a, b| b.score.cmp(&a.score).then(a.username.cmp(&b.username)));
+    players
+}
+
+/// Write every answer in `room` to `path` as CSV, a row per player per
+/// question, and a summary of each question beside it in
+/// `<name>-summary.csv`. Returns the number of rows and the summary's path.
+///
+/// Unlike the live answers on the host's screen, this covers every answer
+/// given since the quiz started.
+pub(crate) fn export_analytics(
+    room: &Room,
+    path: &Path,
+) -> Result<(usize, PathBuf), Box<dyn std::error::Error>> {
+    let mut sessions: Vec<_> = room
+        .sessions
+        .values()
+        .filter(|s| s.username.is_some() && !s.answers.is_empty())
+        .collect();
+    sessions.sort_by(|a, b| a.username.cmp(&b.username));
+    let times: Vec<_> = sessions.iter().map(|s| s.answer_times()).collect();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(ANALYTICS_COLUMNS)?;
+    let mut rows = 0;
+    for (session, times) in sessions.iter().zip(&times) {
+        for (i, question) in room.questions.iter().enumerate() {
+            let answer = session.answers.get(i).cloned().flatten();
+            let answered_at = session.answered_at.get(i).copied().flatten();
+            writer.write_record([
+                session.username.clone().unwrap_or_default(),
+                (i + 1).to_string(),
+                answer.as_deref().map(format_answer).unwrap_or_default(),
+                answer.as_ref().is_some_and(|a| question.is_correct(a)).to_string(),
+                answered_at.map(|at| wall_clock(at).to_rfc3339()).unwrap_or_default(),
+                times
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .map(|t| state::seconds(t).to_string())
+                    .unwrap_or_default(),
+            ])?;
+            rows += 1;
+        }
+    }
+    fs::write(path, writer.into_inner()?)?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(SUMMARY_COLUMNS)?;
+    for (i, question) in room.questions.iter().enumerate() {
+        let answers: Vec<&Vec<usize>> = sessions
+            .iter()
+            .filter_map(|s| s.answers.get(i)?.as_ref())
+            .collect();
+        let seconds: Vec<f64> = times
+            .iter()
+            .filter_map(|times| times.get(i).copied().flatten())
+            .map(state::seconds)
+            .collect();
+        let correct = answers.iter().filter(|a| question.is_correct(a)).count();
+
+        // Most common answer, earliest option first on a tie
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for answer in &answers {
+            let answer = format_answer(answer);
+            match counts.iter_mut().find(|(a, _)| *a == answer) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((answer, 1)),
+            }
+        }
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        writer.write_record([
+            (i + 1).to_string(),
+            question.text.clone(),
+            answers.len().to_string(),
+            correct.to_string(),
+            percent(correct, answers.len()),
+            if seconds.is_empty() {
+                String::new()
+            } else {
+                format!("{:.1}", seconds.iter().sum::<f64>() / seconds.len() as f64)
+            },
+            counts.first().map(|(answer, _)| answer.clone()).unwrap_or_default(),
+        ])?;
+    }
+    let summary_path = summary_path(path);
+    fs::write(&summary_path, writer.into_inner()?)?;
+
+    Ok((rows, summary_path))
+}
+
+/// `answers.csv` becomes `answers-summary.csv`.
+fn summary_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-summary.{}", stem, ext.to_string_lossy()),
+        None => format!("{}-summary", stem),
+    };
+    path.with_file_name(name)
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        String::new()
+    } else {
+        format!("{:.0}", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// The time of day an `Instant` in this run fell on.
+fn wall_clock(at: Instant) -> DateTime<Local> {
+    let ago = chrono::Duration::from_std(at.elapsed()).unwrap_or_default();
+    Local::now() - ago
+}
+
+fn to_csv(
+    players: &[PlayerResult],
+    questions: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    let mut header: Vec<String> = CSV_COLUMNS.map(String::from).to_vec();
+    header.extend((1..=questions).map(|i| format!("Q{}", i)));
+    writer.write_record(&header)?;
+
+    for player in players {
+        let mut record = vec![
+            player.username.clone(),
+            player.score.to_string(),
+            player.correct.to_string(),
+            player.answered.to_string(),
+            player.finished.to_string(),
+            player.seconds.map(|s| s.to_string()).unwrap_or_default(),
+        ];
+        record.extend(
+            player
+                .answers
+                .iter()
+                .map(|a| a.answer.clone().unwrap_or_default()),
+        );
+        writer.write_record(&record)?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::models::{Question, QuizMeta};
+    use crate::server::state::UserSession;
+
+    #[test]
+    fn test_export_results_csv() {
+        let question = Question::new("Pick b", ["a", "b", "c", "d"], 1);
+        let questions = vec![question.clone(), question];
+        let mut room = Room::new("ABCD".to_string(), questions, QuizMeta::default());
+        // bobby joined but never got a question
+        let players = [("alice", vec![Some(vec![1]), Some(vec![0, 2])]), ("bobby", vec![])];
+        for (name, answers) in players {
+            let mut session =
+                UserSession::new(IpAddr::from([127, 0, 0, 1]), mpsc::unbounded_channel().0);
+            session.username = Some(name.to_string());
+            if !answers.is_empty() {
+                session.init_answers(2);
+                session.answers = answers;
+            }
+            room.sessions.insert(session.id, session);
+        }
+
+        let path = std::env::temp_dir()
+            .join(format!("rust-quiz-results-{}.csv", std::process::id()));
+        assert_eq!(export_results(&room, &path).unwrap(), 1);
+        let csv = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("username,score,correct,answered,finished,seconds,Q1,Q2"));
+        // Multiple answers are quoted, as they contain a comma
+        assert_eq!(lines.next(), Some("alice,1,1,2,false,,B,\"A, C\""));
+        assert_eq!(lines.next(), None);
+    }
+}
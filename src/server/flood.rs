@@ -0,0 +1,111 @@
+//! Limiting how fast a connection may send messages.
+
+use std::time::{Duration, Instant};
+
+/// Messages a connection may send in one `MESSAGE_WINDOW`; the rest are
+/// dropped.
+const MAX_MESSAGES: u32 = 10;
+const MESSAGE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Strikes a connection may collect in one `STRIKE_WINDOW` before it is
+/// closed. Dropped, repeated and malformed messages are strikes.
+const MAX_STRIKES: u32 = 10;
+const STRIKE_WINDOW: Duration = Duration::from_secs(10);
+
+/// What to do with a message from a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    /// Handle the message.
+    Allow,
+    /// Ignore the message.
+    Drop,
+    /// Close the connection.
+    Disconnect,
+}
+
+/// Message and strike counts of one connection.
+pub(crate) struct FloodGuard {
+    window_start: Instant,
+    messages: u32,
+    strike_window_start: Instant,
+    strikes: u32,
+}
+
+impl FloodGuard {
+    pub(crate) fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            window_start: now,
+            messages: 0,
+            strike_window_start: now,
+            strikes: 0,
+        }
+    }
+
+    /// Count a message received from the connection.
+    pub(crate) fn check(&mut self) -> Verdict {
+        self.check_at(Instant::now())
+    }
+
+    /// Count a message the server refused, such as a second answer to the
+    /// same question.
+    pub(crate) fn strike(&mut self) -> Verdict {
+        self.strike_at(Instant::now())
+    }
+
+    fn check_at(&mut self, now: Instant) -> Verdict {
+        if now.duration_since(self.window_start) >= MESSAGE_WINDOW {
+            self.window_start = now;
+            self.messages = 0;
+        }
+        self.messages += 1;
+
+        if self.messages > MAX_MESSAGES {
+            match self.strike_at(now) {
+                Verdict::Allow => Verdict::Drop,
+                verdict => verdict,
+            }
+        } else {
+            Verdict::Allow
+        }
+    }
+
+    fn strike_at(&mut self, now: Instant) -> Verdict {
+        if now.duration_since(self.strike_window_start) >= STRIKE_WINDOW {
+            self.strike_window_start = now;
+            self.strikes = 0;
+        }
+        self.strikes += 1;
+
+        if self.strikes > MAX_STRIKES {
+            Verdict::Disconnect
+        } else {
+            Verdict::Allow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flood_guard() {
+        let mut guard = FloodGuard::new();
+        let start = Instant::now();
+
+        for _ in 0..MAX_MESSAGES {
+            assert_eq!(guard.check_at(start), Verdict::Allow);
+        }
+        assert_eq!(guard.check_at(start), Verdict::Drop);
+        // A new window lets messages through again
+        assert_eq!(guard.check_at(start + MESSAGE_WINDOW), Verdict::Allow);
+
+        // One strike is already counted from the dropped message
+        for _ in 1..MAX_STRIKES {
+            assert_eq!(guard.strike_at(start + MESSAGE_WINDOW), Verdict::Allow);
+        }
+        assert_eq!(guard.strike_at(start + MESSAGE_WINDOW), Verdict::Disconnect);
+        assert_eq!(guard.strike_at(start + STRIKE_WINDOW), Verdict::Allow);
+    }
+}
@@ -0,0 +1,234 @@
+//! A small read-only HTTP API beside the WebSocket listener, so scoreboard
+//! pages and other tools can poll a quiz without speaking its protocol.
+//!
+//! Every endpoint answers `GET` with JSON and takes an optional
+//! `?room=CODE`, defaulting to the first room:
+//!
+//! - `/status`: the room's progress and player counts
+//! - `/leaderboard`: everyone who has started, best score first
+//! - `/results.json`: every player's answers, as `results export` writes,
+//!   once the quiz has finished
+//!
+//! `/metrics` serves every room's metrics for Prometheus instead.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::export::results_json;
+use super::metrics;
+use super::server::SharedState;
+use super::state::{self, Room, ServerState, ServerStatus, UserStatus};
+
+/// Largest request head read before giving up on a client.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+/// How long a client has to send its whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Content type of every response but `/metrics`.
+const JSON: &str = "application/json";
+
+/// Progress of a room, served at `/status`.
+#[derive(Debug, Serialize)]
+struct Status<'a> {
+    room: &'a str,
+    title: Option<&'a str>,
+    status: ServerStatus,
+    paused: bool,
+    questions: usize,
+    max_score: usize,
+    /// Connected players with a username.
+    players: usize,
+    answering: usize,
+    finished: usize,
+    /// Codes of every open room.
+    rooms: Vec<&'a str>,
+}
+
+/// A player's place, served at `/leaderboard`.
+#[derive(Debug, Serialize)]
+struct LeaderboardRow {
+    rank: usize,
+    username: String,
+    score: usize,
+    total: usize,
+    answered: usize,
+    finished: bool,
+    /// Seconds from the first question to the last answer.
+    seconds: Option<f64>,
+}
+
+/// Accept HTTP connections until the server shuts down.
+pub(crate) async fn serve(listener: TcpListener, state: SharedState) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_request(stream, state.clone()));
+            }
+            Err(e) => {
+                eprintln!("Failed to accept HTTP connection: {}", e);
+            }
+        }
+    }
+}
+
+/// Read one request, answer it and close the connection.
+async fn handle_request(mut stream: TcpStream, state: SharedState) -> std::io::Result<()> {
+    // However slowly it trickles in, the request must arrive in time
+    let Some(request) = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream)).await??
+    else {
+        return Ok(());
+    };
+
+    let head = String::from_utf8_lossy(&request);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" => respond(&*state.lock().await, target),
+        _ => error("405 Method Not Allowed", "Only GET is supported"),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nCache-Control: no-store\r\n\
+         Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    if method != "HEAD" {
+        stream.write_all(body.as_bytes()).await?;
+    }
+    stream.shutdown().await
+}
+
+/// Read up to the end of the request's headers, as only the request line
+/// matters; `None` if the client gives up or sends too much.
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<Vec<u8>>> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        if request.len() > MAX_REQUEST_SIZE {
+            return Ok(None);
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    Ok(Some(request))
+}
+
+/// Status line, content type and body for a `GET` of `target`, e.g.
+/// `/status?room=ABCD`.
+fn respond(state: &ServerState, target: &str) -> (&'static str, &'static str, String) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path == "/metrics" {
+        return ("200 OK", metrics::CONTENT_TYPE, metrics::render(state));
+    }
+
+    let code = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("room="))
+        .filter(|code| !code.is_empty());
+    let Some(room) = state.find_room(code).and_then(|code| state.rooms.get(&code)) else {
+        return error("404 Not Found", "No such room");
+    };
+
+    let body = match path {
+        "/status" => serde_json::to_string(&status(state, room)),
+        "/leaderboard" => serde_json::to_string(&leaderboard(room)),
+        // Players could copy the right answers from each other's before then
+        "/results.json" if room.status != ServerStatus::Finished => {
+            return error("403 Forbidden", "Results are served once the quiz has finished");
+        }
+        "/results.json" => results_json(room),
+        _ => return error("404 Not Found", "Unknown endpoint"),
+    };
+    match body {
+        Ok(body) => ("200 OK", JSON, body),
+        Err(e) => error("500 Internal Server Error", &e.to_string()),
+    }
+}
+
+fn status<'a>(state: &'a ServerState, room: &'a Room) -> Status<'a> {
+    let count = |wanted: fn(&UserStatus) -> bool| {
+        room.sessions
+            .values()
+            .filter(|s| s.username.is_some() && wanted(&s.status))
+            .count()
+    };
+
+    Status {
+        room: &room.code,
+        title: room.meta.title.as_deref(),
+        status: room.status,
+        paused: room.paused,
+        questions: room.questions.len(),
+        max_score: room.max_score(),
+        players: room.player_count(),
+        answering: count(|status| matches!(status, UserStatus::Answering(_))),
+        finished: count(|status| matches!(status, UserStatus::Finished)),
+        rooms: state.rooms.keys().map(String::as_str).collect(),
+    }
+}
+
+fn leaderboard(room: &Room) -> Vec<LeaderboardRow> {
+    room.generate_standings("")
+        .into_iter()
+        .map(|entry| {
+            let seconds = room
+                .username_to_id
+                .get(&entry.username)
+                .and_then(|id| room.sessions.get(id))
+                .and_then(|session| session.total_time())
+                .map(state::seconds);
+            LeaderboardRow {
+                rank: entry.rank,
+                username: entry.username,
+                score: entry.score,
+                total: room.max_score(),
+                answered: entry.answered,
+                finished: entry.finished,
+                seconds,
+            }
+        })
+        .collect()
+}
+
+fn error(status: &'static str, message: &str) -> (&'static str, &'static str, String) {
+    (status, JSON, serde_json::json!({ "error": message }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Question, QuizMeta};
+
+    #[test]
+    fn test_respond() {
+        let questions = vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)];
+        let mut state = ServerState::new(questions, QuizMeta::default(), 8712);
+        let code = state.default_room.clone();
+
+        let (status, _, body) = respond(&state, "/status");
+        assert_eq!(status, "200 OK");
+        assert!(body.contains(&format!("\"room\":\"{}\"", code)), "{}", body);
+        assert!(body.contains("\"status\":\"Lobby\""));
+
+        let lowercase = format!("/leaderboard?room={}", code.to_lowercase());
+        assert_eq!(respond(&state, &lowercase), ("200 OK", JSON, "[]".to_string()));
+        assert_eq!(respond(&state, "/results.json").0, "403 Forbidden");
+        assert_eq!(respond(&state, "/status?room=ZZ").0, "404 Not Found");
+        assert_eq!(respond(&state, "/").0, "404 Not Found");
+
+        state.room_mut().status = ServerStatus::Finished;
+        assert_eq!(respond(&state, "/results.json").0, "200 OK");
+    }
+}
@@ -0,0 +1,201 @@
+//! The host's command line: editing and recalling earlier commands.
+
+/// Earlier commands kept for recall with Up and Down.
+const MAX_RECALL: usize = 100;
+
+/// Text typed at the host's command prompt, with a cursor.
+#[derive(Debug, Default)]
+pub(crate) struct CommandInput {
+    text: String,
+    /// Byte offset of the cursor, always on a character boundary.
+    cursor: usize,
+    /// Commands entered so far, oldest first.
+    recall: Vec<String>,
+    /// Index in `recall` being shown, while recalling.
+    recalling: Option<usize>,
+    /// What was typed before recalling started, restored by Down.
+    draft: String,
+}
+
+impl CommandInput {
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Text before the cursor, for placing it on screen.
+    pub(crate) fn before_cursor(&self) -> &str {
+        &self.text[..self.cursor]
+    }
+
+    pub(crate) fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Delete the character before the cursor.
+    pub(crate) fn backspace(&mut self) {
+        if let Some(c) = self.before_cursor().chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    /// Delete the character under the cursor.
+    pub(crate) fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    /// Delete the word before the cursor and any spaces after it.
+    pub(crate) fn delete_word(&mut self) {
+        let start = self.word_start();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    pub(crate) fn left(&mut self) {
+        if let Some(c) = self.before_cursor().chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub(crate) fn right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    /// Move to the start of the word before the cursor.
+    pub(crate) fn word_left(&mut self) {
+        self.cursor = self.word_start();
+    }
+
+    /// Move past the end of the word after the cursor.
+    pub(crate) fn word_right(&mut self) {
+        let rest = &self.text[self.cursor..];
+        let word = rest.len() - rest.trim_start().len();
+        let end = rest[word..].find(' ').map_or(rest.len(), |i| word + i);
+        self.cursor += end;
+    }
+
+    pub(crate) fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub(crate) fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    /// Clear the line, leaving recall as it was.
+    pub(crate) fn clear(&mut self) {
+        self.set_text(String::new());
+        self.recalling = None;
+    }
+
+    /// Show the command entered before the one shown.
+    pub(crate) fn previous(&mut self) {
+        let index = match self.recalling {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.recall.is_empty() => return,
+            None => {
+                self.draft = std::mem::take(&mut self.text);
+                self.recall.len() - 1
+            }
+        };
+        self.recalling = Some(index);
+        self.set_text(self.recall[index].clone());
+    }
+
+    /// Show the command entered after the one shown, or what was being
+    /// typed after the newest.
+    pub(crate) fn next(&mut self) {
+        let Some(index) = self.recalling else {
+            return;
+        };
+        if index + 1 < self.recall.len() {
+            self.recalling = Some(index + 1);
+            self.set_text(self.recall[index + 1].clone());
+        } else {
+            self.recalling = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.set_text(draft);
+        }
+    }
+
+    /// Take the line to run it, remembering it for recall.
+    pub(crate) fn submit(&mut self) -> String {
+        let line = std::mem::take(&mut self.text);
+        self.clear();
+        let command = line.trim();
+        if !command.is_empty() && self.recall.last().map(String::as_str) != Some(command) {
+            self.recall.push(command.to_string());
+            if self.recall.len() > MAX_RECALL {
+                self.recall.remove(0);
+            }
+        }
+        line
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.cursor = self.text.len();
+    }
+
+    /// Start of the word before the cursor, skipping spaces after it.
+    fn word_start(&self) -> usize {
+        let before = self.before_cursor().trim_end();
+        before.rfind(' ').map_or(0, |i| i + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> CommandInput {
+        let mut input = CommandInput::default();
+        text.chars().for_each(|c| input.insert(c));
+        input
+    }
+
+    #[test]
+    fn test_editing_and_recall() {
+        let mut input = typed("ban alce");
+        input.left();
+        input.left();
+        input.insert('i');
+        input.word_left();
+        input.left();
+        input.backspace();
+        assert_eq!(input.as_str(), "ba alice");
+        assert_eq!(input.before_cursor(), "ba");
+        input.end();
+        input.delete_word();
+        assert_eq!(input.as_str(), "ba ");
+        input.home();
+        input.word_right();
+        assert_eq!(input.before_cursor(), "ba");
+
+        input.clear();
+        "kick bob".chars().for_each(|c| input.insert(c));
+        assert_eq!(input.submit(), "kick bob");
+        "start".chars().for_each(|c| input.insert(c));
+        input.submit();
+        "ann".chars().for_each(|c| input.insert(c));
+
+        input.previous();
+        assert_eq!(input.as_str(), "start");
+        input.previous();
+        input.previous();
+        assert_eq!(input.as_str(), "kick bob");
+        input.next();
+        input.next();
+        assert_eq!(input.as_str(), "ann");
+    }
+}
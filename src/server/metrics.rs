@@ -0,0 +1,101 @@
+//! Server metrics in the Prometheus text format, served at `/metrics`.
+
+use std::fmt::Write;
+
+use super::state::{Room, ServerState, ServerStatus, UserStatus};
+
+/// Content type of the Prometheus text format.
+pub(crate) const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// A metric reported for each room.
+struct RoomMetric {
+    name: &'static str,
+    /// `gauge` or `counter`.
+    kind: &'static str,
+    help: &'static str,
+    value: fn(&Room) -> u64,
+}
+
+const ROOM_METRICS: [RoomMetric; 6] = [
+    RoomMetric {
+        name: "quiz_connected_clients",
+        kind: "gauge",
+        help: "Open connections, named or not.",
+        value: |room| room.sessions.values().filter(|s| s.is_connected()).count() as u64,
+    },
+    RoomMetric {
+        name: "quiz_players",
+        kind: "gauge",
+        help: "Connected players with a username.",
+        value: |room| room.player_count() as u64,
+    },
+    RoomMetric {
+        name: "quiz_answering_players",
+        kind: "gauge",
+        help: "Players still answering questions.",
+        value: |room| count_status(room, |status| matches!(status, UserStatus::Answering(_))),
+    },
+    RoomMetric {
+        name: "quiz_finished_players",
+        kind: "gauge",
+        help: "Players who have answered every question.",
+        value: |room| count_status(room, |status| matches!(status, UserStatus::Finished)),
+    },
+    RoomMetric {
+        name: "quiz_in_progress",
+        kind: "gauge",
+        help: "Whether a quiz is running (1) or not (0).",
+        value: |room| u64::from(room.status == ServerStatus::InProgress),
+    },
+    RoomMetric {
+        name: "quiz_answers_total",
+        kind: "counter",
+        help: "Answers accepted since the room opened.",
+        value: |room| room.answers_received,
+    },
+];
+
+/// Render the metrics of every room, labelled with its code.
+pub(crate) fn render(state: &ServerState) -> String {
+    let mut out = String::new();
+    // Writing to a String never fails
+    let _ = writeln!(out, "# HELP quiz_rooms Open rooms.");
+    let _ = writeln!(out, "# TYPE quiz_rooms gauge");
+    let _ = writeln!(out, "quiz_rooms {}", state.rooms.len());
+
+    for metric in ROOM_METRICS {
+        let _ = writeln!(out, "# HELP {} {}", metric.name, metric.help);
+        let _ = writeln!(out, "# TYPE {} {}", metric.name, metric.kind);
+        for room in state.rooms.values() {
+            let value = (metric.value)(room);
+            let _ = writeln!(out, "{}{{room=\"{}\"}} {}", metric.name, room.code, value);
+        }
+    }
+    out
+}
+
+fn count_status(room: &Room, wanted: fn(&UserStatus) -> bool) -> u64 {
+    room.sessions
+        .values()
+        .filter(|s| s.username.is_some() && wanted(&s.status))
+        .count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::QuizMeta;
+
+    #[test]
+    fn test_render_metrics() {
+        let mut state = ServerState::new(Vec::new(), QuizMeta::default(), 8712);
+        let code = state.default_room.clone();
+        state.room_mut().answers_received = 7;
+
+        let metrics = render(&state);
+        assert!(metrics.contains("quiz_rooms 1\n"));
+        assert!(metrics.contains("# TYPE quiz_answers_total counter\n"));
+        assert!(metrics.contains(&format!("quiz_answers_total{{room=\"{}\"}} 7\n", code)));
+        assert!(metrics.contains(&format!("quiz_players{{room=\"{}\"}} 0\n", code)));
+    }
+}
@@ -2,9 +2,26 @@
 //!
 //! Provides WebSocket-based multiplayer quiz hosting.
 
+mod blocklist;
+mod bots;
 mod commands;
+mod export;
+mod flood;
+mod http;
+mod input;
+mod metrics;
+mod replay;
+#[allow(clippy::module_inception)]
 mod server;
+mod snapshot;
 mod state;
+mod tls;
+mod transcript;
 mod ui;
 
-pub use server::run;
+pub use bots::BotAccuracy;
+pub use replay::replay;
+pub use server::{run, PlayerInfo, QuizServer, RoomInfo, ServeOptions};
+pub use state::{ServerStatus, UsernamePolicy};
+pub use tls::TlsConfig;
+pub use transcript::{read_transcript, Entry, Event};
@@ -0,0 +1,589 @@
+//! Playing back a transcript in the host's screens, to review how a session
+//! unfolded or look into a player's report.
+//!
+//! The rooms are rebuilt from the transcript one event at a time, at the
+//! pace things happened or faster, and shown in the lobby, analytics,
+//! leaderboard and log views as the host saw them.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::backend::Backend;
+use ratatui::Terminal;
+use tokio::sync::mpsc;
+
+use crate::models::QuizMeta;
+use crate::terminal;
+use crate::theme::Theme;
+use crate::QuizError;
+
+use super::state::{ChatLine, Room, ServerState, ServerStatus, ServerView, UserSession, UserStatus};
+use super::transcript::{read_transcript, Entry, Event};
+use super::ui;
+
+/// Slowest and fastest a replay plays, as a multiple of the real pace.
+const SPEED_LIMITS: (f64, f64) = (0.25, 64.0);
+/// Lines Page Up and Page Down scroll the log by.
+const LOG_PAGE: usize = 10;
+
+/// Play back the transcript at `path` at `speed` times the pace it was
+/// recorded at, until the viewer quits.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::server;
+/// use rust_quiz::theme::Theme;
+///
+/// # fn review() -> Result<(), rust_quiz::QuizError> {
+/// // Twice as fast as it happened
+/// server::replay("session.jsonl", 2.0, Theme::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn replay<P: AsRef<Path>>(path: P, speed: f64, theme: Theme) -> Result<(), QuizError> {
+    let mut replay = open(path.as_ref(), speed)?;
+
+    let mut terminal = terminal::init().map_err(QuizError::Terminal)?;
+    let result = run_event_loop(&mut terminal, &mut replay, &theme);
+    terminal::restore().map_err(QuizError::Terminal)?;
+    result
+}
+
+/// Read the transcript at `path` into a replay at its start.
+fn open(path: &Path, speed: f64) -> Result<Replay, QuizError> {
+    let file_error = |error| QuizError::File {
+        path: path.to_path_buf(),
+        error,
+    };
+    let entries = read_transcript(path).map_err(file_error)?;
+    let mut replay = Replay::new(entries).ok_or_else(|| {
+        file_error(io::Error::new(io::ErrorKind::InvalidData, "No events to replay"))
+    })?;
+    replay.speed = speed.clamp(SPEED_LIMITS.0, SPEED_LIMITS.1);
+    Ok(replay)
+}
+
+fn run_event_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    replay: &mut Replay,
+    theme: &Theme,
+) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
+    let mut last_tick = Instant::now();
+    loop {
+        let now = Instant::now();
+        replay.advance(now - last_tick);
+        last_tick = now;
+
+        let status = replay.status();
+        terminal
+            .draw(|frame| ui::render_replay(frame, &replay.state, &status, theme))
+            .map_err(terminal::backend_error::<B>)?;
+
+        // Wake up now and then to move the replay on
+        if event::poll(Duration::from_millis(100))? {
+            match event::read()? {
+                TermEvent::Key(key) if key.kind == KeyEventKind::Press => {
+                    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                    if replay.handle_key(key.code, ctrl) {
+                        return Ok(());
+                    }
+                }
+                TermEvent::Resize(..) => terminal::handle_resize(terminal)?,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A transcript being played back.
+struct Replay {
+    /// Each event, with how long after the first it happened.
+    events: Vec<(Duration, Entry)>,
+    /// How many events have been played.
+    played: usize,
+    /// How far into the session the replay has got.
+    clock: Duration,
+    /// Multiple of the real pace the replay plays at.
+    speed: f64,
+    paused: bool,
+    /// The rooms as they were at `clock`.
+    state: ServerState,
+    /// When the first event happened, as far as the rebuilt sessions know,
+    /// so answer times come out as they were.
+    origin: Instant,
+}
+
+impl Replay {
+    /// Set up a replay of `entries` at its start; `None` if none has a time
+    /// that can be read.
+    fn new(entries: Vec<Entry>) -> Option<Self> {
+        let mut timed: Vec<_> = entries
+            .into_iter()
+            .filter_map(|entry| Some((DateTime::parse_from_rfc3339(&entry.at).ok()?, entry)))
+            .collect();
+        let first = timed.first()?.0;
+
+        // A clock set back mid-session mustn't play events out of order
+        let mut latest = Duration::ZERO;
+        let events = timed
+            .drain(..)
+            .map(|(at, entry)| {
+                latest = latest.max((at - first).to_std().unwrap_or_default());
+                (latest, entry)
+            })
+            .collect::<Vec<_>>();
+
+        let mut state = empty_state(&events[0].1.room);
+        state.current_view = ServerView::Analytics;
+
+        Some(Self {
+            events,
+            played: 0,
+            clock: Duration::ZERO,
+            speed: 1.0,
+            paused: false,
+            state,
+            origin: Instant::now(),
+        })
+    }
+
+    /// How long the recorded session lasted.
+    fn length(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |(at, _)| *at)
+    }
+
+    /// Move the replay on by `elapsed` of real time, unless paused.
+    fn advance(&mut self, elapsed: Duration) {
+        if self.paused {
+            return;
+        }
+        self.clock = (self.clock + elapsed.mul_f64(self.speed)).min(self.length());
+        while let Some((at, _)) = self.events.get(self.played)
+            && *at <= self.clock
+        {
+            self.play_next();
+        }
+    }
+
+    /// Play the next event, jumping the clock to it.
+    fn play_next(&mut self) {
+        let Some((at, entry)) = self.events.get(self.played) else {
+            return;
+        };
+        self.clock = self.clock.max(*at);
+        apply(&mut self.state, self.origin + *at, entry);
+        self.played += 1;
+    }
+
+    /// Go back to just before event `played`, rebuilding the rooms from the
+    /// start.
+    fn rewind(&mut self, played: usize) {
+        let mut state = empty_state(&self.events[0].1.room);
+        state.current_view = std::mem::take(&mut self.state.current_view);
+        state.current_room = std::mem::take(&mut self.state.current_room);
+        state.log_scroll = self.state.log_scroll;
+        self.state = state;
+
+        self.played = 0;
+        self.clock = Duration::ZERO;
+        while self.played < played {
+            self.play_next();
+        }
+        // The room being looked at may not have opened yet
+        if !self.state.rooms.contains_key(&self.state.current_room) {
+            self.state.current_room = self.state.default_room.clone();
+        }
+    }
+
+    /// Handle a key, returning whether the viewer quits.
+    fn handle_key(&mut self, key: KeyCode, ctrl: bool) -> bool {
+        let state = &mut self.state;
+
+        // Arrow and page keys scroll the log
+        if state.current_view == ServerView::Log {
+            let lines = state.room().command_history.len();
+            let scroll = match key {
+                KeyCode::Up => Some(state.log_scroll + 1),
+                KeyCode::PageUp => Some(state.log_scroll + LOG_PAGE),
+                KeyCode::Down => Some(state.log_scroll.saturating_sub(1)),
+                KeyCode::PageDown => Some(state.log_scroll.saturating_sub(LOG_PAGE)),
+                _ => None,
+            };
+            if let Some(scroll) = scroll {
+                state.log_scroll = scroll.min(lines.saturating_sub(1));
+                return false;
+            }
+        }
+
+        match key {
+            KeyCode::Char('c') if ctrl => return true,
+            KeyCode::Char('q') => return true,
+            KeyCode::Esc if state.current_view == ServerView::Log => {
+                state.current_view = state.previous_view.take().unwrap_or_default();
+            }
+            KeyCode::Esc => return true,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('+' | '=') => self.speed = (self.speed * 2.0).min(SPEED_LIMITS.1),
+            KeyCode::Char('-') => self.speed = (self.speed / 2.0).max(SPEED_LIMITS.0),
+            KeyCode::Right => self.play_next(),
+            KeyCode::Left => self.rewind(self.played.saturating_sub(1)),
+            KeyCode::Home => self.rewind(0),
+            KeyCode::End => {
+                while self.played < self.events.len() {
+                    self.play_next();
+                }
+            }
+            KeyCode::Tab => {
+                state.current_view = match state.current_view {
+                    ServerView::Lobby => ServerView::Analytics,
+                    ServerView::Analytics => ServerView::Leaderboard,
+                    _ => ServerView::Lobby,
+                };
+            }
+            KeyCode::Char('l') if state.current_view != ServerView::Log => {
+                state.previous_view = Some(state.current_view.clone());
+                state.current_view = ServerView::Log;
+                state.log_scroll = 0;
+            }
+            // Each player in turn, by username
+            KeyCode::Char('u') => {
+                let mut usernames: Vec<_> = state.room().username_to_id.keys().cloned().collect();
+                usernames.sort();
+                let next = match &state.current_view {
+                    ServerView::UserDetail(current) => {
+                        usernames.iter().find(|username| *username > current)
+                    }
+                    _ => None,
+                };
+                if let Some(username) = next.or(usernames.first()) {
+                    state.current_view = ServerView::UserDetail(username.clone());
+                }
+            }
+            // Each room in turn
+            KeyCode::Char('r') => {
+                let next = state
+                    .rooms
+                    .keys()
+                    .find(|code| **code > state.current_room)
+                    .or(state.rooms.keys().next())
+                    .cloned();
+                if let Some(code) = next {
+                    state.current_room = code;
+                    state.log_scroll = 0;
+                    if matches!(state.current_view, ServerView::UserDetail(_)) {
+                        state.current_view = ServerView::Analytics;
+                    }
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Where the replay has got to, for the status bar.
+    fn status(&self) -> String {
+        let state = if self.played == self.events.len() {
+            "■ Ended"
+        } else if self.paused {
+            "⏸ Paused"
+        } else {
+            "▶ Playing"
+        };
+        format!(
+            "{} at {}x  ·  {} / {}  ·  event {} of {}",
+            state,
+            self.speed,
+            clock(self.clock),
+            clock(self.length()),
+            self.played,
+            self.events.len()
+        )
+    }
+}
+
+/// The server before anything happened, with only the room of the first
+/// event open.
+fn empty_state(code: &str) -> ServerState {
+    let mut state = ServerState::new(Vec::new(), QuizMeta::default(), 0);
+    state.rooms.clear();
+    state.rooms.insert(code.to_string(), empty_room(code));
+    state.current_room = code.to_string();
+    state.default_room = code.to_string();
+    state
+}
+
+/// A room with no questions yet, as one is before its quiz starts.
+fn empty_room(code: &str) -> Room {
+    Room::new(code.to_string(), Vec::new(), QuizMeta::default())
+}
+
+/// Apply `entry`, which happened `at`, to the rooms.
+fn apply(state: &mut ServerState, at: Instant, entry: &Entry) {
+    let room = state
+        .rooms
+        .entry(entry.room.clone())
+        .or_insert_with(|| empty_room(&entry.room));
+
+    match &entry.event {
+        Event::Started {
+            questions,
+            players,
+            speed_bonus,
+            confidence_mode,
+        } => {
+            room.questions = questions.clone();
+            room.speed_bonus = *speed_bonus;
+            room.confidence_mode = *confidence_mode;
+            room.status = ServerStatus::InProgress;
+            room.paused = false;
+            room.live_answers.clear();
+            for username in players {
+                let session = player(room, username);
+                session.init_answers(questions.len());
+                session.started_at = Some(at);
+                session.status = UserStatus::Answering(0);
+                session.score = None;
+                session.finished_at = None;
+            }
+            room.add_to_history(format!("Quiz started with {} users!", players.len()));
+        }
+        Event::Joined { username, late } => {
+            let total = room.questions.len();
+            let session = player(room, username);
+            if *late {
+                session.init_answers(total);
+                session.started_at = Some(at);
+                session.status = UserStatus::Answering(0);
+                room.add_to_history(format!("User {} joined (late)", username));
+            } else {
+                session.status = UserStatus::InLobby;
+                room.add_to_history(format!("User {} joined", username));
+            }
+        }
+        Event::Rejoined { username } => {
+            let in_progress = room.status == ServerStatus::InProgress;
+            let session = player(room, username);
+            session.status = if session.score.is_some() {
+                UserStatus::Finished
+            } else if in_progress {
+                UserStatus::Answering(session.current_question_index())
+            } else {
+                UserStatus::InLobby
+            };
+            room.add_to_history(format!("User {} reconnected", username));
+        }
+        Event::Left { username } => {
+            if let Some(session) = room.get_user_by_name_mut(username) {
+                session.disconnect();
+                session.disconnected_at = Some(at);
+            }
+            room.add_to_history(format!("User {} disconnected", username));
+        }
+        Event::Question { username, index } => {
+            if let Some(session) = room.get_user_by_name_mut(username)
+                && !session.is_finished()
+            {
+                session.status = UserStatus::Answering(*index);
+            }
+        }
+        Event::Answered {
+            username,
+            question,
+            answers,
+            confidence,
+            seconds,
+            ..
+        } => {
+            let total = room.questions.len();
+            let session = player(room, username);
+            if session.answers.is_empty() {
+                session.init_answers(total);
+            }
+            if let Some(answer) = session.answers.get_mut(*question) {
+                *answer = Some(answers.clone());
+                session.answered_at[*question] = Some(at);
+                session.confidences[*question] = *confidence;
+            }
+            let time = seconds.map(Duration::from_secs_f64);
+            room.record_live_answer(username.clone(), *question, answers.clone(), time);
+        }
+        Event::Finished { username, score } => {
+            let session = player(room, username);
+            session.status = UserStatus::Finished;
+            session.score = Some(*score);
+            session.finished_at = Some(at);
+            room.add_to_history(format!("User {} finished with score {}", username, score));
+        }
+        Event::Chat { username, text } => {
+            room.chat.push(ChatLine {
+                username: username.clone(),
+                text: text.clone(),
+            });
+            // Keep only the last 50 messages, as the server does
+            if room.chat.len() > 50 {
+                room.chat.remove(0);
+            }
+        }
+        Event::Command {
+            input,
+            reply,
+            error,
+        } => {
+            room.add_to_history(format!("> {}", input));
+            match (reply, error) {
+                (_, Some(error)) => room.add_to_history(format!("Error: {}", error)),
+                (Some(reply), None) => room.add_to_history(reply.clone()),
+                (None, None) => {}
+            }
+            // The rest of what commands do shows in the events that follow
+            if error.is_none() {
+                match input.split_whitespace().next().map(str::to_lowercase).as_deref() {
+                    Some("pause") => room.paused = true,
+                    Some("resume") => room.paused = false,
+                    Some("restart") => room.new_round(),
+                    _ => {}
+                }
+            }
+        }
+        Event::Ended { scores } => {
+            room.status = ServerStatus::Finished;
+            room.paused = false;
+            for (username, score) in scores {
+                if let Some(session) = room.get_user_by_name_mut(username) {
+                    session.score = Some(*score);
+                }
+            }
+            room.add_to_history("Quiz ended".to_string());
+        }
+    }
+}
+
+/// The session of the player called `username`, connected, joining them to
+/// `room` if they aren't in it yet.
+fn player<'a>(room: &'a mut Room, username: &str) -> &'a mut UserSession {
+    let id = match room.username_to_id.get(username) {
+        Some(id) => *id,
+        None => {
+            // Transcripts don't keep addresses
+            let mut session = UserSession::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), sender());
+            session.username = Some(username.to_string());
+            let id = session.id;
+            room.username_to_id.insert(username.to_string(), id);
+            room.sessions.insert(id, session);
+            id
+        }
+    };
+    let session = room.sessions.get_mut(&id).expect("usernames map to sessions");
+    if session.sender.is_none() {
+        session.sender = Some(sender());
+        session.disconnected_at = None;
+    }
+    session
+}
+
+/// A sender for a rebuilt session, so it shows as connected; nothing sent
+/// on it goes anywhere.
+fn sender() -> mpsc::UnboundedSender<crate::protocol::ServerMessage> {
+    mpsc::unbounded_channel().0
+}
+
+/// `time` as minutes and seconds, with hours when it runs that long.
+fn clock(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Question;
+
+    fn entry(seconds: u32, event: Event) -> Entry {
+        Entry {
+            at: format!("2026-10-17T14:00:{:02}.000+00:00", seconds),
+            room: "ABCD".to_string(),
+            event,
+        }
+    }
+
+    #[test]
+    fn test_replay_and_rewind() {
+        let questions = vec![
+            Question::new("Pick b", ["a", "b", "c", "d"], 1),
+            Question::new("Pick c", ["a", "b", "c", "d"], 2),
+        ];
+        let answered = |username: &str, question, answer| Event::Answered {
+            username: username.to_string(),
+            question,
+            answers: vec![answer],
+            confidence: None,
+            correct: answer == question + 1,
+            seconds: Some(3.0),
+        };
+        let entries = vec![
+            entry(0, Event::Joined { username: "alice".to_string(), late: false }),
+            entry(1, Event::Joined { username: "bob".to_string(), late: false }),
+            entry(
+                5,
+                Event::Started {
+                    questions,
+                    players: vec!["alice".to_string(), "bob".to_string()],
+                    speed_bonus: false,
+                    confidence_mode: false,
+                },
+            ),
+            entry(8, answered("alice", 0, 1)),
+            entry(9, answered("bob", 0, 3)),
+            entry(9, Event::Question { username: "bob".to_string(), index: 1 }),
+            entry(11, answered("alice", 1, 2)),
+            entry(11, Event::Finished { username: "alice".to_string(), score: 2 }),
+            entry(12, Event::Left { username: "bob".to_string() }),
+        ];
+        let mut replay = Replay::new(entries).unwrap();
+        assert_eq!(replay.length(), Duration::from_secs(12));
+
+        // Four times as fast, two seconds in is eight into the session
+        replay.speed = 4.0;
+        replay.advance(Duration::from_secs(2));
+        assert_eq!(replay.played, 4);
+        let room = replay.state.room();
+        assert_eq!(room.status, ServerStatus::InProgress);
+        assert_eq!(room.live_answers.len(), 1);
+        let alice = room.get_user_by_name("alice").unwrap();
+        assert_eq!(alice.current_question_index(), 1);
+
+        replay.handle_key(KeyCode::End, false);
+        let room = replay.state.room();
+        let alice = room.get_user_by_name("alice").unwrap();
+        assert_eq!((alice.status, alice.score), (UserStatus::Finished, Some(2)));
+        assert_eq!(alice.total_time(), Some(Duration::from_secs(6)));
+        assert_eq!(room.get_user_by_name("bob").unwrap().status, UserStatus::Disconnected);
+        assert_eq!(room.generate_standings("")[0].username, "alice");
+        assert!(replay.status().starts_with("■ Ended at 4x  ·  0:12 / 0:12"));
+        let screen = terminal::buffer_lines(&terminal::render_to_buffer(100, 30, |frame| {
+            ui::render_replay(frame, &replay.state, &replay.status(), &Theme::default())
+        }));
+        assert!(screen[0].starts_with("┌ Replay · Room ABCD ─"));
+        assert!(screen[1].starts_with("│ Status: In Progress  |  Questions: 2  |"));
+        assert!(screen[28].starts_with("│ ■ Ended at 4x  ·  0:12 / 0:12  ·  event 9 of 9"));
+
+        // Stepping back undoes bob leaving
+        replay.handle_key(KeyCode::Left, false);
+        assert_eq!(replay.played, 8);
+        assert_eq!(replay.clock, Duration::from_secs(11));
+        let bob = replay.state.room().get_user_by_name("bob").unwrap();
+        assert_eq!(bob.status, UserStatus::Answering(1));
+    }
+}
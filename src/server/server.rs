@@ -1,78 +1,739 @@
 //! WebSocket server implementation.
 
-use std::net::{IpAddr, SocketAddr};
-use std::path::Path;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use futures_util::{SinkExt, StreamExt};
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::http::header::{HeaderValue, SEC_WEBSOCKET_PROTOCOL};
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
 
-use crate::data::load_questions_from_json;
-use crate::protocol::{validate_username, ClientMessage, ServerMessage};
+use crate::data::{
+    filter_questions_by_tags, load_bank_encrypted, load_bank_from_json, sample_pools_with_rng,
+    seeded_rng,
+};
+use crate::models::{normalize_answer, Confidence};
+use crate::protocol::{
+    decode, validate_username, ClientMessage, Encoding, ServerMessage, SubmittedAnswer,
+    DEFAULT_PORT, MSGPACK_PROTOCOL,
+};
 use crate::terminal;
+use crate::theme::Theme;
+use crate::{NetworkError, QuizError};
 
+use super::blocklist::Blocklist;
+use super::bots::{self, BotAccuracy, BotRequest};
 use super::commands::{execute_command, CommandResult};
-use super::state::{ServerState, ServerStatus, ServerView, UserSession, UserStatus};
+use super::flood::{FloodGuard, Verdict};
+use super::http;
+use super::snapshot::{SavedServer, SAVE_INTERVAL};
+use super::state::{
+    Room, ServerState, ServerStatus, ServerView, UserSession, UserStatus, UsernamePolicy, seconds,
+};
+use super::tls::TlsConfig;
+use super::transcript::{self, Transcript};
 use super::ui;
 
+/// Longest the server waits for connections to close when shutting down.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+/// How often to check whether a countdown to the first question has ended.
+const COUNTDOWN_TICK: Duration = Duration::from_millis(50);
+/// Lines Page Up and Page Down scroll the log view by.
+const LOG_PAGE: usize = 10;
+/// Questions Page Up and Page Down move the preview view by.
+const PREVIEW_PAGE: usize = 5;
+/// How often players waiting for results hear how many others are done.
+const SUMMARY_INTERVAL: Duration = Duration::from_secs(1);
+/// How often idle connections and long-gone players are cleaned up.
+const PURGE_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Shared server state wrapped in Arc<Mutex> for async access.
-type SharedState = Arc<Mutex<ServerState>>;
-
-/// Run the quiz server.
-pub async fn run<P: AsRef<Path>>(port: u16, questions_path: P) -> Result<(), Box<dyn std::error::Error>> {
-    // Load questions
-    let questions = load_questions_from_json(questions_path)?;
-    println!("Loaded {} questions", questions.len());
-
-    // Create shared state
-    let state = Arc::new(Mutex::new(ServerState::new(questions, port)));
-
-    // Start WebSocket server
-    let addr = format!("0.0.0.0:{}", port);
-    let listener = TcpListener::bind(&addr).await?;
-    println!("Server listening on {}", addr);
-
-    // Spawn connection acceptor
-    let state_clone = Arc::clone(&state);
-    tokio::spawn(async move {
-        loop {
-            match listener.accept().await {
-                Ok((stream, addr)) => {
-                    let state = Arc::clone(&state_clone);
-                    tokio::spawn(handle_connection(stream, addr, state));
+pub(super) type SharedState = Arc<Mutex<ServerState>>;
+
+/// Settings for [`run`].
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    /// Port to listen on.
+    pub port: u16,
+    /// Only questions tagged with one of these are used; empty uses all.
+    pub tags: Vec<String>,
+    /// Passphrase of an encrypted questions file.
+    pub passphrase: Option<String>,
+    /// Certificate and key to serve over `wss://` instead of `ws://`.
+    pub tls: Option<TlsConfig>,
+    /// File to save the rooms, players and answers to every few seconds and
+    /// on shutdown; `None` to not save them.
+    pub state_file: Option<PathBuf>,
+    /// Restore the rooms last saved to `state_file` instead of loading the
+    /// questions file.
+    pub resume: bool,
+    /// Most players a room takes at once; `None` for no limit.
+    pub max_players: Option<usize>,
+    /// Give faster correct answers more points, up to double.
+    pub speed_bonus: bool,
+    /// Have players press Enter twice to send an answer, so a stray
+    /// keypress doesn't send one.
+    pub confirm_answers: bool,
+    /// Have players say how sure they are of each answer: sure answers
+    /// earn double when right and lose points when wrong.
+    pub confidence_mode: bool,
+    /// Tell players whether each answer was right, and the right one, as
+    /// soon as they send it, for study; otherwise they find out at the end.
+    pub feedback: bool,
+    /// Send every question, without the answers, when the quiz starts, so
+    /// players keep answering while their connection is down and catch up
+    /// once it is back. Feedback isn't sent, as players move on by
+    /// themselves, and results wait for disconnected players until they
+    /// are given up on.
+    pub offline: bool,
+    /// What to do when a player asks for a username someone in the room
+    /// already has.
+    pub username_policy: UsernamePolicy,
+    /// File of words refused in usernames, one per line.
+    pub blocklist: Option<PathBuf>,
+    /// Seconds to count down between `start` and the first question, so
+    /// everyone starts together; 0 to start at once.
+    pub countdown: u32,
+    /// Seed for the `limit` command, so it picks the same questions each
+    /// time; `None` for a fresh pick.
+    pub seed: Option<u64>,
+    /// Port for the read-only HTTP API (`/status`, `/leaderboard`,
+    /// `/results.json` and `/metrics`); `None` to not serve it.
+    pub http_port: Option<u16>,
+    /// Address the HTTP API listens on; only this machine by default.
+    pub http_address: IpAddr,
+    /// How long a connection may go without picking a username before it
+    /// is closed; `None` to wait forever.
+    pub join_timeout: Option<Duration>,
+    /// How long a disconnected player may be away before their username and
+    /// address are freed, keeping their answers for the results; `None` to
+    /// wait forever.
+    pub abandon_after: Option<Duration>,
+    /// Simulated players joining the first room at startup.
+    pub bots: usize,
+    /// How often the startup bots, and those added without an accuracy,
+    /// answer right.
+    pub bot_accuracy: BotAccuracy,
+    /// File to add a JSON line to for every join, question sent, answer and
+    /// host command; `None` to keep no transcript.
+    pub transcript: Option<PathBuf>,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            tags: Vec::new(),
+            passphrase: None,
+            tls: None,
+            state_file: None,
+            resume: false,
+            max_players: None,
+            speed_bonus: false,
+            confirm_answers: false,
+            confidence_mode: false,
+            feedback: false,
+            offline: false,
+            username_policy: UsernamePolicy::Reject,
+            blocklist: None,
+            countdown: 5,
+            seed: None,
+            http_port: None,
+            http_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            join_timeout: Some(Duration::from_secs(120)),
+            abandon_after: Some(Duration::from_secs(30 * 60)),
+            bots: 0,
+            bot_accuracy: BotAccuracy::default(),
+            transcript: None,
+        }
+    }
+}
+
+/// Run the quiz server, drawing the host's interface with `theme`.
+///
+/// With `options.state_file`, the rooms, players and answers are saved every
+/// few seconds and on shutdown. With `options.resume`, the last save is
+/// restored instead of loading `questions_path`, and players continue when
+/// they reconnect.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::server::{self, ServeOptions};
+/// use rust_quiz::theme::Theme;
+///
+/// # async fn host() -> Result<(), rust_quiz::QuizError> {
+/// let options = ServeOptions { max_players: Some(30), ..ServeOptions::default() };
+/// server::run("questions.json", Theme::default(), options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run<P: AsRef<Path>>(
+    questions_path: P,
+    theme: Theme,
+    options: ServeOptions,
+) -> Result<(), QuizError> {
+    let server = QuizServer::start(questions_path, options).await?;
+    println!("Server listening on {}://{}", server.scheme(), server.local_addr());
+    if let Some(addr) = server.http_addr() {
+        println!("HTTP API on http://{}", addr);
+    }
+    server.run_tui(theme).await
+}
+
+/// A quiz server running in the background, for hosting from another
+/// program.
+///
+/// The server accepts players as soon as it starts, without drawing
+/// anything; host commands go through [`QuizServer::execute`], or the
+/// host's interface can be shown with [`QuizServer::run_tui`]. Dropping the
+/// server stops it without telling players or saving, so prefer
+/// [`QuizServer::stop`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_quiz::server::{QuizServer, ServeOptions};
+///
+/// # async fn host() -> Result<(), Box<dyn std::error::Error>> {
+/// // Port 0 picks a free port
+/// let options = ServeOptions { port: 0, ..ServeOptions::default() };
+/// let server = QuizServer::start("questions.json", options).await?;
+/// println!("Join at ws://{}", server.local_addr());
+///
+/// // ...once players have joined
+/// server.execute("start").await?;
+/// for room in server.rooms().await {
+///     println!("{}: {} players", room.code, room.players.len());
+/// }
+/// server.stop().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct QuizServer {
+    state: SharedState,
+    addr: SocketAddr,
+    http_addr: Option<SocketAddr>,
+    tls: bool,
+    save_path: Option<PathBuf>,
+    /// Tells the accept task to stop, and the task, until stopped.
+    accepting: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
+    /// Timers, saving and the HTTP API, ended with the server.
+    background: Vec<JoinHandle<()>>,
+}
+
+/// A room on a [`QuizServer`].
+#[derive(Debug, Clone)]
+pub struct RoomInfo {
+    /// Code players give to join the room.
+    pub code: String,
+    pub status: ServerStatus,
+    pub paused: bool,
+    pub questions: usize,
+    pub max_score: usize,
+    /// Everyone who has picked a username, by username.
+    pub players: Vec<PlayerInfo>,
+}
+
+/// A player in a [`RoomInfo`].
+#[derive(Debug, Clone)]
+pub struct PlayerInfo {
+    pub username: String,
+    pub connected: bool,
+    /// Number of questions answered so far.
+    pub answered: usize,
+    /// Points earned so far.
+    pub score: usize,
+    /// Whether the player has answered every question.
+    pub finished: bool,
+}
+
+impl QuizServer {
+    /// Load the questions, or the last save with `options.resume`, and start
+    /// accepting players on `options.port`.
+    pub async fn start<P: AsRef<Path>>(
+        questions_path: P,
+        options: ServeOptions,
+    ) -> Result<Self, QuizError> {
+        let ServeOptions {
+            port,
+            tags,
+            passphrase,
+            tls,
+            state_file,
+            resume,
+            max_players,
+            speed_bonus,
+            confirm_answers,
+            confidence_mode,
+            feedback,
+            offline,
+            username_policy,
+            blocklist,
+            countdown,
+            seed,
+            http_port,
+            http_address,
+            join_timeout,
+            abandon_after,
+            bots,
+            bot_accuracy,
+            transcript,
+        } = options;
+        let save_path = state_file;
+
+        let mut state = if resume {
+            let path = save_path.as_deref().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "No state file to resume the server from")
+            })?;
+            let saved = SavedServer::load(path).map_err(|error| QuizError::File {
+                path: path.to_path_buf(),
+                error,
+            })?;
+            let mut state = saved.into_state(port);
+            state.add_to_history(format!(
+                "Resumed {} rooms from {}",
+                state.rooms.len(),
+                path.display()
+            ));
+            state
+        } else {
+            // Load questions
+            let bank = match passphrase {
+                Some(passphrase) => load_bank_encrypted(questions_path, &passphrase)?,
+                None => load_bank_from_json(questions_path)?,
+            };
+            let questions = filter_questions_by_tags(bank.questions, &tags)?;
+            let questions =
+                sample_pools_with_rng(questions, &bank.meta.pools, &mut seeded_rng(seed))?;
+            let count = questions.len();
+            let mut state = ServerState::new(questions, bank.meta, port);
+            state.add_to_history(format!("Loaded {} questions", count));
+            state
+        };
+
+        let blocklist = match blocklist {
+            Some(path) => {
+                let blocklist = match Blocklist::load(&path) {
+                    Ok(blocklist) => blocklist,
+                    Err(error) => return Err(QuizError::File { path, error }),
+                };
+                state.add_to_history(format!("Blocking {} words in usernames", blocklist.len()));
+                blocklist
+            }
+            None => Blocklist::default(),
+        };
+
+        let transcript = match transcript {
+            Some(path) => match Transcript::open(&path) {
+                Ok(transcript) => {
+                    state.add_to_history(format!("Writing a transcript to {}", path.display()));
+                    Some(transcript)
                 }
-                Err(e) => {
-                    eprintln!("Failed to accept connection: {}", e);
+                Err(error) => return Err(QuizError::File { path, error }),
+            },
+            None => None,
+        };
+
+        for room in state.rooms.values_mut() {
+            room.max_players = max_players;
+            room.speed_bonus = speed_bonus;
+            room.confirm_answers = confirm_answers;
+            room.confidence_mode = confidence_mode;
+            room.feedback = feedback;
+            room.offline = offline;
+            room.username_policy = username_policy;
+            room.blocklist = blocklist.clone();
+            room.countdown = countdown;
+            room.seed = seed;
+            room.transcript = transcript.clone();
+        }
+        state.max_players = max_players;
+        state.speed_bonus = speed_bonus;
+        state.confirm_answers = confirm_answers;
+        state.confidence_mode = confidence_mode;
+        state.feedback = feedback;
+        state.offline = offline;
+        state.username_policy = username_policy;
+        state.blocklist = blocklist;
+        state.countdown = countdown;
+        state.seed = seed;
+        state.join_timeout = join_timeout;
+        state.abandon_after = abandon_after;
+        state.transcript = transcript;
+        state.bot_accuracy = bot_accuracy;
+        if bots > 0 {
+            state.bot_requests.push(BotRequest {
+                room: state.default_room.clone(),
+                count: bots,
+                accuracy: bot_accuracy,
+            });
+        }
+
+        // Read the certificate before binding, so a bad one fails fast
+        let acceptor = tls.as_ref().map(TlsConfig::acceptor).transpose()?;
+
+        // Create shared state
+        let state = Arc::new(Mutex::new(state));
+        let mut background = Vec::new();
+
+        // Start WebSocket server
+        let listener = bind(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port).await?;
+        let addr = listener.local_addr()?;
+        // Shown in the host's interface, so the one picked for port 0
+        state.lock().await.port = addr.port();
+
+        let http_addr = match http_port {
+            Some(http_port) => {
+                let listener = bind(http_address, http_port).await?;
+                let addr = listener.local_addr()?;
+                background.push(tokio::spawn(http::serve(listener, Arc::clone(&state))));
+                Some(addr)
+            }
+            None => None,
+        };
+
+        // Spawn connection acceptor, which stops when told to and then waits
+        // for the connections to close
+        let state_clone = Arc::clone(&state);
+        let tls = acceptor.is_some();
+        let (stop_accepting, mut stopped) = oneshot::channel::<()>();
+        let accept_task = tokio::spawn(async move {
+            let mut connections = JoinSet::new();
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, addr)) => {
+                            let state = Arc::clone(&state_clone);
+                            let acceptor = acceptor.clone();
+                            connections.spawn(handle_connection(stream, addr, state, acceptor));
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to accept connection: {}", e);
+                        }
+                    },
+                    _ = &mut stopped => break,
                 }
+                while connections.try_join_next().is_some() {}
             }
+
+            let closed = async { while connections.join_next().await.is_some() {} };
+            let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, closed).await;
+        });
+
+        if let Some(path) = save_path.clone() {
+            background.push(tokio::spawn(save_periodically(Arc::clone(&state), path)));
         }
-    });
+        background.push(tokio::spawn(run_timers(Arc::clone(&state))));
+
+        Ok(Self {
+            state,
+            addr,
+            http_addr,
+            tls,
+            save_path,
+            accepting: Some((stop_accepting, accept_task)),
+            background,
+        })
+    }
 
-    // Run TUI on main thread
-    run_tui(state).await?;
+    /// Address players connect to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
 
-    Ok(())
+    /// Address of the HTTP API, if `options.http_port` asked for it.
+    pub fn http_addr(&self) -> Option<SocketAddr> {
+        self.http_addr
+    }
+
+    /// `wss` with TLS, otherwise `ws`.
+    pub fn scheme(&self) -> &'static str {
+        if self.tls { "wss" } else { "ws" }
+    }
+
+    /// Run a host command as typed at the host's prompt, e.g. `start`,
+    /// `kick alice` or `room new`, in the room the host is looking at.
+    ///
+    /// Returns the command's message, or why it failed. After `quit`, the
+    /// server only waits to be stopped.
+    pub async fn execute(&self, command: &str) -> Result<Option<String>, String> {
+        match run_command(&mut *self.state.lock().await, command) {
+            CommandResult::Ok(msg) => Ok(msg),
+            CommandResult::Error(msg) => Err(msg),
+            CommandResult::Quit => Ok(None),
+        }
+    }
+
+    /// The open rooms and their players.
+    pub async fn rooms(&self) -> Vec<RoomInfo> {
+        let state = self.state.lock().await;
+        state.rooms.values().map(room_info).collect()
+    }
+
+    /// The host's log: events and command results, oldest first.
+    pub async fn history(&self) -> Vec<String> {
+        self.state.lock().await.room().command_history.clone()
+    }
+
+    /// Draw the host's interface at `width` by `height` cells with the
+    /// default theme, off screen, and return what was drawn.
+    pub async fn render_to_buffer(&self, width: u16, height: u16) -> Buffer {
+        let state = self.state.lock().await;
+        terminal::render_to_buffer(width, height, |frame| {
+            ui::render(frame, &state, &Theme::default())
+        })
+    }
+
+    /// Draw the host's interface in the terminal until the host quits, then
+    /// stop the server.
+    pub async fn run_tui(self, theme: Theme) -> Result<(), QuizError> {
+        let mut terminal = terminal::init().map_err(QuizError::Terminal)?;
+        let result = self.run_tui_on(&mut terminal, theme).await;
+        terminal::restore().map_err(QuizError::Terminal)?;
+        result
+    }
+
+    /// Draw the host's interface on a terminal the caller has set up, e.g.
+    /// one drawing to stderr, until the host quits, then stop the server.
+    ///
+    /// Keys are still read with crossterm. Unlike [`QuizServer::run_tui`],
+    /// raw mode and the alternate screen are left to the caller.
+    pub async fn run_tui_on<B: Backend>(
+        mut self,
+        terminal: &mut Terminal<B>,
+        theme: Theme,
+    ) -> Result<(), QuizError>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        let drawn = run_tui(terminal, &self.state, &theme).await;
+        // Players get ServerClosing and a close frame before the host's
+        // screen goes
+        self.shut_down().await;
+        drawn?;
+        self.save().await
+    }
+
+    /// Tell every player the server is closing, wait briefly for their
+    /// connections to close, and save the rooms.
+    pub async fn stop(mut self) -> Result<(), QuizError> {
+        self.shut_down().await;
+        self.save().await
+    }
+
+    async fn shut_down(&mut self) {
+        {
+            let mut state = self.state.lock().await;
+            state.close_connections();
+            state.should_quit = true;
+        }
+        if let Some((stop_accepting, accept_task)) = self.accepting.take() {
+            let _ = stop_accepting.send(());
+            let _ = accept_task.await;
+        }
+        for task in &self.background {
+            task.abort();
+        }
+    }
+
+    async fn save(&self) -> Result<(), QuizError> {
+        if let Some(path) = &self.save_path {
+            SavedServer::from_state(&*self.state.lock().await)
+                .save(path)
+                .map_err(|error| QuizError::File {
+                    path: path.clone(),
+                    error,
+                })?;
+        }
+        Ok(())
+    }
 }
 
-/// Handle a single WebSocket connection.
-async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedState) {
+impl Drop for QuizServer {
+    fn drop(&mut self) {
+        if let Some((_, accept_task)) = &self.accepting {
+            accept_task.abort();
+        }
+        for task in &self.background {
+            task.abort();
+        }
+    }
+}
+
+/// Listen on `port` at `address`, which may be every interface.
+async fn bind(address: IpAddr, port: u16) -> Result<TcpListener, NetworkError> {
+    TcpListener::bind((address, port))
+        .await
+        .map_err(|error| NetworkError::Bind { port, error })
+}
+
+fn room_info(room: &Room) -> RoomInfo {
+    let mut players: Vec<PlayerInfo> = room
+        .sessions
+        .values()
+        .filter_map(|session| {
+            Some(PlayerInfo {
+                username: session.username.clone()?,
+                connected: session.is_connected(),
+                answered: session.answered_count(),
+                score: session.calculate_score(&room.questions, room.speed_bonus),
+                finished: session.is_finished(),
+            })
+        })
+        .collect();
+    players.sort_by(|a, b| a.username.cmp(&b.username));
+
+    RoomInfo {
+        code: room.code.clone(),
+        status: room.status,
+        paused: room.paused,
+        questions: room.questions.len(),
+        max_score: room.max_score(),
+        players,
+    }
+}
+
+/// Run a host command, noting its message or error in the log and the
+/// transcript.
+fn run_command(state: &mut ServerState, input: &str) -> CommandResult {
+    // In the room it was typed in, though it may switch rooms
+    let code = state.current_room.clone();
+    let result = execute_command(state, input);
+    if let Some(room) = state.rooms.get(&code)
+        && !input.trim().is_empty()
+    {
+        let (reply, error) = match &result {
+            CommandResult::Ok(msg) => (msg.clone(), None),
+            CommandResult::Error(msg) => (None, Some(msg.clone())),
+            CommandResult::Quit => (None, None),
+        };
+        room.record(transcript::Event::Command {
+            input: input.trim().to_string(),
+            reply,
+            error,
+        });
+    }
+    match &result {
+        CommandResult::Ok(Some(msg)) => state.add_to_history(msg.clone()),
+        CommandResult::Error(msg) => state.add_to_history(format!("Error: {}", msg)),
+        CommandResult::Ok(None) | CommandResult::Quit => {}
+    }
+    result
+}
+
+/// Send the first question in each room when its countdown runs out, add
+/// the bots the host asked for, keep players who have finished up to date
+/// while they wait for results, and clean up idle sessions.
+async fn run_timers(state: SharedState) {
+    let mut countdowns = tokio::time::interval(COUNTDOWN_TICK);
+    let mut summaries = tokio::time::interval(SUMMARY_INTERVAL);
+    let mut purges = tokio::time::interval(PURGE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = countdowns.tick() => {
+                let requests = {
+                    let mut state = state.lock().await;
+                    state.end_countdowns();
+                    std::mem::take(&mut state.bot_requests)
+                };
+                for request in requests {
+                    bots::add_bots(&state, request).await;
+                }
+            }
+            _ = summaries.tick() => state.lock().await.broadcast_progress_summaries(),
+            _ = purges.tick() => state.lock().await.purge_idle_sessions(),
+        }
+    }
+}
+
+/// Save the server every few seconds, so a crashed server can be resumed.
+async fn save_periodically(state: SharedState, path: PathBuf) {
+    let mut interval = tokio::time::interval(SAVE_INTERVAL);
+    let mut failing = false;
+
+    loop {
+        interval.tick().await;
+        let saved = SavedServer::from_state(&*state.lock().await);
+        match saved.save(&path) {
+            Ok(()) => failing = false,
+            // Report the first failure only, not one every few seconds
+            Err(e) if !failing => {
+                failing = true;
+                state.lock().await.add_to_history(format!(
+                    "Failed to save server state to {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Handle a single connection, over TLS if there is an `acceptor`.
+async fn handle_connection(
+    stream: TcpStream,
+    addr: SocketAddr,
+    state: SharedState,
+    acceptor: Option<TlsAcceptor>,
+) {
     let ip = addr.ip();
 
-    // Check if banned
+    // Check if banned, or too late to join
     {
         let state_guard = state.lock().await;
-        if state_guard.banned_ips.contains(&ip) {
+        if state_guard.banned_ips.contains(&ip) || state_guard.should_quit {
             return;
         }
     }
 
-    // Upgrade to WebSocket
-    let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+    match acceptor {
+        Some(acceptor) => match acceptor.accept(stream).await {
+            Ok(stream) => handle_websocket(stream, ip, state).await,
+            Err(e) => eprintln!("TLS handshake failed: {}", e),
+        },
+        None => handle_websocket(stream, ip, state).await,
+    }
+}
+
+/// Handle a single WebSocket connection.
+async fn handle_websocket<S>(stream: S, ip: IpAddr, state: SharedState)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    // Upgrade to WebSocket, noting the room code in the path, e.g. `/ABCD`,
+    // and agreeing on MessagePack if the client offers it
+    let mut path = String::new();
+    let mut encoding = Encoding::Json;
+    // The error type is tungstenite's, and is never returned here
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &Request, mut response: Response| {
+        path = request.uri().path().to_string();
+        let protocols = request.headers().get(SEC_WEBSOCKET_PROTOCOL);
+        encoding = Encoding::negotiate(protocols.and_then(|value| value.to_str().ok()));
+        if encoding == Encoding::MessagePack {
+            response
+                .headers_mut()
+                .insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(MSGPACK_PROTOCOL));
+        }
+        Ok(response)
+    };
+    let ws_stream = match tokio_tungstenite::accept_hdr_async(stream, callback).await {
         Ok(ws) => ws,
         Err(e) => {
             eprintln!("WebSocket handshake failed: {}", e);
@@ -80,19 +741,37 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedSta
         }
     };
 
-    let (ws_sender, ws_receiver) = ws_stream.split();
+    let (mut ws_sender, ws_receiver) = ws_stream.split();
 
     // Create channel for sending messages to this client
     let (tx, rx) = mpsc::unbounded_channel::<ServerMessage>();
 
-    // Check for reconnection and get session_id
+    // Find the room, then check for reconnection and get session_id
+    let requested = path.trim_matches('/');
+    let Some(code) = state
+        .lock()
+        .await
+        .find_room((!requested.is_empty()).then_some(requested))
+    else {
+        let msg = ServerMessage::RoomNotFound {
+            code: requested.to_string(),
+        };
+        let _ = ws_sender.send(encoding.encode(&msg)).await;
+        let _ = ws_sender.close().await;
+        return;
+    };
     let session_id = {
         let mut state_guard = state.lock().await;
+        let Some(room) = state_guard.rooms.get_mut(&code) else {
+            return;
+        };
         
-        // First, gather info we need without holding mutable borrow
-        let reconnect_info = state_guard.ip_to_id.get(&ip).copied().and_then(|existing_id| {
-            let session = state_guard.sessions.get(&existing_id)?;
-            if matches!(session.status, UserStatus::Disconnected) {
+        // First, gather info we need without holding mutable borrow. A
+        // player may come back before their old connection is known to be
+        // dead, and then takes it over
+        let reconnect_info = room.ip_to_id.get(&ip).copied().and_then(|existing_id| {
+            let session = room.sessions.get(&existing_id)?;
+            if matches!(session.status, UserStatus::Disconnected) || session.is_connected() {
                 let username = session.username.clone()?;
                 let current_q = session.current_question_index();
                 Some((existing_id, username, current_q))
@@ -102,14 +781,14 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedSta
         });
         
         // Get status and questions info
-        let server_status = state_guard.status;
-        let questions_len = state_guard.questions.len();
-        let question_data = if server_status == ServerStatus::InProgress {
+        let server_status = room.status;
+        let questions_len = room.questions.len();
+        let question_data = if server_status == ServerStatus::InProgress
+            && room.countdown_ends.is_none()
+        {
             reconnect_info.as_ref().and_then(|(_, _, current_q)| {
                 if *current_q < questions_len {
-                    state_guard.questions.get(*current_q).map(|q| {
-                        (*current_q, q.text.clone(), q.code.clone(), q.options.clone())
-                    })
+                    room.questions.get(*current_q).map(|q| ServerMessage::question(*current_q, q))
                 } else {
                     None
                 }
@@ -120,8 +799,16 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedSta
         
         if let Some((existing_id, username, current_q)) = reconnect_info {
             // Now do the mutable operations
-            if let Some(existing) = state_guard.sessions.get_mut(&existing_id) {
+            if let Some(existing) = room.sessions.get_mut(&existing_id) {
+                // Dropping the old sender closes the old connection, and if
+                // it was still alive, it shouldn't try to come back
+                if let Some(stale) = existing.sender.take() {
+                    let _ = stale.send(ServerMessage::Kicked {
+                        reason: "Connected again from elsewhere".to_string(),
+                    });
+                }
                 existing.sender = Some(tx.clone());
+                existing.disconnected_at = None;
                 
                 // Restore status based on quiz state
                 if server_status == ServerStatus::InProgress {
@@ -135,22 +822,50 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedSta
                 }
             }
             
-            state_guard.add_to_history(format!("User {} reconnected", username));
+            room.record(transcript::Event::Rejoined {
+                username: username.clone(),
+            });
+            room.add_to_history(format!("User {} reconnected", username));
+            room.broadcast_lobby();
             
             // Send reconnection message
+            let questions = if server_status == ServerStatus::InProgress {
+                room.question_set()
+            } else {
+                Vec::new()
+            };
             let _ = tx.send(ServerMessage::ReconnectAccepted {
-                username,
+                username: username.clone(),
                 current_question: current_q,
+                confirm_answers: room.confirm_answers,
+                confidence_mode: room.confidence_mode,
+                speed_bonus_window: room.speed_bonus_window(),
+                questions,
             });
             
+            if let Some(seconds) = room.starts_in() {
+                let _ = tx.send(ServerMessage::StartsIn { seconds });
+            }
+
             // If quiz is in progress and not finished, send current question
-            if let Some((index, text, code, options)) = question_data {
-                let _ = tx.send(ServerMessage::Question {
-                    index,
-                    text,
-                    code,
-                    options,
+            if let Some(question) = question_data {
+                let _ = tx.send(question);
+                room.record(transcript::Event::Question {
+                    username: username.clone(),
+                    index: current_q,
                 });
+                if room.paused {
+                    let _ = tx.send(ServerMessage::Paused);
+                }
+            }
+
+            // A player who dropped out before the end still gets the final
+            // results and leaderboard
+            if server_status == ServerStatus::Finished
+                && let Some(session) = room.sessions.get(&existing_id)
+                && !session.answers.is_empty()
+            {
+                let _ = tx.send(room.final_results(session));
             }
             
             existing_id
@@ -158,68 +873,127 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, state: SharedSta
             // New connection
             let session = UserSession::new(ip, tx.clone());
             let id = session.id;
-            state_guard.sessions.insert(id, session);
-            state_guard.ip_to_id.insert(ip, id);
+            room.sessions.insert(id, session);
+            room.ip_to_id.insert(ip, id);
             let _ = tx.send(ServerMessage::ConnectionAck);
             id
         }
     };
+    // The session holds the only sender now, so dropping it on shutdown
+    // ends the send task below
+    let sender = tx.downgrade();
+    drop(tx);
 
     // Now handle messages (lock is released)
-    handle_messages(session_id, ws_sender, ws_receiver, rx, state, ip).await;
+    let connection = Connection {
+        session_id,
+        code,
+        ip,
+        encoding,
+        sender,
+    };
+    handle_messages(connection, ws_sender, ws_receiver, rx, state).await;
 }
 
-/// Handle messages for a connected session.
-async fn handle_messages(
+/// A client with a session, and how to talk to it.
+struct Connection {
     session_id: uuid::Uuid,
-    mut ws_sender: futures_util::stream::SplitSink<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-        Message,
-    >,
-    mut ws_receiver: futures_util::stream::SplitStream<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-    >,
+    /// Code of the client's room.
+    code: String,
+    ip: IpAddr,
+    encoding: Encoding,
+    /// The session's sender while it is this client's, until a reconnect
+    /// takes the session over.
+    sender: mpsc::WeakUnboundedSender<ServerMessage>,
+}
+
+/// Handle messages for a connected session.
+async fn handle_messages<S>(
+    connection: Connection,
+    mut ws_sender: futures_util::stream::SplitSink<WebSocketStream<S>, Message>,
+    mut ws_receiver: futures_util::stream::SplitStream<WebSocketStream<S>>,
     mut rx: mpsc::UnboundedReceiver<ServerMessage>,
     state: SharedState,
-    _ip: IpAddr,
-) {
-    // Spawn task to forward messages from channel to WebSocket
-    let send_task = tokio::spawn(async move {
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let Connection {
+        session_id,
+        code,
+        ip,
+        encoding,
+        sender,
+    } = connection;
+
+    // Spawn task to forward messages from channel to WebSocket, closing it
+    // once the session lets go of its sender
+    let mut send_task = tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
-            let json = serde_json::to_string(&msg).unwrap();
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
-                break;
+            if ws_sender.send(encoding.encode(&msg)).await.is_err() {
+                return;
             }
         }
+        let _ = ws_sender.close().await;
     });
 
     // Process incoming messages
+    let mut guard = FloodGuard::new();
     while let Some(msg) = ws_receiver.next().await {
-        let text = match msg {
-            Ok(Message::Text(text)) => text.to_string(),
+        let frame = match msg {
+            Ok(frame @ (Message::Text(_) | Message::Binary(_))) => frame,
             Ok(Message::Close(_)) => break,
             Err(_) => break,
             _ => continue,
         };
 
-        let client_msg: ClientMessage = match serde_json::from_str(&text) {
-            Ok(m) => m,
-            Err(_) => continue,
+        let verdict = match guard.check() {
+            Verdict::Allow => match decode::<ClientMessage>(&frame) {
+                Some(client_msg) => {
+                    if handle_client_message(session_id, &code, client_msg, &state).await {
+                        Verdict::Allow
+                    } else {
+                        guard.strike()
+                    }
+                }
+                None => guard.strike(),
+            },
+            verdict => verdict,
         };
 
-        handle_client_message(session_id, client_msg, &state).await;
+        if verdict == Verdict::Disconnect {
+            if let Some(room) = state.lock().await.rooms.get_mut(&code)
+                && let Some(session) = room.sessions.get(&session_id)
+            {
+                session.send(ServerMessage::Kicked {
+                    reason: "Too many messages".to_string(),
+                });
+                let who = session.username.clone().unwrap_or_else(|| ip.to_string());
+                room.add_to_history(format!("Disconnected {} for flooding", who));
+            }
+            // Give the reason a moment to reach the client
+            let _ = tokio::time::timeout(Duration::from_millis(200), &mut send_task).await;
+            break;
+        }
     }
 
-    // Mark as disconnected
+    // Mark as disconnected, or forget a client that never joined, unless
+    // the session has moved on to a newer connection
+    if let Some(room) = state.lock().await.rooms.get_mut(&code)
+        && !room
+            .sessions
+            .get(&session_id)
+            .is_some_and(|s| s.sender.is_some() && sender.upgrade().is_none())
     {
-        let mut state = state.lock().await;
         let username_to_log = {
-            if let Some(session) = state.sessions.get_mut(&session_id) {
-                session.sender = None;
+            if room.sessions.get(&session_id).is_some_and(|s| s.username.is_none()) {
+                room.remove_session(session_id);
+                None
+            } else if let Some(session) = room.sessions.get_mut(&session_id) {
                 if !matches!(session.status, UserStatus::Finished) {
-                    session.status = UserStatus::Disconnected;
+                    session.disconnect();
                     session.username.clone()
                 } else {
+                    session.sender = None;
                     None
                 }
             } else {
@@ -228,37 +1002,87 @@ async fn handle_messages(
         };
         
         if let Some(username) = username_to_log {
-            state.add_to_history(format!("User {} disconnected", username));
+            room.add_to_history(format!("User {} disconnected", username));
+            room.record(transcript::Event::Left { username });
         }
+        room.broadcast_lobby();
+        // The others may have been waiting only on this player
+        room.finish_if_everyone_done();
     }
 
     send_task.abort();
 }
 
 /// Handle a single client message.
-async fn handle_client_message(session_id: uuid::Uuid, msg: ClientMessage, state: &SharedState) {
+///
+/// Returns `false` if the message was refused as a repeat: a second `Join`,
+/// or an answer to a question the player already answered.
+pub(super) async fn handle_client_message(
+    session_id: uuid::Uuid,
+    code: &str,
+    msg: ClientMessage,
+    state: &SharedState,
+) -> bool {
     let mut state = state.lock().await;
+    let Some(room) = state.rooms.get_mut(code) else {
+        return true;
+    };
+    let Some(session) = room.sessions.get(&session_id) else {
+        return true;
+    };
+
+    let repeated = match &msg {
+        ClientMessage::Join { .. } => session.username.is_some(),
+        ClientMessage::SubmitAnswer { question_index, .. } => {
+            *question_index < session.current_question_index()
+        }
+        ClientMessage::Chat { .. } | ClientMessage::SyncAnswers { .. } => false,
+    };
+    if repeated {
+        return false;
+    }
 
     match msg {
         ClientMessage::Join { username } => {
-            handle_join(session_id, username, &mut state);
+            handle_join(session_id, username, room);
         }
         ClientMessage::SubmitAnswer {
             question_index,
-            answer,
+            answers,
+            confidence,
         } => {
-            handle_answer(session_id, question_index, answer, &mut state);
+            handle_answer(session_id, question_index, answers, confidence, None, room);
+        }
+        ClientMessage::SyncAnswers { answers } => {
+            handle_sync(session_id, answers, room);
+        }
+        ClientMessage::Chat { text } => {
+            if let Err(reason) = room.post_chat(session_id, &text)
+                && let Some(session) = room.sessions.get(&session_id)
+            {
+                session.send(ServerMessage::ChatRejected {
+                    reason: reason.to_string(),
+                });
+            }
         }
     }
+    true
 }
 
 /// Handle a Join message.
-fn handle_join(session_id: uuid::Uuid, username: String, state: &mut ServerState) {
+pub(super) fn handle_join(session_id: uuid::Uuid, username: String, room: &mut Room) {
     let username = username.trim().to_string();
 
     // Validate username
-    if let Err(reason) = validate_username(&username) {
-        if let Some(session) = state.sessions.get(&session_id) {
+    let valid = validate_username(&username).and_then(|()| {
+        if room.blocklist.blocks(&username) {
+            Err("That username isn't allowed here")
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(reason) = valid {
+        if let Some(session) = room.sessions.get(&session_id) {
             session.send(ServerMessage::JoinRejected {
                 reason: reason.to_string(),
             });
@@ -266,74 +1090,146 @@ fn handle_join(session_id: uuid::Uuid, username: String, state: &mut ServerState
         return;
     }
 
-    // Check if username is taken
-    if state.is_username_taken(&username) {
-        if let Some(session) = state.sessions.get(&session_id) {
+    if room.is_full() {
+        if let Some(session) = room.sessions.get(&session_id) {
             session.send(ServerMessage::JoinRejected {
-                reason: "Username is already taken".to_string(),
+                reason: format!(
+                    "Room is full ({}/{} players)",
+                    room.player_count(),
+                    room.max_players.unwrap_or_default()
+                ),
             });
         }
         return;
     }
 
+    // Settle a username someone already has by the room's policy
+    let asked = username;
+    let username = match room.claim_username(&asked) {
+        Ok(username) => username,
+        Err(reason) => {
+            if let Some(session) = room.sessions.get(&session_id) {
+                session.send(ServerMessage::JoinRejected {
+                    reason: reason.to_string(),
+                });
+            }
+            return;
+        }
+    };
+    let renamed = if username == asked {
+        String::new()
+    } else {
+        format!(" (asked for {})", asked)
+    };
+
+    let speed_bonus_window = room.speed_bonus_window();
+    let starts_in = room.starts_in();
+    let question_set = room.question_set();
+
     // Accept join
-    if let Some(session) = state.sessions.get_mut(&session_id) {
-        state.username_to_id.insert(username.clone(), session_id);
+    if let Some(session) = room.sessions.get_mut(&session_id) {
+        room.username_to_id.insert(username.clone(), session_id);
         session.username = Some(username.clone());
         
-        // Set status based on quiz state
-        if state.status == ServerStatus::InProgress {
+        // Set status based on quiz room
+        if room.status == ServerStatus::InProgress {
             // Late joiner - start from question 0
-            session.init_answers(state.questions.len());
+            session.init_answers(room.questions.len());
             session.status = UserStatus::Answering(0);
             
             session.send(ServerMessage::JoinAccepted {
                 username: username.clone(),
+                confirm_answers: room.confirm_answers,
+                confidence_mode: room.confidence_mode,
+                speed_bonus_window,
             });
             session.send(ServerMessage::QuizStart {
-                total_questions: state.questions.len(),
+                total_questions: room.questions.len(),
+                questions: question_set,
             });
             
-            // Send first question
-            if let Some(q) = state.questions.first() {
-                session.send(ServerMessage::Question {
-                    index: 0,
-                    text: q.text.clone(),
-                    code: q.code.clone(),
-                    options: q.options.clone(),
+            // Send first question, or how long until it comes
+            if let Some(ends) = room.countdown_ends {
+                let left = ends.saturating_duration_since(Instant::now());
+                session.send(ServerMessage::Countdown {
+                    seconds: left.as_secs_f64().ceil() as u32,
                 });
+            } else if let Some(q) = room.questions.first() {
+                session.send(ServerMessage::question(0, q));
+            }
+            if room.paused {
+                session.send(ServerMessage::Paused);
             }
             
-            state.add_to_history(format!("User {} joined (late)", username));
+            room.record(transcript::Event::Joined {
+                username: username.clone(),
+                late: true,
+            });
+            if room.countdown_ends.is_none() && !room.questions.is_empty() {
+                room.record(transcript::Event::Question {
+                    username: username.clone(),
+                    index: 0,
+                });
+            }
+            room.add_to_history(format!("User {} joined (late){}", username, renamed));
+            room.broadcast_progress();
         } else {
             session.status = UserStatus::InLobby;
             session.send(ServerMessage::JoinAccepted {
                 username: username.clone(),
+                confirm_answers: room.confirm_answers,
+                confidence_mode: room.confidence_mode,
+                speed_bonus_window,
             });
-            state.add_to_history(format!("User {} joined", username));
+            if let Some(seconds) = starts_in {
+                session.send(ServerMessage::StartsIn { seconds });
+            }
+            room.record(transcript::Event::Joined {
+                username: username.clone(),
+                late: false,
+            });
+            room.add_to_history(format!("User {} joined{}", username, renamed));
         }
+        room.broadcast_lobby();
     }
 }
 
 /// Handle an answer submission.
+///
+/// `elapsed` is how long the player took, for answers given offline; others
+/// are timed as they arrive. Answers only count while the quiz is running.
 fn handle_answer(
     session_id: uuid::Uuid,
     question_index: usize,
-    answer: usize,
-    state: &mut ServerState,
+    answer: Vec<usize>,
+    confidence: Option<Confidence>,
+    elapsed: Option<Duration>,
+    room: &mut Room,
 ) {
-    let questions_len = state.questions.len();
-    let questions = state.questions.clone(); // Clone to avoid borrow issues
-    
+    let answer = normalize_answer(answer);
+    if answer.is_empty()
+        || room.paused
+        || room.countdown_ends.is_some()
+        || room.status != ServerStatus::InProgress
+    {
+        return;
+    }
+
+    let questions_len = room.questions.len();
+    let max_score = room.max_score();
+    let speed_bonus = room.speed_bonus;
+    let confidence = confidence.filter(|_| room.confidence_mode);
+    let questions = &room.questions;
+
     // Get username for live answer recording
-    let username = state
+    let username = room
         .sessions
         .get(&session_id)
         .and_then(|s| s.username.clone());
 
     // First, update the session and collect necessary data
-    let (should_finish, next_question_data, result_data) = {
-        let Some(session) = state.sessions.get_mut(&session_id) else {
+    let (should_finish, next_question_data, result_data, time) = {
+        let Some(session) = room.sessions.get_mut(&session_id) else {
             return;
         };
         
@@ -343,93 +1239,151 @@ fn handle_answer(
             return;
         }
 
-        // Record the answer
+        // Record the answer, counting the time taken offline from when the
+        // previous one was given, but never before the server could have
+        // missed it nor after it arrived
+        let now = Instant::now();
+        let previous = match question_index {
+            0 => session.started_at,
+            _ => session.answered_at.get(question_index - 1).copied().flatten(),
+        };
+        let earliest = previous.max(session.synced_at);
+        let answered_at = elapsed
+            .zip(previous)
+            .and_then(|(elapsed, previous)| previous.checked_add(elapsed))
+            .map(|answered_at| answered_at.max(earliest.unwrap_or(now)).min(now))
+            .unwrap_or(now);
         if question_index < session.answers.len() {
-            session.answers[question_index] = Some(answer);
+            session.answers[question_index] = Some(answer.clone());
+            session.answered_at[question_index] = Some(answered_at);
+            session.confidences[question_index] = confidence;
         }
+        let times = session.answer_times();
+        let time = times.get(question_index).copied().flatten();
 
         // Move to next question or finish
         let next_index = question_index + 1;
         if next_index >= questions_len {
             // Quiz finished for this user
             session.status = UserStatus::Finished;
-            session.finished_at = Some(Instant::now());
-            session.score = Some(session.calculate_score(&questions));
+            session.finished_at = Some(answered_at);
+            session.score = Some(session.calculate_score(questions, speed_bonus));
             
             let score = session.score.unwrap_or(0);
             let username_for_results = session.username.clone().unwrap_or_default();
             
-            // Collect answer results
-            let answers: Vec<_> = session.answers
-                .iter()
-                .enumerate()
-                .filter_map(|(i, ans)| {
-                    let question = questions.get(i)?;
-                    let your_answer = (*ans)?;
-                    Some(crate::protocol::AnswerResult {
-                        question_index: i,
-                        question_text: question.text.clone(),
-                        your_answer,
-                        correct_answer: question.correct_answer,
-                        is_correct: your_answer == question.correct_answer,
-                        options: question.options.clone(),
-                    })
-                })
-                .collect();
-            
-            (true, None, Some((score, username_for_results, answers)))
+            (true, None, Some((score, username_for_results)), time)
         } else {
             // Prepare next question
             session.status = UserStatus::Answering(next_index);
-            let q_data = questions.get(next_index).map(|q| {
-                (next_index, q.text.clone(), q.code.clone(), q.options.clone())
-            });
-            (false, q_data, None)
+            let q_data = questions.get(next_index).map(|q| ServerMessage::question(next_index, q));
+            (false, q_data, None, time)
         }
     };
 
+    // In study mode the verdict comes before the next question or results.
+    // Players answering offline have already moved on
+    let correct = questions.get(question_index).is_some_and(|q| q.is_correct(&answer));
+    if room.feedback
+        && !room.offline
+        && let Some(session) = room.sessions.get(&session_id)
+        && let Some(question) = questions.get(question_index)
+    {
+        session.send(ServerMessage::AnswerFeedback {
+            correct,
+            correct_answer: question.correct_answer.indices(),
+        });
+    }
+
     // Record for live feed (outside the session borrow)
     if let Some(uname) = username.clone() {
-        state.record_live_answer(uname, question_index, answer);
+        room.record(transcript::Event::Answered {
+            username: uname.clone(),
+            question: question_index,
+            answers: answer.clone(),
+            confidence,
+            correct,
+            seconds: time.map(seconds),
+        });
+        room.record_live_answer(uname, question_index, answer, time);
     }
+    room.answers_received += 1;
 
     // Handle finish or send next question
     if should_finish {
-        if let Some((score, username_for_results, answers)) = result_data {
-            let leaderboard = state.generate_leaderboard(&username_for_results);
-            
-            if let Some(session) = state.sessions.get(&session_id) {
-                session.send(ServerMessage::QuizResults {
-                    score,
-                    total: questions_len,
-                    answers,
-                    leaderboard,
-                });
-            }
-            
-            state.add_to_history(format!(
+        if let Some((score, username_for_results)) = result_data {
+            room.record(transcript::Event::Finished {
+                username: username_for_results.clone(),
+                score,
+            });
+            room.add_to_history(format!(
                 "User {} finished with score {}/{}",
                 username_for_results,
                 score,
-                questions_len
+                max_score
             ));
         }
-    } else if let Some((index, text, code, options)) = next_question_data {
-        if let Some(session) = state.sessions.get(&session_id) {
-            session.send(ServerMessage::Question {
-                index,
-                text,
-                code,
-                options,
+
+        // Results go out once everyone is done; until then players wait
+        if !room.finish_if_everyone_done() {
+            room.broadcast_progress_summary();
+        }
+    } else if let Some(question) = next_question_data
+        && let Some(session) = room.sessions.get(&session_id)
+    {
+        // Players answering offline have every question already
+        if !room.offline {
+            session.send(question);
+        }
+        if let Some(username) = username {
+            room.record(transcript::Event::Question {
+                username,
+                index: question_index + 1,
             });
         }
     }
+
+    room.broadcast_standings();
+    room.broadcast_progress();
 }
 
-/// Run the server TUI.
-async fn run_tui(state: SharedState) -> Result<(), Box<dyn std::error::Error>> {
-    let mut terminal = terminal::init()?;
+/// Handle the answers a player gave while answering offline, in order,
+/// skipping those already received. Rooms not playing offline ignore them.
+fn handle_sync(session_id: uuid::Uuid, answers: Vec<SubmittedAnswer>, room: &mut Room) {
+    if !room.offline {
+        return;
+    }
+    let arrived = Instant::now();
+    for answer in answers {
+        let Some(session) = room.sessions.get(&session_id) else {
+            return;
+        };
+        if answer.question_index < session.current_question_index() {
+            continue;
+        }
+        let SubmittedAnswer {
+            question_index,
+            answers,
+            confidence,
+            seconds,
+        } = answer;
+        let elapsed = seconds.and_then(|seconds| Duration::try_from_secs_f64(seconds).ok());
+        handle_answer(session_id, question_index, answers, confidence, elapsed, room);
+    }
+    if let Some(session) = room.sessions.get_mut(&session_id) {
+        session.synced_at = Some(arrived);
+    }
+}
 
+/// Draw the host's interface and handle keys until the host quits.
+async fn run_tui<B: Backend>(
+    terminal: &mut Terminal<B>,
+    state: &SharedState,
+    theme: &Theme,
+) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
     loop {
         // Check if should quit
         {
@@ -442,31 +1396,35 @@ async fn run_tui(state: SharedState) -> Result<(), Box<dyn std::error::Error>> {
         // Render UI
         {
             let state = state.lock().await;
-            terminal.draw(|frame| ui::render(frame, &state))?;
+            terminal
+                .draw(|frame| ui::render(frame, &state, theme))
+                .map_err(terminal::backend_error::<B>)?;
         }
 
         // Handle input with timeout to allow for periodic updates
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
-
-                let should_quit = handle_input(&state, key.code).await;
-                if should_quit {
-                    break;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let should_quit = handle_input(state, key).await;
+                    if should_quit {
+                        break;
+                    }
                 }
+                Event::Resize(..) => terminal::handle_resize(terminal)?,
+                _ => {}
             }
         }
     }
 
-    terminal::restore()?;
     Ok(())
 }
 
 /// Handle keyboard input for the server TUI.
-async fn handle_input(state: &SharedState, key: KeyCode) -> bool {
+async fn handle_input(state: &SharedState, key: KeyEvent) -> bool {
     let mut state = state.lock().await;
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let key = key.code;
 
     // If in Help view, Esc or Enter returns to previous view
     if matches!(state.current_view, ServerView::Help) {
@@ -480,28 +1438,77 @@ async fn handle_input(state: &SharedState, key: KeyCode) -> bool {
         return false;
     }
 
-    match key {
-        KeyCode::Char(c) => {
-            state.command_input.push(c);
+    // With nothing typed, arrow, page, Home and End keys scroll the log,
+    // and Esc leaves it
+    if state.current_view == ServerView::Log && state.command_input.is_empty() {
+        let lines = state.room().command_history.len();
+        let scroll = state.log_scroll;
+        let scrolled = match key {
+            KeyCode::Up => scroll + 1,
+            KeyCode::PageUp => scroll + LOG_PAGE,
+            KeyCode::Down => scroll.saturating_sub(1),
+            KeyCode::PageDown => scroll.saturating_sub(LOG_PAGE),
+            KeyCode::Home => lines,
+            KeyCode::End => 0,
+            KeyCode::Esc => {
+                state.current_view = state.previous_view.take().unwrap_or_default();
+                0
+            }
+            _ => usize::MAX,
+        };
+        if scrolled != usize::MAX {
+            state.log_scroll = scrolled.min(lines.saturating_sub(1));
+            return false;
         }
-        KeyCode::Backspace => {
-            state.command_input.pop();
+    }
+
+    // The preview pages through questions the same way
+    if state.current_view == ServerView::Preview && state.command_input.is_empty() {
+        let last = state.room().questions.len().saturating_sub(1);
+        let scroll = state.preview_scroll;
+        let scrolled = match key {
+            KeyCode::Up => Some(scroll.saturating_sub(1)),
+            KeyCode::PageUp => Some(scroll.saturating_sub(PREVIEW_PAGE)),
+            KeyCode::Down => Some(scroll + 1),
+            KeyCode::PageDown => Some(scroll + PREVIEW_PAGE),
+            KeyCode::Home => Some(0),
+            KeyCode::End => Some(last),
+            KeyCode::Esc => {
+                state.current_view = state.previous_view.take().unwrap_or_default();
+                Some(0)
+            }
+            _ => None,
+        };
+        if let Some(scrolled) = scrolled {
+            state.preview_scroll = scrolled.min(last);
+            return false;
         }
-        KeyCode::Enter => {
-            let input = std::mem::take(&mut state.command_input);
-            let result = execute_command(&mut state, &input);
+    }
 
-            match result {
-                CommandResult::Ok(Some(msg)) => {
-                    state.add_to_history(msg);
-                }
-                CommandResult::Ok(None) => {}
-                CommandResult::Error(msg) => {
-                    state.add_to_history(format!("Error: {}", msg));
-                }
-                CommandResult::Quit => {
-                    return true;
-                }
+    let input = &mut state.command_input;
+    match key {
+        // Readline-style shortcuts
+        KeyCode::Char('a') if ctrl => input.home(),
+        KeyCode::Char('e') if ctrl => input.end(),
+        KeyCode::Char('u') if ctrl => input.clear(),
+        KeyCode::Char('w') if ctrl => input.delete_word(),
+        KeyCode::Backspace if ctrl || alt => input.delete_word(),
+        KeyCode::Left if ctrl || alt => input.word_left(),
+        KeyCode::Right if ctrl || alt => input.word_right(),
+        KeyCode::Char(_) if ctrl => {}
+        KeyCode::Char(c) => input.insert(c),
+        KeyCode::Backspace => input.backspace(),
+        KeyCode::Delete => input.delete(),
+        KeyCode::Left => input.left(),
+        KeyCode::Right => input.right(),
+        KeyCode::Home => input.home(),
+        KeyCode::End => input.end(),
+        KeyCode::Up => input.previous(),
+        KeyCode::Down => input.next(),
+        KeyCode::Enter => {
+            let input = input.submit();
+            if let CommandResult::Quit = run_command(&mut state, &input) {
+                return true;
             }
         }
         KeyCode::Esc => {
@@ -511,9 +1518,10 @@ async fn handle_input(state: &SharedState, key: KeyCode) -> bool {
             // Cycle through views
             state.current_view = match state.current_view {
                 ServerView::Lobby => ServerView::Analytics,
-                ServerView::Analytics => ServerView::Lobby,
+                ServerView::Analytics => ServerView::Leaderboard,
+                ServerView::Leaderboard => ServerView::Lobby,
                 ServerView::UserDetail(_) => ServerView::Analytics,
-                ServerView::Help => ServerView::Lobby,
+                ServerView::Help | ServerView::Log | ServerView::Preview => ServerView::Lobby,
             };
         }
         _ => {}
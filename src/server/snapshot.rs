@@ -0,0 +1,231 @@
+//! Saving the server's rooms to disk and restoring them with `--resume`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{Confidence, Question, QuizMeta};
+
+use super::state::{Room, ServerState, ServerStatus, ServerView, UserSession};
+
+/// How often the server state is written while running.
+pub(crate) const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Snapshot of every room on the server, its players and their answers.
+///
+/// Connections can't be saved, so on restore every player is disconnected
+/// and picks up where they left off when they reconnect from the same IP.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SavedServer {
+    rooms: Vec<SavedRoom>,
+    default_room: String,
+    question_bank: Vec<Question>,
+    #[serde(default)]
+    meta: QuizMeta,
+    banned_ips: HashSet<IpAddr>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedRoom {
+    code: String,
+    status: ServerStatus,
+    paused: bool,
+    questions: Vec<Question>,
+    question_bank: Vec<Question>,
+    #[serde(default)]
+    meta: QuizMeta,
+    muted: HashSet<String>,
+    players: Vec<SavedPlayer>,
+}
+
+/// A player with a username. Times are in seconds before the save, since
+/// `Instant`s only make sense within one run.
+#[derive(Serialize, Deserialize)]
+struct SavedPlayer {
+    username: String,
+    ip_addr: IpAddr,
+    answers: Vec<Option<Vec<usize>>>,
+    score: Option<usize>,
+    started_secs_ago: Option<f64>,
+    answered_secs_ago: Vec<Option<f64>>,
+    finished_secs_ago: Option<f64>,
+    #[serde(default)]
+    confidences: Vec<Option<Confidence>>,
+}
+
+impl SavedServer {
+    /// Take a snapshot of the server.
+    pub(crate) fn from_state(state: &ServerState) -> Self {
+        let now = Instant::now();
+        let secs_ago = |at: Option<Instant>| at.map(|at| now.duration_since(at).as_secs_f64());
+
+        let rooms = state
+            .rooms
+            .values()
+            .map(|room| SavedRoom {
+                code: room.code.clone(),
+                status: room.status,
+                paused: room.paused,
+                questions: room.questions.clone(),
+                question_bank: room.question_bank.clone(),
+                meta: room.meta.clone(),
+                muted: room.muted.clone(),
+                players: room
+                    .sessions
+                    .values()
+                    .filter_map(|session| {
+                        Some(SavedPlayer {
+                            username: session.username.clone()?,
+                            ip_addr: session.ip_addr,
+                            answers: session.answers.clone(),
+                            score: session.score,
+                            started_secs_ago: secs_ago(session.started_at),
+                            answered_secs_ago: session
+                                .answered_at
+                                .iter()
+                                .map(|&at| secs_ago(at))
+                                .collect(),
+                            finished_secs_ago: secs_ago(session.finished_at),
+                            confidences: session.confidences.clone(),
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            rooms,
+            default_room: state.default_room.clone(),
+            question_bank: state.question_bank.clone(),
+            meta: state.meta.clone(),
+            banned_ips: state.banned_ips.clone(),
+        }
+    }
+
+    /// Rebuild the server from the snapshot, with every player disconnected.
+    pub(crate) fn into_state(self, port: u16) -> ServerState {
+        let now = Instant::now();
+        // A hand-edited or corrupted file may hold negative or huge times,
+        // which are dropped
+        let at = |secs_ago: Option<f64>| {
+            let ago = Duration::try_from_secs_f64(secs_ago?).ok()?;
+            now.checked_sub(ago)
+        };
+
+        let mut rooms = BTreeMap::new();
+        for saved in self.rooms {
+            let mut room = Room::new(saved.code.clone(), saved.question_bank, saved.meta);
+            room.status = saved.status;
+            room.paused = saved.paused;
+            room.questions = saved.questions;
+            room.muted = saved.muted;
+
+            for player in saved.players {
+                // Nothing is sent without a sender, so this one is never used
+                let (sender, _) = tokio::sync::mpsc::unbounded_channel();
+                let mut session = UserSession::new(player.ip_addr, sender);
+                session.disconnect();
+                session.answers = player.answers;
+                session.score = player.score;
+                session.started_at = at(player.started_secs_ago);
+                session.answered_at = player.answered_secs_ago.into_iter().map(at).collect();
+                session.answered_at.resize(session.answers.len(), None);
+                session.finished_at = at(player.finished_secs_ago);
+                session.confidences = player.confidences;
+                session.confidences.resize(session.answers.len(), None);
+
+                room.username_to_id.insert(player.username.clone(), session.id);
+                room.ip_to_id.insert(player.ip_addr, session.id);
+                session.username = Some(player.username);
+                room.sessions.insert(session.id, session);
+            }
+            rooms.insert(saved.code, room);
+        }
+
+        let mut state = ServerState::new(self.question_bank, self.meta, port);
+        if rooms.contains_key(&self.default_room) {
+            state.rooms = rooms;
+            state.default_room = self.default_room;
+            state.current_room = state.default_room.clone();
+        }
+        if state.room().status != ServerStatus::Lobby {
+            state.current_view = ServerView::Analytics;
+        }
+        state.banned_ips = self.banned_ips;
+        state
+    }
+
+    /// Read a saved server.
+    pub(crate) fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the snapshot, creating its directory if needed.
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first, so a crash mid-write can't leave
+        // a truncated snapshot behind, named so that servers saving beside
+        // each other don't trip over one another's
+        let temp = path.with_extension(format!("{}.tmp", Uuid::new_v4().simple()));
+        fs::write(&temp, serde_json::to_string(self)?)?;
+        fs::rename(temp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::state::UserStatus;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let question = Question::new("Pick b", ["a", "b", "c", "d"], 1);
+        let questions = vec![question.clone(), question];
+        let mut state = ServerState::new(questions, QuizMeta::default(), 8712);
+        let ip = IpAddr::from([10, 0, 0, 7]);
+        state.banned_ips.insert(IpAddr::from([10, 0, 0, 9]));
+        let room = state.room_mut();
+        room.status = ServerStatus::InProgress;
+        let mut session = UserSession::new(ip, tokio::sync::mpsc::unbounded_channel().0);
+        session.username = Some("alice".to_string());
+        session.status = UserStatus::Answering(1);
+        session.init_answers(2);
+        session.answers[0] = Some(vec![1]);
+        room.username_to_id.insert("alice".to_string(), session.id);
+        room.ip_to_id.insert(ip, session.id);
+        room.sessions.insert(session.id, session);
+
+        let json = serde_json::to_string(&SavedServer::from_state(&state)).unwrap();
+        let restored = serde_json::from_str::<SavedServer>(&json).unwrap().into_state(9000);
+
+        assert_eq!(restored.default_room, state.default_room);
+        assert_eq!(restored.banned_ips, state.banned_ips);
+        let room = restored.room();
+        assert_eq!(room.status, ServerStatus::InProgress);
+        let alice = room.get_user_by_ip(&ip).unwrap();
+        assert_eq!(alice.username.as_deref(), Some("alice"));
+        assert_eq!(alice.status, UserStatus::Disconnected);
+        assert_eq!(alice.current_question_index(), 1);
+        assert!(alice.started_at.is_some() && alice.answered_at.len() == 2);
+
+        // Bad times are dropped rather than bringing the server down
+        let mut saved = SavedServer::from_state(&state);
+        let player = &mut saved.rooms[0].players[0];
+        player.started_secs_ago = Some(-1.0);
+        player.answered_secs_ago = vec![Some(f64::NAN)];
+        player.finished_secs_ago = Some(f64::MAX);
+        let restored = saved.into_state(9000);
+        let alice = restored.room().get_user_by_ip(&ip).unwrap();
+        assert!(alice.started_at.is_none() && alice.finished_at.is_none());
+        assert_eq!(alice.answered_at, vec![None, None]);
+    }
+}
@@ -1,20 +1,35 @@
 //! Server state management.
 //!
 //! This module contains all the state structures for managing
-//! connected users, quiz progress, and server status.
+//! rooms, connected users, quiz progress, and server status.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::net::IpAddr;
-use std::time::Instant;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use crate::models::Question;
-use crate::protocol::{AnswerResult, LeaderboardEntry, ServerMessage};
+use crate::models::{max_score, Confidence, Question, QuizMeta};
+use crate::protocol::{
+    AnswerResult, LeaderboardEntry, PlayerProgress, QuizQuestion, ServerMessage,
+    StandingEntry, CHAT_MAX_LENGTH, USERNAME_MAX_LENGTH,
+};
+
+use super::blocklist::Blocklist;
+use super::bots::{BotAccuracy, BotRequest};
+use super::input::CommandInput;
+use super::transcript::{Event, Transcript};
+
+/// How long a correct answer earns a speed bonus: the bonus starts at the
+/// question's points and shrinks to nothing over this window.
+pub const SPEED_BONUS_WINDOW: Duration = Duration::from_secs(30);
 
 /// Current status of the server.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ServerStatus {
     /// Waiting for host to start the quiz.
     Lobby,
@@ -37,25 +52,54 @@ pub enum UserStatus {
     Finished,
     /// Was connected but disconnected (can reconnect).
     Disconnected,
+    /// Disconnected for too long to come back; kept for the results only.
+    Abandoned,
+}
+
+/// What to do when a player asks for a username someone in the room has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsernamePolicy {
+    /// Turn the new player away.
+    #[default]
+    Reject,
+    /// Give the new player the name with a number added, e.g. `alice-2`.
+    Suffix,
+    /// Give the new player the name if its holder has disconnected,
+    /// dropping the old session and its answers.
+    Replace,
+}
+
+impl FromStr for UsernamePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(UsernamePolicy::Reject),
+            "suffix" => Ok(UsernamePolicy::Suffix),
+            "replace" => Ok(UsernamePolicy::Replace),
+            _ => Err(format!("expected reject, suffix or replace, got '{}'", s)),
+        }
+    }
 }
 
 /// What view the host is currently seeing.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ServerView {
     /// Lobby view showing connected users.
+    #[default]
     Lobby,
     /// Analytics view showing all users' progress.
     Analytics,
+    /// Everyone taking the quiz, ranked by points so far.
+    Leaderboard,
     /// Detailed view of a specific user.
     UserDetail(String),
     /// Help view showing available commands.
     Help,
-}
-
-impl Default for ServerView {
-    fn default() -> Self {
-        Self::Lobby
-    }
+    /// Scrollable log of the room's events and command results.
+    Log,
+    /// The room's questions with their answers, to check before starting.
+    Preview,
 }
 
 /// A single user session.
@@ -69,13 +113,26 @@ pub struct UserSession {
     /// Current status.
     pub status: UserStatus,
     /// Submitted answers (None = not answered yet).
-    pub answers: Vec<Option<usize>>,
+    pub answers: Vec<Option<Vec<usize>>>,
+    /// When each answer was submitted.
+    pub answered_at: Vec<Option<Instant>>,
+    /// How sure the user was of each answer, in confidence mode.
+    pub confidences: Vec<Option<Confidence>>,
+    /// When the user got their first question.
+    pub started_at: Option<Instant>,
+    /// When the user's answers given offline last arrived; any synced
+    /// after were given later.
+    pub synced_at: Option<Instant>,
     /// Final score (calculated when finished).
     pub score: Option<usize>,
     /// When the user finished (for leaderboard ordering).
     pub finished_at: Option<Instant>,
     /// Channel to send messages to this client.
     pub sender: Option<mpsc::UnboundedSender<ServerMessage>>,
+    /// When the client connected.
+    pub connected_at: Instant,
+    /// When the client last disconnected, while it is away.
+    pub disconnected_at: Option<Instant>,
 }
 
 impl UserSession {
@@ -87,15 +144,25 @@ impl UserSession {
             ip_addr,
             status: UserStatus::Connected,
             answers: Vec::new(),
+            answered_at: Vec::new(),
+            confidences: Vec::new(),
+            started_at: None,
+            synced_at: None,
             score: None,
             finished_at: None,
             sender: Some(sender),
+            connected_at: Instant::now(),
+            disconnected_at: None,
         }
     }
 
-    /// Initialize answers vector for the quiz.
+    /// Initialize answers vector for the quiz and start the user's clock.
     pub fn init_answers(&mut self, num_questions: usize) {
         self.answers = vec![None; num_questions];
+        self.answered_at = vec![None; num_questions];
+        self.confidences = vec![None; num_questions];
+        self.started_at = Some(Instant::now());
+        self.synced_at = None;
     }
 
     /// Get current question index (0-based).
@@ -113,6 +180,14 @@ impl UserSession {
         self.sender.is_some() && !matches!(self.status, UserStatus::Disconnected)
     }
 
+    /// Drop the connection to this user, who may come back while their
+    /// session lasts.
+    pub fn disconnect(&mut self) {
+        self.sender = None;
+        self.status = UserStatus::Disconnected;
+        self.disconnected_at = Some(Instant::now());
+    }
+
     /// Send a message to this user.
     pub fn send(&self, msg: ServerMessage) -> bool {
         if let Some(sender) = &self.sender {
@@ -122,13 +197,59 @@ impl UserSession {
         }
     }
 
-    /// Calculate score based on answers and questions.
-    pub fn calculate_score(&self, questions: &[Question]) -> usize {
-        self.answers
+    /// Calculate score as the sum of points for correct answers, plus a
+    /// bonus for answering quickly with `speed_bonus`.
+    ///
+    /// Answers given with a [`Confidence`] are weighted by it: sure answers
+    /// earn double when right and lose the question's points when wrong.
+    pub fn calculate_score(&self, questions: &[Question], speed_bonus: bool) -> usize {
+        let times = self.answer_times();
+        let mut bonus = 0.0;
+        let mut penalty = 0;
+        let points: usize = self
+            .answers
             .iter()
             .zip(questions.iter())
-            .filter(|(answer, question)| **answer == Some(question.correct_answer))
-            .count()
+            .enumerate()
+            .map(|(i, (answer, question))| {
+                let confidence = self.confidence(i);
+                if answer.as_deref().is_some_and(|answer| !question.is_correct(answer)) {
+                    penalty += confidence.penalty(question.points);
+                }
+                let points = confidence.reward(question.points_for(answer.as_deref()));
+                if let Some(time) = times.get(i).copied().flatten() {
+                    bonus += speed_bonus_points(points, time);
+                }
+                points
+            })
+            .sum();
+        // Rounded once, so quick answers to 1-point questions still add up
+        let points = if speed_bonus { points + bonus.round() as usize } else { points };
+        points.saturating_sub(penalty)
+    }
+
+    /// How sure the user was of answer `index`; unsure when not given.
+    pub fn confidence(&self, index: usize) -> Confidence {
+        self.confidences.get(index).copied().flatten().unwrap_or(Confidence::Unsure)
+    }
+
+    /// Time taken on each question, from getting it to answering it.
+    pub fn answer_times(&self) -> Vec<Option<Duration>> {
+        let mut previous = self.started_at;
+        self.answered_at
+            .iter()
+            .map(|&answered_at| {
+                let time = answered_at?.checked_duration_since(previous?);
+                previous = answered_at;
+                time
+            })
+            .collect()
+    }
+
+    /// Time from the first question to the latest answer.
+    pub fn total_time(&self) -> Option<Duration> {
+        let last = self.answered_at.iter().rev().find_map(|&answered_at| answered_at)?;
+        last.checked_duration_since(self.started_at?)
     }
 
     /// Get the number of correct answers so far.
@@ -138,7 +259,7 @@ impl UserSession {
             .enumerate()
             .filter(|(i, answer)| {
                 if let Some(ans) = answer {
-                    questions.get(*i).is_some_and(|q| q.correct_answer == *ans)
+                    questions.get(*i).is_some_and(|q| q.is_correct(ans))
                 } else {
                     false
                 }
@@ -152,64 +273,166 @@ impl UserSession {
     }
 }
 
+/// Bonus for a correct answer worth `points` given after `time`: as much
+/// again when instant, falling to nothing at [`SPEED_BONUS_WINDOW`].
+fn speed_bonus_points(points: usize, time: Duration) -> f64 {
+    let left = SPEED_BONUS_WINDOW.saturating_sub(time);
+    points as f64 * left.as_secs_f64() / SPEED_BONUS_WINDOW.as_secs_f64()
+}
+
+/// Time in seconds to the tenth, as shown to players and exported.
+pub(crate) fn seconds(time: Duration) -> f64 {
+    (time.as_secs_f64() * 10.0).round() / 10.0
+}
+
 /// A record of a recent answer for the live feed.
 #[derive(Debug, Clone)]
 pub struct LiveAnswer {
     pub username: String,
     pub question_index: usize,
-    pub answer: usize,
+    pub answer: Vec<usize>,
+    /// Time the player took on the question.
+    pub time: Option<Duration>,
     #[allow(dead_code)]
     pub timestamp: Instant,
 }
 
-/// Main server state.
-pub struct ServerState {
-    /// Current server status.
+/// A chat message posted in a room.
+#[derive(Debug, Clone)]
+pub struct ChatLine {
+    pub username: String,
+    pub text: String,
+}
+
+/// Letters of room codes, without I and O, which look like 1 and 0.
+const ROOM_CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+/// Length of room codes.
+const ROOM_CODE_LENGTH: usize = 4;
+/// Most history lines each room keeps for the log view.
+const HISTORY_LIMIT: usize = 500;
+
+/// One quiz on the server, with its own players, questions and progress.
+pub struct Room {
+    /// Code players give to join this room.
+    pub code: String,
+    /// Current status of the quiz in this room.
     pub status: ServerStatus,
-    /// Loaded questions.
+    /// Whether the host has paused the quiz in progress.
+    pub paused: bool,
+    /// Questions used for this quiz (may be a sample of `question_bank`).
     pub questions: Vec<Question>,
+    /// Every loaded question.
+    pub question_bank: Vec<Question>,
+    /// Details of the room's question file, such as its title.
+    pub meta: QuizMeta,
     /// All user sessions (by session ID).
     pub sessions: HashMap<Uuid, UserSession>,
     /// Username to session ID mapping.
     pub username_to_id: HashMap<String, Uuid>,
     /// IP address to session ID mapping (for reconnection).
     pub ip_to_id: HashMap<IpAddr, Uuid>,
-    /// Banned IP addresses.
-    pub banned_ips: HashSet<IpAddr>,
-    /// Current view for the host.
-    pub current_view: ServerView,
-    /// Previous view (for returning from Help).
-    pub previous_view: Option<ServerView>,
-    /// Current command input.
-    pub command_input: String,
-    /// Command history for display.
+    /// Command results and events in this room, for display.
     pub command_history: Vec<String>,
     /// Recent live answers for analytics.
     pub live_answers: Vec<LiveAnswer>,
-    /// Whether the server should shut down.
-    pub should_quit: bool,
-    /// Server port (for display).
-    pub port: u16,
+    /// Recent chat messages, for the host's feed.
+    pub chat: Vec<ChatLine>,
+    /// Usernames the host has muted in chat.
+    pub muted: HashSet<String>,
+    /// Most players the room takes at once, if limited.
+    pub max_players: Option<usize>,
+    /// Whether faster correct answers earn more points.
+    pub speed_bonus: bool,
+    /// Whether players confirm each answer before it is sent.
+    pub confirm_answers: bool,
+    /// Whether players say how sure they are of each answer.
+    pub confidence_mode: bool,
+    /// Whether players are told if each answer was right as they send it.
+    pub feedback: bool,
+    /// Whether players get every question at the start and may keep
+    /// answering while their connection is down.
+    pub offline: bool,
+    /// How a username someone already has is handled.
+    pub username_policy: UsernamePolicy,
+    /// Words refused in usernames.
+    pub blocklist: Blocklist,
+    /// Seconds counted down between `start` and the first question.
+    pub countdown: u32,
+    /// Seed the `limit` command picks questions with, if any.
+    pub seed: Option<u64>,
+    /// When the countdown to the first question ends, while it runs.
+    pub countdown_ends: Option<Instant>,
+    /// When the quiz starts by itself, after `start in`.
+    pub starts_at: Option<Instant>,
+    /// Answers accepted since the room opened, for metrics.
+    pub answers_received: u64,
+    /// Where the room's events are recorded, if anywhere.
+    pub transcript: Option<Transcript>,
 }
 
-impl ServerState {
-    /// Create a new server state with the given questions.
-    pub fn new(questions: Vec<Question>, port: u16) -> Self {
+impl Room {
+    /// Create an empty room in the lobby with the given questions.
+    pub fn new(code: String, questions: Vec<Question>, meta: QuizMeta) -> Self {
         Self {
+            code,
             status: ServerStatus::Lobby,
+            paused: false,
+            question_bank: questions.clone(),
             questions,
+            meta,
             sessions: HashMap::new(),
             username_to_id: HashMap::new(),
             ip_to_id: HashMap::new(),
-            banned_ips: HashSet::new(),
-            current_view: ServerView::Lobby,
-            previous_view: None,
-            command_input: String::new(),
             command_history: Vec::new(),
             live_answers: Vec::new(),
-            should_quit: false,
-            port,
+            chat: Vec::new(),
+            muted: HashSet::new(),
+            max_players: None,
+            speed_bonus: false,
+            confirm_answers: false,
+            confidence_mode: false,
+            feedback: false,
+            offline: false,
+            username_policy: UsernamePolicy::Reject,
+            blocklist: Blocklist::default(),
+            countdown: 0,
+            seed: None,
+            countdown_ends: None,
+            starts_at: None,
+            answers_received: 0,
+            transcript: None,
+        }
+    }
+
+    /// Maximum achievable score for the loaded questions, doubled by a
+    /// speed bonus and again in confidence mode.
+    pub fn max_score(&self) -> usize {
+        let mut points = max_score(&self.questions);
+        if self.speed_bonus {
+            points *= 2;
+        }
+        if self.confidence_mode {
+            points *= 2;
         }
+        points
+    }
+
+    /// Every question, without the answers, for players to answer offline;
+    /// empty unless the room lets them.
+    pub fn question_set(&self) -> Vec<QuizQuestion> {
+        if !self.offline {
+            return Vec::new();
+        }
+        self.questions
+            .iter()
+            .enumerate()
+            .map(|(index, question)| QuizQuestion::new(index, question))
+            .collect()
+    }
+
+    /// Seconds in which a correct answer earns a speed bonus, if it is on.
+    pub fn speed_bonus_window(&self) -> Option<u64> {
+        self.speed_bonus.then(|| SPEED_BONUS_WINDOW.as_secs())
     }
 
     /// Get all users with usernames (in lobby or playing).
@@ -229,6 +452,17 @@ impl ServerState {
             .collect()
     }
 
+    /// Leave out the question at `index`, also from the bank `limit` picks
+    /// from, so it isn't picked again.
+    pub fn drop_question(&mut self, index: usize) -> Option<Question> {
+        if index >= self.questions.len() {
+            return None;
+        }
+        let question = self.questions.remove(index);
+        self.question_bank.retain(|q| q.text != question.text);
+        Some(question)
+    }
+
     /// Get count of users who have finished.
     pub fn finished_count(&self) -> usize {
         self.sessions.values().filter(|s| s.is_finished()).count()
@@ -242,11 +476,136 @@ impl ServerState {
             .count()
     }
 
+    /// Get the number of connected users with usernames.
+    pub fn player_count(&self) -> usize {
+        self.sessions
+            .values()
+            .filter(|s| s.username.is_some() && s.is_connected())
+            .count()
+    }
+
+    /// Check if the room has as many players as it takes.
+    pub fn is_full(&self) -> bool {
+        self.max_players.is_some_and(|max| self.player_count() >= max)
+    }
+
     /// Check if a username is taken.
     pub fn is_username_taken(&self, username: &str) -> bool {
         self.username_to_id.contains_key(username)
     }
 
+    /// The username a player asking for `username` gets under the room's
+    /// policy, or why they can't have one.
+    pub fn claim_username(&mut self, username: &str) -> Result<String, &'static str> {
+        let Some(&holder) = self.username_to_id.get(username) else {
+            return Ok(username.to_string());
+        };
+        match self.username_policy {
+            UsernamePolicy::Reject => Err("Username is already taken"),
+            UsernamePolicy::Suffix => {
+                let name = (2..)
+                    .map(|n| {
+                        // Shortened to make room for the number if need be
+                        let suffix = format!("-{}", n);
+                        let mut end =
+                            username.len().min(USERNAME_MAX_LENGTH.saturating_sub(suffix.len()));
+                        while !username.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        format!("{}{}", &username[..end], suffix)
+                    })
+                    .find(|name| !self.is_username_taken(name))
+                    .expect("some number is free");
+                Ok(name)
+            }
+            UsernamePolicy::Replace => {
+                if self.sessions.get(&holder).is_some_and(|s| s.is_connected()) {
+                    return Err("Username is in use by a connected player");
+                }
+                self.remove_session(holder);
+                Ok(username.to_string())
+            }
+        }
+    }
+
+    /// Forget a session entirely, freeing its username.
+    pub fn remove_session(&mut self, id: Uuid) {
+        if let Some(session) = self.sessions.remove(&id)
+            && let Some(username) = session.username
+            && self.username_to_id.get(&username) == Some(&id)
+        {
+            self.username_to_id.remove(&username);
+        }
+        self.ip_to_id.retain(|_, session_id| *session_id != id);
+    }
+
+    /// Close connections that never picked a username within `join_timeout`,
+    /// and give up on players disconnected for `abandon_after`: they are
+    /// forgotten if they hadn't started the quiz, and otherwise kept for the
+    /// results but free their username and address. `None` keeps waiting.
+    pub fn purge_idle_sessions(
+        &mut self,
+        join_timeout: Option<Duration>,
+        abandon_after: Option<Duration>,
+    ) {
+        let now = Instant::now();
+        let never_joined: Vec<Uuid> = self
+            .sessions
+            .values()
+            .filter(|s| s.username.is_none())
+            .filter(|s| join_timeout.is_some_and(|t| now.duration_since(s.connected_at) >= t))
+            .map(|s| s.id)
+            .collect();
+        for &id in &never_joined {
+            if let Some(session) = self.sessions.get(&id) {
+                session.send(ServerMessage::Kicked {
+                    reason: "Took too long to pick a username".to_string(),
+                });
+            }
+            // Dropping the sender closes the connection
+            self.remove_session(id);
+        }
+        if !never_joined.is_empty() {
+            self.add_to_history(format!(
+                "Closed {} connections that never picked a username",
+                never_joined.len()
+            ));
+        }
+
+        let away: Vec<Uuid> = self
+            .sessions
+            .values()
+            .filter(|s| s.status == UserStatus::Disconnected)
+            .filter(|s| {
+                s.disconnected_at
+                    .zip(abandon_after)
+                    .is_some_and(|(at, after)| now.duration_since(at) >= after)
+            })
+            .map(|s| s.id)
+            .collect();
+        for &id in &away {
+            let Some(session) = self.sessions.get_mut(&id) else {
+                continue;
+            };
+            let username = session.username.clone().unwrap_or_default();
+            if session.answers.is_empty() {
+                self.remove_session(id);
+            } else {
+                session.status = UserStatus::Abandoned;
+                if self.username_to_id.get(&username) == Some(&id) {
+                    self.username_to_id.remove(&username);
+                }
+                self.ip_to_id.retain(|_, session_id| *session_id != id);
+            }
+            self.add_to_history(format!("User {} abandoned the quiz", username));
+        }
+        if !away.is_empty() {
+            self.broadcast_lobby();
+            // The others may have been waiting only on them
+            self.finish_if_everyone_done();
+        }
+    }
+
     /// Get a user session by username.
     pub fn get_user_by_name(&self, username: &str) -> Option<&UserSession> {
         self.username_to_id
@@ -280,11 +639,18 @@ impl ServerState {
     }
 
     /// Add a live answer record.
-    pub fn record_live_answer(&mut self, username: String, question_index: usize, answer: usize) {
+    pub fn record_live_answer(
+        &mut self,
+        username: String,
+        question_index: usize,
+        answer: Vec<usize>,
+        time: Option<Duration>,
+    ) {
         self.live_answers.push(LiveAnswer {
             username,
             question_index,
             answer,
+            time,
             timestamp: Instant::now(),
         });
 
@@ -294,6 +660,286 @@ impl ServerState {
         }
     }
 
+    /// Post a chat message from a player and pass it on to the room.
+    ///
+    /// Players can chat in the lobby and once the quiz is over, but not
+    /// while answering, nor after finishing while others are still at it,
+    /// as they could pass on the answers. Control characters are dropped, the message is cut to
+    /// [`CHAT_MAX_LENGTH`] and empty messages are ignored.
+    pub fn post_chat(&mut self, session_id: Uuid, text: &str) -> Result<(), &'static str> {
+        let Some(session) = self.sessions.get(&session_id) else {
+            return Ok(());
+        };
+        let Some(username) = session.username.clone() else {
+            return Err("Pick a username before chatting");
+        };
+        if self.muted.contains(&username) {
+            return Err("You have been muted by the host");
+        }
+        if matches!(session.status, UserStatus::Answering(_)) {
+            return Err("Chat opens again when you finish the quiz");
+        }
+        if self.status == ServerStatus::InProgress && session.status == UserStatus::Finished {
+            return Err("Chat opens again once everyone has finished");
+        }
+
+        let text: String = text
+            .trim()
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(CHAT_MAX_LENGTH)
+            .collect();
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        self.chat.push(ChatLine {
+            username: username.clone(),
+            text: text.clone(),
+        });
+        // Keep only the last 50 messages
+        if self.chat.len() > 50 {
+            self.chat.remove(0);
+        }
+        self.record(Event::Chat {
+            username: username.clone(),
+            text: text.clone(),
+        });
+        self.broadcast(ServerMessage::Chat { username, text });
+        Ok(())
+    }
+
+    /// Add `event` to the transcript, if the room keeps one.
+    pub fn record(&self, event: Event) {
+        if let Some(transcript) = &self.transcript {
+            transcript.record(&self.code, event);
+        }
+    }
+
+    /// Start the quiz for everyone in the lobby, counting down to the first
+    /// question if the room has a countdown.
+    ///
+    /// Returns how many players took part, or why the quiz can't start.
+    pub fn start(&mut self) -> Result<usize, &'static str> {
+        if self.status != ServerStatus::Lobby {
+            return Err("Quiz has already started.");
+        }
+        let named_count = self.named_user_count();
+        if named_count == 0 {
+            return Err("No users have joined yet.");
+        }
+        self.starts_at = None;
+
+        // Initialize all users for the quiz
+        let num_questions = self.questions.len();
+        for session in self.sessions.values_mut() {
+            if session.username.is_some() && session.status == UserStatus::InLobby {
+                session.init_answers(num_questions);
+                session.status = UserStatus::Answering(0);
+            }
+        }
+
+        self.status = ServerStatus::InProgress;
+        let mut players: Vec<String> = self
+            .sessions
+            .values()
+            .filter(|s| s.status == UserStatus::Answering(0))
+            .filter_map(|s| s.username.clone())
+            .collect();
+        players.sort();
+        self.record(Event::Started {
+            questions: self.questions.clone(),
+            players,
+            speed_bonus: self.speed_bonus,
+            confidence_mode: self.confidence_mode,
+        });
+
+        // Broadcast quiz start
+        self.broadcast(ServerMessage::QuizStart {
+            total_questions: num_questions,
+            questions: self.question_set(),
+        });
+
+        // Send the first question now or once the countdown ends
+        if self.countdown > 0 {
+            let seconds = self.countdown;
+            self.countdown_ends = Some(Instant::now() + Duration::from_secs(seconds.into()));
+            self.broadcast(ServerMessage::Countdown { seconds });
+        } else {
+            self.send_first_question();
+        }
+        Ok(named_count)
+    }
+
+    /// Start the quiz by itself after `delay`, telling the lobby, or call
+    /// off a scheduled start with `None`.
+    pub fn schedule_start(&mut self, delay: Option<Duration>) {
+        self.starts_at = delay.map(|delay| Instant::now() + delay);
+        match self.starts_in() {
+            Some(seconds) => self.broadcast(ServerMessage::StartsIn { seconds }),
+            None => self.broadcast(ServerMessage::StartCancelled),
+        }
+    }
+
+    /// Seconds until a scheduled start, rounded up.
+    pub fn starts_in(&self) -> Option<u32> {
+        let left = self.starts_at?.saturating_duration_since(Instant::now());
+        Some(left.as_secs_f64().ceil() as u32)
+    }
+
+    /// Send the first question to everyone taking the quiz, timing their
+    /// answers from now.
+    pub fn send_first_question(&mut self) {
+        self.countdown_ends = None;
+        let now = Instant::now();
+        for session in self.sessions.values_mut() {
+            if session.status == UserStatus::Answering(0) {
+                session.started_at = Some(now);
+            }
+        }
+
+        if let Some(first_question) = self.questions.first() {
+            self.broadcast(ServerMessage::question(0, first_question));
+            for session in self.sessions.values() {
+                if session.status == UserStatus::Answering(0)
+                    && let Some(username) = &session.username
+                {
+                    self.record(Event::Question {
+                        username: username.clone(),
+                        index: 0,
+                    });
+                }
+            }
+        }
+        self.broadcast_standings();
+        self.broadcast_progress();
+    }
+
+    /// End the quiz, sending results to everyone who finished and telling
+    /// the rest the host ended it.
+    pub fn finish_quiz(&mut self) {
+        self.status = ServerStatus::Finished;
+        self.countdown_ends = None;
+        self.paused = false;
+
+        for session in self.sessions.values_mut() {
+            if session.is_finished() {
+                session.score = Some(session.calculate_score(&self.questions, self.speed_bonus));
+            }
+        }
+
+        let mut scores = Vec::new();
+        for session in self.sessions.values() {
+            if session.is_finished() {
+                session.send(self.final_results(session));
+                if let Some(username) = &session.username {
+                    scores.push((username.clone(), session.score.unwrap_or(0)));
+                }
+            } else if session.is_connected() {
+                session.send(ServerMessage::HostEndedQuiz);
+            }
+        }
+        scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        self.record(Event::Ended { scores });
+    }
+
+    /// End the quiz once every player still taking it has finished, logging
+    /// it. Returns whether the quiz ended.
+    pub fn finish_if_everyone_done(&mut self) -> bool {
+        if self.status != ServerStatus::InProgress || !self.everyone_finished() {
+            return false;
+        }
+        self.finish_quiz();
+        let count = self.finished_count();
+        let players = if count == 1 { "player" } else { "players" };
+        self.add_to_history(format!(
+            "Everyone finished ({} {}). Quiz ended and results sent.",
+            count, players
+        ));
+        true
+    }
+
+    /// The results and final leaderboard for a player who took part.
+    pub fn final_results(&self, session: &UserSession) -> ServerMessage {
+        let username = session.username.as_deref().unwrap_or_default();
+        let score = session
+            .score
+            .unwrap_or_else(|| session.calculate_score(&self.questions, self.speed_bonus));
+        ServerMessage::QuizResults {
+            score,
+            total: self.max_score(),
+            seconds: session.total_time().map(seconds),
+            answers: self.generate_answer_results(session),
+            leaderboard: self.generate_leaderboard(username),
+            grades: self.meta.grades.clone(),
+        }
+    }
+
+    /// Whether every player still taking the quiz has answered every
+    /// question. Players who dropped out partway don't hold up the results,
+    /// unless they may be answering offline and haven't been given up on,
+    /// but someone must have finished.
+    pub fn everyone_finished(&self) -> bool {
+        let taking: Vec<_> = self
+            .sessions
+            .values()
+            .filter(|s| s.username.is_some() && !s.answers.is_empty())
+            .collect();
+        let gone = |s: &UserSession| {
+            !s.is_connected() && (!self.offline || s.status == UserStatus::Abandoned)
+        };
+        taking.iter().any(|s| s.is_finished())
+            && taking.iter().all(|s| s.is_finished() || gone(s))
+    }
+
+    /// Tell players waiting for results how many of those taking the quiz
+    /// have finished.
+    pub fn broadcast_progress_summary(&self) {
+        if self.status != ServerStatus::InProgress {
+            return;
+        }
+        let taking: Vec<_> = self
+            .sessions
+            .values()
+            .filter(|s| s.username.is_some() && !s.answers.is_empty())
+            .collect();
+        let players = taking.len();
+        let finished = taking.iter().filter(|s| s.is_finished()).count();
+
+        for session in taking {
+            if session.is_finished() && session.is_connected() {
+                session.send(ServerMessage::ProgressSummary { finished, players });
+            }
+        }
+    }
+
+    /// Send everyone back to the lobby for another round, keeping their
+    /// connections and usernames.
+    pub fn new_round(&mut self) {
+        self.status = ServerStatus::Lobby;
+        self.paused = false;
+        self.countdown_ends = None;
+        self.live_answers.clear();
+
+        for session in self.sessions.values_mut() {
+            session.answers.clear();
+            session.answered_at.clear();
+            session.started_at = None;
+            session.synced_at = None;
+            session.score = None;
+            session.finished_at = None;
+            if let Some(username) = &session.username
+                && session.is_connected()
+            {
+                session.status = UserStatus::InLobby;
+                session.send(ServerMessage::ReturnToLobby {
+                    username: username.clone(),
+                });
+            }
+        }
+        self.broadcast_lobby();
+    }
+
     /// Generate leaderboard sorted by score (desc) then finish time (asc).
     pub fn generate_leaderboard(&self, requesting_username: &str) -> Vec<LeaderboardEntry> {
         let mut finished_users: Vec<_> = self
@@ -319,28 +965,107 @@ impl ServerState {
                 rank: i + 1,
                 username: user.username.clone().unwrap_or_default(),
                 score: user.score.unwrap_or(0),
-                total: self.questions.len(),
+                total: self.max_score(),
+                seconds: user.total_time().map(seconds),
+                is_you: user.username.as_deref() == Some(requesting_username),
+            })
+            .collect()
+    }
+
+    /// Generate interim standings of everyone taking the quiz, sorted by
+    /// points so far, then by questions answered.
+    pub fn generate_standings(&self, requesting_username: &str) -> Vec<StandingEntry> {
+        let mut players: Vec<_> = self
+            .sessions
+            .values()
+            .filter(|s| s.username.is_some() && !s.answers.is_empty())
+            .map(|s| (s, s.calculate_score(&self.questions, self.speed_bonus), s.answered_count()))
+            .collect();
+
+        players.sort_by(|(a, a_score, a_answered), (b, b_score, b_answered)| {
+            b_score
+                .cmp(a_score)
+                .then(b_answered.cmp(a_answered))
+                .then(a.finished_at.cmp(&b.finished_at))
+                .then(a.username.cmp(&b.username))
+        });
+
+        players
+            .into_iter()
+            .enumerate()
+            .map(|(i, (user, score, answered))| StandingEntry {
+                rank: i + 1,
+                username: user.username.clone().unwrap_or_default(),
+                answered,
+                score,
+                finished: user.is_finished(),
                 is_you: user.username.as_deref() == Some(requesting_username),
             })
             .collect()
     }
 
+    /// Send the interim standings to every player still answering.
+    pub fn broadcast_standings(&self) {
+        for session in self.sessions.values() {
+            if let (Some(username), UserStatus::Answering(_)) = (&session.username, &session.status)
+                && session.is_connected()
+            {
+                session.send(ServerMessage::Standings {
+                    standings: self.generate_standings(username),
+                });
+            }
+        }
+    }
+
+    /// Send every player how far everyone taking the quiz has got, furthest
+    /// first.
+    pub fn broadcast_progress(&self) {
+        let mut players: Vec<PlayerProgress> = self
+            .sessions
+            .values()
+            .filter(|s| !s.answers.is_empty())
+            .filter_map(|s| {
+                Some(PlayerProgress {
+                    username: s.username.clone()?,
+                    answered: s.answered_count(),
+                })
+            })
+            .collect();
+        players.sort_by(|a, b| b.answered.cmp(&a.answered).then(a.username.cmp(&b.username)));
+        self.broadcast(ServerMessage::Progress { players });
+    }
+
+    /// Send every player the names of everyone connected, after someone
+    /// joins or leaves.
+    pub fn broadcast_lobby(&self) {
+        let mut players: Vec<String> = self
+            .sessions
+            .values()
+            .filter(|s| s.is_connected())
+            .filter_map(|s| s.username.clone())
+            .collect();
+        players.sort_by_key(|name| name.to_lowercase());
+        self.broadcast(ServerMessage::LobbyUpdate { players });
+    }
+
     /// Generate answer results for a user.
-    #[allow(dead_code)]
     pub fn generate_answer_results(&self, user: &UserSession) -> Vec<AnswerResult> {
+        let times = user.answer_times();
         user.answers
             .iter()
             .enumerate()
             .filter_map(|(i, answer)| {
                 let question = self.questions.get(i)?;
-                let your_answer = (*answer)?;
+                let your_answer = answer.clone()?;
                 Some(AnswerResult {
                     question_index: i,
                     question_text: question.text.clone(),
+                    is_correct: question.is_correct(&your_answer),
                     your_answer,
-                    correct_answer: question.correct_answer,
-                    is_correct: your_answer == question.correct_answer,
+                    correct_answer: question.correct_answer.indices(),
                     options: question.options.clone(),
+                    seconds: times.get(i).copied().flatten().map(seconds),
+                    confidence: user.confidences.get(i).copied().flatten(),
                 })
             })
             .collect()
@@ -367,9 +1092,528 @@ impl ServerState {
     /// Add a message to command history.
     pub fn add_to_history(&mut self, msg: String) {
         self.command_history.push(msg);
-        // Keep only the last 100 messages
-        if self.command_history.len() > 100 {
+        if self.command_history.len() > HISTORY_LIMIT {
             self.command_history.remove(0);
         }
     }
 }
+
+/// Main server state: the open rooms, and what the host is doing.
+pub struct ServerState {
+    /// Open rooms by code.
+    pub rooms: BTreeMap<String, Room>,
+    /// Code of the room the host sees and commands apply to.
+    pub current_room: String,
+    /// Code of the first room, joined by players who don't give a code.
+    pub default_room: String,
+    /// Questions loaded at startup, given to new rooms.
+    pub question_bank: Vec<Question>,
+    /// Details of the startup question file, given to new rooms.
+    pub meta: QuizMeta,
+    /// Most players each room takes at once, if limited.
+    pub max_players: Option<usize>,
+    /// Whether rooms give a bonus for fast correct answers.
+    pub speed_bonus: bool,
+    /// Whether players in every room confirm each answer.
+    pub confirm_answers: bool,
+    /// Whether players in every room say how sure they are of each answer.
+    pub confidence_mode: bool,
+    /// Whether players in new rooms are told if each answer was right as
+    /// they send it.
+    pub feedback: bool,
+    /// Whether players in every room may answer offline.
+    pub offline: bool,
+    /// How every room handles a username someone already has.
+    pub username_policy: UsernamePolicy,
+    /// Words refused in usernames in every room.
+    pub blocklist: Blocklist,
+    /// Seconds every room counts down before the first question.
+    pub countdown: u32,
+    /// Seed every room's `limit` command picks questions with, if any.
+    pub seed: Option<u64>,
+    /// How often bots added without an accuracy answer right.
+    pub bot_accuracy: BotAccuracy,
+    /// Bots asked for and not yet added.
+    pub bot_requests: Vec<BotRequest>,
+    /// Where every room's events are recorded, if anywhere.
+    pub transcript: Option<Transcript>,
+    /// How long a connection may go without picking a username; `None` to
+    /// wait forever.
+    pub join_timeout: Option<Duration>,
+    /// How long a disconnected player may be away before they are given up
+    /// on; `None` to wait forever.
+    pub abandon_after: Option<Duration>,
+    /// Banned IP addresses, kept out of every room.
+    pub banned_ips: HashSet<IpAddr>,
+    /// Current view for the host.
+    pub current_view: ServerView,
+    /// Previous view (for returning from Help).
+    pub previous_view: Option<ServerView>,
+    /// Current command input.
+    pub command_input: CommandInput,
+    /// Lines the log view is scrolled back from the newest.
+    pub log_scroll: usize,
+    /// Index of the first question in the preview view.
+    pub preview_scroll: usize,
+    /// Whether the server should shut down.
+    pub should_quit: bool,
+    /// Server port (for display).
+    pub port: u16,
+}
+
+impl ServerState {
+    /// Create a new server state with one room using the given questions.
+    pub fn new(questions: Vec<Question>, meta: QuizMeta, port: u16) -> Self {
+        let mut state = Self {
+            rooms: BTreeMap::new(),
+            current_room: String::new(),
+            default_room: String::new(),
+            question_bank: questions,
+            meta,
+            max_players: None,
+            speed_bonus: false,
+            confirm_answers: false,
+            confidence_mode: false,
+            feedback: false,
+            offline: false,
+            username_policy: UsernamePolicy::Reject,
+            blocklist: Blocklist::default(),
+            countdown: 0,
+            seed: None,
+            bot_accuracy: BotAccuracy::default(),
+            bot_requests: Vec::new(),
+            transcript: None,
+            join_timeout: None,
+            abandon_after: None,
+            banned_ips: HashSet::new(),
+            current_view: ServerView::Lobby,
+            previous_view: None,
+            command_input: CommandInput::default(),
+            log_scroll: 0,
+            preview_scroll: 0,
+            should_quit: false,
+            port,
+        };
+        let code = state.open_room();
+        state.current_room = code.clone();
+        state.default_room = code;
+        state
+    }
+
+    /// The room the host is looking at.
+    pub fn room(&self) -> &Room {
+        &self.rooms[&self.current_room]
+    }
+
+    /// The room the host is looking at, mutably.
+    pub fn room_mut(&mut self) -> &mut Room {
+        self.rooms
+            .get_mut(&self.current_room)
+            .expect("the current room is always open")
+    }
+
+    /// Open a new room with the startup questions, returning its code.
+    pub fn open_room(&mut self) -> String {
+        let mut rng = rand::rng();
+        let code = loop {
+            let code: String = (0..ROOM_CODE_LENGTH)
+                .map(|_| *ROOM_CODE_LETTERS.choose(&mut rng).unwrap() as char)
+                .collect();
+            if !self.rooms.contains_key(&code) {
+                break code;
+            }
+        };
+        let mut room = Room::new(code.clone(), self.question_bank.clone(), self.meta.clone());
+        room.max_players = self.max_players;
+        room.speed_bonus = self.speed_bonus;
+        room.confirm_answers = self.confirm_answers;
+        room.confidence_mode = self.confidence_mode;
+        room.feedback = self.feedback;
+        room.offline = self.offline;
+        room.username_policy = self.username_policy;
+        room.blocklist = self.blocklist.clone();
+        room.countdown = self.countdown;
+        room.seed = self.seed;
+        room.transcript = self.transcript.clone();
+        self.rooms.insert(code.clone(), room);
+        code
+    }
+
+    /// Clean up idle and long-gone sessions in every room (see
+    /// [`Room::purge_idle_sessions`]).
+    pub fn purge_idle_sessions(&mut self) {
+        for room in self.rooms.values_mut() {
+            room.purge_idle_sessions(self.join_timeout, self.abandon_after);
+        }
+    }
+
+    /// Update players waiting for results in every room.
+    pub fn broadcast_progress_summaries(&self) {
+        for room in self.rooms.values() {
+            room.broadcast_progress_summary();
+        }
+    }
+
+    /// Send the first question in every room whose countdown has run out,
+    /// and start the quiz in those whose scheduled start has come.
+    pub fn end_countdowns(&mut self) {
+        let now = Instant::now();
+        let mut started_here = false;
+        for room in self.rooms.values_mut() {
+            if room.countdown_ends.is_some_and(|ends| ends <= now) {
+                room.send_first_question();
+            }
+            if room.starts_at.is_some_and(|at| at <= now) {
+                room.starts_at = None;
+                let message = match room.start() {
+                    Ok(count) => {
+                        started_here |= room.code == self.current_room;
+                        format!("Scheduled start: quiz started with {} users!", count)
+                    }
+                    Err(reason) => format!("Scheduled start called off: {}", reason),
+                };
+                room.add_to_history(message);
+            }
+        }
+        // As after typing `start`
+        if started_here {
+            self.current_view = ServerView::Analytics;
+        }
+    }
+
+    /// Code of the room a player asked for, ignoring case, or the default
+    /// room if they gave no code.
+    pub fn find_room(&self, code: Option<&str>) -> Option<String> {
+        match code {
+            Some(code) => {
+                let code = code.to_uppercase();
+                self.rooms.contains_key(&code).then_some(code)
+            }
+            None => Some(self.default_room.clone()),
+        }
+    }
+
+    /// Add a message to the current room's command history.
+    pub fn add_to_history(&mut self, msg: String) {
+        self.room_mut().add_to_history(msg);
+    }
+
+    /// Tell everyone the server is closing and let go of their senders, so
+    /// each connection closes once its pending messages are sent.
+    pub fn close_connections(&mut self) {
+        for room in self.rooms.values_mut() {
+            room.broadcast_all(ServerMessage::ServerClosing);
+            for session in room.sessions.values_mut() {
+                session.sender = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rooms_are_separate() {
+        let mut state = ServerState::new(Vec::new(), QuizMeta::default(), 8712);
+        let first = state.default_room.clone();
+        let second = state.open_room();
+
+        assert_ne!(first, second);
+        assert_eq!(second.len(), ROOM_CODE_LENGTH);
+        assert_eq!(state.find_room(None), Some(first.clone()));
+        assert_eq!(state.find_room(Some(&second.to_lowercase())), Some(second.clone()));
+        assert_eq!(state.find_room(Some("1234")), None);
+
+        state.rooms.get_mut(&second).unwrap().status = ServerStatus::InProgress;
+        assert_eq!(state.room().status, ServerStatus::Lobby);
+    }
+
+    #[test]
+    fn test_end_countdowns() {
+        let questions = vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)];
+        let mut state = ServerState::new(questions, QuizMeta::default(), 8712);
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+        session.username = Some("alice".to_string());
+        session.status = UserStatus::Answering(0);
+        state.room_mut().sessions.insert(session.id, session);
+
+        state.room_mut().countdown_ends = Some(Instant::now() + Duration::from_secs(60));
+        state.end_countdowns();
+        assert!(receiver.try_recv().is_err());
+
+        state.room_mut().countdown_ends = Some(Instant::now());
+        state.end_countdowns();
+        assert!(state.room().countdown_ends.is_none());
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::Question { index: 0, .. })));
+    }
+
+    #[test]
+    fn test_scheduled_start() {
+        let questions = vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)];
+        let mut state = ServerState::new(questions, QuizMeta::default(), 8712);
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+        session.username = Some("alice".to_string());
+        session.status = UserStatus::InLobby;
+        state.room_mut().sessions.insert(session.id, session);
+
+        state.room_mut().schedule_start(Some(Duration::from_secs(60)));
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::StartsIn { seconds: 60 })));
+        state.end_countdowns();
+        assert_eq!(state.room().status, ServerStatus::Lobby);
+
+        state.room_mut().starts_at = Some(Instant::now());
+        state.end_countdowns();
+        assert_eq!(state.room().status, ServerStatus::InProgress);
+        assert_eq!(state.room().starts_in(), None);
+        assert_eq!(state.current_view, ServerView::Analytics);
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::QuizStart { .. })));
+        let log = state.room().command_history.last().unwrap();
+        assert_eq!(log, "Scheduled start: quiz started with 1 users!");
+    }
+
+    #[test]
+    fn test_finish_when_everyone_done() {
+        let questions = vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)];
+        let mut room = Room::new("ABCD".to_string(), questions, QuizMeta::default());
+        room.status = ServerStatus::InProgress;
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut ids = Vec::new();
+        for name in ["alice", "bobby"] {
+            let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender.clone());
+            session.username = Some(name.to_string());
+            session.init_answers(1);
+            session.status = UserStatus::Answering(0);
+            ids.push(session.id);
+            room.sessions.insert(session.id, session);
+        }
+        assert!(!room.finish_if_everyone_done());
+
+        let alice = room.sessions.get_mut(&ids[0]).unwrap();
+        alice.answers[0] = Some(vec![1]);
+        alice.status = UserStatus::Finished;
+        assert!(!room.finish_if_everyone_done());
+
+        // Bobby dropping out leaves nobody to wait for
+        room.sessions.get_mut(&ids[1]).unwrap().disconnect();
+        assert!(room.finish_if_everyone_done());
+        assert_eq!(room.status, ServerStatus::Finished);
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::QuizResults { score: 1, .. })));
+        let log = room.command_history.last().unwrap();
+        assert_eq!(log, "Everyone finished (1 player). Quiz ended and results sent.");
+    }
+
+    #[test]
+    fn test_offline_room() {
+        let questions = vec![
+            Question::new("Pick b", ["a", "b", "c", "d"], 1),
+            Question::new("Pick c", ["a", "b", "c", "d"], 2),
+        ];
+        let mut room = Room::new("ABCD".to_string(), questions, QuizMeta::default());
+        room.offline = true;
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut ids = Vec::new();
+        for name in ["alice", "bobby"] {
+            let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender.clone());
+            session.username = Some(name.to_string());
+            session.status = UserStatus::InLobby;
+            ids.push(session.id);
+            room.sessions.insert(session.id, session);
+        }
+
+        // Everyone gets every question, without the answers
+        assert_eq!(room.start(), Ok(2));
+        let Ok(ServerMessage::QuizStart { questions, .. }) = receiver.try_recv() else {
+            panic!("expected the quiz to start");
+        };
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[1].text, "Pick c");
+
+        // Bobby may still be answering while disconnected
+        let alice = room.sessions.get_mut(&ids[0]).unwrap();
+        alice.answers = vec![Some(vec![1]), Some(vec![2])];
+        alice.status = UserStatus::Finished;
+        room.sessions.get_mut(&ids[1]).unwrap().disconnect();
+        assert!(!room.finish_if_everyone_done());
+
+        // ...until they are given up on
+        room.purge_idle_sessions(None, Some(Duration::ZERO));
+        assert_eq!(room.status, ServerStatus::Finished);
+    }
+
+    #[test]
+    fn test_purge_idle_sessions() {
+        let mut room = Room::new("ABCD".to_string(), Vec::new(), QuizMeta::default());
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let mut ids = Vec::new();
+        for (i, name) in [None, Some("alice"), Some("bobby")].into_iter().enumerate() {
+            let mut session = UserSession::new(IpAddr::from([10, 0, 0, i as u8]), sender.clone());
+            if let Some(name) = name {
+                session.username = Some(name.to_string());
+                room.username_to_id.insert(name.to_string(), session.id);
+                session.disconnect();
+            }
+            room.ip_to_id.insert(session.ip_addr, session.id);
+            ids.push(session.id);
+            room.sessions.insert(session.id, session);
+        }
+        // Bobby took part in the quiz
+        room.sessions.get_mut(&ids[2]).unwrap().answers = vec![Some(vec![0])];
+
+        room.purge_idle_sessions(Some(Duration::from_secs(60)), None);
+        assert_eq!(room.sessions.len(), 3);
+
+        room.purge_idle_sessions(Some(Duration::ZERO), Some(Duration::ZERO));
+        assert_eq!(room.sessions.len(), 1);
+        assert_eq!(room.sessions[&ids[2]].status, UserStatus::Abandoned);
+        assert!(room.username_to_id.is_empty());
+        assert!(room.ip_to_id.is_empty());
+        assert!(room.command_history.iter().any(|line| line == "User bobby abandoned the quiz"));
+    }
+
+    #[test]
+    fn test_drop_question() {
+        let questions = vec![
+            Question::new("First?", ["a", "b", "c", "d"], 0),
+            Question::new("Second?", ["a", "b", "c", "d"], 1),
+        ];
+        let mut room = Room::new("ABCD".to_string(), questions, QuizMeta::default());
+
+        assert!(room.drop_question(2).is_none());
+        assert_eq!(room.drop_question(0).map(|q| q.text), Some("First?".to_string()));
+        let texts = |questions: &[Question]| -> Vec<String> {
+            questions.iter().map(|q| q.text.clone()).collect()
+        };
+        assert_eq!(texts(&room.questions), ["Second?"]);
+        assert_eq!(texts(&room.question_bank), ["Second?"]);
+    }
+
+    #[test]
+    fn test_claim_username() {
+        let mut room = Room::new("ABCD".to_string(), Vec::new(), QuizMeta::default());
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        for name in ["alice", "abcdefghijklmnop"] {
+            let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender.clone());
+            session.username = Some(name.to_string());
+            room.username_to_id.insert(name.to_string(), session.id);
+            room.ip_to_id.insert(session.ip_addr, session.id);
+            room.sessions.insert(session.id, session);
+        }
+
+        assert_eq!(room.claim_username("bobby"), Ok("bobby".to_string()));
+        assert!(room.claim_username("alice").is_err());
+
+        room.username_policy = UsernamePolicy::Suffix;
+        assert_eq!(room.claim_username("alice"), Ok("alice-2".to_string()));
+        assert_eq!(room.claim_username("abcdefghijklmnop"), Ok("abcdefghijklmn-2".to_string()));
+
+        room.username_policy = UsernamePolicy::Replace;
+        assert!(room.claim_username("alice").is_err());
+        room.get_user_by_name_mut("alice").unwrap().sender = None;
+        assert_eq!(room.claim_username("alice"), Ok("alice".to_string()));
+        assert!(!room.is_username_taken("alice"));
+        assert_eq!(room.sessions.len(), 1);
+    }
+
+    #[test]
+    fn test_speed_bonus() {
+        let questions = vec![
+            Question::new("First?", ["a", "b", "c", "d"], 0),
+            Question::new("Second?", ["a", "b", "c", "d"], 1).with_points(2),
+        ];
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+        session.init_answers(questions.len());
+        let start = session.started_at.unwrap();
+        session.answers = vec![Some(vec![0]), Some(vec![1])];
+        session.answered_at = vec![
+            Some(start + Duration::from_secs(3)),
+            Some(start + Duration::from_secs(18)),
+        ];
+
+        let times = session.answer_times();
+        assert_eq!(times, [Some(Duration::from_secs(3)), Some(Duration::from_secs(15))]);
+        assert_eq!(session.total_time(), Some(Duration::from_secs(18)));
+        assert_eq!(session.calculate_score(&questions, false), 3);
+        // Bonuses of 0.9 and 1.0 on top of the 3 points
+        assert_eq!(session.calculate_score(&questions, true), 5);
+    }
+
+    #[test]
+    fn test_confidence_score() {
+        let questions = vec![
+            Question::new("First?", ["a", "b", "c", "d"], 0).with_points(2),
+            Question::new("Second?", ["a", "b", "c", "d"], 1),
+            Question::new("Third?", ["a", "b", "c", "d"], 2),
+        ];
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+        session.init_answers(questions.len());
+        session.answers = vec![Some(vec![0]), Some(vec![0]), Some(vec![0])];
+        session.confidences = vec![Some(Confidence::Sure), Some(Confidence::Sure), None];
+
+        // 4 for the sure right answer, less 1 for the sure wrong one
+        assert_eq!(session.calculate_score(&questions, false), 3);
+    }
+
+    #[test]
+    fn test_post_chat() {
+        let mut room = Room::new("ABCD".to_string(), Vec::new(), QuizMeta::default());
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+        session.username = Some("alice".to_string());
+        session.status = UserStatus::InLobby;
+        let id = session.id;
+        room.sessions.insert(id, session);
+
+        room.broadcast_lobby();
+        let Ok(ServerMessage::LobbyUpdate { players }) = receiver.try_recv() else {
+            panic!("no roster sent");
+        };
+        assert_eq!(players, ["alice"]);
+
+        room.post_chat(id, "  hi\nall  ").unwrap();
+        room.post_chat(id, "   ").unwrap();
+        assert_eq!(room.chat.len(), 1);
+        assert_eq!(room.chat[0].text, "hiall");
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::Chat { .. })));
+        assert!(receiver.try_recv().is_err());
+
+        room.sessions.get_mut(&id).unwrap().status = UserStatus::Answering(0);
+        assert!(room.post_chat(id, "b is right").is_err());
+        room.status = ServerStatus::InProgress;
+        room.sessions.get_mut(&id).unwrap().status = UserStatus::Finished;
+        assert!(room.post_chat(id, "b is right").is_err());
+        room.status = ServerStatus::Finished;
+        room.post_chat(id, "gg").unwrap();
+        assert!(matches!(receiver.try_recv(), Ok(ServerMessage::Chat { .. })));
+        room.muted.insert("alice".to_string());
+        assert!(room.post_chat(id, "gg").is_err());
+        assert_eq!(room.chat.len(), 2);
+    }
+
+    #[test]
+    fn test_generate_standings() {
+        let question = Question::new("Pick b", ["a", "b", "c", "d"], 1);
+        let questions = vec![question.clone(), question];
+        let mut room = Room::new("ABCD".to_string(), questions, QuizMeta::default());
+        // carol is still in the lobby, so not taking the quiz
+        let players = [("alice", vec![Some(vec![0]), None]), ("bobby", vec![Some(vec![1]), None])];
+        for (name, answers) in players.into_iter().chain([("carol", Vec::new())]) {
+            let (sender, _) = mpsc::unbounded_channel();
+            let mut session = UserSession::new(IpAddr::from([127, 0, 0, 1]), sender);
+            session.username = Some(name.to_string());
+            session.answers = answers;
+            room.sessions.insert(session.id, session);
+        }
+
+        let standings = room.generate_standings("alice");
+        assert_eq!(standings.len(), 2);
+        assert_eq!((standings[0].username.as_str(), standings[0].score), ("bobby", 1));
+        assert_eq!((standings[1].rank, standings[1].answered), (2, 1));
+        assert!(standings[1].is_you && !standings[1].finished);
+    }
+}
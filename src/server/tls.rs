@@ -0,0 +1,60 @@
+//! TLS for serving quizzes over `wss://`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+use crate::QuizError;
+
+/// Certificate and private key for serving over TLS, both PEM files.
+///
+/// The certificate file may hold a whole chain, leaf first, as issued by
+/// e.g. Let's Encrypt (`fullchain.pem` and `privkey.pem`).
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl TlsConfig {
+    /// Read the certificate and key, ready to accept connections.
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor, QuizError> {
+        let cert = self.cert.display();
+        let certs = CertificateDer::pem_file_iter(&self.cert)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| QuizError::Tls(format!("Failed to read certificate {}: {}", cert, e)))?;
+        if certs.is_empty() {
+            return Err(QuizError::Tls(format!("No certificate found in {}", cert)));
+        }
+        let key = PrivateKeyDer::from_pem_file(&self.key).map_err(|e| {
+            QuizError::Tls(format!("Failed to read private key {}: {}", self.key.display(), e))
+        })?;
+
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| QuizError::Tls(format!("Failed to use certificate {}: {}", cert, e)))?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acceptor_names_missing_file() {
+        let dir = std::env::temp_dir();
+        let config = TlsConfig {
+            cert: dir.join("rust-quiz-missing-cert.pem"),
+            key: dir.join("rust-quiz-missing-key.pem"),
+        };
+        let error = config.acceptor().err().unwrap().to_string();
+        assert!(error.starts_with("Failed to read certificate"), "{}", error);
+        assert!(error.contains("rust-quiz-missing-cert.pem"));
+    }
+}
@@ -0,0 +1,162 @@
+//! A record of everything that happened on the server, for settling grading
+//! disputes and analysing a session afterwards.
+//!
+//! Each line of the transcript is one JSON [`Entry`]: when it happened, in
+//! which room, and the [`Event`].
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Confidence, Question};
+
+/// A transcript file being written; clones write to the same file.
+#[derive(Clone)]
+pub struct Transcript {
+    file: Arc<Mutex<BufWriter<File>>>,
+}
+
+/// One line of a transcript.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// When it happened, in RFC 3339 with milliseconds.
+    pub at: String,
+    /// Code of the room it happened in.
+    pub room: String,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Something that happened in a room.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// The quiz started with these questions and players.
+    Started {
+        questions: Vec<Question>,
+        players: Vec<String>,
+        speed_bonus: bool,
+        #[serde(default)]
+        confidence_mode: bool,
+    },
+    /// A player picked a username, in the lobby or once the quiz had
+    /// started.
+    Joined { username: String, late: bool },
+    /// A disconnected player came back.
+    Rejoined { username: String },
+    /// A player's connection closed.
+    Left { username: String },
+    /// A question was sent to a player; `index` counts from 0.
+    Question { username: String, index: usize },
+    /// A player answered a question.
+    Answered {
+        username: String,
+        question: usize,
+        answers: Vec<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        confidence: Option<Confidence>,
+        correct: bool,
+        /// Seconds since the question was sent.
+        seconds: Option<f64>,
+    },
+    /// A player answered the last question.
+    Finished { username: String, score: usize },
+    /// A player's chat message.
+    Chat { username: String, text: String },
+    /// A command the host typed, and its message or error.
+    Command {
+        input: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reply: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// The quiz ended, with the score of each player who finished.
+    Ended { scores: Vec<(String, usize)> },
+}
+
+impl Transcript {
+    /// Open `path` to add to, creating it if need be, so a resumed server
+    /// carries on the same transcript.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Add `event` in `room`, stamped with the time now.
+    ///
+    /// Each line is written out at once, so a crash loses nothing. Failing
+    /// to write doesn't stop the quiz.
+    pub fn record(&self, room: &str, event: Event) {
+        let entry = Entry {
+            at: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            room: room.to_string(),
+            event,
+        };
+        let Ok(line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", line).and_then(|()| file.flush());
+        }
+    }
+}
+
+/// Read every entry of a transcript, skipping lines that don't parse, such
+/// as one cut short by a crash.
+pub fn read_transcript(path: &Path) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        if let Ok(entry) = serde_json::from_str(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_read() {
+        let name = format!("rust-quiz-transcript-{}", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let transcript = Transcript::open(&path).unwrap();
+        transcript.record(
+            "ABCD",
+            Event::Joined {
+                username: "alice".to_string(),
+                late: false,
+            },
+        );
+        transcript.clone().record(
+            "ABCD",
+            Event::Answered {
+                username: "alice".to_string(),
+                question: 0,
+                answers: vec![1],
+                confidence: None,
+                correct: true,
+                seconds: Some(2.5),
+            },
+        );
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, format!("{}{{\"at\": \"cut short", text)).unwrap();
+        let entries = read_transcript(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let first = text.lines().next().unwrap();
+        assert!(first.ends_with(
+            r#""room":"ABCD","event":"joined","username":"alice","late":false}"#
+        ));
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[1].event, Event::Answered { seconds: Some(2.5), .. }));
+    }
+}
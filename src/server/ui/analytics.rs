@@ -3,10 +3,15 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 
-use crate::server::state::{ServerState, UserStatus};
+use crate::models::format_answer;
+use crate::server::state::{self, Room, UserStatus};
+use crate::text;
+use crate::theme::Theme;
+
+use super::chat;
 
 /// Render the analytics view.
-pub fn render(frame: &mut Frame, area: Rect, state: &ServerState) {
+pub fn render(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
     let chunks = Layout::vertical([
         Constraint::Percentage(50), // User progress
         Constraint::Percentage(50), // Live answers
@@ -14,14 +19,21 @@ pub fn render(frame: &mut Frame, area: Rect, state: &ServerState) {
     .margin(1)
     .split(area);
 
-    render_user_progress(frame, chunks[0], state);
-    render_live_answers(frame, chunks[1], state);
+    let columns = Layout::horizontal([
+        Constraint::Percentage(60), // Live answers
+        Constraint::Percentage(40), // Chat
+    ])
+    .split(chunks[1]);
+
+    render_user_progress(frame, chunks[0], room, theme);
+    render_live_answers(frame, columns[0], room, theme);
+    chat::render(frame, columns[1], room, theme);
 }
 
-fn render_user_progress(frame: &mut Frame, area: Rect, state: &ServerState) {
+fn render_user_progress(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
     let mut lines: Vec<Line> = Vec::new();
 
-    let mut users: Vec<_> = state
+    let mut users: Vec<_> = room
         .sessions
         .values()
         .filter(|s| s.username.is_some())
@@ -40,27 +52,35 @@ fn render_user_progress(frame: &mut Frame, area: Rect, state: &ServerState) {
 
     for user in users {
         let username = user.username.as_deref().unwrap_or("???");
-        let total = state.questions.len();
+        let total = room.questions.len();
 
         match user.status {
             UserStatus::Finished => {
                 let score = user.score.unwrap_or(0);
-                let pct = if total > 0 {
-                    (score as f64 / total as f64) * 100.0
+                let max_score = room.max_score();
+                let pct = if max_score > 0 {
+                    (score as f64 / max_score as f64) * 100.0
                 } else {
                     0.0
                 };
 
                 lines.push(Line::from(vec![
-                    Span::styled("  + ", Style::default().fg(Color::Green)),
+                    Span::styled("  + ", Style::default().fg(theme.correct)),
                     Span::styled(
-                        format!("{:<14}", username),
-                        Style::default().fg(Color::White),
+                        text::pad(username, 14),
+                        Style::default().fg(theme.text),
                     ),
-                    Span::styled("[DONE]   ", Style::default().fg(Color::Cyan)),
+                    Span::styled("[DONE]   ", Style::default().fg(theme.accent)),
                     Span::styled(
-                        format!("Score: {}/{} ({:.0}%)", score, total, pct),
-                        Style::default().fg(Color::Green),
+                        format!("Score: {}/{} ({:.0}%)", score, max_score, pct),
+                        Style::default().fg(theme.correct),
+                    ),
+                    Span::styled(
+                        user
+                            .total_time()
+                            .map(|time| format!("  in {}", text::seconds(state::seconds(time))))
+                            .unwrap_or_default(),
+                        Style::default().fg(theme.dimmed),
                     ),
                 ]));
             }
@@ -79,30 +99,40 @@ fn render_user_progress(frame: &mut Frame, area: Rect, state: &ServerState) {
                 let bar = format!("{}{}", "█".repeat(filled), "░".repeat(empty));
 
                 lines.push(Line::from(vec![
-                    Span::styled("  * ", Style::default().fg(Color::Yellow)),
+                    Span::styled("  * ", Style::default().fg(theme.highlight)),
                     Span::styled(
-                        format!("{:<14}", username),
-                        Style::default().fg(Color::White),
+                        text::pad(username, 14),
+                        Style::default().fg(theme.text),
                     ),
                     Span::styled(
                         format!("[Q {:>2}/{}] ", progress + 1, total),
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(theme.highlight),
                     ),
-                    Span::styled(bar, Style::default().fg(Color::Yellow)),
+                    Span::styled(bar, Style::default().fg(theme.highlight)),
                     Span::styled(
                         format!(" {:>3.0}%", pct),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme.dimmed),
                     ),
                 ]));
             }
             UserStatus::Disconnected => {
                 lines.push(Line::from(vec![
-                    Span::styled("  x ", Style::default().fg(Color::Red)),
+                    Span::styled("  x ", Style::default().fg(theme.incorrect)),
                     Span::styled(
-                        format!("{:<14}", username),
-                        Style::default().fg(Color::DarkGray),
+                        text::pad(username, 14),
+                        Style::default().fg(theme.dimmed),
                     ),
-                    Span::styled("[DISCONNECTED]", Style::default().fg(Color::Red)),
+                    Span::styled("[DISCONNECTED]", Style::default().fg(theme.incorrect)),
+                ]));
+            }
+            UserStatus::Abandoned => {
+                lines.push(Line::from(vec![
+                    Span::styled("  x ", Style::default().fg(theme.incorrect)),
+                    Span::styled(
+                        text::pad(username, 14),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                    Span::styled("[ABANDONED]", Style::default().fg(theme.dimmed)),
                 ]));
             }
             _ => {}
@@ -112,75 +142,75 @@ fn render_user_progress(frame: &mut Frame, area: Rect, state: &ServerState) {
     if lines.is_empty() {
         lines.push(Line::from(Span::styled(
             "  No users in quiz yet...",
-            Style::default().fg(Color::DarkGray).italic(),
+            Style::default().fg(theme.dimmed).italic(),
         )));
     }
 
     let widget = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(" User Progress ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(1)),
     );
 
     frame.render_widget(widget, area);
 }
 
-fn render_live_answers(frame: &mut Frame, area: Rect, state: &ServerState) {
+fn render_live_answers(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
     let mut lines: Vec<Line> = Vec::new();
 
     // Show last N answers (most recent first)
     let max_display = (area.height as usize).saturating_sub(4);
-    let answers: Vec<_> = state.live_answers.iter().rev().take(max_display).collect();
+    let answers: Vec<_> = room.live_answers.iter().rev().take(max_display).collect();
 
     for answer in answers {
-        let question = state.questions.get(answer.question_index);
-        let is_correct = question.is_some_and(|q| q.correct_answer == answer.answer);
-
-        let (symbol, color) = if is_correct {
-            ("+", Color::Green)
-        } else {
-            ("-", Color::Red)
-        };
-
-        let option_letter = match answer.answer {
-            0 => "A",
-            1 => "B",
-            2 => "C",
-            3 => "D",
-            _ => "?",
-        };
+        let question = room.questions.get(answer.question_index);
+        let is_correct = question.is_some_and(|q| q.is_correct(&answer.answer));
+
+        let color = if is_correct { theme.correct } else { theme.incorrect };
+
+        let option_letter = format_answer(&answer.answer);
 
         lines.push(Line::from(vec![
-            Span::styled(format!("  {} ", symbol), Style::default().fg(color)),
+            Span::styled(
+                format!("  {} ", theme.verdict(is_correct)),
+                Style::default().fg(color),
+            ),
             Span::styled(
                 format!("Q{:<3}", answer.question_index + 1),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dimmed),
             ),
             Span::styled(
-                format!("{:<14}", answer.username),
-                Style::default().fg(Color::White),
+                text::pad(&answer.username, 14),
+                Style::default().fg(theme.text),
             ),
-            Span::styled(" -> ", Style::default().fg(Color::DarkGray)),
+            Span::styled(" -> ", Style::default().fg(theme.dimmed)),
             Span::styled(option_letter, Style::default().fg(color)),
+            Span::styled(
+                answer
+                    .time
+                    .map(|time| format!("  {}", text::seconds(state::seconds(time))))
+                    .unwrap_or_default(),
+                Style::default().fg(theme.dimmed),
+            ),
         ]));
     }
 
     if lines.is_empty() {
         lines.push(Line::from(Span::styled(
             "  Waiting for answers...",
-            Style::default().fg(Color::DarkGray).italic(),
+            Style::default().fg(theme.dimmed).italic(),
         )));
     }
 
     let widget = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(" Live Answers ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(1)),
     );
 
@@ -0,0 +1,48 @@
+//! Chat feed panel for the server.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+
+use crate::server::state::Room;
+use crate::theme::Theme;
+
+/// Render the room's recent chat messages, newest at the bottom.
+pub fn render(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
+    let max_display = (area.height as usize).saturating_sub(2);
+    let skip = room.chat.len().saturating_sub(max_display);
+
+    let mut lines: Vec<Line> = room
+        .chat
+        .iter()
+        .skip(skip)
+        .map(|line| {
+            let color = if room.muted.contains(&line.username) {
+                theme.dimmed
+            } else {
+                theme.accent
+            };
+            Line::from(vec![
+                Span::styled(format!("{}: ", line.username), Style::default().fg(color)),
+                Span::styled(line.text.as_str(), Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No messages yet...",
+            Style::default().fg(theme.dimmed).italic(),
+        )));
+    }
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(" Chat ")
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
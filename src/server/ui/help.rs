@@ -3,72 +3,158 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 
+use crate::theme::Theme;
+
 /// Render the help view.
-pub fn render(frame: &mut Frame, area: Rect) {
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme) {
     let help_text = vec![
         Line::from(""),
         Line::from(Span::styled(
             "AVAILABLE COMMANDS",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  start          ", Style::default().fg(Color::Yellow)),
+            Span::styled("  start          ", Style::default().fg(theme.highlight)),
             Span::raw("Start the quiz (lobby only)"),
         ]),
         Line::from(vec![
-            Span::styled("  stop           ", Style::default().fg(Color::Yellow)),
+            Span::styled("  start in <s>   ", Style::default().fg(theme.highlight)),
+            Span::raw("Start by itself after s seconds; 'start cancel' calls it off"),
+        ]),
+        Line::from(vec![
+            Span::styled("  stop           ", Style::default().fg(theme.highlight)),
             Span::raw("End quiz, send results to finished users"),
         ]),
         Line::from(vec![
-            Span::styled("  quit / exit    ", Style::default().fg(Color::Yellow)),
+            Span::styled("  restart [n]    ", Style::default().fg(theme.highlight)),
+            Span::raw("Back to the lobby for another round (optionally 'limit n')"),
+        ]),
+        Line::from(vec![
+            Span::styled("  pause          ", Style::default().fg(theme.highlight)),
+            Span::raw("Freeze the quiz; answers are refused until resume"),
+        ]),
+        Line::from(vec![
+            Span::styled("  resume         ", Style::default().fg(theme.highlight)),
+            Span::raw("Continue a paused quiz"),
+        ]),
+        Line::from(vec![
+            Span::styled("  announce <msg> ", Style::default().fg(theme.highlight)),
+            Span::raw("Show a message on every player's screen"),
+        ]),
+        Line::from(vec![
+            Span::styled("  feedback on/off", Style::default().fg(theme.highlight)),
+            Span::raw("Tell players if each answer was right as they send it"),
+        ]),
+        Line::from(vec![
+            Span::styled("  limit <n>      ", Style::default().fg(theme.highlight)),
+            Span::raw("Use n random questions (add 'by-tag' to keep tag shares)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  limit off      ", Style::default().fg(theme.highlight)),
+            Span::raw("Use the full question bank"),
+        ]),
+        Line::from(vec![
+            Span::styled("  load <path>    ", Style::default().fg(theme.highlight)),
+            Span::raw("Load another question file (lobby only)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  preview [n]    ", Style::default().fg(theme.highlight)),
+            Span::raw("Page through the questions and answers (arrows, PgUp/PgDn, Esc)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  drop <n>       ", Style::default().fg(theme.highlight)),
+            Span::raw("Leave question n out of the quiz (lobby only)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  bots <n> [acc] ", Style::default().fg(theme.highlight)),
+            Span::raw("Add n simulated players, right acc% of the time (e.g. 40-90)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  results export <path>", Style::default().fg(theme.highlight)),
+            Span::raw("  Save answers, scores and timings (.csv or JSON)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  export analytics <path>", Style::default().fg(theme.highlight)),
+            Span::raw(" Save every answer with its time, and a summary (.csv)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  quit / exit    ", Style::default().fg(theme.highlight)),
             Span::raw("Shutdown server"),
         ]),
         Line::from(vec![
-            Span::styled("  kick <user>    ", Style::default().fg(Color::Yellow)),
+            Span::styled("  kick <user>    ", Style::default().fg(theme.highlight)),
             Span::raw("Disconnect a user"),
         ]),
         Line::from(vec![
-            Span::styled("  ban <user>     ", Style::default().fg(Color::Yellow)),
+            Span::styled("  mute <user>    ", Style::default().fg(theme.highlight)),
+            Span::raw("Stop a user's chat messages"),
+        ]),
+        Line::from(vec![
+            Span::styled("  unmute <user>  ", Style::default().fg(theme.highlight)),
+            Span::raw("Let a muted user chat again"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ban <user>     ", Style::default().fg(theme.highlight)),
             Span::raw("Kick and ban user's IP"),
         ]),
         Line::from(vec![
-            Span::styled("  unban <ip>     ", Style::default().fg(Color::Yellow)),
+            Span::styled("  unban <ip>     ", Style::default().fg(theme.highlight)),
             Span::raw("Remove IP from ban list"),
         ]),
         Line::from(vec![
-            Span::styled("  view <user>    ", Style::default().fg(Color::Yellow)),
+            Span::styled("  view <user>    ", Style::default().fg(theme.highlight)),
             Span::raw("Show detailed view of user"),
         ]),
         Line::from(vec![
-            Span::styled("  view all       ", Style::default().fg(Color::Yellow)),
+            Span::styled("  view all       ", Style::default().fg(theme.highlight)),
             Span::raw("Show all users analytics"),
         ]),
         Line::from(vec![
-            Span::styled("  list           ", Style::default().fg(Color::Yellow)),
+            Span::styled("  leaderboard    ", Style::default().fg(theme.highlight)),
+            Span::raw("Rank everyone by points so far (also Tab)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  list           ", Style::default().fg(theme.highlight)),
             Span::raw("List connected users"),
         ]),
         Line::from(vec![
-            Span::styled("  list bans      ", Style::default().fg(Color::Yellow)),
+            Span::styled("  list bans      ", Style::default().fg(theme.highlight)),
             Span::raw("List banned IPs"),
         ]),
         Line::from(vec![
-            Span::styled("  help / ?       ", Style::default().fg(Color::Yellow)),
+            Span::styled("  log            ", Style::default().fg(theme.highlight)),
+            Span::raw("Scroll through the room's events (arrows, PgUp/PgDn, Esc)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  room new       ", Style::default().fg(theme.highlight)),
+            Span::raw("Open another room, with its own players and quiz"),
+        ]),
+        Line::from(vec![
+            Span::styled("  room <code>    ", Style::default().fg(theme.highlight)),
+            Span::raw("Switch to a room; commands apply to the current room"),
+        ]),
+        Line::from(vec![
+            Span::styled("  rooms          ", Style::default().fg(theme.highlight)),
+            Span::raw("List open rooms"),
+        ]),
+        Line::from(vec![
+            Span::styled("  help / ?       ", Style::default().fg(theme.highlight)),
             Span::raw("Show this help"),
         ]),
         Line::from(""),
         Line::from(Span::styled(
             "Press Esc or Enter to close",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dimmed),
         )),
     ];
 
     let widget = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(" Help ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(2)),
     );
 
@@ -0,0 +1,54 @@
+//! Leaderboard view for the server: everyone taking the quiz, by points.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+
+use crate::server::state::Room;
+use crate::text;
+use crate::theme::Theme;
+
+/// Render the leaderboard view, with the standings players see.
+pub fn render(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
+    let total = room.questions.len();
+    let max_score = room.max_score();
+
+    let mut lines: Vec<Line> = Vec::new();
+    for entry in room.generate_standings("") {
+        let (mark, color) = if entry.finished {
+            ("[DONE]", theme.accent)
+        } else {
+            ("", theme.highlight)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>3}. ", entry.rank), Style::default().fg(theme.dimmed)),
+            Span::styled(text::pad(&entry.username, 16), Style::default().fg(theme.text)),
+            Span::styled(
+                format!("{:>4}/{:<4} pts  ", entry.score, max_score),
+                Style::default().fg(theme.correct).bold(),
+            ),
+            Span::styled(
+                format!("{:>3}/{} answered  ", entry.answered, total),
+                Style::default().fg(theme.dimmed),
+            ),
+            Span::styled(mark, Style::default().fg(color)),
+        ]));
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No answers yet...",
+            Style::default().fg(theme.dimmed).italic(),
+        )));
+    }
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(" Leaderboard ")
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area.inner(Margin::new(1, 1)));
+}
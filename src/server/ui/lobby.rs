@@ -3,10 +3,14 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 
-use crate::server::state::{ServerState, UserStatus};
+use crate::server::state::{Room, UserStatus};
+use crate::text;
+use crate::theme::Theme;
+
+use super::chat;
 
 /// Render the lobby view.
-pub fn render(frame: &mut Frame, area: Rect, state: &ServerState) {
+pub fn render(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
     let chunks = Layout::vertical([
         Constraint::Length(3), // Title
         Constraint::Min(5),    // User list
@@ -15,23 +19,30 @@ pub fn render(frame: &mut Frame, area: Rect, state: &ServerState) {
     .margin(1)
     .split(area);
 
-    render_title(frame, chunks[0]);
-    render_user_list(frame, chunks[1], state);
-    render_instructions(frame, chunks[2], state);
+    let columns = Layout::horizontal([
+        Constraint::Percentage(60), // User list
+        Constraint::Percentage(40), // Chat
+    ])
+    .split(chunks[1]);
+
+    render_title(frame, chunks[0], theme);
+    render_user_list(frame, columns[0], room, theme);
+    chat::render(frame, columns[1], room, theme);
+    render_instructions(frame, chunks[2], room, theme);
 }
 
-fn render_title(frame: &mut Frame, area: Rect) {
+fn render_title(frame: &mut Frame, area: Rect, theme: &Theme) {
     let title = Paragraph::new("CONNECTED USERS")
-        .style(Style::default().fg(Color::Cyan).bold())
+        .style(Style::default().fg(theme.accent).bold())
         .alignment(Alignment::Center);
     frame.render_widget(title, area);
 }
 
-fn render_user_list(frame: &mut Frame, area: Rect, state: &ServerState) {
+fn render_user_list(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
     let mut lines: Vec<Line> = Vec::new();
 
     // First show users with usernames
-    let mut named_users: Vec<_> = state
+    let mut named_users: Vec<_> = room
         .sessions
         .values()
         .filter(|s| s.username.is_some() && s.is_connected())
@@ -41,45 +52,47 @@ fn render_user_list(frame: &mut Frame, area: Rect, state: &ServerState) {
     for user in named_users {
         let username = user.username.as_deref().unwrap_or("???");
         let status = match user.status {
-            UserStatus::InLobby => ("Ready", Color::Green),
+            UserStatus::InLobby => ("Ready", theme.correct),
             UserStatus::Answering(i) => {
-                let s = format!("Q{}/{}", i + 1, state.questions.len());
+                let s = format!("Q{}/{}", i + 1, room.questions.len());
                 // We need to handle this differently since we can't return a String
                 lines.push(Line::from(vec![
-                    Span::styled("  * ", Style::default().fg(Color::Green)),
+                    Span::styled("  * ", Style::default().fg(theme.correct)),
                     Span::styled(
-                        format!("{:<16}", username),
-                        Style::default().fg(Color::White),
+                        text::pad(username, 16),
+                        Style::default().fg(theme.text),
                     ),
                     Span::styled(
                         format!("{:<16}", user.ip_addr),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme.dimmed),
                     ),
-                    Span::styled(s, Style::default().fg(Color::Yellow)),
+                    Span::styled(s, Style::default().fg(theme.highlight)),
                 ]));
                 continue;
             }
-            UserStatus::Finished => ("Done", Color::Cyan),
-            UserStatus::Disconnected => ("Disconnected", Color::Red),
-            UserStatus::Connected => ("Connecting...", Color::Yellow),
+            UserStatus::Finished => ("Done", theme.accent),
+            UserStatus::Disconnected => ("Disconnected", theme.incorrect),
+            UserStatus::Connected => ("Connecting...", theme.highlight),
+            // Gone for good, so not worth listing
+            UserStatus::Abandoned => continue,
         };
 
         lines.push(Line::from(vec![
-            Span::styled("  * ", Style::default().fg(Color::Green)),
+            Span::styled("  * ", Style::default().fg(theme.correct)),
             Span::styled(
-                format!("{:<16}", username),
-                Style::default().fg(Color::White),
+                text::pad(username, 16),
+                Style::default().fg(theme.text),
             ),
             Span::styled(
                 format!("{:<16}", user.ip_addr),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dimmed),
             ),
             Span::styled(status.0, Style::default().fg(status.1)),
         ]));
     }
 
     // Then show users without usernames (connecting)
-    let unnamed_users: Vec<_> = state
+    let unnamed_users: Vec<_> = room
         .sessions
         .values()
         .filter(|s| s.username.is_none() && s.is_connected())
@@ -87,45 +100,47 @@ fn render_user_list(frame: &mut Frame, area: Rect, state: &ServerState) {
 
     for user in unnamed_users {
         lines.push(Line::from(vec![
-            Span::styled("  o ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  o ", Style::default().fg(theme.dimmed)),
             Span::styled(
                 format!("{:<16}", "(unnamed)"),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dimmed),
             ),
             Span::styled(
                 format!("{:<16}", user.ip_addr),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dimmed),
             ),
-            Span::styled("Connecting...", Style::default().fg(Color::Yellow)),
+            Span::styled("Connecting...", Style::default().fg(theme.highlight)),
         ]));
     }
 
     if lines.is_empty() {
         lines.push(Line::from(Span::styled(
             "  No users connected yet...",
-            Style::default().fg(Color::DarkGray).italic(),
+            Style::default().fg(theme.dimmed).italic(),
         )));
     }
 
     let list = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .padding(Padding::horizontal(1)),
     );
 
     frame.render_widget(list, area);
 }
 
-fn render_instructions(frame: &mut Frame, area: Rect, state: &ServerState) {
-    let text = if state.named_user_count() > 0 {
-        "Type 'start' to begin the quiz  |  'help' for commands"
+fn render_instructions(frame: &mut Frame, area: Rect, room: &Room, theme: &Theme) {
+    let text = if let Some(seconds) = room.starts_in() {
+        format!("Starting in {}s  |  'start cancel' to call it off", seconds)
+    } else if room.named_user_count() > 0 {
+        "Type 'start' to begin the quiz  |  'help' for commands".to_string()
     } else {
-        "Waiting for users to connect...  |  'help' for commands"
+        "Waiting for users to connect...  |  'help' for commands".to_string()
     };
 
     let instructions = Paragraph::new(text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.dimmed))
         .alignment(Alignment::Center);
 
     frame.render_widget(instructions, area);
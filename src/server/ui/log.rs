@@ -0,0 +1,47 @@
+//! Log view for the server: every event and command result in the room.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+
+use crate::server::state::ServerState;
+use crate::theme::Theme;
+
+/// Render the log view, scrolled back `state.log_scroll` lines.
+pub fn render(frame: &mut Frame, area: Rect, state: &ServerState, theme: &Theme) {
+    let history = &state.room().command_history;
+
+    // Leave room for the borders
+    let height = area.height.saturating_sub(2) as usize;
+    let end = history.len().saturating_sub(state.log_scroll);
+    let start = end.saturating_sub(height);
+
+    let lines: Vec<Line> = history[start..end]
+        .iter()
+        .map(|line| {
+            let color = if line.starts_with("Error:") {
+                theme.incorrect
+            } else {
+                theme.text
+            };
+            Line::from(Span::styled(line.as_str(), Style::default().fg(color)))
+        })
+        .collect();
+
+    let position = if state.log_scroll == 0 {
+        "newest".to_string()
+    } else {
+        format!("{} back", state.log_scroll)
+    };
+    let keys = Line::from(" ↑/↓ PgUp/PgDn scroll · End newest · Esc back ").right_aligned();
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(format!(" Log · {} lines · {} ", history.len(), position))
+            .title_style(Style::default().fg(theme.accent))
+            .title_bottom(keys)
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
@@ -1,9 +1,79 @@
 //! Server UI components.
 
 mod analytics;
+mod chat;
 mod help;
+mod leaderboard;
 mod lobby;
+mod log;
+mod preview;
 mod render;
 mod user_view;
 
-pub use render::render;
+pub use render::{render, render_replay};
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::models::{Question, QuizMeta};
+    use crate::server::state::{ServerState, ServerView, UserSession, UserStatus};
+    use crate::terminal::{buffer_lines, render_to_buffer};
+    use crate::theme::Theme;
+
+    fn screen(state: &ServerState) -> Vec<String> {
+        buffer_lines(&render_to_buffer(100, 30, |frame| render(frame, state, &Theme::default())))
+    }
+
+    #[test]
+    fn test_render_views() {
+        let questions = vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)];
+        let mut state = ServerState::new(questions, QuizMeta::default(), 8712);
+        let mut session =
+            UserSession::new(IpAddr::from([127, 0, 0, 1]), mpsc::unbounded_channel().0);
+        session.username = Some("alice".to_string());
+        session.status = UserStatus::InLobby;
+        state.room_mut().username_to_id.insert("alice".to_string(), session.id);
+        state.room_mut().sessions.insert(session.id, session);
+
+        let lobby = screen(&state);
+        let code = state.current_room.clone();
+        assert!(lobby[0].starts_with(&format!("┌ Quiz Server · Room {} ─", code)));
+        assert_eq!(
+            lobby[1],
+            "│ Status: Lobby  |  Port: 8712  |  Questions: 1  |  Connected: 1 (1 named)  \
+             |  Finished: 0         │"
+        );
+        assert!(lobby.iter().any(|line| line.contains("* alice           127.0.0.1       Ready")));
+        assert!(lobby.iter().any(|line| line.contains("Type 'start' to begin the quiz")));
+        assert_eq!(lobby[28], format!("│>{}│", " ".repeat(97)));
+
+        state.current_view = ServerView::Analytics;
+        let analytics = screen(&state);
+        assert!(analytics.iter().any(|line| line.contains("┌ User Progress ─")));
+        assert!(analytics.iter().any(|line| line.contains("No users in quiz yet...")));
+        assert!(analytics.iter().any(|line| line.contains("Waiting for answers...")));
+
+        state.current_view = ServerView::Preview;
+        let preview = screen(&state);
+        assert!(preview.iter().any(|line| line.contains("┌ Preview · questions 1–1 of 1 ─")));
+        assert!(preview.iter().any(|line| line.contains("1. Pick b  (1 pt)")));
+        assert!(preview.iter().any(|line| line.contains("   B) b ✓")));
+        assert!(preview.iter().any(|line| line.contains("   C) c ") && !line.contains('✓')));
+
+        state.current_view = ServerView::Leaderboard;
+        assert!(screen(&state).iter().any(|line| line.contains("No answers yet...")));
+        let alice = state.room_mut().get_user_by_name_mut("alice").unwrap();
+        alice.init_answers(1);
+        alice.answers[0] = Some(vec![1]);
+        alice.status = UserStatus::Finished;
+        let leaderboard = screen(&state);
+        assert!(leaderboard.iter().any(|line| line.contains("┌ Leaderboard ─")));
+        assert!(leaderboard
+            .iter()
+            .any(|line| line.contains("1. alice              1/1    pts    1/1 answered  [DONE]")));
+    }
+}
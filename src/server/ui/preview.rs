@@ -0,0 +1,82 @@
+//! Preview view for the server: the room's questions with their answers.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+
+use crate::models::OPTION_LABELS;
+use crate::server::state::ServerState;
+use crate::theme::Theme;
+
+/// Render the preview view, from question `state.preview_scroll`.
+pub fn render(frame: &mut Frame, area: Rect, state: &ServerState, theme: &Theme) {
+    let questions = &state.room().questions;
+
+    // Leave room for the borders
+    let height = area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut shown = 0;
+    for (index, question) in questions.iter().enumerate().skip(state.preview_scroll) {
+        if lines.len() >= height {
+            break;
+        }
+        shown = index + 1;
+
+        let mut details = vec![format!("{} pt", question.points)];
+        if question.code.is_some() {
+            details.push("code".to_string());
+        }
+        if !question.tags.is_empty() {
+            details.push(question.tags.join(", "));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}. {}", index + 1, question.text),
+                Style::default().fg(theme.accent).bold(),
+            ),
+            Span::styled(
+                format!("  ({})", details.join(" · ")),
+                Style::default().fg(theme.dimmed),
+            ),
+        ]));
+
+        let correct = question.correct_answer.indices();
+        for (option_index, (option, label)) in
+            question.options.iter().zip(OPTION_LABELS).enumerate()
+        {
+            let line = if correct.contains(&option_index) {
+                Span::styled(
+                    format!("   {}) {} ✓", label, option),
+                    Style::default().fg(theme.correct),
+                )
+            } else {
+                Span::styled(format!("   {}) {}", label, option), Style::default().fg(theme.text))
+            };
+            lines.push(Line::from(line));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let title = if questions.is_empty() {
+        " Preview · no questions ".to_string()
+    } else {
+        format!(
+            " Preview · questions {}–{} of {} ",
+            state.preview_scroll + 1,
+            shown,
+            questions.len()
+        )
+    };
+    let keys = Line::from(" ↑/↓ PgUp/PgDn scroll · drop <n> leaves one out · Esc back ")
+        .right_aligned();
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dimmed))
+            .title(title)
+            .title_style(Style::default().fg(theme.accent))
+            .title_bottom(keys)
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(widget, area);
+}
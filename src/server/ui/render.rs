@@ -4,11 +4,31 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use crate::server::state::{ServerState, ServerStatus, ServerView};
+use crate::terminal;
+use crate::text;
+use crate::theme::Theme;
 
-use super::{analytics, help, lobby, user_view};
+use super::{analytics, help, leaderboard, lobby, log, preview, user_view};
+
+/// Smallest terminal the host screens fit in.
+const MIN_SIZE: Size = Size::new(80, 20);
 
 /// Render the server UI based on current state.
-pub fn render(frame: &mut Frame, state: &ServerState) {
+pub fn render(frame: &mut Frame, state: &ServerState, theme: &Theme) {
+    render_screen(frame, state, None, theme);
+}
+
+/// Render a recorded session being played back, with `status` in place of
+/// the command line.
+pub fn render_replay(frame: &mut Frame, state: &ServerState, status: &str, theme: &Theme) {
+    render_screen(frame, state, Some(status), theme);
+}
+
+fn render_screen(frame: &mut Frame, state: &ServerState, replay: Option<&str>, theme: &Theme) {
+    if terminal::render_too_small(frame, MIN_SIZE, theme) {
+        return;
+    }
+
     let area = frame.area();
 
     let chunks = Layout::vertical([
@@ -19,93 +39,140 @@ pub fn render(frame: &mut Frame, state: &ServerState) {
     ])
     .split(area);
 
-    render_header(frame, chunks[0], state);
-    render_main_content(frame, chunks[1], state);
-    render_command_history(frame, chunks[2], state);
-    render_command_input(frame, chunks[3], state);
+    render_header(frame, chunks[0], state, replay.is_some(), theme);
+    render_main_content(frame, chunks[1], state, theme);
+    render_command_history(frame, chunks[2], state, theme);
+    match replay {
+        Some(status) => render_replay_status(frame, chunks[3], status, theme),
+        None => render_command_input(frame, chunks[3], state, theme),
+    }
 }
 
 /// Render the header with status info.
-fn render_header(frame: &mut Frame, area: Rect, state: &ServerState) {
-    let status_str = match state.status {
+fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    state: &ServerState,
+    replay: bool,
+    theme: &Theme,
+) {
+    let room = state.room();
+    let status_str = match room.status {
+        ServerStatus::InProgress if room.paused => "Paused",
         ServerStatus::Lobby => "Lobby",
         ServerStatus::InProgress => "In Progress",
         ServerStatus::Finished => "Finished",
     };
 
-    let status_color = match state.status {
-        ServerStatus::Lobby => Color::Yellow,
-        ServerStatus::InProgress => Color::Green,
-        ServerStatus::Finished => Color::Cyan,
+    let status_color = match room.status {
+        ServerStatus::InProgress if room.paused => theme.incorrect,
+        ServerStatus::Lobby => theme.highlight,
+        ServerStatus::InProgress => theme.correct,
+        ServerStatus::Finished => theme.accent,
     };
 
-    let connected = state.connected_users().len();
-    let named = state.named_user_count();
-    let finished = state.finished_count();
+    let connected = room.connected_users().len();
+    let named = match room.max_players {
+        Some(max) => format!("{}/{} players", room.player_count(), max),
+        None => format!("{} named", room.named_user_count()),
+    };
+    let finished = room.finished_count();
 
+    // A replay has no port of its own to show
+    let port = if replay { String::new() } else { format!("  |  Port: {}", state.port) };
     let header_text = format!(
-        " Status: {}  |  Port: {}  |  Questions: {}  |  Connected: {} ({} named)  |  Finished: {}",
+        " Status: {}{}  |  Questions: {}  |  Connected: {} ({})  |  Finished: {}",
         status_str,
-        state.port,
-        state.questions.len(),
+        port,
+        room.questions.len(),
         connected,
         named,
         finished
     );
 
+    let mut title = if replay { " Replay" } else { " Quiz Server" }.to_string();
+    if let Some(quiz_title) = &room.meta.title {
+        title.push_str(&format!(" · {}", quiz_title));
+    }
+    title.push_str(&format!(" · Room {} ", room.code));
+    if state.rooms.len() > 1 {
+        title.push_str(&format!("({} rooms) ", state.rooms.len()));
+    }
+
     let header = Paragraph::new(header_text)
         .style(Style::default().fg(status_color).bold())
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Quiz Server ")
-                .title_style(Style::default().fg(Color::Cyan).bold()),
+                .title(title)
+                .title_style(Style::default().fg(theme.accent).bold()),
         );
 
     frame.render_widget(header, area);
 }
 
 /// Render the main content based on current view.
-fn render_main_content(frame: &mut Frame, area: Rect, state: &ServerState) {
+fn render_main_content(frame: &mut Frame, area: Rect, state: &ServerState, theme: &Theme) {
+    let room = state.room();
     match &state.current_view {
-        ServerView::Lobby => lobby::render(frame, area, state),
-        ServerView::Analytics => analytics::render(frame, area, state),
-        ServerView::UserDetail(username) => user_view::render(frame, area, state, username),
-        ServerView::Help => help::render(frame, area),
+        ServerView::Lobby => lobby::render(frame, area, room, theme),
+        ServerView::Analytics => analytics::render(frame, area, room, theme),
+        ServerView::Leaderboard => leaderboard::render(frame, area, room, theme),
+        ServerView::UserDetail(username) => user_view::render(frame, area, room, username, theme),
+        ServerView::Help => help::render(frame, area, theme),
+        ServerView::Log => log::render(frame, area, state, theme),
+        ServerView::Preview => preview::render(frame, area, state, theme),
     }
 }
 
 /// Render the last command history message.
-fn render_command_history(frame: &mut Frame, area: Rect, state: &ServerState) {
+fn render_command_history(frame: &mut Frame, area: Rect, state: &ServerState, theme: &Theme) {
     let last_msg = state
+        .room()
         .command_history
         .last()
         .map(|s| s.as_str())
         .unwrap_or("");
 
     let history = Paragraph::new(last_msg)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.dimmed))
         .block(Block::default().borders(Borders::TOP));
 
     frame.render_widget(history, area);
 }
 
 /// Render the command input bar.
-fn render_command_input(frame: &mut Frame, area: Rect, state: &ServerState) {
-    let input_text = format!("> {}", state.command_input);
+fn render_command_input(frame: &mut Frame, area: Rect, state: &ServerState, theme: &Theme) {
+    let input_text = format!("> {}", state.command_input.as_str());
 
     let input = Paragraph::new(input_text)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.dimmed)),
         );
 
     frame.render_widget(input, area);
 
     // Show cursor position
-    let cursor_x = area.x + 3 + state.command_input.len() as u16;
+    let cursor_x = area.x + 3 + text::width(state.command_input.before_cursor()) as u16;
     let cursor_y = area.y + 1;
     frame.set_cursor_position(Position::new(cursor_x, cursor_y));
 }
+
+/// Render where a replay has got to, and its keys.
+fn render_replay_status(frame: &mut Frame, area: Rect, status: &str, theme: &Theme) {
+    let keys = Line::from(" Space pause · ←/→ step · +/- speed · Tab view · l log · q quit ")
+        .right_aligned();
+    let bar = Paragraph::new(format!(" {}", status))
+        .style(Style::default().fg(theme.highlight))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.dimmed))
+                .title_bottom(keys),
+        );
+
+    frame.render_widget(bar, area);
+}
@@ -3,15 +3,17 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 
-use crate::server::state::{ServerState, UserStatus};
+use crate::models::OPTION_LABELS;
+use crate::server::state::{Room, UserStatus};
+use crate::theme::Theme;
 
 /// Render the user detail view.
-pub fn render(frame: &mut Frame, area: Rect, state: &ServerState, username: &str) {
-    let user = state.get_user_by_name(username);
+pub fn render(frame: &mut Frame, area: Rect, room: &Room, username: &str, theme: &Theme) {
+    let user = room.get_user_by_name(username);
 
     let Some(user) = user else {
         let not_found = Paragraph::new(format!("User '{}' not found", username))
-            .style(Style::default().fg(Color::Red))
+            .style(Style::default().fg(theme.incorrect))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL).title(" User View "));
         frame.render_widget(not_found, area);
@@ -26,47 +28,49 @@ pub fn render(frame: &mut Frame, area: Rect, state: &ServerState, username: &str
     .margin(1)
     .split(area);
 
-    render_user_header(frame, chunks[0], state, user, username);
-    render_answers_grid(frame, chunks[1], state, user);
-    render_user_stats(frame, chunks[2], state, user);
+    render_user_header(frame, chunks[0], room, user, username, theme);
+    render_answers_grid(frame, chunks[1], room, user, theme);
+    render_user_stats(frame, chunks[2], room, user, theme);
 }
 
 fn render_user_header(
     frame: &mut Frame,
     area: Rect,
-    state: &ServerState,
+    room: &Room,
     user: &crate::server::state::UserSession,
     username: &str,
+    theme: &Theme,
 ) {
     let status_str = match user.status {
         UserStatus::Connected => "Connecting...".to_string(),
         UserStatus::InLobby => "In Lobby".to_string(),
-        UserStatus::Answering(i) => format!("Answering Q{}/{}", i + 1, state.questions.len()),
+        UserStatus::Answering(i) => format!("Answering Q{}/{}", i + 1, room.questions.len()),
         UserStatus::Finished => "Finished".to_string(),
         UserStatus::Disconnected => "Disconnected".to_string(),
+        UserStatus::Abandoned => "Abandoned".to_string(),
     };
 
     let status_color = match user.status {
-        UserStatus::Connected | UserStatus::InLobby => Color::Yellow,
-        UserStatus::Answering(_) => Color::Green,
-        UserStatus::Finished => Color::Cyan,
-        UserStatus::Disconnected => Color::Red,
+        UserStatus::Connected | UserStatus::InLobby => theme.highlight,
+        UserStatus::Answering(_) => theme.correct,
+        UserStatus::Finished => theme.accent,
+        UserStatus::Disconnected | UserStatus::Abandoned => theme.incorrect,
     };
 
     let header_text = vec![
         Line::from(vec![
-            Span::styled("  User: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(username, Style::default().fg(Color::White).bold()),
+            Span::styled("  User: ", Style::default().fg(theme.dimmed)),
+            Span::styled(username, Style::default().fg(theme.text).bold()),
         ]),
         Line::from(vec![
-            Span::styled("  IP:   ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  IP:   ", Style::default().fg(theme.dimmed)),
             Span::styled(
                 user.ip_addr.to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dimmed),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  Status: ", Style::default().fg(theme.dimmed)),
             Span::styled(status_str, Style::default().fg(status_color)),
         ]),
     ];
@@ -74,9 +78,9 @@ fn render_user_header(
     let header = Paragraph::new(header_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(format!(" Viewing: {} ", username))
-            .title_style(Style::default().fg(Color::Cyan)),
+            .title_style(Style::default().fg(theme.accent)),
     );
 
     frame.render_widget(header, area);
@@ -85,14 +89,16 @@ fn render_user_header(
 fn render_answers_grid(
     frame: &mut Frame,
     area: Rect,
-    state: &ServerState,
+    room: &Room,
     user: &crate::server::state::UserSession,
+    theme: &Theme,
 ) {
     let mut lines: Vec<Line> = Vec::new();
-    let questions = &state.questions;
+    let questions = &room.questions;
 
     // Show answers in a grid format (5 per row)
     let answers_per_row = 5;
+    let cell_width = if theme.text_labels { 11 } else { 5 };
     let mut row_spans: Vec<Span> = Vec::new();
 
     for (i, answer) in user.answers.iter().enumerate() {
@@ -100,35 +106,29 @@ fn render_answers_grid(
 
         let (symbol, color) = match answer {
             Some(ans) => {
-                let is_correct = question.is_some_and(|q| q.correct_answer == *ans);
-                let letter = match ans {
-                    0 => "A",
-                    1 => "B",
-                    2 => "C",
-                    3 => "D",
-                    _ => "?",
-                };
-                if is_correct {
-                    (format!("{} +", letter), Color::Green)
-                } else {
-                    (format!("{} -", letter), Color::Red)
-                }
+                let is_correct = question.is_some_and(|q| q.is_correct(ans));
+                let letter: String = ans
+                    .iter()
+                    .map(|&index| OPTION_LABELS.get(index).copied().unwrap_or('?'))
+                    .collect();
+                let color = if is_correct { theme.correct } else { theme.incorrect };
+                (format!("{} {}", letter, theme.verdict(is_correct).trim_end()), color)
             }
             None => {
                 if matches!(user.status, UserStatus::Answering(idx) if idx == i) {
-                    ("...".to_string(), Color::Yellow)
+                    ("...".to_string(), theme.highlight)
                 } else {
-                    ("---".to_string(), Color::DarkGray)
+                    ("---".to_string(), theme.dimmed)
                 }
             }
         };
 
         row_spans.push(Span::styled(
             format!("  Q{:<2}: ", i + 1),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dimmed),
         ));
         row_spans.push(Span::styled(
-            format!("{:<5}", symbol),
+            format!("{:<1$}", symbol, cell_width),
             Style::default().fg(color),
         ));
 
@@ -140,16 +140,16 @@ fn render_answers_grid(
     if lines.is_empty() {
         lines.push(Line::from(Span::styled(
             "  No answers yet...",
-            Style::default().fg(Color::DarkGray).italic(),
+            Style::default().fg(theme.dimmed).italic(),
         )));
     }
 
     let widget = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.dimmed))
             .title(" Answers ")
-            .title_style(Style::default().fg(Color::Cyan))
+            .title_style(Style::default().fg(theme.accent))
             .padding(Padding::horizontal(1)),
     );
 
@@ -159,12 +159,13 @@ fn render_answers_grid(
 fn render_user_stats(
     frame: &mut Frame,
     area: Rect,
-    state: &ServerState,
+    room: &Room,
     user: &crate::server::state::UserSession,
+    theme: &Theme,
 ) {
     let answered = user.answered_count();
-    let correct = user.correct_count(&state.questions);
-    let total = state.questions.len();
+    let correct = user.correct_count(&room.questions);
+    let total = room.questions.len();
 
     let pct = if answered > 0 {
         (correct as f64 / answered as f64) * 100.0
@@ -177,12 +178,7 @@ fn render_user_stats(
         answered, total, correct, answered, pct
     );
 
-    let color = match pct as u32 {
-        90..=100 => Color::Green,
-        70..=89 => Color::Cyan,
-        50..=69 => Color::Yellow,
-        _ => Color::Red,
-    };
+    let color = theme.grade(room.meta.grades.band(pct));
 
     let stats = Paragraph::new(stats_text)
         .style(Style::default().fg(color))
@@ -0,0 +1,67 @@
+//! Saving an unfinished quiz to disk and picking it up later.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Confidence, Question, QuizMeta};
+
+/// Snapshot of an unfinished quiz.
+///
+/// The questions are stored with the progress, so a session resumes exactly
+/// as it was left even if the question file changed or was sampled.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// The question file or URL the quiz was loaded from.
+    pub(crate) source: String,
+    pub(crate) questions: Vec<Question>,
+    pub(crate) current_question: usize,
+    /// Questions still to be answered, current question first.
+    pub(crate) queue: Vec<usize>,
+    pub(crate) answers: Vec<Option<Vec<usize>>>,
+    pub(crate) skipped: Vec<bool>,
+    pub(crate) hints_used: Vec<bool>,
+    /// Time already spent, in seconds.
+    pub(crate) elapsed_secs: u64,
+    pub(crate) time_limit_secs: Option<u64>,
+    pub(crate) exam_mode: bool,
+    pub(crate) instant_feedback: bool,
+    pub(crate) hint_penalty: usize,
+    #[serde(default)]
+    pub(crate) confidence_mode: bool,
+    #[serde(default)]
+    pub(crate) confidences: Vec<Option<Confidence>>,
+    #[serde(default)]
+    pub(crate) meta: QuizMeta,
+}
+
+impl SavedSession {
+    /// `session.json` in the user's data directory, next to the score history.
+    ///
+    /// Returns `None` if the platform has no data directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("rust-quiz").join("session.json"))
+    }
+
+    /// Read a saved session.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, crate::QuizError> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(crate::QuizError::Session)
+    }
+
+    /// Write the session, creating its directory if needed.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Where the questions of this session came from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
@@ -5,8 +5,24 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::{Backend, CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Size},
+    style::Stylize,
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::theme::Theme;
+use crate::QuizError;
 
+/// The terminal [`init`] sets up, drawing to stdout.
+///
+/// Every screen can also be drawn on a `Terminal` of any other ratatui
+/// [`Backend`], e.g. one writing to stderr or a TTY handle, or a
+/// `TestBackend` to check what is drawn.
 pub type AppTerminal = Terminal<CrosstermBackend<Stdout>>;
 
 pub fn init() -> io::Result<AppTerminal> {
@@ -30,3 +46,74 @@ fn setup_panic_hook() {
         original_hook(panic_info);
     }));
 }
+
+/// Draw one frame of `width` by `height` cells with `render`, off screen,
+/// and return what was drawn, e.g. to check a screen in a test.
+pub fn render_to_buffer(width: u16, height: u16, render: impl FnOnce(&mut Frame)) -> Buffer {
+    // A TestBackend never fails
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(render).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// The text of each row of `buffer`, without trailing spaces.
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let row: String = (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            row.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// An error from a terminal's backend as a [`QuizError::Terminal`], with
+/// the `io::Error` unwrapped if it already is one, as crossterm's are.
+pub(crate) fn backend_error<B: Backend>(error: B::Error) -> QuizError
+where
+    B::Error: Send + Sync + 'static,
+{
+    let error: Box<dyn std::error::Error + Send + Sync> = Box::new(error);
+    QuizError::Terminal(match error.downcast::<io::Error>() {
+        Ok(error) => *error,
+        Err(error) => io::Error::other(error),
+    })
+}
+
+/// Repaint everything on the next draw after the terminal is resized, in
+/// case it reflowed the old screen.
+pub(crate) fn handle_resize<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), QuizError>
+where
+    B::Error: Send + Sync + 'static,
+{
+    terminal.clear().map_err(backend_error::<B>)
+}
+
+/// Ask for a bigger window if the frame is smaller than `min`.
+///
+/// Returns true if the notice was drawn, in which case the caller should
+/// skip its own widgets: they would overlap or be cut off.
+pub(crate) fn render_too_small(frame: &mut Frame, min: Size, theme: &Theme) -> bool {
+    let area = frame.area();
+    if area.width >= min.width && area.height >= min.height {
+        return false;
+    }
+
+    let content = vec![
+        Line::from("Terminal too small".fg(theme.incorrect).bold()),
+        Line::from(format!("{}×{}", area.width, area.height).fg(theme.text)),
+        Line::from(
+            format!("Please enlarge to at least {}×{}", min.width, min.height).fg(theme.dimmed),
+        ),
+    ];
+    let [row] = Layout::vertical([Constraint::Length(content.len() as u16)])
+        .flex(Flex::Center)
+        .areas(area);
+    let widget = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(widget, row);
+    true
+}
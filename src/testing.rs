@@ -0,0 +1,23 @@
+//! Question fixtures shared by the unit tests.
+
+use crate::models::Question;
+
+/// A question with options a to d, of which a is right.
+pub(crate) fn question(text: &str) -> Question {
+    Question::new(text, ["a", "b", "c", "d"], 0)
+}
+
+/// `count` questions, "Question 0?" onwards, each with b right.
+pub(crate) fn questions(count: usize) -> Vec<Question> {
+    (0..count)
+        .map(|i| Question::new(format!("Question {}?", i), ["a", "b", "c", "d"], 1))
+        .collect()
+}
+
+/// A question for each of `tags`, "Question 0?" onwards, each with a right.
+pub(crate) fn tagged_questions(tags: &[&str]) -> Vec<Question> {
+    tags.iter()
+        .enumerate()
+        .map(|(i, tag)| question(&format!("Question {}?", i)).with_tags([*tag]))
+        .collect()
+}
@@ -0,0 +1,102 @@
+//! Fitting text into terminal columns.
+//!
+//! CJK characters and most emoji take two columns, so lengths here are
+//! display widths rather than character counts.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Columns `text` takes up in a terminal.
+pub(crate) fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// Marks where text was cut.
+const ELLIPSIS: &str = "...";
+
+/// Cut `text` to at most `max_width` columns, marking the cut with `...`.
+pub(crate) fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    let (kept, ellipsis) = cut(text.chars(), max_width);
+    format!("{}{}", kept.into_iter().collect::<String>(), ellipsis)
+}
+
+/// Like [`truncate`], but keep the end of `text`, e.g. to show where the
+/// user is typing.
+pub(crate) fn truncate_start(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    let (kept, ellipsis) = cut(text.chars().rev(), max_width);
+    format!("{}{}", ellipsis, kept.into_iter().rev().collect::<String>())
+}
+
+/// The first of `chars` that fit in `max_width` columns along with the
+/// ellipsis, and as much of the ellipsis as fits.
+fn cut(chars: impl Iterator<Item = char>, max_width: usize) -> (Vec<char>, &'static str) {
+    let ellipsis = &ELLIPSIS[..ELLIPSIS.len().min(max_width)];
+    let mut kept = Vec::new();
+    let mut used = ellipsis.len();
+    for c in chars {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > max_width {
+            break;
+        }
+        used += char_width;
+        kept.push(c);
+    }
+    (kept, ellipsis)
+}
+
+/// Pad `text` with spaces to `min_width` columns, like `{:<N}` does for
+/// characters.
+pub(crate) fn pad(text: &str, min_width: usize) -> String {
+    let padding = min_width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Show a time in seconds as e.g. `8.4s`, or `2:05` from a minute up.
+pub(crate) fn seconds(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else {
+        let whole = seconds.round() as u64;
+        format!("{}:{:02}", whole / 60, whole % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_characters() {
+        assert_eq!(width("所有権"), 6);
+        assert_eq!(truncate("所有権とは？", 7), "所有...");
+        assert_eq!(truncate("Ownership", 20), "Ownership");
+        assert_eq!(truncate_start("所有権とは？", 7), "...は？");
+        assert_eq!(pad("🦀", 4), "🦀  ");
+        assert_eq!(pad("ferris", 4), "ferris");
+    }
+
+    #[test]
+    fn test_truncate_fits() {
+        for text in ["Ownership and borrowing", "所有権とは？", "🦀🦀🦀🦀"] {
+            for max_width in 0..12 {
+                assert!(width(&truncate(text, max_width)) <= max_width, "{} {}", text, max_width);
+                assert!(width(&truncate_start(text, max_width)) <= max_width);
+            }
+        }
+        assert_eq!(truncate("Ownership", 8), "Owner...");
+        assert_eq!(truncate("Ownership", 2), "..");
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(seconds(8.44), "8.4s");
+        assert_eq!(seconds(125.2), "2:05");
+    }
+}
@@ -0,0 +1,307 @@
+//! Colors used by the terminal interfaces.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// Time left under which a countdown turns red, whatever its length.
+pub const COUNTDOWN_FINAL: Duration = Duration::from_secs(10);
+
+/// Palette shared by the single-player, client and server interfaces.
+///
+/// The default is the [`dark`](Self::dark) preset. A theme file is TOML with
+/// an optional `preset` to start from and any colors to override, as names
+/// (`"cyan"`, `"dark gray"`), hex codes (`"#1e66f5"`) or 256-color indices:
+///
+/// ```toml
+/// preset = "light"
+/// accent = "#1e66f5"
+/// code = "magenta"
+/// ```
+///
+/// `text_labels = true` marks answers with words and ASCII symbols, for
+/// players who can't tell the colors apart. The
+/// [`high_contrast`](Self::high_contrast) preset turns it on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Titles, the option cursor and other focus points.
+    pub accent: Color,
+    /// Hints, pending states and warnings.
+    pub highlight: Color,
+    /// Correct answers and good scores.
+    pub correct: Color,
+    /// Wrong answers, poor scores and errors.
+    pub incorrect: Color,
+    /// Main text, such as the question.
+    pub text: Color,
+    /// Secondary text, such as unselected options.
+    pub muted: Color,
+    /// Borders, key hints and other low-key text.
+    pub dimmed: Color,
+    /// Code snippets.
+    pub code: Color,
+    /// Spell out right and wrong answers instead of telling them apart by
+    /// color alone.
+    pub text_labels: bool,
+}
+
+/// Error loading a theme.
+#[derive(Debug)]
+pub enum ThemeError {
+    /// The theme file could not be read.
+    Io(io::Error),
+    /// The theme file is not valid TOML or has an invalid color.
+    Parse(toml::de::Error),
+    /// The preset name is not `dark`, `light` or `high-contrast`.
+    UnknownPreset(String),
+}
+
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "Failed to read theme: {}", e),
+            ThemeError::Parse(e) => write!(f, "Failed to parse theme: {}", e),
+            ThemeError::UnknownPreset(name) => {
+                write!(f, "Unknown theme preset '{}' (expected dark, light or high-contrast)", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ThemeError::Io(e) => Some(e),
+            ThemeError::Parse(e) => Some(e),
+            ThemeError::UnknownPreset(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ThemeError {
+    fn from(err: io::Error) -> Self {
+        ThemeError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for ThemeError {
+    fn from(err: toml::de::Error) -> Self {
+        ThemeError::Parse(err)
+    }
+}
+
+/// A theme file: a preset plus individual overrides.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    preset: Option<String>,
+    accent: Option<Color>,
+    highlight: Option<Color>,
+    correct: Option<Color>,
+    incorrect: Option<Color>,
+    text: Option<Color>,
+    muted: Option<Color>,
+    dimmed: Option<Color>,
+    code: Option<Color>,
+    text_labels: Option<bool>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Bright colors for terminals with a dark background.
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            correct: Color::Green,
+            incorrect: Color::Red,
+            text: Color::White,
+            muted: Color::Gray,
+            dimmed: Color::DarkGray,
+            code: Color::Yellow,
+            text_labels: false,
+        }
+    }
+
+    /// Deep colors for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            highlight: Color::Magenta,
+            correct: Color::Rgb(0x1b, 0x7f, 0x2a),
+            incorrect: Color::Rgb(0xc0, 0x1c, 0x28),
+            text: Color::Black,
+            muted: Color::DarkGray,
+            dimmed: Color::Gray,
+            code: Color::Rgb(0x8a, 0x4b, 0x00),
+            text_labels: false,
+        }
+    }
+
+    /// Bright, colorblind-safe colors on a dark background, with answers
+    /// spelled out as `CORRECT` and `WRONG`.
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightCyan,
+            highlight: Color::LightYellow,
+            correct: Color::LightBlue,
+            incorrect: Color::Rgb(0xff, 0x8c, 0x00),
+            text: Color::White,
+            muted: Color::White,
+            dimmed: Color::Gray,
+            code: Color::LightYellow,
+            text_labels: true,
+        }
+    }
+
+    /// Look up a built-in preset by name.
+    pub fn preset(name: &str) -> Result<Self, ThemeError> {
+        match name.to_lowercase().as_str() {
+            "dark" => Ok(Self::dark()),
+            "light" => Ok(Self::light()),
+            "high-contrast" => Ok(Self::high_contrast()),
+            _ => Err(ThemeError::UnknownPreset(name.to_string())),
+        }
+    }
+
+    /// Parse a theme from TOML.
+    ///
+    /// Colors that aren't set come from the `preset`, or the dark preset.
+    pub fn from_toml(content: &str) -> Result<Self, ThemeError> {
+        let file: ThemeFile = toml::from_str(content)?;
+        let base = match &file.preset {
+            Some(name) => Self::preset(name)?,
+            None => Self::dark(),
+        };
+
+        Ok(Self {
+            accent: file.accent.unwrap_or(base.accent),
+            highlight: file.highlight.unwrap_or(base.highlight),
+            correct: file.correct.unwrap_or(base.correct),
+            incorrect: file.incorrect.unwrap_or(base.incorrect),
+            text: file.text.unwrap_or(base.text),
+            muted: file.muted.unwrap_or(base.muted),
+            dimmed: file.dimmed.unwrap_or(base.dimmed),
+            code: file.code.unwrap_or(base.code),
+            text_labels: file.text_labels.unwrap_or(base.text_labels),
+        })
+    }
+
+    /// Load a theme file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ThemeError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// `theme.toml` in the user's config directory.
+    ///
+    /// Returns `None` if the platform has no config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-quiz").join("theme.toml"))
+    }
+
+    /// The theme from [`default_path`](Self::default_path) if that file
+    /// exists, otherwise the dark preset.
+    pub fn load_default() -> Result<Self, ThemeError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load(path),
+            _ => Ok(Self::dark()),
+        }
+    }
+
+    /// Color of a score in `band`, from [`Grades::band`].
+    ///
+    /// [`Grades::band`]: crate::Grades::band
+    pub fn grade(&self, band: usize) -> Color {
+        match band {
+            0 => self.correct,
+            1 => self.accent,
+            2 => self.highlight,
+            _ => self.incorrect,
+        }
+    }
+
+    /// Color of a countdown with `remaining` of `limit` left: red for the
+    /// last fifth or the final [`COUNTDOWN_FINAL`] seconds.
+    pub fn countdown(&self, remaining: Duration, limit: Duration) -> Color {
+        let ratio = if limit.is_zero() {
+            0.0
+        } else {
+            remaining.as_secs_f64() / limit.as_secs_f64()
+        };
+        match ratio {
+            _ if remaining <= COUNTDOWN_FINAL => self.incorrect,
+            r if r > 0.5 => self.correct,
+            r if r > 0.2 => self.highlight,
+            _ => self.incorrect,
+        }
+    }
+
+    /// Mark for a right or wrong answer in a list of results.
+    pub fn verdict(&self, is_correct: bool) -> &'static str {
+        match (self.text_labels, is_correct) {
+            (true, true) => "CORRECT",
+            (true, false) => "WRONG  ",
+            (false, true) => "+",
+            (false, false) => "-",
+        }
+    }
+
+    /// Mark for the correct option, or a wrong option that was chosen.
+    pub fn option_marker(&self, is_correct: bool) -> &'static str {
+        match (self.text_labels, is_correct) {
+            (true, true) => "+",
+            (true, false) => "x",
+            (false, true) => "✓",
+            (false, false) => "✗",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml_overrides_preset() {
+        let theme = Theme::from_toml("preset = \"light\"\naccent = \"#1e66f5\"\ncode = \"magenta\"")
+            .unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0x1e, 0x66, 0xf5));
+        assert_eq!(theme.code, Color::Magenta);
+        assert_eq!(theme.text, Theme::light().text);
+
+        let theme = Theme::from_toml("preset = \"high-contrast\"\ntext_labels = false").unwrap();
+        assert_eq!(theme.correct, Theme::high_contrast().correct);
+        assert!(!theme.text_labels);
+
+        assert!(matches!(
+            Theme::from_toml("preset = \"solarized\""),
+            Err(ThemeError::UnknownPreset(_))
+        ));
+        assert!(matches!(
+            Theme::from_toml("accent = \"not a color\""),
+            Err(ThemeError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_countdown_turns_red_in_final_seconds() {
+        let theme = Theme::dark();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert_eq!(theme.countdown(minutes(8), minutes(10)), theme.correct);
+        assert_eq!(theme.countdown(minutes(3), minutes(10)), theme.highlight);
+        assert_eq!(theme.countdown(minutes(1), minutes(10)), theme.incorrect);
+        assert_eq!(
+            theme.countdown(Duration::from_secs(9), Duration::from_secs(15)),
+            theme.incorrect
+        );
+    }
+}
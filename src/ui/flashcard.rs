@@ -0,0 +1,179 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use super::{format_duration, quiz::render_code_block};
+use crate::app::App;
+use crate::flashcards::Deck;
+use crate::models::{Question, OPTION_LABELS};
+use crate::theme::Theme;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let Some(deck) = app.deck() else {
+        return;
+    };
+    let Some(question) = app.current_card() else {
+        render_summary(frame, area, app, deck, theme);
+        return;
+    };
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(8),
+        Constraint::Length(4),
+        Constraint::Length(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    render_header(frame, chunks[0], app, deck, theme);
+    let widget = Paragraph::new(question.text.as_str())
+        .wrap(Wrap { trim: true })
+        .fg(theme.text)
+        .bold();
+    frame.render_widget(widget, chunks[1]);
+    if question.code.is_some() {
+        render_code_block(frame, chunks[2], question, theme);
+    }
+    render_options(frame, chunks[3], question, deck.is_revealed(), theme);
+    if deck.is_revealed()
+        && let Some(explanation) = &question.explanation
+    {
+        let widget = Paragraph::new(explanation.as_str())
+            .wrap(Wrap { trim: true })
+            .fg(theme.muted);
+        frame.render_widget(widget, chunks[4]);
+    }
+    render_controls(frame, chunks[5], deck.is_revealed(), theme);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, app: &App, deck: &Deck, theme: &Theme) {
+    let line = Line::from(vec![
+        Span::styled(
+            format!("Card {}/{}", deck.card() + 1, app.engine().total_questions()),
+            Style::default().fg(theme.accent).bold(),
+        ),
+        Span::styled(
+            format!("  ·  knew {} so far", deck.known_count()),
+            Style::default().fg(theme.dimmed),
+        ),
+    ]);
+
+    let widget = Paragraph::new(line).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme.dimmed),
+    );
+    frame.render_widget(widget, area);
+}
+
+/// The options, with the correct ones marked once revealed.
+fn render_options(
+    frame: &mut Frame,
+    area: Rect,
+    question: &Question,
+    revealed: bool,
+    theme: &Theme,
+) {
+    let correct = question.correct_answer.indices();
+
+    let lines: Vec<Line> = question
+        .options
+        .iter()
+        .enumerate()
+        .flat_map(|(index, option)| {
+            let (marker, style) = if revealed && correct.contains(&index) {
+                (theme.option_marker(true), Style::default().fg(theme.correct).bold())
+            } else if revealed {
+                (" ", Style::default().fg(theme.dimmed))
+            } else {
+                (" ", Style::default().fg(theme.muted))
+            };
+
+            let mut spans = vec![
+                Span::styled(format!(" {} ", marker), style),
+                Span::styled(format!("{}. ", OPTION_LABELS[index]), style),
+                Span::styled(option.as_str(), style),
+            ];
+            if revealed && correct.contains(&index) && theme.text_labels {
+                spans.push(Span::styled("  (correct answer)", style));
+            }
+            [Line::from(spans), Line::from("")]
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, revealed: bool, theme: &Theme) {
+    let controls = if revealed {
+        "y knew it  ·  n didn't  ·  q quit"
+    } else {
+        "enter/space show answer  ·  q quit"
+    };
+    let widget = Paragraph::new(controls)
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, area);
+}
+
+/// How the study session went, once every card is rated.
+fn render_summary(frame: &mut Frame, area: Rect, app: &App, deck: &Deck, theme: &Theme) {
+    let total = deck.known().len();
+    let missed = total - deck.known_count();
+    let review = match missed {
+        0 => "Nothing to go over again".to_string(),
+        1 => "1 card to go over again".to_string(),
+        _ => format!("{} cards to go over again", missed),
+    };
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "DONE STUDYING",
+            Style::default().fg(theme.accent).bold(),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Knew {} of {} cards", deck.known_count(), total),
+            Style::default().fg(theme.text).bold(),
+        )),
+        Line::from(
+            format!(
+                "{}  ·  Time {}",
+                review,
+                format_duration(deck.time_taken().unwrap_or_default())
+            )
+            .fg(theme.dimmed),
+        ),
+        Line::from(""),
+        Line::from(app.status_message().unwrap_or_default().fg(theme.highlight)),
+        Line::from(match app.playlist() {
+            Some(playlist) if playlist.is_last() => {
+                "r restart  ·  n playlist scores  ·  q quit".fg(theme.dimmed)
+            }
+            Some(_) => "r restart  ·  n next quiz  ·  q quit".fg(theme.dimmed),
+            None => "r restart  ·  q quit".fg(theme.dimmed),
+        }),
+    ];
+
+    let chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(content.len() as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let widget = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.dimmed),
+        );
+    frame.render_widget(widget, chunks[1]);
+}
@@ -0,0 +1,142 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use super::format_duration;
+use crate::app::App;
+use crate::models::AppState;
+use crate::theme::Theme;
+
+const WIDTH: u16 = 76;
+
+const QUIZ_KEYS: &[(&str, &str)] = &[
+    ("↑/↓", "Select an option"),
+    ("space", "Tick an option (checkbox questions)"),
+    ("enter", "Submit the answer"),
+    ("s", "Skip the question and come back to it later"),
+    ("h", "Reveal the hint"),
+    ("←/→ p/n", "Previous/next question (exam mode)"),
+    ("f", "Finish and score the quiz (exam mode)"),
+    ("q", "Quit"),
+];
+
+const RESULT_KEYS: &[(&str, &str)] = &[
+    ("j/k pgup/pgdn", "Move through the questions (home/end: first/last)"),
+    ("enter", "Review the question"),
+    ("x / w", "Show missed answers / list only wrong answers"),
+    ("t / r", "Retake missed questions / restart"),
+    ("n", "Next quiz of the playlist"),
+    ("e / m", "Save an HTML report / Moodle XML"),
+    ("q", "Quit"),
+];
+
+const WELCOME_KEYS: &[(&str, &str)] = &[
+    ("enter", "Start"),
+    ("↑/↓ j/k", "Choose another quiz, or a recently played one"),
+    ("h / s", "Past attempts / statistics"),
+    ("r", "Reload the question file after it changed (--watch)"),
+    ("q", "Quit"),
+];
+
+const FLASHCARD_KEYS: &[(&str, &str)] = &[
+    ("enter/space", "Show the answer"),
+    ("y / n", "Knew it / didn't"),
+    ("r", "Restart once every card is rated"),
+    ("q", "Quit"),
+];
+
+/// Draw the list of keys and the quiz's settings over the current screen.
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    // Keys of the screen underneath
+    let keys = match app.state {
+        AppState::Quiz | AppState::Feedback | AppState::QuitPrompt => QUIZ_KEYS,
+        AppState::Passed | AppState::Result | AppState::Review => RESULT_KEYS,
+        AppState::Flashcards => FLASHCARD_KEYS,
+        AppState::Welcome | AppState::History | AppState::Stats => WELCOME_KEYS,
+    };
+    let mut content = vec![Line::from(Span::styled(
+        "Keys",
+        Style::default().fg(theme.accent).bold(),
+    ))];
+    for (key, action) in keys {
+        content.push(Line::from(vec![
+            Span::styled(format!("  {:<18}", key), Style::default().fg(theme.highlight)),
+            Span::styled(*action, Style::default().fg(theme.text)),
+        ]));
+    }
+    content.push(Line::from(""));
+
+    content.push(Line::from(Span::styled(
+        "Settings",
+        Style::default().fg(theme.accent).bold(),
+    )));
+    for (setting, value) in settings(app) {
+        content.push(Line::from(vec![
+            Span::styled(format!("  {:<18}", setting), Style::default().fg(theme.muted)),
+            Span::styled(value, Style::default().fg(theme.text)),
+        ]));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(
+        "? or esc to close  ·  the timer keeps running".fg(theme.dimmed),
+    ));
+
+    let height = (content.len() as u16 + 2).min(area.height);
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, popup, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(WIDTH.min(area.width)),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+
+    let widget = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.accent)
+            .title(" Help ")
+            .title_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+/// The quiz's modes, as `(setting, value)` pairs.
+fn settings(app: &App) -> Vec<(&'static str, String)> {
+    let engine = app.engine();
+    let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+
+    let timer = match (engine.time_limit(), engine.remaining_time()) {
+        (Some(limit), Some(remaining)) if engine.is_started() => format!(
+            "{} ({} left)",
+            format_duration(limit),
+            format_duration(remaining)
+        ),
+        (Some(limit), _) => format_duration(limit),
+        (None, _) => "off".to_string(),
+    };
+    let mut settings = vec![
+        ("Timer", timer),
+        ("Shuffle", on_off(app.is_shuffled())),
+        ("Exam mode", on_off(engine.is_exam_mode())),
+        (
+            "Instant feedback",
+            on_off(app.is_instant_feedback() && !engine.is_exam_mode()),
+        ),
+        ("Confidence", on_off(engine.is_confidence_mode())),
+    ];
+    if engine.hint_penalty() > 0 {
+        settings.push(("Hint penalty", format!("{} point(s)", engine.hint_penalty())));
+    }
+    if app.deck().is_some() {
+        settings.push(("Flashcards", "on".to_string()));
+    }
+    settings
+}
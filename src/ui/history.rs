@@ -0,0 +1,107 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph},
+};
+
+use super::result::get_grade_color;
+use crate::app::App;
+use crate::history::HistoryEntry;
+use crate::theme::Theme;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+    ])
+    .margin(1)
+    .split(area);
+
+    render_header(frame, chunks[1], app.history().len(), theme);
+    render_entries(frame, chunks[2], app.history(), app.history_scroll(), theme);
+    render_controls(frame, chunks[3], theme);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, count: usize, theme: &Theme) {
+    let summary = match count {
+        0 => "No finished quizzes yet".to_string(),
+        1 => "1 attempt".to_string(),
+        _ => format!("{} attempts", count),
+    };
+    let content = vec![
+        Line::from(Span::styled(
+            "HISTORY",
+            Style::default().fg(theme.accent).bold(),
+        )),
+        Line::from(""),
+        Line::from(summary.fg(theme.dimmed)),
+    ];
+
+    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme.dimmed),
+    );
+    frame.render_widget(widget, area);
+}
+
+/// List runs newest first.
+fn render_entries(
+    frame: &mut Frame,
+    area: Rect,
+    entries: &[HistoryEntry],
+    scroll: usize,
+    theme: &Theme,
+) {
+    let lines: Vec<Line> = entries
+        .iter()
+        .rev()
+        .map(|entry| {
+            let date = entry
+                .local_date()
+                .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unknown date".to_string());
+            let percentage = entry.percentage();
+
+            Line::from(vec![
+                Span::styled(format!("{:<18}", date), Style::default().fg(theme.muted)),
+                Span::styled(
+                    format!("{:>9}", format!("{}/{}", entry.score, entry.max_score)),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    format!("{:>6.0}%", percentage),
+                    Style::default().fg(get_grade_color(percentage, theme)).bold(),
+                ),
+                Span::styled(
+                    format!(
+                        "{:>8}",
+                        format!("{:02}:{:02}", entry.duration_secs / 60, entry.duration_secs % 60)
+                    ),
+                    Style::default().fg(theme.dimmed),
+                ),
+                Span::styled(
+                    format!(
+                        "   {}{}",
+                        entry.quiz,
+                        if entry.flashcards { "  (flashcards)" } else { "" }
+                    ),
+                    Style::default().fg(theme.dimmed),
+                ),
+            ])
+        })
+        .collect();
+
+    let widget = Paragraph::new(lines)
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(widget, area);
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let widget = Paragraph::new("j/k scroll  ·  esc back  ·  q quit")
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, area);
+}
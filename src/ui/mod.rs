@@ -1,19 +1,215 @@
+mod flashcard;
+mod help;
+mod history;
+mod passed;
+mod quit_prompt;
 mod quiz;
 mod result;
+mod review;
+mod stats;
 mod welcome;
 
+use std::time::Duration;
+
 use ratatui::{prelude::*, widgets::Block};
 
 use crate::app::App;
+use crate::layout::LayoutOptions;
 use crate::models::AppState;
+use crate::terminal;
+use crate::theme::Theme;
+
+/// Smallest terminal the quiz screens fit in.
+const MIN_SIZE: Size = Size::new(60, 20);
 
-pub fn render(frame: &mut Frame, app: &App) {
+pub fn render(frame: &mut Frame, app: &App, theme: &Theme, layout: &LayoutOptions) {
     let area = frame.area();
     frame.render_widget(Block::default().bg(Color::Reset), area);
+    if terminal::render_too_small(frame, MIN_SIZE, theme) {
+        return;
+    }
 
     match app.state {
-        AppState::Welcome => welcome::render(frame, area),
-        AppState::Quiz => quiz::render(frame, area, app),
-        AppState::Result => result::render(frame, area, app),
+        AppState::Welcome => welcome::render(frame, area, app, theme),
+        AppState::History => history::render(frame, area, app, theme),
+        AppState::Stats => stats::render(frame, area, app, theme),
+        AppState::Quiz | AppState::Feedback => quiz::render(frame, area, app, theme, layout),
+        AppState::QuitPrompt => {
+            quiz::render(frame, area, app, theme, layout);
+            quit_prompt::render(frame, area, app.status_message(), theme);
+        }
+        AppState::Passed => passed::render(frame, area, app, theme),
+        AppState::Result => result::render(frame, area, app, theme),
+        AppState::Review => review::render(frame, area, app, theme),
+        AppState::Flashcards => flashcard::render(frame, area, app, theme),
+    }
+    if app.is_showing_help() {
+        help::render(frame, area, app, theme);
+    }
+}
+
+/// The current question's image and where on screen it goes, on screens
+/// that show it.
+pub(crate) fn image_area<'a>(
+    area: Rect,
+    app: &'a App,
+    layout: &LayoutOptions,
+) -> Option<(&'a str, Rect)> {
+    let fits = area.width >= MIN_SIZE.width && area.height >= MIN_SIZE.height;
+    let shown = matches!(app.state, AppState::Quiz | AppState::Feedback) && !app.is_showing_help();
+    if !fits || !shown {
+        return None;
+    }
+    let image = app.current_question().image.as_deref()?;
+    quiz::image_area(area, app, layout).map(|inner| (image, inner))
+}
+
+/// Draw `app`'s current screen at `width` by `height` cells, with the
+/// default theme and layout, and return what was drawn.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_quiz::terminal::buffer_lines;
+/// use rust_quiz::{render_to_buffer, App, Question};
+///
+/// let app = App::with_questions(vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)]);
+/// let screen = buffer_lines(&render_to_buffer(&app, 80, 24));
+/// assert!(screen.iter().any(|line| line.contains("1 Question")));
+/// ```
+pub fn render_to_buffer(app: &App, width: u16, height: u16) -> Buffer {
+    terminal::render_to_buffer(width, height, |frame| {
+        render(frame, app, &Theme::default(), &LayoutOptions::default())
+    })
+}
+
+/// Format a duration as `mm:ss`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::QuizEntry;
+    use crate::history::HistoryEntry;
+    use crate::models::Question;
+    use crate::playlist::Playlist;
+    use crate::terminal::buffer_lines;
+
+    const WELCOME: &str = "\
+┌──────────────────────────────────────────────────────────────────────────────┐
+│                                                                              │
+│                                   RUST QUIZ                                  │
+│                                                                              │
+│                                  1 Question                                  │
+│                                                                              │
+│                                                                              │
+│                                     ENTER                                    │
+│                                   to start                                   │
+│                                                                              │
+│                  h history  ·  s stats  ·  ? help  ·  q quit                 │
+└──────────────────────────────────────────────────────────────────────────────┘";
+
+    fn screen(app: &App) -> String {
+        buffer_lines(&render_to_buffer(app, 80, 24)).join("\n")
+    }
+
+    #[test]
+    fn test_render_screens() {
+        let mut app = App::with_questions(vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)]);
+        assert_eq!(screen(&app).trim_matches('\n'), WELCOME);
+
+        app.start_quiz();
+        let quiz = screen(&app);
+        let lines: Vec<&str> = quiz.lines().filter(|line| !line.is_empty()).collect();
+        assert!(lines[0].starts_with(" 00:00") && lines[0].contains("1/1 ───"), "{}", quiz);
+        assert_eq!(lines[1..6], [" Pick b", "  > A. a", "    B. b", "    C. c", "    D. d"]);
+
+        app.select_next_option();
+        app.submit_answer();
+        let result = screen(&app);
+        assert!(result.contains("RESULTS"), "{}", result);
+        assert!(result.contains("1 / 1  (100%)"), "{}", result);
+        assert!(result.contains("1. Pick b"), "{}", result);
+
+        let small = buffer_lines(&render_to_buffer(&app, 40, 10)).join("\n");
+        assert!(small.contains("Terminal too small"), "{}", small);
+
+        app.toggle_help();
+        let help = buffer_lines(&render_to_buffer(&app, 80, 40)).join("\n");
+        assert!(help.contains(" Help "), "{}", help);
+        assert!(help.contains("Instant feedback  off"), "{}", help);
+    }
+
+    #[test]
+    fn test_render_quiz_picker() {
+        let mut app = App::with_questions(Vec::new());
+        let quiz = |title: &str, questions| QuizEntry {
+            path: format!("{}.json", title).into(),
+            title: title.to_string(),
+            questions,
+        };
+        app.set_quizzes(vec![quiz("Ownership", 12), quiz("Traits", 1)]);
+        app.select_next_quiz();
+        app.select_next_quiz();
+
+        let welcome = screen(&app);
+        assert!(welcome.contains("  Ownership   12 questions"), "{}", welcome);
+        assert!(welcome.contains("> Traits        1 question"), "{}", welcome);
+    }
+
+    #[test]
+    fn test_render_recent_quizzes() {
+        let mut app = App::with_questions(vec![Question::new("Pick b", ["a", "b", "c", "d"], 1)]);
+        app.set_history(vec![HistoryEntry {
+            date: "2026-10-16T09:00:00+00:00".to_string(),
+            quiz: "traits.json".to_string(),
+            score: 4,
+            max_score: 5,
+            duration_secs: 60,
+            tags: Vec::new(),
+            missed: Vec::new(),
+            correct: Vec::new(),
+            flashcards: false,
+        }]);
+        app.set_recent_quizzes(vec![QuizEntry {
+            path: "traits.json".into(),
+            title: "Traits".to_string(),
+            questions: 5,
+        }]);
+        let welcome = screen(&app);
+        assert!(welcome.contains("Recently played"), "{}", welcome);
+        assert!(welcome.contains("  Traits    5 questions  last 80%"), "{}", welcome);
+
+        app.select_next_quiz();
+        let welcome = screen(&app);
+        assert!(welcome.contains("to start Traits"), "{}", welcome);
+        assert!(welcome.contains("> Traits"), "{}", welcome);
+        app.select_previous_quiz();
+        assert_eq!(app.chosen_quiz(), None);
+    }
+
+    #[test]
+    fn test_render_playlist_scores() {
+        let mut app = App::with_questions(Vec::new());
+        let quiz = |title: &str| QuizEntry {
+            path: format!("{}.json", title).into(),
+            title: title.to_string(),
+            questions: 4,
+        };
+        let mut playlist = Playlist::new(None, vec![quiz("Ownership"), quiz("Traits")]);
+        playlist.record(3, 4);
+        playlist.advance();
+        playlist.record(2, 4);
+        playlist.advance();
+        app.set_playlist(playlist);
+
+        let welcome = screen(&app);
+        assert!(welcome.contains("1. Ownership    4 questions        3/4"), "{}", welcome);
+        assert!(welcome.contains("2. Traits       4 questions        2/4"), "{}", welcome);
+        assert!(welcome.contains("Total 5/8 (62%)"), "{}", welcome);
+        assert!(welcome.contains("to play the playlist again"), "{}", welcome);
     }
 }
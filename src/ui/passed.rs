@@ -0,0 +1,69 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::theme::Theme;
+
+/// Congratulate a player who passed, before showing the results.
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let engine = app.engine();
+    let score = engine.calculate_score();
+    let max_score = engine.max_score();
+    let percentage = engine.result().map_or(0.0, |result| result.percentage());
+    let grades = &app.meta().grades;
+    let color = theme.grade(grades.band(percentage));
+
+    let mut score_line = vec![Span::styled(
+        format!("{} / {}  ({:.0}%)", score, max_score, percentage),
+        Style::default().fg(color).bold(),
+    )];
+    if let Some(grade) = grades.grade(percentage) {
+        score_line.push(Span::styled("  ·  ", Style::default().fg(theme.dimmed)));
+        score_line.push(Span::styled(grade.label.clone(), Style::default().fg(color).bold()));
+    }
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "PASSED",
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from(""),
+        Line::from("Congratulations!".fg(theme.text)),
+    ];
+    if let Some(title) = &app.meta().title {
+        content.push(Line::from(title.as_str().fg(theme.accent)));
+    }
+    content.extend([
+        Line::from(""),
+        Line::from(score_line),
+        Line::from(""),
+        Line::from(app.status_message().unwrap_or_default().fg(theme.highlight)),
+        Line::from(Span::styled(
+            "ENTER",
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from("to see your results".fg(theme.dimmed)),
+        Line::from(""),
+        Line::from("q quit".fg(theme.dimmed)),
+    ]);
+
+    let chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(content.len() as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let widget = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.correct),
+        );
+    frame.render_widget(widget, chunks[1]);
+}
@@ -0,0 +1,45 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::theme::Theme;
+
+const WIDTH: u16 = 48;
+const HEIGHT: u16 = 8;
+
+/// Draw the "save before quitting?" dialog over the quiz.
+pub fn render(frame: &mut Frame, area: Rect, status_message: Option<&str>, theme: &Theme) {
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(HEIGHT),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, popup, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(WIDTH),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Save progress before quitting?",
+            Style::default().fg(theme.text).bold(),
+        )),
+        Line::from("Resume later with --resume".fg(theme.dimmed)),
+        Line::from(""),
+        Line::from(status_message.unwrap_or_default().fg(theme.incorrect)),
+        Line::from("y save  ·  n discard  ·  esc keep going".fg(theme.dimmed)),
+    ];
+
+    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.accent),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
@@ -1,122 +1,405 @@
+use std::path::Path;
+
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    widgets::{Block, Borders, Gauge, LineGauge, Padding, Paragraph, Wrap},
 };
 
+use super::format_duration;
 use crate::app::App;
+use crate::highlight;
+use crate::layout::LayoutOptions;
+use crate::models::{format_answer, AppState, Question, OPTION_LABELS};
+use crate::text;
+use crate::theme::Theme;
 
-const OPTION_LABELS: [char; 4] = ['A', 'B', 'C', 'D'];
+/// Height of a question's image box, borders included.
+const IMAGE_HEIGHT: u16 = 12;
+/// Width of the progress gauge in the header, label included.
+const PROGRESS_WIDTH: u16 = 24;
 
-pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, layout: &LayoutOptions) {
+    let engine = app.engine();
     let question = app.current_question();
-    let has_code = question.code.is_some();
-    let chunks = create_layout(area, has_code);
+    let show_feedback = app.state == AppState::Feedback;
+    let chunks = layout_chunks(area, app, layout);
+    let has_figure = chunks.len() == 7;
 
-    render_progress(frame, chunks[0], app);
-    render_question_text(frame, chunks[1], &question.text);
+    render_progress(frame, chunks[0], app, theme);
+    render_question_text(frame, chunks[1], &question.text, question.is_multiple(), theme);
 
-    let options_chunk = if has_code {
-        render_code_block(frame, chunks[2], question.code.as_ref().unwrap());
+    let options_chunk = if has_figure {
+        let (code_chunk, image_chunk) = split_figure(chunks[2], question);
+        if let Some(chunk) = code_chunk {
+            render_code_block(frame, chunk, question, theme);
+        }
+        if let (Some(image), Some(chunk)) = (&question.image, image_chunk) {
+            render_image_block(frame, chunk, image, app.has_graphics(), theme);
+        }
         chunks[3]
     } else {
         chunks[2]
     };
 
-    render_options(
-        frame,
-        options_chunk,
-        &question.options,
-        app.selected_option(),
-    );
+    render_options(frame, options_chunk, app, theme);
+
+    let [hint_chunk, feedback_chunk, controls_chunk] = if has_figure {
+        [chunks[4], chunks[5], chunks[6]]
+    } else {
+        [chunks[3], chunks[4], chunks[5]]
+    };
+    if let Some(hint) = question.hint.as_deref().filter(|_| engine.is_hint_revealed()) {
+        render_hint(frame, hint_chunk, hint, theme);
+    }
+    if show_feedback {
+        render_feedback(frame, feedback_chunk, question, engine.current_answer(), theme);
+        render_feedback_controls(frame, controls_chunk, theme);
+    } else {
+        render_controls(frame, controls_chunk, app, theme);
+    }
+}
+
+/// Where the current question's image is drawn, inside its box, if it has
+/// one.
+pub(super) fn image_area(area: Rect, app: &App, layout: &LayoutOptions) -> Option<Rect> {
+    let question = app.current_question();
+    question.image.as_ref()?;
+    let chunks = layout_chunks(area, app, layout);
+    let (_, image_chunk) = split_figure(chunks[2], question);
+    image_chunk.map(|chunk| image_block().inner(chunk))
+}
+
+fn layout_chunks(area: Rect, app: &App, layout: &LayoutOptions) -> std::rc::Rc<[Rect]> {
+    let question = app.current_question();
+    let code_height = question.code.as_deref().map(|code| layout.code_height(code));
+    let image_height = question.image.as_ref().map(|_| IMAGE_HEIGHT);
+    let feedback_height = match app.state {
+        AppState::Feedback => feedback_height(question),
+        _ => 0,
+    };
+    let hint_height = if app.engine().is_hint_revealed() { 2 } else { 0 };
+    let figure_height = code_height.max(image_height);
+    create_layout(area, layout, figure_height, hint_height, feedback_height)
+}
+
+/// Areas of the code and the image of a question with either, side by side
+/// when it has both.
+fn split_figure(area: Rect, question: &Question) -> (Option<Rect>, Option<Rect>) {
+    match (&question.code, &question.image) {
+        (Some(_), Some(_)) => {
+            let [code, image] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+            (Some(code), Some(image))
+        }
+        (Some(_), None) => (Some(area), None),
+        (None, Some(_)) => (None, Some(area)),
+        (None, None) => (None, None),
+    }
+}
 
-    let controls_chunk = if has_code { chunks[4] } else { chunks[3] };
-    render_controls(frame, controls_chunk);
+/// Height of the feedback panel: the verdict line, plus the explanation.
+fn feedback_height(question: &Question) -> u16 {
+    if question.explanation.is_some() { 5 } else { 2 }
 }
 
-fn create_layout(area: Rect, has_code: bool) -> std::rc::Rc<[Rect]> {
-    if has_code {
+fn create_layout(
+    area: Rect,
+    layout: &LayoutOptions,
+    figure_height: Option<u16>,
+    hint_height: u16,
+    feedback_height: u16,
+) -> std::rc::Rc<[Rect]> {
+    if let Some(figure_height) = figure_height {
         Layout::vertical([
             Constraint::Length(1),
             Constraint::Length(2),
-            Constraint::Min(8),
-            Constraint::Length(10),
+            Constraint::Length(figure_height),
+            Constraint::Min(layout.options_height),
+            Constraint::Length(hint_height),
+            Constraint::Length(feedback_height),
             Constraint::Length(1),
         ])
-        .margin(1)
+        .margin(layout.margin)
         .split(area)
     } else {
         Layout::vertical([
             Constraint::Length(1),
             Constraint::Length(4),
-            Constraint::Fill(1),
+            Constraint::Min(layout.options_height),
+            Constraint::Length(hint_height),
+            Constraint::Length(feedback_height),
             Constraint::Length(1),
         ])
-        .margin(2)
+        .margin(layout.margin)
         .split(area)
     }
 }
 
-fn render_progress(frame: &mut Frame, area: Rect, app: &App) {
-    let progress = format!(
-        "{}/{}",
-        app.current_question_number(),
-        app.total_questions()
-    );
-    let widget = Paragraph::new(progress)
+/// The timer, then how many questions are answered as a gauge labelled with
+/// the question number, and in exam mode or after skipping, the counts.
+fn render_progress(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let engine = app.engine();
+    let total = engine.total_questions();
+    let counts = if engine.is_exam_mode() {
+        let saved = if engine.is_current_answered() { "saved  ·  " } else { "" };
+        format!(
+            "{}{} answered  ·  {} open",
+            saved,
+            engine.answered_count(),
+            total - engine.answered_count()
+        )
+    } else if engine.skipped_count() > 0 {
+        format!("{} skipped", engine.skipped_count())
+    } else {
+        String::new()
+    };
+
+    let counts_width = if counts.is_empty() { 0 } else { text::width(&counts) as u16 + 2 };
+    let [timer_area, _, gauge_area, counts_area] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(2),
+        Constraint::Length(PROGRESS_WIDTH),
+        Constraint::Length(counts_width),
+    ])
+    .areas(area);
+
+    render_timer(frame, timer_area, app, theme);
+
+    let ratio = if total > 0 {
+        engine.answered_count() as f64 / total as f64
+    } else {
+        0.0
+    };
+    let gauge = LineGauge::default()
+        .ratio(ratio)
+        .label(format!("{}/{}", engine.current_question_number(), total).fg(theme.dimmed))
+        .filled_style(Style::default().fg(theme.accent))
+        .unfilled_style(Style::default().fg(theme.dimmed));
+    frame.render_widget(gauge, gauge_area);
+
+    let widget = Paragraph::new(counts)
         .alignment(Alignment::Right)
-        .fg(Color::DarkGray);
+        .fg(theme.dimmed);
+    frame.render_widget(widget, counts_area);
+}
+
+fn render_timer(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let engine = app.engine();
+    let (Some(limit), Some(remaining)) = (engine.time_limit(), engine.remaining_time()) else {
+        let widget = Paragraph::new(format_duration(engine.elapsed())).fg(theme.dimmed);
+        frame.render_widget(widget, area);
+        return;
+    };
+
+    let ratio = if limit.is_zero() {
+        0.0
+    } else {
+        remaining.as_secs_f64() / limit.as_secs_f64()
+    };
+
+    let widget = Gauge::default()
+        .ratio(ratio.clamp(0.0, 1.0))
+        .label(format!("{} left", format_duration(remaining)))
+        .gauge_style(
+            Style::default()
+                .fg(theme.countdown(remaining, limit))
+                .bg(theme.dimmed),
+        );
     frame.render_widget(widget, area);
 }
 
-fn render_question_text(frame: &mut Frame, area: Rect, text: &str) {
-    let widget = Paragraph::new(text)
+fn render_question_text(
+    frame: &mut Frame,
+    area: Rect,
+    text: &str,
+    is_multiple: bool,
+    theme: &Theme,
+) {
+    let mut lines = vec![Line::from(text)];
+    if is_multiple {
+        lines.push(Line::from(
+            Span::styled("(select all that apply)", Style::default().fg(theme.dimmed))
+                .not_bold(),
+        ));
+    }
+
+    let widget = Paragraph::new(lines)
         .wrap(Wrap { trim: true })
-        .fg(Color::White)
+        .fg(theme.text)
         .bold();
     frame.render_widget(widget, area);
 }
 
-fn render_code_block(frame: &mut Frame, area: Rect, code: &str) {
-    let code_lines: Vec<Line> = code
-        .lines()
-        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::Yellow))))
-        .collect();
+pub(super) fn render_code_block(
+    frame: &mut Frame,
+    area: Rect,
+    question: &Question,
+    theme: &Theme,
+) {
+    let code = question.code.as_deref().unwrap_or_default();
+    let language = question.code_language.as_deref();
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.dimmed)
+        .padding(Padding::horizontal(1));
+    if let Some(language) = language {
+        block = block
+            .title(format!(" {} ", highlight::label(language)))
+            .title_style(Style::default().fg(theme.accent));
+    }
 
-    let widget = Paragraph::new(code_lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Color::DarkGray)
-            .padding(Padding::horizontal(1)),
-    );
+    let widget = Paragraph::new(highlight::highlight(code, language, theme)).block(block);
     frame.render_widget(widget, area);
 }
 
-fn render_options(frame: &mut Frame, area: Rect, options: &[String; 4], selected: usize) {
+fn image_block() -> Block<'static> {
+    Block::default().borders(Borders::ALL)
+}
+
+/// The box an image is drawn in, or a placeholder naming the image when the
+/// terminal can't show it.
+fn render_image_block(frame: &mut Frame, area: Rect, image: &str, graphics: bool, theme: &Theme) {
+    let name = Path::new(image)
+        .file_name()
+        .map_or(image.into(), |name| name.to_string_lossy());
+    let block = image_block().border_style(theme.dimmed);
+    if graphics {
+        frame.render_widget(block, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [middle] = Layout::vertical([Constraint::Length(1)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(inner);
+    let widget = Paragraph::new(format!("[image: {}]", name))
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, middle);
+}
+
+fn render_options(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let question = app.current_question();
+    let options = &question.options;
+    let selected = app.selected_option();
+    // After answering in instant feedback mode, mark options instead of
+    // showing the cursor.
+    let feedback = (app.state == AppState::Feedback)
+        .then(|| {
+            let answer = app.engine().current_answer().unwrap_or(&[]);
+            (question.correct_answer.indices(), answer)
+        });
     let mut lines: Vec<Line> = Vec::with_capacity(options.len() * 2);
 
     for (index, option) in options.iter().enumerate() {
         let is_selected = index == selected;
-        let style = if is_selected {
-            Style::default().fg(Color::Cyan).bold()
-        } else {
-            Style::default().fg(Color::Gray)
+        let (style, marker) = match &feedback {
+            Some((correct, _)) if correct.contains(&index) => {
+                (Style::default().fg(theme.correct).bold(), theme.option_marker(true))
+            }
+            Some((_, answer)) if answer.contains(&index) => {
+                (Style::default().fg(theme.incorrect).bold(), theme.option_marker(false))
+            }
+            Some(_) => (Style::default().fg(theme.dimmed), " "),
+            None if is_selected => (Style::default().fg(theme.accent).bold(), ">"),
+            None => (Style::default().fg(theme.muted), " "),
         };
-        let marker = if is_selected { ">" } else { " " };
 
-        lines.push(Line::from(vec![
-            Span::styled(format!(" {} ", marker), style),
-            Span::styled(format!("{}. ", OPTION_LABELS[index]), style),
-            Span::styled(option.as_str(), style),
-        ]));
+        let mut spans = vec![Span::styled(format!(" {} ", marker), style)];
+        if question.is_multiple() {
+            let checkbox = if app.is_option_checked(index) { "[x] " } else { "[ ] " };
+            spans.push(Span::styled(checkbox, style));
+        }
+        spans.push(Span::styled(format!("{}. ", OPTION_LABELS[index]), style));
+        spans.push(Span::styled(option.as_str(), style));
+        if theme.text_labels
+            && let Some((correct, _)) = &feedback
+            && correct.contains(&index)
+        {
+            spans.push(Span::styled("  (correct answer)", style));
+        }
+        lines.push(Line::from(spans));
         lines.push(Line::from(""));
     }
 
     frame.render_widget(Paragraph::new(lines), area);
 }
 
-fn render_controls(frame: &mut Frame, area: Rect) {
-    let widget = Paragraph::new("j/k navigate  ·  enter select  ·  q quit")
+fn render_hint(frame: &mut Frame, area: Rect, hint: &str, theme: &Theme) {
+    let line = Line::from(vec![
+        Span::styled("Hint: ", Style::default().fg(theme.highlight).bold()),
+        Span::styled(hint, Style::default().fg(theme.highlight)),
+    ]);
+    frame.render_widget(Paragraph::new(line).wrap(Wrap { trim: true }), area);
+}
+
+fn render_feedback(
+    frame: &mut Frame,
+    area: Rect,
+    question: &Question,
+    answer: Option<&[usize]>,
+    theme: &Theme,
+) {
+    let is_correct = answer.is_some_and(|answer| question.is_correct(answer));
+    let verdict = if is_correct {
+        Line::from(Span::styled("Correct!", Style::default().fg(theme.correct).bold()))
+    } else {
+        Line::from(vec![
+            Span::styled("Incorrect", Style::default().fg(theme.incorrect).bold()),
+            Span::styled(
+                format!("  ·  correct answer: {}", format_answer(&question.correct_answer.indices())),
+                Style::default().fg(theme.muted),
+            ),
+        ])
+    };
+
+    let mut lines = vec![verdict];
+    if let Some(explanation) = &question.explanation {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            explanation.as_str(),
+            Style::default().fg(theme.text),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+}
+
+fn render_feedback_controls(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let widget = Paragraph::new("press any key to continue  ·  q quit")
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, area);
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    if app.is_choosing_confidence() {
+        let widget = Paragraph::new("How sure are you?  s sure  ·  u unsure  ·  esc change answer")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.highlight).bold());
+        frame.render_widget(widget, area);
+        return;
+    }
+
+    let question = app.current_question();
+    let mut controls = vec!["j/k navigate"];
+    if question.is_multiple() {
+        controls.push("space toggle");
+    }
+    if app.engine().is_exam_mode() {
+        controls.extend(["enter save", "n/p next/prev", "f finish"]);
+    } else {
+        controls.push(if question.is_multiple() { "enter submit" } else { "enter select" });
+        controls.push("s skip");
+    }
+    if question.hint.is_some() && !app.engine().is_hint_revealed() {
+        controls.push("h hint");
+    }
+    controls.extend(["? help", "q quit"]);
+
+    let widget = Paragraph::new(controls.join("  ·  "))
         .alignment(Alignment::Center)
-        .fg(Color::DarkGray);
+        .fg(theme.dimmed);
     frame.render_widget(widget, area);
 }
@@ -1,30 +1,38 @@
+use std::time::Duration;
+
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Padding, Paragraph},
+    widgets::{
+        Block, Borders, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+    },
 };
 
+use super::format_duration;
 use crate::app::App;
+use crate::models::{format_answer, Grades, Question};
+use crate::text;
+use crate::theme::Theme;
 
 const QUESTION_PREVIEW_LENGTH: usize = 55;
 
-pub fn render(frame: &mut Frame, area: Rect, app: &App) {
-    let score = app.calculate_score();
-    let total = app.total_questions();
-    let percentage = calculate_percentage(score, total);
-    let grade_color = get_grade_color(percentage);
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let tag_scores = app.engine().score_by_tag();
+    let tag_height = if tag_scores.is_empty() { 0 } else { 3 };
 
     let chunks = Layout::vertical([
         Constraint::Length(1),
-        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(tag_height),
         Constraint::Fill(1),
-        Constraint::Length(2),
+        Constraint::Length(3),
     ])
     .margin(1)
     .split(area);
 
-    render_score_summary(frame, chunks[1], score, total, percentage, grade_color);
-    render_question_breakdown(frame, chunks[2], app, app.result_scroll());
-    render_controls(frame, chunks[3]);
+    render_score_summary(frame, chunks[1], app, theme);
+    render_tag_breakdown(frame, chunks[2], &tag_scores, &app.meta().grades, theme);
+    render_question_breakdown(frame, chunks[3], app, theme);
+    render_controls(frame, chunks[4], app, theme);
 }
 
 fn calculate_percentage(score: usize, total: usize) -> f64 {
@@ -35,91 +43,241 @@ fn calculate_percentage(score: usize, total: usize) -> f64 {
     }
 }
 
-fn get_grade_color(percentage: f64) -> Color {
-    match percentage as u32 {
-        90..=100 => Color::Green,
-        70..=89 => Color::Cyan,
-        50..=69 => Color::Yellow,
-        _ => Color::Red,
-    }
+/// Color of a score in the default bands, for scores across quizzes that
+/// may grade differently.
+pub(super) fn get_grade_color(percentage: f64, theme: &Theme) -> Color {
+    theme.grade(Grades::default().band(percentage))
 }
 
-fn render_score_summary(
-    frame: &mut Frame,
-    area: Rect,
-    score: usize,
-    total: usize,
-    percentage: f64,
-    grade_color: Color,
-) {
+fn render_score_summary(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let score = app.engine().calculate_score();
+    let total = app.engine().max_score();
+    let percentage = calculate_percentage(score, total);
+    let mut details = time_summary(app);
+    if let Some(hints) = hint_summary(app) {
+        details = format!("{}  ·  {}", details, hints);
+    }
+
+    let grades = &app.meta().grades;
+    let color = theme.grade(grades.band(percentage));
+    let mut score_line = vec![Span::styled(
+        format!("{} / {}  ({:.0}%)", score, total, percentage),
+        Style::default().fg(color).bold(),
+    )];
+    if let Some(grade) = grades.grade(percentage) {
+        score_line.push(Span::styled("  ·  ", Style::default().fg(theme.dimmed)));
+        score_line.push(Span::styled(grade.label.clone(), Style::default().fg(color).bold()));
+    }
+    if let Some(passed) = app.meta().passed(score, total) {
+        let (verdict, color) = if passed {
+            ("PASSED", theme.correct)
+        } else {
+            ("FAILED", theme.incorrect)
+        };
+        score_line.push(Span::styled("  ·  ", Style::default().fg(theme.dimmed)));
+        score_line.push(Span::styled(verdict, Style::default().fg(color).bold()));
+    }
+    let title = app.meta().title.as_deref().map_or_else(
+        || "RESULTS".to_string(),
+        |title| format!("{}  ·  RESULTS", title),
+    );
+
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "RESULTS",
-            Style::default().fg(Color::Cyan).bold(),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("{} / {}  ({:.0}%)", score, total, percentage),
-            Style::default().fg(grade_color).bold(),
+            title,
+            Style::default().fg(theme.accent).bold(),
         )),
         Line::from(""),
+        Line::from(score_line),
+        Line::from(details.fg(theme.dimmed)),
+        Line::from(
+            app.engine()
+                .calibration()
+                .map(|calibration| calibration.summary())
+                .unwrap_or_default()
+                .fg(theme.dimmed),
+        ),
     ];
 
     let widget = Paragraph::new(content).alignment(Alignment::Center).block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Color::DarkGray),
+            .border_style(theme.dimmed),
     );
     frame.render_widget(widget, area);
 }
 
-fn render_question_breakdown(frame: &mut Frame, area: Rect, app: &App, scroll: usize) {
-    let lines: Vec<Line> = app
+fn time_summary(app: &App) -> String {
+    let engine = app.engine();
+    let time_taken = format_duration(engine.time_taken().unwrap_or_default());
+    match engine.time_limit() {
+        Some(limit) if engine.remaining_time() == Some(Duration::ZERO) => {
+            format!("Time's up  ·  {}", format_duration(limit))
+        }
+        Some(limit) => format!("Time {} / {}", time_taken, format_duration(limit)),
+        None => format!("Time {}", time_taken),
+    }
+}
+
+fn hint_summary(app: &App) -> Option<String> {
+    let used = app.engine().hints_used().iter().filter(|&&used| used).count();
+    match used {
+        0 => None,
+        1 => Some("1 hint used".to_string()),
+        _ => Some(format!("{} hints used", used)),
+    }
+}
+
+fn render_tag_breakdown(
+    frame: &mut Frame,
+    area: Rect,
+    tag_scores: &[(String, usize, usize)],
+    grades: &Grades,
+    theme: &Theme,
+) {
+    let mut spans: Vec<Span> = Vec::with_capacity(tag_scores.len() * 2);
+
+    for (index, (tag, correct, total)) in tag_scores.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled("  ·  ", Style::default().fg(theme.dimmed)));
+        }
+        let color = theme.grade(grades.band(calculate_percentage(*correct, *total)));
+        spans.push(Span::styled(
+            format!("{} {}/{}", tag, correct, total),
+            Style::default().fg(color),
+        ));
+    }
+
+    let widget = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(theme.dimmed),
+        );
+    frame.render_widget(widget, area);
+}
+
+fn render_question_breakdown(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let engine = app.engine();
+    let selected = app.selected_result();
+    // Line of the selected question, after any answer lines above it
+    let mut selected_line: usize = 0;
+    let mut lines: Vec<Line> = engine
         .answers()
         .iter()
-        .zip(app.questions().iter())
+        .zip(engine.questions().iter())
+        .zip(engine.hints_used().iter())
         .enumerate()
-        .map(|(index, (answer, question))| {
-            let is_correct = *answer == Some(question.correct_answer);
-            let (symbol, color) = if is_correct {
-                ("+", Color::Green)
+        .filter(|&(index, _)| app.is_result_listed(index))
+        .flat_map(|(index, ((answer, question), &hint_used))| {
+            let is_correct = answer
+                .as_deref()
+                .is_some_and(|answer| question.is_correct(answer));
+            let color = if is_correct { theme.correct } else { theme.incorrect };
+
+            let preview = text::truncate(&question.text, QUESTION_PREVIEW_LENGTH);
+            let preview_style = if index == selected {
+                Style::default().fg(theme.text).bold()
             } else {
-                ("-", Color::Red)
+                Style::default().fg(theme.muted)
             };
 
-            let preview = truncate_question(&question.text);
-
-            Line::from(vec![
-                Span::styled(format!(" {} ", symbol), Style::default().fg(color)),
+            let marker = if index == selected { ">" } else { " " };
+            let mut spans = vec![
+                Span::styled(marker, Style::default().fg(theme.accent).bold()),
+                Span::styled(format!("{} ", theme.verdict(is_correct)), Style::default().fg(color)),
                 Span::styled(
                     format!("{:2}. ", index + 1),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.dimmed),
                 ),
-                Span::styled(preview, Style::default().fg(Color::Gray)),
-            ])
+                Span::styled(preview, preview_style),
+            ];
+            if hint_used {
+                spans.push(Span::styled("  (hint)", Style::default().fg(theme.highlight)));
+            }
+            if index < selected {
+                selected_line += 1;
+            }
+            let mut lines = vec![Line::from(spans)];
+            if app.is_showing_answers() && !is_correct {
+                lines.push(answer_line(answer.as_deref(), question, theme));
+                if index < selected {
+                    selected_line += 1;
+                }
+            }
+            lines
         })
         .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("  No wrong answers".fg(theme.correct)));
+    }
 
+    // Keep the selected question in view.
+    let height = area.height as usize;
+    let scroll = selected_line.saturating_sub(height.saturating_sub(1));
+    let content_length = lines.len();
     let widget = Paragraph::new(lines)
         .block(Block::default().padding(Padding::horizontal(1)))
         .scroll((scroll as u16, 0));
     frame.render_widget(widget, area);
-}
 
-fn truncate_question(text: &str) -> String {
-    let char_count = text.chars().count();
-    if char_count > QUESTION_PREVIEW_LENGTH {
-        let truncated: String = text.chars().take(QUESTION_PREVIEW_LENGTH).collect();
-        format!("{}...", truncated)
-    } else {
-        text.to_string()
+    if content_length > height {
+        let mut state = ScrollbarState::new(content_length.saturating_sub(height))
+            .position(scroll)
+            .viewport_content_length(height);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .style(Style::default().fg(theme.dimmed));
+        frame.render_stateful_widget(scrollbar, area, &mut state);
     }
 }
 
-fn render_controls(frame: &mut Frame, area: Rect) {
-    let widget = Paragraph::new("j/k scroll  ·  r restart  ·  q quit")
-        .alignment(Alignment::Center)
-        .fg(Color::DarkGray);
+/// "your answer: B · correct: D — <option text>" under a missed question.
+fn answer_line(answer: Option<&[usize]>, question: &Question, theme: &Theme) -> Line<'static> {
+    let correct = question.correct_answer.indices();
+    let correct_text: Vec<&str> = correct
+        .iter()
+        .filter_map(|&index| question.options.get(index))
+        .map(String::as_str)
+        .collect();
+    Line::from(vec![
+        Span::raw("       "),
+        Span::styled(
+            format!("your answer: {}", answer.map_or_else(|| "none".to_string(), format_answer)),
+            Style::default().fg(theme.incorrect),
+        ),
+        Span::styled(" · ", Style::default().fg(theme.dimmed)),
+        Span::styled(
+            format!(
+                "correct: {} — {}",
+                format_answer(&correct),
+                text::truncate(&correct_text.join(" / "), QUESTION_PREVIEW_LENGTH)
+            ),
+            Style::default().fg(theme.correct),
+        ),
+    ])
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    // Restarting after a retake goes back to every question
+    let restart = if app.is_retaking() { "r all questions" } else { "r restart" };
+    let mut keys = vec!["m moodle", "t retake missed", restart];
+    match app.playlist() {
+        Some(playlist) if playlist.is_last() => keys.push("n playlist scores"),
+        Some(_) => keys.push("n next quiz"),
+        None => {}
+    }
+    keys.push("q quit");
+    let lines = vec![
+        Line::from(app.status_message().unwrap_or_default().fg(theme.highlight)),
+        Line::from(
+            "j/k/pgup/pgdn select  ·  enter review  ·  x answers  ·  w wrong only  ·  e export"
+                .fg(theme.dimmed),
+        ),
+        Line::from(keys.join("  ·  ").fg(theme.dimmed)),
+    ];
+    let widget = Paragraph::new(lines).alignment(Alignment::Center);
     frame.render_widget(widget, area);
 }
@@ -0,0 +1,176 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use super::quiz::render_code_block;
+use crate::app::App;
+use crate::models::{format_answer, Question, OPTION_LABELS};
+use crate::theme::Theme;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let engine = app.engine();
+    let index = app.selected_result();
+    let question = &engine.questions()[index];
+    let answer = engine.answers()[index].as_deref();
+    let hint_used = engine.hints_used()[index];
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(8),
+        Constraint::Length(5),
+        Constraint::Length(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    render_header(frame, chunks[0], app, question, answer, theme);
+    render_question_text(frame, chunks[1], &question.text, theme);
+    if question.code.is_some() {
+        render_code_block(frame, chunks[2], question, theme);
+    }
+    render_options(frame, chunks[3], question, answer, theme);
+    render_answer_summary(frame, chunks[4], question, answer, hint_used, theme);
+    render_controls(frame, chunks[5], theme);
+}
+
+fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    question: &Question,
+    answer: Option<&[usize]>,
+    theme: &Theme,
+) {
+    let (verdict, color) = match answer {
+        Some(answer) if question.is_correct(answer) => ("Correct", theme.correct),
+        Some(_) => ("Incorrect", theme.incorrect),
+        None => ("Unanswered", theme.incorrect),
+    };
+    let points = app.engine().question_score(app.selected_result());
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!(
+                "Question {}/{}",
+                app.selected_result() + 1,
+                app.engine().total_questions()
+            ),
+            Style::default().fg(theme.accent).bold(),
+        ),
+        Span::styled("  ·  ", Style::default().fg(theme.dimmed)),
+        Span::styled(verdict, Style::default().fg(color).bold()),
+        Span::styled(
+            format!("  ·  {}/{} points", points, question.points),
+            Style::default().fg(theme.dimmed),
+        ),
+    ]);
+
+    let widget = Paragraph::new(line).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme.dimmed),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn render_question_text(frame: &mut Frame, area: Rect, text: &str, theme: &Theme) {
+    let widget = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .fg(theme.text)
+        .bold();
+    frame.render_widget(widget, area);
+}
+
+fn render_options(
+    frame: &mut Frame,
+    area: Rect,
+    question: &Question,
+    answer: Option<&[usize]>,
+    theme: &Theme,
+) {
+    let correct = question.correct_answer.indices();
+    let answer = answer.unwrap_or(&[]);
+
+    let lines: Vec<Line> = question
+        .options
+        .iter()
+        .enumerate()
+        .flat_map(|(index, option)| {
+            let is_correct = correct.contains(&index);
+            let is_chosen = answer.contains(&index);
+            let (marker, style) = match (is_correct, is_chosen) {
+                (true, _) => (theme.option_marker(true), Style::default().fg(theme.correct).bold()),
+                (false, true) => {
+                    (theme.option_marker(false), Style::default().fg(theme.incorrect).bold())
+                }
+                (false, false) => (" ", Style::default().fg(theme.muted)),
+            };
+
+            let mut spans = vec![
+                Span::styled(format!(" {} ", marker), style),
+                Span::styled(format!("{}. ", OPTION_LABELS[index]), style),
+                Span::styled(option.as_str(), style),
+            ];
+            if is_correct && theme.text_labels {
+                spans.push(Span::styled("  (correct answer)", style));
+            }
+            if is_chosen {
+                spans.push(Span::styled(
+                    "  (your answer)",
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+            [Line::from(spans), Line::from("")]
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_answer_summary(
+    frame: &mut Frame,
+    area: Rect,
+    question: &Question,
+    answer: Option<&[usize]>,
+    hint_used: bool,
+    theme: &Theme,
+) {
+    let your_answer = answer.map_or_else(|| "none".to_string(), format_answer);
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Your answer: ", Style::default().fg(theme.dimmed)),
+        Span::styled(your_answer, Style::default().fg(theme.text)),
+        Span::styled(
+            "  ·  Correct answer: ",
+            Style::default().fg(theme.dimmed),
+        ),
+        Span::styled(
+            format_answer(&question.correct_answer.indices()),
+            Style::default().fg(theme.correct),
+        ),
+    ])];
+
+    if hint_used && let Some(hint) = &question.hint {
+        lines.push(Line::from(vec![
+            Span::styled("Hint used: ", Style::default().fg(theme.highlight).bold()),
+            Span::styled(hint.as_str(), Style::default().fg(theme.highlight)),
+        ]));
+    }
+    if let Some(explanation) = &question.explanation {
+        lines.push(Line::from(Span::styled(
+            explanation.as_str(),
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let widget = Paragraph::new("n/p next/prev  ·  esc back  ·  q quit")
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, area);
+}
@@ -0,0 +1,156 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph, Sparkline},
+};
+
+use super::result::get_grade_color;
+use crate::app::App;
+use crate::history::HistoryStats;
+use crate::text;
+use crate::theme::Theme;
+
+const MOST_MISSED_SHOWN: usize = 5;
+const MISSED_PREVIEW_LENGTH: usize = 40;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let stats = HistoryStats::from_entries(app.history());
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+    ])
+    .margin(1)
+    .split(area);
+
+    render_summary(frame, chunks[1], &stats, theme);
+    if stats.attempts > 0 {
+        render_trend(frame, chunks[2], &stats.trend, theme);
+        let [tags_area, missed_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(chunks[3]);
+        render_tag_accuracy(frame, tags_area, &stats.tag_accuracy, theme);
+        render_most_missed(frame, missed_area, &stats.most_missed, theme);
+    }
+    render_controls(frame, chunks[4], theme);
+}
+
+fn render_summary(frame: &mut Frame, area: Rect, stats: &HistoryStats, theme: &Theme) {
+    let mut content = vec![
+        Line::from(Span::styled(
+            "STATISTICS",
+            Style::default().fg(theme.accent).bold(),
+        )),
+        Line::from(""),
+    ];
+
+    if stats.attempts == 0 {
+        content.push(Line::from("No finished quizzes yet".fg(theme.dimmed)));
+    } else {
+        content.push(Line::from(Span::styled(
+            format!("Average {:.0}%", stats.average),
+            Style::default().fg(get_grade_color(stats.average, theme)).bold(),
+        )));
+        content.push(Line::from(
+            format!("Best {:.0}%  ·  {} attempts", stats.best, stats.attempts).fg(theme.dimmed),
+        ));
+    }
+
+    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme.dimmed),
+    );
+    frame.render_widget(widget, area);
+}
+
+/// Show the most recent runs that fit, oldest on the left.
+fn render_trend(frame: &mut Frame, area: Rect, trend: &[f64], theme: &Theme) {
+    let block = Block::default()
+        .title(" Score trend ".fg(theme.dimmed))
+        .borders(Borders::ALL)
+        .border_style(theme.dimmed);
+    let width = block.inner(area).width as usize;
+    let recent: Vec<u64> = trend[trend.len().saturating_sub(width)..]
+        .iter()
+        .map(|percentage| percentage.round() as u64)
+        .collect();
+
+    let widget = Sparkline::default()
+        .block(block)
+        .data(&recent)
+        .max(100)
+        .style(Style::default().fg(theme.accent));
+    frame.render_widget(widget, area);
+}
+
+fn render_tag_accuracy(
+    frame: &mut Frame,
+    area: Rect,
+    tag_accuracy: &[(String, f64)],
+    theme: &Theme,
+) {
+    let lines: Vec<Line> = if tag_accuracy.is_empty() {
+        vec![Line::from("No tagged questions".fg(theme.dimmed))]
+    } else {
+        tag_accuracy
+            .iter()
+            .map(|(tag, percentage)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>4.0}%  ", percentage),
+                        Style::default().fg(get_grade_color(*percentage, theme)).bold(),
+                    ),
+                    Span::styled(tag.as_str(), Style::default().fg(theme.muted)),
+                ])
+            })
+            .collect()
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Accuracy by tag ".fg(theme.dimmed))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn render_most_missed(
+    frame: &mut Frame,
+    area: Rect,
+    most_missed: &[(String, usize)],
+    theme: &Theme,
+) {
+    let lines: Vec<Line> = if most_missed.is_empty() {
+        vec![Line::from("Nothing missed yet".fg(theme.dimmed))]
+    } else {
+        most_missed
+            .iter()
+            .take(MOST_MISSED_SHOWN)
+            .map(|(text, count)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>3}×  ", count), Style::default().fg(theme.incorrect)),
+                    Span::styled(
+                        text::truncate(text, MISSED_PREVIEW_LENGTH),
+                        Style::default().fg(theme.muted),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Most missed ".fg(theme.dimmed))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn render_controls(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let widget = Paragraph::new("esc back  ·  q quit")
+        .alignment(Alignment::Center)
+        .fg(theme.dimmed);
+    frame.render_widget(widget, area);
+}
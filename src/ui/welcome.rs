@@ -1,38 +1,265 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-pub fn render(frame: &mut Frame, area: Rect) {
-    let chunks = Layout::vertical([
-        Constraint::Fill(1),
-        Constraint::Length(9),
-        Constraint::Fill(1),
-    ])
-    .split(area);
+use super::format_duration;
+use crate::app::App;
+use crate::catalog::QuizEntry;
+use crate::playlist::Playlist;
+use crate::text;
+use crate::theme::Theme;
+
+/// Most quizzes listed at once when offered several.
+const PICKER_ROWS: usize = 8;
+/// Widest a quiz title is shown in the list.
+const TITLE_WIDTH: usize = 40;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    if let Some(playlist) = app.playlist() {
+        render_playlist(frame, area, app, playlist, theme);
+        return;
+    }
+    if !app.quizzes().is_empty() {
+        render_picker(frame, area, app, theme);
+        return;
+    }
+    let meta = app.meta();
+    let title = meta.title.as_deref().unwrap_or("RUST QUIZ");
 
-    let content = vec![
+    let mut content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "RUST QUIZ",
-            Style::default().fg(Color::Cyan).bold(),
+            title,
+            Style::default().fg(theme.accent).bold(),
         )),
+    ];
+    if let Some(byline) = meta.byline() {
+        content.push(Line::from(byline.fg(theme.dimmed)));
+    }
+    content.push(Line::from(""));
+    if let Some(description) = &meta.description {
+        content.push(Line::from(description.as_str().fg(theme.text)));
+        content.push(Line::from(""));
+    }
+    content.extend([
+        Line::from(quiz_summary(app).fg(theme.dimmed)),
         Line::from(""),
-        Line::from("25 Questions · Code Snippets".fg(Color::DarkGray)),
+        Line::from(status_line(app).fg(theme.highlight)),
+        Line::from(Span::styled(
+            "ENTER",
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from(match app.chosen_quiz() {
+            Some(quiz) => format!("to start {}", quiz.title).fg(theme.dimmed),
+            None => "to start".fg(theme.dimmed),
+        }),
+    ]);
+    if !app.recent_quizzes().is_empty() {
+        content.push(Line::from(""));
+        content.push(Line::from("Recently played".fg(theme.muted).bold()));
+        let recent = quiz_rows(app, app.recent_quizzes(), app.selected_recent(), theme);
+        content.extend(recent);
+    }
+
+    let mut controls = Vec::new();
+    if !app.recent_quizzes().is_empty() {
+        controls.push("↑/↓ recent");
+    }
+    if app.is_reload_offered() {
+        controls.push("r reload");
+    }
+    controls.extend(["h history", "s stats", "? help", "q quit"]);
+    content.push(Line::from(""));
+    content.push(Line::from(controls.join("  ·  ").fg(theme.dimmed)));
+
+    render_box(frame, area, content, theme);
+}
+
+/// The quizzes to choose from, with the highlighted one started by Enter.
+fn render_picker(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "RUST QUIZ",
+            Style::default().fg(theme.accent).bold(),
+        )),
         Line::from(""),
+    ];
+    content.extend(quiz_rows(app, app.quizzes(), Some(app.selected_quiz()), theme));
+    content.extend([
         Line::from(""),
+        Line::from(status_line(app).fg(theme.highlight)),
         Line::from(Span::styled(
             "ENTER",
-            Style::default().fg(Color::Green).bold(),
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from("to start".fg(theme.dimmed)),
+        Line::from(""),
+        Line::from(if app.is_reload_offered() {
+            "↑/↓ choose  ·  r reload  ·  h history  ·  s stats  ·  q quit".fg(theme.dimmed)
+        } else {
+            "↑/↓ choose  ·  h history  ·  s stats  ·  ? help  ·  q quit".fg(theme.dimmed)
+        }),
+    ]);
+
+    render_box(frame, area, content, theme);
+}
+
+/// The playlist's quizzes with the score of each one played so far, and
+/// the total, which makes the report once every quiz was played.
+fn render_playlist(frame: &mut Frame, area: Rect, app: &App, playlist: &Playlist, theme: &Theme) {
+    let titles: Vec<String> = playlist
+        .quizzes()
+        .iter()
+        .map(|quiz| text::truncate(&quiz.title, TITLE_WIDTH))
+        .collect();
+    let title_width = titles.iter().map(|title| text::width(title)).max().unwrap_or(0);
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            playlist.title().unwrap_or("PLAYLIST"),
+            Style::default().fg(theme.accent).bold(),
         )),
-        Line::from("to start".fg(Color::DarkGray)),
+        Line::from(""),
     ];
+    let quizzes = playlist.quizzes().iter().zip(playlist.scores()).zip(titles);
+    for (index, ((quiz, score), title)) in quizzes.enumerate() {
+        let questions = match quiz.questions {
+            1 => "1 question".to_string(),
+            count => format!("{} questions", count),
+        };
+        let score = score.map(|(score, max)| format!("{}/{}", score, max)).unwrap_or_default();
+        let is_current = index == playlist.current();
+        let line = format!(
+            "{} {}. {}  {:>13}  {:>9}",
+            if is_current { ">" } else { " " },
+            index + 1,
+            text::pad(&title, title_width),
+            questions,
+            score
+        );
+        content.push(Line::from(if is_current {
+            Span::styled(line, Style::default().fg(theme.highlight).bold())
+        } else {
+            Span::styled(line, Style::default().fg(theme.text))
+        }));
+    }
+    let (score, max) = playlist.total();
+    if max > 0 {
+        let percentage = score as f64 / max as f64 * 100.0;
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            format!("Total {}/{} ({:.0}%)", score, max, percentage),
+            Style::default().fg(theme.text).bold(),
+        )));
+    }
+    content.extend([
+        Line::from(""),
+        Line::from(status_line(app).fg(theme.highlight)),
+        Line::from(Span::styled(
+            "ENTER",
+            Style::default().fg(theme.correct).bold(),
+        )),
+        Line::from(match playlist.current_quiz() {
+            Some(quiz) => format!("to start {}", quiz.title).fg(theme.dimmed),
+            None => "to play the playlist again".fg(theme.dimmed),
+        }),
+        Line::from(""),
+        Line::from("h history  ·  s stats  ·  ? help  ·  q quit".fg(theme.dimmed)),
+    ]);
 
-    let widget = Paragraph::new(content).alignment(Alignment::Center).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Color::DarkGray),
-    );
+    render_box(frame, area, content, theme);
+}
+
+/// One line per quiz with its number of questions and latest score, lined
+/// up in columns, scrolled to keep the `selected` one in view.
+fn quiz_rows<'a>(
+    app: &App,
+    quizzes: &[QuizEntry],
+    selected: Option<usize>,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    let titles: Vec<String> = quizzes
+        .iter()
+        .map(|quiz| text::truncate(&quiz.title, TITLE_WIDTH))
+        .collect();
+    let title_width = titles.iter().map(|title| text::width(title)).max().unwrap_or(0);
+    let first = selected.map_or(0, |selected| (selected + 1).saturating_sub(PICKER_ROWS));
+
+    let rows = quizzes.iter().zip(titles).enumerate().skip(first).take(PICKER_ROWS);
+    rows.map(|(index, (quiz, title))| {
+        let questions = match quiz.questions {
+            1 => "1 question".to_string(),
+            count => format!("{} questions", count),
+        };
+        let last = match app.last_run(&quiz.path.display().to_string()) {
+            Some(run) => format!("last {:.0}%", run.percentage()),
+            None => String::new(),
+        };
+        let is_selected = selected == Some(index);
+        let line = format!(
+            "{} {}  {:>13}  {:<9}",
+            if is_selected { ">" } else { " " },
+            text::pad(&title, title_width),
+            questions,
+            last
+        );
+        if is_selected {
+            Line::from(Span::styled(line, Style::default().fg(theme.highlight).bold()))
+        } else {
+            Line::from(Span::styled(line, Style::default().fg(theme.text)))
+        }
+    })
+    .collect()
+}
+
+/// Draw `content` centered in a box.
+fn render_box(frame: &mut Frame, area: Rect, content: Vec<Line>, theme: &Theme) {
+    let chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(content.len() as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let widget = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.dimmed),
+        );
 
     frame.render_widget(widget, chunks[1]);
 }
+
+/// Notice that the question file changed, or the outcome of reloading it.
+fn status_line(app: &App) -> String {
+    if app.is_reload_offered() {
+        "Question file changed · press r to reload".to_string()
+    } else {
+        app.status_message().unwrap_or_default().to_string()
+    }
+}
+
+/// "25 Questions · 10:00 · Pass at 70%", leaving out what isn't set.
+fn quiz_summary(app: &App) -> String {
+    let engine = app.engine();
+    let mut parts = vec![match engine.total_questions() {
+        1 => "1 Question".to_string(),
+        total => format!("{} Questions", total),
+    }];
+    if let Some(limit) = engine.time_limit() {
+        parts.push(format!("{} time limit", format_duration(limit)));
+    }
+    if let Some(threshold) = app.meta().pass_threshold {
+        parts.push(format!("Pass at {:.0}%", threshold));
+    }
+    if let Some(score) = app.meta().pass_score {
+        parts.push(format!("Pass at {} points", score));
+    }
+    parts.join(" · ")
+}
@@ -0,0 +1,216 @@
+//! Noticing edits to the question file while a quiz is open.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use rand::seq::SliceRandom;
+
+use crate::data::{
+    filter_questions_by_tags, load_bank_encrypted, load_bank_from_json, load_bank_from_toml,
+    sample_pools_with_rng, sample_questions_adaptive_with_rng, sample_questions_with_rng,
+    seeded_rng, shuffle_options_with_rng, LoadError, QuestionBank, Sampling,
+};
+use crate::history::HistoryEntry;
+use crate::models::{Pool, Question};
+
+/// A local question file, and how to read it.
+pub(crate) enum Origin {
+    Json(PathBuf),
+    Toml(PathBuf),
+    Encrypted { path: PathBuf, passphrase: String },
+}
+
+impl Origin {
+    /// A plain question file, read as TOML or JSON by its extension.
+    pub(crate) fn for_file(path: &Path) -> Self {
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Origin::Toml(path.into())
+        } else {
+            Origin::Json(path.into())
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            Origin::Json(path) | Origin::Toml(path) => path,
+            Origin::Encrypted { path, .. } => path,
+        }
+    }
+
+    fn load(&self) -> Result<QuestionBank, LoadError> {
+        match self {
+            Origin::Json(path) => load_bank_from_json(path),
+            Origin::Toml(path) => load_bank_from_toml(path),
+            Origin::Encrypted { path, passphrase } => load_bank_encrypted(path, passphrase),
+        }
+    }
+}
+
+/// A change made to the question list while building the quiz, made again
+/// after reloading.
+#[derive(Clone)]
+pub(crate) enum Selection {
+    Tags(Vec<String>),
+    Sample(usize, Sampling),
+    Adaptive(usize),
+    Shuffle,
+    ShuffleOptions,
+    Pools(Vec<Pool>),
+}
+
+impl Selection {
+    /// Apply the change, picking the same questions in the same order each
+    /// time for a given `seed`.
+    pub(crate) fn apply(
+        &self,
+        questions: Vec<Question>,
+        history: &[HistoryEntry],
+        seed: Option<u64>,
+    ) -> Result<Vec<Question>, LoadError> {
+        let mut rng = seeded_rng(seed);
+        let questions = match self {
+            Selection::Tags(tags) => filter_questions_by_tags(questions, tags)?,
+            Selection::Sample(limit, sampling) => {
+                sample_questions_with_rng(questions, *limit, *sampling, &mut rng)
+            }
+            Selection::Adaptive(limit) => {
+                sample_questions_adaptive_with_rng(questions, *limit, history, &mut rng)
+            }
+            Selection::Shuffle => {
+                let mut questions = questions;
+                questions.shuffle(&mut rng);
+                questions
+            }
+            Selection::ShuffleOptions => shuffle_options_with_rng(questions, &mut rng),
+            Selection::Pools(pools) => sample_pools_with_rng(questions, pools, &mut rng)?,
+        };
+        Ok(questions)
+    }
+}
+
+/// A directory being watched, and what happens in it.
+type Events = (RecommendedWatcher, mpsc::Receiver<notify::Result<notify::Event>>);
+
+/// Hears from the operating system when the question file changes.
+pub(crate) struct Watcher {
+    origin: Origin,
+    selections: Vec<Selection>,
+    seed: Option<u64>,
+    /// Notifications for the file's directory, as editors often replace
+    /// the file rather than write to it; `None` if they can't be had.
+    events: Option<Events>,
+}
+
+impl Watcher {
+    pub(crate) fn new(origin: Origin, selections: Vec<Selection>, seed: Option<u64>) -> Self {
+        let events = watch(origin.path());
+        Self {
+            origin,
+            selections,
+            seed,
+            events,
+        }
+    }
+
+    /// Whether the file changed since the last call.
+    pub(crate) fn poll(&mut self) -> bool {
+        let Some((_, events)) = &self.events else {
+            return false;
+        };
+        let name = self.origin.path().file_name();
+        let mut changed = false;
+        for event in events.try_iter().flatten() {
+            changed |= matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                && event.paths.iter().any(|path| path.file_name() == name);
+        }
+        changed
+    }
+
+    /// Read the file again, with the same tag filter, sampling and order as
+    /// when the quiz was built.
+    pub(crate) fn reload(&self, history: &[HistoryEntry]) -> Result<QuestionBank, LoadError> {
+        let mut bank = self.origin.load()?;
+        for selection in &self.selections {
+            bank.questions = match selection {
+                // Follow the pools as edited
+                Selection::Pools(_) => Selection::Pools(bank.meta.pools.clone()).apply(
+                    bank.questions,
+                    history,
+                    self.seed,
+                )?,
+                selection => selection.apply(bank.questions, history, self.seed)?,
+            };
+        }
+        Ok(bank)
+    }
+}
+
+/// Start watching the directory holding `path`.
+fn watch(path: &Path) -> Option<Events> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).ok()?;
+    watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+    Some((watcher, events))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_reload_applies_selections() {
+        let path = std::env::temp_dir()
+            .join(format!("rust-quiz-watch-{}.json", std::process::id()));
+        let question = |text: &str, tag: &str| {
+            format!(
+                r#"{{"text": "{}", "options": ["a", "b", "c", "d"], "correct_answer": 0,
+                    "tags": ["{}"]}}"#,
+                text, tag
+            )
+        };
+        fs::write(&path, format!("[{}]", question("Q1", "traits"))).unwrap();
+        let watcher = Watcher::new(
+            Origin::Json(path.clone()),
+            vec![Selection::Tags(vec!["traits".to_string()]), Selection::Shuffle],
+            Some(7),
+        );
+
+        let content = [question("Q1", "traits"), question("Q2", "traits"), question("Q3", "io")];
+        fs::write(&path, format!("[{}]", content.join(","))).unwrap();
+        let bank = watcher.reload(&[]).unwrap();
+        let again = watcher.reload(&[]).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut texts: Vec<&str> = bank.questions.iter().map(|q| q.text.as_str()).collect();
+        let seeded: Vec<&str> = again.questions.iter().map(|q| q.text.as_str()).collect();
+        assert_eq!(texts, seeded);
+        texts.sort();
+        assert_eq!(texts, ["Q1", "Q2"]);
+    }
+
+    #[test]
+    fn test_poll_notices_edits() {
+        let dir = std::env::temp_dir().join(format!("rust-quiz-poll-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("questions.json");
+        fs::write(&path, "[]").unwrap();
+        let mut watcher = Watcher::new(Origin::Json(path.clone()), Vec::new(), None);
+        fs::write(dir.join("notes.txt"), "not the questions").unwrap();
+        fs::write(&path, "[ ]").unwrap();
+
+        // Notifications arrive from another thread
+        let noticed = (0..50).any(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            watcher.poll()
+        });
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(noticed);
+    }
+}